@@ -1,16 +1,22 @@
 use std::{
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    str::FromStr,
     sync::Arc,
 };
 
+use alloy_primitives::B256;
 use clap::{Parser, Subcommand};
+use ream_consensus::checkpoint::Checkpoint;
 use ream_network_spec::{cli::network_parser, networks::NetworkSpec};
 use ream_node::version::FULL_VERSION;
 use ream_p2p::bootnodes::Bootnodes;
 
 const DEFAULT_DISABLE_DISCOVERY: bool = false;
 const DEFAULT_DISCOVERY_PORT: u16 = 9000;
+const DEFAULT_GENESIS_NETWORK: &str = "dev";
+const DEFAULT_GENESIS_OUT: &str = "genesis.ssz";
+const DEFAULT_GENESIS_VALIDATORS: u64 = 64;
 const DEFAULT_HTTP_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
 const DEFAULT_HTTP_ALLOW_ORIGIN: bool = false;
 const DEFAULT_HTTP_PORT: u16 = 5052;
@@ -30,6 +36,10 @@ pub enum Commands {
     /// Start the node
     #[command(name = "node")]
     Node(NodeConfig),
+
+    /// Generate an interop genesis state for a network starting at Electra
+    #[command(name = "genesis")]
+    Genesis(GenesisConfig),
 }
 
 #[derive(Debug, Parser)]
@@ -40,7 +50,7 @@ pub struct NodeConfig {
 
     #[arg(
         long,
-        help = "Choose mainnet, holesky, sepolia, hoodi or dev",
+        help = "Choose mainnet, holesky, sepolia, hoodi, dev or a path to a testnet directory",
         default_value = DEFAULT_NETWORK,
         value_parser = network_parser
     )]
@@ -86,6 +96,67 @@ pub struct NodeConfig {
         help = "One or more comma-delimited base64-encoded ENR's of peers to initially connect to. Use 'default' to use the default bootnodes for the network. Use 'none' to disable bootnodes."
     )]
     pub bootnodes: Bootnodes,
+
+    #[arg(
+        long,
+        help = "Weak subjectivity checkpoint in the format `block_root:epoch`. The node refuses to sync a chain which contradicts it.",
+        value_parser = weak_subjectivity_checkpoint_parser
+    )]
+    pub wss_checkpoint: Option<Checkpoint>,
+
+    #[arg(
+        long,
+        help = "URL of a trusted beacon node to checkpoint sync from. The node starts from its finalized state and backfills the blocks before it."
+    )]
+    pub checkpoint_sync_url: Option<String>,
+
+    #[arg(
+        long,
+        help = "URL of the authenticated JSON-RPC of the execution node, used to follow the deposit contract",
+        requires = "execution_jwt_secret"
+    )]
+    pub execution_endpoint: Option<String>,
+
+    #[arg(
+        long,
+        help = "Path to the hex encoded JWT secret shared with the execution node",
+        requires = "execution_endpoint"
+    )]
+    pub execution_jwt_secret: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub struct GenesisConfig {
+    #[arg(
+        long,
+        help = "Choose mainnet, holesky, sepolia, hoodi, dev or a path to a testnet directory",
+        default_value = DEFAULT_GENESIS_NETWORK,
+        value_parser = network_parser
+    )]
+    pub network: Arc<NetworkSpec>,
+
+    #[arg(long, help = "Number of interop validators", default_value_t = DEFAULT_GENESIS_VALIDATORS)]
+    pub validators: u64,
+
+    #[arg(long, help = "Genesis time as a unix timestamp, defaults to now")]
+    pub genesis_time: Option<u64>,
+
+    #[arg(long, help = "Path to write the SSZ encoded genesis state to", default_value = DEFAULT_GENESIS_OUT)]
+    pub out: PathBuf,
+}
+
+pub fn weak_subjectivity_checkpoint_parser(checkpoint: &str) -> Result<Checkpoint, String> {
+    let (root, epoch) = checkpoint
+        .split_once(':')
+        .ok_or_else(|| format!("Expected `block_root:epoch`, got: {checkpoint}"))?;
+
+    Ok(Checkpoint {
+        epoch: epoch
+            .parse()
+            .map_err(|err| format!("Invalid weak subjectivity checkpoint epoch {epoch}: {err}"))?,
+        root: B256::from_str(root)
+            .map_err(|err| format!("Invalid weak subjectivity checkpoint root {root}: {err}"))?,
+    })
 }
 
 #[cfg(test)]
@@ -119,7 +190,58 @@ mod tests {
                 );
                 assert_eq!(config.socket_port, 9001);
                 assert_eq!(config.discovery_port, 9002);
+                assert_eq!(config.wss_checkpoint, None);
+            }
+            Commands::Genesis(_) => panic!("Expected the node command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_wss_checkpoint() {
+        let cli = Cli::parse_from([
+            "program",
+            "node",
+            "--wss-checkpoint",
+            "0x9a8a6a3f2b2a0e3c8a1b5e7d3c2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b7c6d5e4f:1024",
+        ]);
+
+        match cli.command {
+            Commands::Node(config) => {
+                let checkpoint = config.wss_checkpoint.expect("wss checkpoint is set");
+                assert_eq!(checkpoint.epoch, 1024);
+                assert_eq!(
+                    checkpoint.root,
+                    B256::from_str(
+                        "0x9a8a6a3f2b2a0e3c8a1b5e7d3c2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b7c6d5e4f"
+                    )
+                    .expect("valid root")
+                );
+            }
+            Commands::Genesis(_) => panic!("Expected the node command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_genesis_command() {
+        let cli = Cli::parse_from([
+            "program",
+            "genesis",
+            "--validators",
+            "16",
+            "--genesis-time",
+            "1700000000",
+            "--out",
+            "/tmp/genesis.ssz",
+        ]);
+
+        match cli.command {
+            Commands::Genesis(config) => {
+                assert_eq!(config.network.network, Network::Dev);
+                assert_eq!(config.validators, 16);
+                assert_eq!(config.genesis_time, Some(1700000000));
+                assert_eq!(config.out, PathBuf::from("/tmp/genesis.ssz"));
             }
+            Commands::Node(_) => panic!("Expected the genesis command"),
         }
     }
 }
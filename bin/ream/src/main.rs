@@ -1,13 +1,37 @@
-use std::env;
+use std::{
+    env, fs,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use clap::Parser;
 use ream::cli::{Cli, Commands};
-use ream_discv5::{config::NetworkConfig, subnet::Subnets};
+use ream_consensus::{
+    chain_spec::{chain_spec, set_chain_spec},
+    genesis::{ensure_interop_genesis_fork, interop_genesis_state},
+    misc::compute_epoch_at_slot,
+};
+use ream_discv5::{config::NetworkConfig, eth2::ENRForkID, subnet::Subnets};
+use ream_execution_engine::ExecutionEngine;
 use ream_executor::ReamExecutor;
-use ream_p2p::network::Network;
+use ream_node::{
+    eth1::{init_eth1_follower, run_eth1_follower},
+    gossip::GossipHandler,
+    operation_pool::{load_operation_pool, persist_operation_pool},
+};
+use ream_p2p::network::{Network, ReamNetworkEvent};
 use ream_rpc::{config::ServerConfig, start_server};
 use ream_storage::db::ReamDB;
-use tracing::{error, info};
+use ream_sync::{
+    backfill::{BackfillSync, compute_backfill_target_slot},
+    checkpoint_sync::{BeaconApiClient, checkpoint_sync},
+    weak_subjectivity::{
+        verify_weak_subjectivity_checkpoint, warn_if_outside_weak_subjectivity_period,
+    },
+};
+use ssz::Encode;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info};
 use tracing_subscriber::EnvFilter;
 
 #[tokio::main]
@@ -31,6 +55,22 @@ async fn main() {
         Commands::Node(config) => {
             info!("starting up...");
 
+            if let Err(err) = set_chain_spec(config.network.chain_spec.clone()) {
+                error!(
+                    "Unable to use the {} config: {err}",
+                    config.network.chain_spec.config_name
+                );
+                return;
+            }
+
+            let current_slot = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system time is before the unix epoch")
+                .as_secs()
+                .saturating_sub(config.network.genesis.genesis_time)
+                / chain_spec().seconds_per_slot;
+            let current_epoch = compute_epoch_at_slot(current_slot);
+
             let server_config = ServerConfig::new(
                 config.http_address,
                 config.http_port,
@@ -43,7 +83,7 @@ async fn main() {
             ))
             .build();
 
-            let bootnodes = config.bootnodes.to_enrs(config.network.network);
+            let bootnodes = config.bootnodes.to_enrs(&config.network);
             let binding = NetworkConfig {
                 discv5_config,
                 bootnodes,
@@ -51,6 +91,7 @@ async fn main() {
                 socket_port: Some(config.socket_port),
                 disable_discovery: config.disable_discovery,
                 subnets: Subnets::new(),
+                enr_fork_id: ENRForkID::new(&config.network, current_epoch),
             };
 
             let ream_db = ReamDB::new(config.data_dir, config.ephemeral)
@@ -58,13 +99,123 @@ async fn main() {
 
             info!("ream database initialized ");
 
-            let http_future = start_server(config.network.clone(), server_config, ream_db);
+            if let Some(checkpoint_sync_url) = config.checkpoint_sync_url {
+                let client = BeaconApiClient::new(checkpoint_sync_url, config.network.clone());
+                let (anchor_block_root, anchor_state) =
+                    match checkpoint_sync(&ream_db, &client, config.wss_checkpoint, current_epoch)
+                        .await
+                    {
+                        Ok(anchor) => anchor,
+                        Err(err) => {
+                            error!("Checkpoint sync failed: {err}");
+                            return;
+                        }
+                    };
+                let backfill_sync = BackfillSync::new(
+                    ream_db.clone(),
+                    client,
+                    config.network.clone(),
+                    anchor_state,
+                    anchor_block_root,
+                    compute_backfill_target_slot(current_epoch),
+                    config.wss_checkpoint,
+                );
+                main_executor.spawn(async move {
+                    if let Err(err) = backfill_sync.run().await {
+                        error!("Backfill failed: {err}");
+                    }
+                });
+            }
+
+            if let Some(ws_checkpoint) = config.wss_checkpoint {
+                if let Err(err) = verify_weak_subjectivity_checkpoint(&ream_db, ws_checkpoint) {
+                    error!("Weak subjectivity checkpoint verification failed: {err}");
+                    return;
+                }
+            }
+
+            if let Err(err) = warn_if_outside_weak_subjectivity_period(&ream_db, current_epoch) {
+                error!("Unable to check the weak subjectivity period: {err}");
+            }
+
+            let execution_engine = match (config.execution_endpoint, config.execution_jwt_secret) {
+                (Some(execution_endpoint), Some(execution_jwt_secret)) => {
+                    match ExecutionEngine::new(
+                        &execution_jwt_secret.to_string_lossy(),
+                        execution_endpoint,
+                    ) {
+                        Ok(execution_engine) => Some(Arc::new(execution_engine)),
+                        Err(err) => {
+                            error!("Unable to connect to the execution engine: {err}");
+                            return;
+                        }
+                    }
+                }
+                _ => None,
+            };
 
+            if let Some(execution_engine) = &execution_engine {
+                match init_eth1_follower(
+                    &ream_db,
+                    execution_engine.clone(),
+                    config.network.deposit_contract_block,
+                ) {
+                    Ok(eth1_follower) => {
+                        main_executor.spawn(run_eth1_follower(
+                            ream_db.clone(),
+                            Arc::new(RwLock::new(eth1_follower)),
+                        ));
+                    }
+                    Err(err) => {
+                        error!("Unable to follow the deposit contract: {err}");
+                        return;
+                    }
+                }
+            }
+
+            let operation_pool = Arc::new(RwLock::new(
+                load_operation_pool(&ream_db).unwrap_or_else(|err| {
+                    error!("Unable to load the operation pool, starting with an empty one: {err}");
+                    Default::default()
+                }),
+            ));
+
+            // Blocks are produced on top of the fork choice store, which the node doesn't run yet,
+            // so the block production route is not served
+            let http_future = start_server(
+                config.network.clone(),
+                server_config,
+                ream_db.clone(),
+                operation_pool.clone(),
+                None,
+            );
+
+            let gossip_handler = GossipHandler {
+                db: ream_db.clone(),
+                network_spec: config.network.clone(),
+                execution_engine: execution_engine.clone(),
+                operation_pool: operation_pool.clone(),
+            };
+
+            let network_spec = config.network.clone();
             let network_future = async {
-                match Network::init(async_executor, &binding).await {
+                match Network::init(async_executor, &binding, network_spec).await {
                     Ok(mut network) => {
                         main_executor.spawn(async move {
-                            network.polling_events().await;
+                            loop {
+                                if let ReamNetworkEvent::GossipMessage { topic, data } =
+                                    network.polling_events().await
+                                {
+                                    if let Err(err) =
+                                        gossip_handler.handle_gossip_message(topic, &data).await
+                                    {
+                                        debug!(
+                                            "Unable to handle a {} gossip message: {err}",
+                                            topic.kind
+                                        );
+                                    }
+                                }
+                            }
                         });
                         tokio::signal::ctrl_c()
                             .await
@@ -84,6 +235,48 @@ async fn main() {
                     info!("Network future completed!");
                 },
             }
+
+            if let Err(err) = persist_operation_pool(&ream_db, &*operation_pool.read().await) {
+                error!("Unable to persist the operation pool: {err}");
+            }
+        }
+        Commands::Genesis(config) => {
+            if let Err(err) = set_chain_spec(config.network.chain_spec.clone()) {
+                error!(
+                    "Unable to use the {} config: {err}",
+                    config.network.chain_spec.config_name
+                );
+                return;
+            }
+            if let Err(err) = ensure_interop_genesis_fork(&config.network.fork_schedule()) {
+                error!("Unable to build a genesis state for this network: {err}");
+                return;
+            }
+
+            let genesis_time = config.genesis_time.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("system time is before the unix epoch")
+                    .as_secs()
+            });
+            let state = match interop_genesis_state(config.validators, genesis_time) {
+                Ok(state) => state,
+                Err(err) => {
+                    error!("Failed to build the genesis state: {err}");
+                    return;
+                }
+            };
+
+            if let Err(err) = fs::write(&config.out, state.as_ssz_bytes()) {
+                error!("Failed to write {}: {err}", config.out.display());
+                return;
+            }
+            info!(
+                "Wrote the genesis state of {} validators to {}, genesis validators root {}",
+                config.validators,
+                config.out.display(),
+                state.genesis_validators_root
+            );
         }
     }
 }
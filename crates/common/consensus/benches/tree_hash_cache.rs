@@ -0,0 +1,73 @@
+use alloy_primitives::B256;
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use ream_bls::PubKey;
+use ream_consensus::{
+    constants::FAR_FUTURE_EPOCH, tree_hash_cache::TreeHashCache, validator::Validator,
+};
+use ssz_types::{
+    VariableList,
+    typenum::{U1099511627776, Unsigned},
+};
+use tree_hash::TreeHash;
+
+const VALIDATOR_COUNT: usize = 100_000;
+const CHANGED_VALIDATOR_COUNT: usize = 1_000;
+
+fn validators() -> VariableList<Validator, U1099511627776> {
+    VariableList::from(
+        (0..VALIDATOR_COUNT)
+            .map(|index| Validator {
+                pubkey: PubKey::default(),
+                withdrawal_credentials: B256::left_padding_from(&index.to_le_bytes()),
+                effective_balance: 32_000_000_000,
+                slashed: false,
+                activation_eligibility_epoch: 0,
+                activation_epoch: 0,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn change_validators(validators: &mut VariableList<Validator, U1099511627776>) {
+    for index in (0..VALIDATOR_COUNT).step_by(VALIDATOR_COUNT / CHANGED_VALIDATOR_COUNT) {
+        validators[index].effective_balance -= 1_000_000_000;
+    }
+}
+
+fn bench_validators_root(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validators_root");
+    group.sample_size(10);
+
+    group.bench_function("uncached", |b| {
+        b.iter_batched(
+            || {
+                let mut validators = validators();
+                change_validators(&mut validators);
+                validators
+            },
+            |validators| validators.tree_hash_root(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("cached", |b| {
+        b.iter_batched(
+            || {
+                let mut validators = validators();
+                let mut cache = TreeHashCache::new(U1099511627776::to_usize());
+                cache.recalculate_list_root(&validators);
+                change_validators(&mut validators);
+                (validators, cache)
+            },
+            |(validators, mut cache)| cache.recalculate_list_root(&validators),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_validators_root);
+criterion_main!(benches);
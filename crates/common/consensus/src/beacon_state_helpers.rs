@@ -0,0 +1,478 @@
+//! ``BeaconState`` methods which are unchanged between Deneb and Electra.
+//!
+//! The methods are expanded into the ``impl BeaconState`` of the module invoking
+//! ``impl_beacon_state_helpers!``, so the state, types and helpers they refer to are the ones of
+//! that fork. Methods they call which changed in Electra, e.g. ``compute_proposer_index`` or
+//! ``get_next_sync_committee_indices``, stay with each fork.
+
+macro_rules! impl_beacon_state_helpers {
+    () => {
+        impl BeaconState {
+            // Accessors
+
+            /// Return the current epoch.
+            pub fn get_current_epoch(&self) -> u64 {
+                compute_epoch_at_slot(self.slot)
+            }
+
+            /// Return the previous epoch (unless the current epoch is ``GENESIS_EPOCH``).
+            pub fn get_previous_epoch(&self) -> u64 {
+                let current_epoch = self.get_current_epoch();
+                if current_epoch == GENESIS_EPOCH {
+                    GENESIS_EPOCH
+                } else {
+                    current_epoch - 1
+                }
+            }
+
+            /// Return the block root at the start of a recent ``epoch``.
+            pub fn get_block_root(&self, epoch: u64) -> anyhow::Result<B256> {
+                self.get_block_root_at_slot(compute_start_slot_at_epoch(epoch))
+            }
+
+            /// Return the block root at a recent ``slot``.
+            pub fn get_block_root_at_slot(&self, slot: u64) -> anyhow::Result<B256> {
+                ensure!(
+                    slot < self.slot && self.slot <= slot + SLOTS_PER_HISTORICAL_ROOT,
+                    "slot given was outside of block_roots range"
+                );
+                Ok(self.block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT) as usize])
+            }
+
+            /// Return the randao mix at a recent ``epoch``.
+            pub fn get_randao_mix(&self, epoch: u64) -> B256 {
+                self.randao_mixes[(epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize]
+            }
+
+            /// Return the sequence of active validator indices at ``epoch``.
+            pub fn get_active_validator_indices(&self, epoch: u64) -> Vec<u64> {
+                self.validators
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, v)| {
+                        if v.is_active_validator(epoch) {
+                            Some(i as u64)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            }
+
+            /// Return the signature domain (fork version concatenated with domain type) of a
+            /// message.
+            pub fn get_domain(&self, domain_type: B32, epoch: Option<u64>) -> B256 {
+                let epoch = match epoch {
+                    Some(epoch) => epoch,
+                    None => self.get_current_epoch(),
+                };
+                let fork_version = if epoch < self.fork.epoch {
+                    self.fork.previous_version
+                } else {
+                    self.fork.current_version
+                };
+                compute_domain(
+                    domain_type,
+                    Some(fork_version),
+                    Some(self.genesis_validators_root),
+                )
+            }
+
+            // Shuffling and committees
+
+            /// Return the seed at ``epoch``.
+            pub fn get_seed(&self, epoch: u64, domain_type: B32) -> B256 {
+                let mix = self
+                    .get_randao_mix(epoch + EPOCHS_PER_HISTORICAL_VECTOR - MIN_SEED_LOOKAHEAD - 1);
+                let epoch_with_index =
+                    [domain_type.as_slice(), &epoch.to_le_bytes(), mix.as_slice()].concat();
+                B256::from(hash_fixed(&epoch_with_index))
+            }
+
+            /// Return the root of the block deciding the shuffling of the epoch starting at
+            /// ``epoch_start_slot``, i.e. the block at the slot before it, or the
+            /// ``genesis_validators_root`` when that slot is before genesis.
+            ///
+            /// The cached shufflings assume the state was reached by the state transition, so
+            /// states sharing this root share the validators and randao mixes of the shuffling.
+            fn get_shuffling_decision_root(&self, epoch_start_slot: u64) -> B256 {
+                epoch_start_slot
+                    .checked_sub(1)
+                    .and_then(|slot| self.get_block_root_at_slot(slot).ok())
+                    .unwrap_or(self.genesis_validators_root)
+            }
+
+            /// Return the attester shuffling of ``epoch``, shared through the ``SHUFFLING_CACHE``.
+            pub fn get_shuffling(&self, epoch: u64) -> Arc<Shuffling> {
+                let key = ShufflingKey {
+                    epoch,
+                    seed: self.get_seed(epoch, DOMAIN_BEACON_ATTESTER),
+                    decision_root: self.get_shuffling_decision_root(compute_start_slot_at_epoch(
+                        epoch.saturating_sub(1),
+                    )),
+                    fork_version: self.fork.current_version,
+                };
+                get_or_compute_shuffling(key, || self.get_active_validator_indices(epoch))
+            }
+
+            /// Return the number of committees in each slot for the given ``epoch``.
+            pub fn get_committee_count_per_slot(&self, epoch: u64) -> u64 {
+                self.get_shuffling(epoch).committees_per_slot
+            }
+
+            /// Return the beacon committee at ``slot`` for ``index``.
+            pub fn get_beacon_committee(&self, slot: u64, index: u64) -> anyhow::Result<Vec<u64>> {
+                let shuffling = self.get_shuffling(compute_epoch_at_slot(slot));
+                let committees_per_slot = shuffling.committees_per_slot;
+                let committee_index = (slot % SLOTS_PER_EPOCH) * committees_per_slot + index;
+                let committee_count = committees_per_slot * SLOTS_PER_EPOCH;
+                ensure!(
+                    committee_index < committee_count,
+                    "Committee index {index} out of range"
+                );
+                Ok(shuffling
+                    .committee(committee_index, committee_count)
+                    .to_vec())
+            }
+
+            /// Return the beacon proposers of each slot of the current epoch, shared through the
+            /// ``PROPOSER_CACHE``.
+            pub fn get_beacon_proposer_indices(&self) -> anyhow::Result<Arc<Vec<u64>>> {
+                let epoch = self.get_current_epoch();
+                let epoch_seed = self.get_seed(epoch, DOMAIN_BEACON_PROPOSER);
+                let start_slot = compute_start_slot_at_epoch(epoch);
+                let key = ShufflingKey {
+                    epoch,
+                    seed: epoch_seed,
+                    decision_root: self.get_shuffling_decision_root(start_slot),
+                    fork_version: self.fork.current_version,
+                };
+                get_or_compute_proposers(key, || {
+                    let shuffling = self.get_shuffling(epoch);
+                    (start_slot..start_slot + SLOTS_PER_EPOCH)
+                        .map(|slot| {
+                            let seed = B256::from(hash_fixed(
+                                &[epoch_seed.as_slice(), &slot.to_le_bytes()].concat(),
+                            ));
+                            self.compute_proposer_index(&shuffling.active_validator_indices, seed)
+                        })
+                        .collect()
+                })
+            }
+
+            /// Return the beacon proposer index at the current slot.
+            pub fn get_beacon_proposer_index(&self) -> anyhow::Result<u64> {
+                Ok(self.get_beacon_proposer_indices()?[(self.slot % SLOTS_PER_EPOCH) as usize])
+            }
+
+            // Balances, rewards and penalties
+
+            /// Return the combined effective balance of the ``indices``.
+            /// ``EFFECTIVE_BALANCE_INCREMENT`` Gwei minimum to avoid divisions by zero.
+            /// Math safe up to ~10B ETH, after which this overflows uint64.
+            pub fn get_total_balance(&self, indices: HashSet<u64>) -> u64 {
+                max(
+                    EFFECTIVE_BALANCE_INCREMENT,
+                    indices
+                        .iter()
+                        .map(|index| self.validators[*index as usize].effective_balance)
+                        .sum(),
+                )
+            }
+
+            /// Return the combined effective balance of the active validators.
+            /// Note: ``get_total_balance`` returns ``EFFECTIVE_BALANCE_INCREMENT`` Gwei minimum to
+            /// avoid divisions by zero.
+            pub fn get_total_active_balance(&self) -> u64 {
+                self.get_total_balance(
+                    self.get_active_validator_indices(self.get_current_epoch())
+                        .into_iter()
+                        .collect::<HashSet<_>>(),
+                )
+            }
+
+            /// Increase the validator balance at index ``index`` by ``delta``.
+            pub fn increase_balance(&mut self, index: u64, delta: u64) -> anyhow::Result<()> {
+                if let Some(balance) = self.balances.get_mut(index as usize) {
+                    *balance += delta;
+                    Ok(())
+                } else {
+                    Err(anyhow!("failed to increase balance"))
+                }
+            }
+
+            /// Decrease the validator balance at index ``index`` by ``delta`` with underflow
+            /// protection.
+            pub fn decrease_balance(&mut self, index: u64, delta: u64) -> anyhow::Result<()> {
+                if let Some(balance) = self.balances.get_mut(index as usize) {
+                    *balance = balance.saturating_sub(delta);
+                    Ok(())
+                } else {
+                    Err(anyhow!("failed to decrease balance"))
+                }
+            }
+
+            pub fn add_flag(flags: u8, flag_index: u8) -> u8 {
+                let flag = 1 << flag_index;
+                flags | flag
+            }
+
+            pub fn has_flag(flags: u8, flag_index: u8) -> bool {
+                let flag = 1 << flag_index;
+                flags & flag == flag
+            }
+
+            /// Return the set of validator indices that are both active and unslashed for the given
+            /// ``flag_index`` and ``epoch``.
+            pub fn get_unslashed_participating_indices(
+                &self,
+                flag_index: u8,
+                epoch: u64,
+            ) -> anyhow::Result<HashSet<u64>> {
+                ensure!(
+                    epoch == self.get_previous_epoch() || epoch == self.get_current_epoch(),
+                    "Epoch must be either the previous or current epoch"
+                );
+                let epoch_participation = if epoch == self.get_current_epoch() {
+                    &self.current_epoch_participation
+                } else {
+                    &self.previous_epoch_participation
+                };
+                let active_validator_indices = self.get_active_validator_indices(epoch);
+                let mut participating_indices = vec![];
+                for i in active_validator_indices {
+                    if Self::has_flag(epoch_participation[i as usize], flag_index) {
+                        participating_indices.push(i);
+                    }
+                }
+                let filtered_indices: HashSet<u64> = participating_indices
+                    .into_iter()
+                    .filter(|&index| !self.validators[index as usize].slashed)
+                    .collect();
+                Ok(filtered_indices)
+            }
+
+            pub fn get_base_reward_per_increment(&self) -> u64 {
+                EFFECTIVE_BALANCE_INCREMENT * BASE_REWARD_FACTOR
+                    / integer_squareroot(self.get_total_active_balance())
+            }
+
+            /// Return the base reward for the validator defined by ``index`` with respect to the
+            /// current ``state``.
+            pub fn get_base_reward(&self, index: u64) -> u64 {
+                let increments =
+                    self.validators[index as usize].effective_balance / EFFECTIVE_BALANCE_INCREMENT;
+                increments * self.get_base_reward_per_increment()
+            }
+
+            pub fn get_proposer_reward(&self, attesting_index: u64) -> u64 {
+                self.get_base_reward(attesting_index) / PROPOSER_REWARD_QUOTIENT
+            }
+
+            pub fn get_finality_delay(&self) -> u64 {
+                self.get_previous_epoch() - self.finalized_checkpoint.epoch
+            }
+
+            pub fn is_in_inactivity_leak(&self) -> bool {
+                self.get_finality_delay() > MIN_EPOCHS_TO_INACTIVITY_PENALTY
+            }
+
+            pub fn get_eligible_validator_indices(&self) -> anyhow::Result<Vec<u64>> {
+                let previous_epoch = self.get_previous_epoch();
+                let mut validator_indices = vec![];
+                for (index, v) in self.validators.iter().enumerate() {
+                    if v.is_active_validator(previous_epoch)
+                        || (v.slashed && previous_epoch + 1 < v.withdrawable_epoch)
+                    {
+                        validator_indices.push(index as u64)
+                    }
+                }
+                Ok(validator_indices)
+            }
+
+            /// Return the flag indices that are satisfied by an attestation.
+            pub fn get_attestation_participation_flag_indices(
+                &self,
+                data: &AttestationData,
+                inclusion_delay: u64,
+            ) -> anyhow::Result<Vec<u8>> {
+                let justified_checkpoint = if data.target.epoch == self.get_current_epoch() {
+                    self.current_justified_checkpoint
+                } else {
+                    self.previous_justified_checkpoint
+                };
+                let is_matching_source = data.source == justified_checkpoint;
+                let is_matching_target = is_matching_source
+                    && data.target.root == self.get_block_root(data.target.epoch)?;
+                let is_matching_head = is_matching_target
+                    && data.beacon_block_root == self.get_block_root_at_slot(data.slot)?;
+                ensure!(is_matching_source);
+
+                let mut participation_flag_indices = vec![];
+
+                if is_matching_source && inclusion_delay <= integer_squareroot(SLOTS_PER_EPOCH) {
+                    participation_flag_indices.push(TIMELY_SOURCE_FLAG_INDEX);
+                }
+                if is_matching_target {
+                    participation_flag_indices.push(TIMELY_TARGET_FLAG_INDEX);
+                }
+                if is_matching_head && inclusion_delay == MIN_ATTESTATION_INCLUSION_DELAY {
+                    participation_flag_indices.push(TIMELY_HEAD_FLAG_INDEX);
+                }
+
+                Ok(participation_flag_indices)
+            }
+
+            /// Return the deltas for a given ``flag_index`` by scanning through the participation
+            /// flags.
+            pub fn get_flag_index_deltas(
+                &self,
+                flag_index: u8,
+            ) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
+                let mut rewards = vec![0; self.validators.len()];
+                let mut penalties = vec![0; self.validators.len()];
+
+                let previous_epoch = self.get_previous_epoch();
+                let unslashed_participating_indices =
+                    self.get_unslashed_participating_indices(flag_index, previous_epoch)?;
+                let weight = PARTICIPATION_FLAG_WEIGHTS[flag_index as usize];
+                let unslashed_participating_balance =
+                    self.get_total_balance(unslashed_participating_indices.clone());
+                let unslashed_participating_increments =
+                    unslashed_participating_balance / EFFECTIVE_BALANCE_INCREMENT;
+                let active_increments =
+                    self.get_total_active_balance() / EFFECTIVE_BALANCE_INCREMENT;
+
+                for index in self.get_eligible_validator_indices()? {
+                    let base_reward = self.get_base_reward(index);
+
+                    if unslashed_participating_indices.contains(&index) {
+                        if !self.is_in_inactivity_leak() {
+                            let reward_numerator =
+                                base_reward * weight * unslashed_participating_increments;
+                            rewards[index as usize] +=
+                                reward_numerator / (active_increments * WEIGHT_DENOMINATOR);
+                        }
+                    } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
+                        penalties[index as usize] += base_reward * weight / WEIGHT_DENOMINATOR;
+                    }
+                }
+
+                Ok((rewards, penalties))
+            }
+
+            /// Return the inactivity penalty deltas by considering timely target participation
+            /// flags and inactivity scores.
+            pub fn get_inactivity_penalty_deltas(&self) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
+                let rewards = vec![0; self.validators.len()];
+                let mut penalties = vec![0; self.validators.len()];
+                let previous_epoch = self.get_previous_epoch();
+                let matching_target_indices = self.get_unslashed_participating_indices(
+                    TIMELY_TARGET_FLAG_INDEX,
+                    previous_epoch,
+                )?;
+                for index in self.get_eligible_validator_indices()? {
+                    if !matching_target_indices.contains(&index) {
+                        let penalty_numerator = self.validators[index as usize].effective_balance
+                            * self.inactivity_scores[index as usize];
+                        let penalty_denominator = chain_spec().inactivity_score_bias
+                            * INACTIVITY_PENALTY_QUOTIENT_BELLATRIX;
+                        penalties[index as usize] += penalty_numerator / penalty_denominator;
+                    }
+                }
+                Ok((rewards, penalties))
+            }
+
+            pub fn process_rewards_and_penalties(&mut self) -> anyhow::Result<()> {
+                // No rewards are applied at the end of `GENESIS_EPOCH` because rewards are for work
+                // done in the previous epoch
+                if self.get_current_epoch() == GENESIS_EPOCH {
+                    return Ok(());
+                }
+
+                // Get deltas for each flag index and inactivity penalties
+                let mut deltas = vec![];
+
+                // Collect the flag deltas for each participation flag index
+                for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
+                    deltas.push(self.get_flag_index_deltas(flag_index as u8)?);
+                }
+
+                // Add the inactivity penalties
+                deltas.push(self.get_inactivity_penalty_deltas()?);
+
+                // Iterate over rewards and penalties for each delta
+                for (rewards, penalties) in deltas {
+                    for index in 0..self.validators.len() {
+                        self.increase_balance(index as u64, rewards[index])?;
+                        self.decrease_balance(index as u64, penalties[index])?;
+                    }
+                }
+                Ok(())
+            }
+
+            pub fn process_inactivity_updates(&mut self) -> anyhow::Result<()> {
+                // Skip the genesis epoch as score updates are based on the previous epoch
+                // participation
+                if self.get_current_epoch() == GENESIS_EPOCH {
+                    return Ok(());
+                }
+                for index in self.get_eligible_validator_indices()? {
+                    // Increase the inactivity score of inactive validators
+                    if self
+                        .get_unslashed_participating_indices(
+                            TIMELY_TARGET_FLAG_INDEX,
+                            self.get_previous_epoch(),
+                        )?
+                        .contains(&index)
+                    {
+                        self.inactivity_scores[index as usize] -=
+                            min(1, self.inactivity_scores[index as usize])
+                    } else {
+                        self.inactivity_scores[index as usize] += chain_spec().inactivity_score_bias
+                    }
+
+                    // Decrease the inactivity score of all eligible validators during a leak-free
+                    // epoch
+                    if !self.is_in_inactivity_leak() {
+                        self.inactivity_scores[index as usize] -= min(
+                            chain_spec().inactivity_score_recovery_rate,
+                            self.inactivity_scores[index as usize],
+                        )
+                    }
+                }
+                Ok(())
+            }
+
+            // Sync committees
+
+            /// Return the next sync committee, with possible pubkey duplicates.
+            pub fn get_next_sync_committee(&self) -> anyhow::Result<SyncCommittee> {
+                let indices = self.get_next_sync_committee_indices()?;
+                let mut pubkeys = vec![];
+
+                for index in indices {
+                    pubkeys.push(self.validators[index as usize].pubkey.clone());
+                }
+
+                let aggregate_pubkey = eth_aggregate_pubkeys(&pubkeys.iter().collect::<Vec<_>>())?;
+
+                Ok(SyncCommittee {
+                    pubkeys: FixedVector::from(pubkeys),
+                    aggregate_pubkey,
+                })
+            }
+
+            pub fn process_sync_committee_updates(&mut self) -> anyhow::Result<()> {
+                let next_epoch = self.get_current_epoch() + 1;
+                if next_epoch % EPOCHS_PER_SYNC_COMMITTEE_PERIOD == 0 {
+                    self.current_sync_committee = self.next_sync_committee.clone();
+                    self.next_sync_committee = Arc::new(self.get_next_sync_committee()?);
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_beacon_state_helpers;
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    BitVector, FixedVector, VariableList,
+    typenum::{U4, U16777216, U1099511627776},
+};
+use tree_hash_derive::TreeHash;
+
+use super::execution_payload_header::ExecutionPayloadHeader;
+use crate::{
+    beacon_block_header::BeaconBlockHeader,
+    checkpoint::Checkpoint,
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, SlotsPerEth1VotingPeriod,
+        SlotsPerHistoricalRoot,
+    },
+    sync_committee::SyncCommittee,
+    validator::Validator,
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconState {
+    // Versioning
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub historical_roots: VariableList<B256, U16777216>,
+
+    // Eth1
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: VariableList<Eth1Data, SlotsPerEth1VotingPeriod>,
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: VariableList<Validator, U1099511627776>,
+    pub balances: VariableList<u64, U1099511627776>,
+
+    // Randomness
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
+
+    // Slashings
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
+
+    // Participation
+    pub previous_epoch_participation: VariableList<u8, U1099511627776>,
+    pub current_epoch_participation: VariableList<u8, U1099511627776>,
+
+    // Finality
+    pub justification_bits: BitVector<U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    pub inactivity_scores: VariableList<u64, U1099511627776>,
+
+    // Sync
+    pub current_sync_committee: Arc<SyncCommittee>,
+    pub next_sync_committee: Arc<SyncCommittee>,
+
+    // Execution
+    pub latest_execution_payload_header: ExecutionPayloadHeader,
+}
@@ -0,0 +1,174 @@
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::FixedVector;
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use crate::{
+    beacon_block_header::SignedBeaconBlockHeader,
+    constants::{
+        BLOB_KZG_COMMITMENTS_GINDEX, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    },
+    deneb::beacon_state::is_valid_merkle_branch,
+    electra::{beacon_block::SignedBeaconBlock, beacon_block_body::BeaconBlockBody},
+    execution_engine::rpc_types::get_blobs::Blob,
+    kzg_commitment::KZGCommitment,
+    merkle_proof::{MerkleProof, concat_generalized_indices},
+    polynomial_commitments::kzg_proof::KZGProof,
+    preset::KzgCommitmentInclusionProofDepth,
+};
+
+#[derive(
+    Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize, Encode, Decode, TreeHash,
+)]
+pub struct BlobIdentifier {
+    pub block_root: B256,
+    pub index: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BlobSidecar {
+    pub index: u64,
+    pub blob: Blob,
+    pub kzg_commitment: KZGCommitment,
+    pub kzg_proof: KZGProof,
+    pub signed_block_header: SignedBeaconBlockHeader,
+    pub kzg_commitment_inclusion_proof: FixedVector<B256, KzgCommitmentInclusionProofDepth>,
+}
+
+impl BlobSidecar {
+    /// Check ``kzg_commitment`` is the ``index``-th of the ``blob_kzg_commitments`` of the block
+    /// body whose root is in ``signed_block_header``.
+    pub fn verify_blob_sidecar_inclusion_proof(&self) -> bool {
+        let gindex = get_blob_kzg_commitment_generalized_index(self.index);
+        is_valid_merkle_branch(
+            self.kzg_commitment.tree_hash_root(),
+            &self.kzg_commitment_inclusion_proof,
+            KZG_COMMITMENT_INCLUSION_PROOF_DEPTH,
+            gindex % (1 << KZG_COMMITMENT_INCLUSION_PROOF_DEPTH),
+            self.signed_block_header.message.body_root,
+        )
+    }
+
+    pub fn blob_identifier(&self) -> BlobIdentifier {
+        BlobIdentifier {
+            block_root: self.signed_block_header.message.tree_hash_root(),
+            index: self.index,
+        }
+    }
+}
+
+/// Return the generalized index of the ``index``-th of the ``blob_kzg_commitments`` of a
+/// ``BeaconBlockBody``.
+pub fn get_blob_kzg_commitment_generalized_index(index: u64) -> u64 {
+    concat_generalized_indices(
+        concat_generalized_indices(BLOB_KZG_COMMITMENTS_GINDEX, 2),
+        MAX_BLOB_COMMITMENTS_PER_BLOCK + index,
+    )
+}
+
+/// Return the branch proving the ``index``-th of the ``blob_kzg_commitments`` of ``body`` against
+/// its root.
+pub fn compute_kzg_commitment_inclusion_proof(
+    body: &BeaconBlockBody,
+    index: u64,
+) -> anyhow::Result<FixedVector<B256, KzgCommitmentInclusionProofDepth>> {
+    let gindex =
+        body.get_generalized_index(&["blob_kzg_commitments", index.to_string().as_str()])?;
+    FixedVector::new(body.compute_merkle_proof(gindex)?.branch)
+        .map_err(|err| anyhow!("Unexpected inclusion proof depth: {err:?}"))
+}
+
+/// Return the sidecars of the blobs of ``signed_block``, ``blobs`` and ``blob_kzg_proofs`` being
+/// in the order of its ``blob_kzg_commitments``.
+pub fn get_blob_sidecars(
+    signed_block: &SignedBeaconBlock,
+    blobs: Vec<Blob>,
+    blob_kzg_proofs: Vec<KZGProof>,
+) -> anyhow::Result<Vec<BlobSidecar>> {
+    let block = &signed_block.message;
+    let blob_kzg_commitments = &block.body.blob_kzg_commitments;
+    ensure!(
+        blobs.len() == blob_kzg_commitments.len() && blob_kzg_proofs.len() == blobs.len(),
+        "Expected {} blobs and proofs, got {} blobs and {} proofs",
+        blob_kzg_commitments.len(),
+        blobs.len(),
+        blob_kzg_proofs.len()
+    );
+
+    let signed_block_header = SignedBeaconBlockHeader {
+        message: block.block_header(),
+        signature: signed_block.signature.clone(),
+    };
+    blobs
+        .into_iter()
+        .zip(blob_kzg_proofs)
+        .zip(blob_kzg_commitments.iter())
+        .enumerate()
+        .map(|(index, ((blob, kzg_proof), kzg_commitment))| {
+            Ok(BlobSidecar {
+                index: index as u64,
+                blob,
+                kzg_commitment: *kzg_commitment,
+                kzg_proof,
+                signed_block_header: signed_block_header.clone(),
+                kzg_commitment_inclusion_proof: compute_kzg_commitment_inclusion_proof(
+                    &block.body,
+                    index as u64,
+                )?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_bls::BLSSignature;
+    use ssz_types::VariableList;
+
+    use super::*;
+    use crate::electra::beacon_block::BeaconBlock;
+
+    #[test]
+    fn inclusion_proofs_verify_against_the_body_root() {
+        let body = BeaconBlockBody {
+            blob_kzg_commitments: VariableList::new(
+                (0..3).map(|index| KZGCommitment([index; 48])).collect(),
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(
+            body.get_generalized_index(&["blob_kzg_commitments", "2"])
+                .unwrap(),
+            get_blob_kzg_commitment_generalized_index(2)
+        );
+        let signed_block = SignedBeaconBlock {
+            message: BeaconBlock {
+                slot: 1,
+                proposer_index: 0,
+                parent_root: B256::ZERO,
+                state_root: B256::ZERO,
+                body,
+            },
+            signature: BLSSignature::default(),
+        };
+
+        let mut blob_sidecars =
+            get_blob_sidecars(&signed_block, vec![Blob::ZERO; 3], vec![KZGProof::ZERO; 3]).unwrap();
+
+        assert!(
+            blob_sidecars
+                .iter()
+                .all(BlobSidecar::verify_blob_sidecar_inclusion_proof)
+        );
+        blob_sidecars[1].index = 2;
+        assert!(!blob_sidecars[1].verify_blob_sidecar_inclusion_proof());
+        blob_sidecars[2].kzg_commitment = KZGCommitment([0; 48]);
+        assert!(!blob_sidecars[2].verify_blob_sidecar_inclusion_proof());
+    }
+}
@@ -0,0 +1,140 @@
+use std::{cmp::min, sync::Arc};
+
+use alloy_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    BitVector, FixedVector, VariableList,
+    typenum::{U4, U16777216, U1099511627776},
+};
+use tree_hash_derive::TreeHash;
+
+use super::execution_payload_header::ExecutionPayloadHeader;
+use crate::{
+    beacon_block_header::BeaconBlockHeader,
+    checkpoint::Checkpoint,
+    constants::{
+        MAX_EFFECTIVE_BALANCE, MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP, MAX_WITHDRAWALS_PER_PAYLOAD,
+    },
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    historical_summary::HistoricalSummary,
+    misc::compute_epoch_at_slot,
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, SlotsPerEth1VotingPeriod,
+        SlotsPerHistoricalRoot,
+    },
+    sync_committee::SyncCommittee,
+    validator::Validator,
+    withdrawal::Withdrawal,
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconState {
+    // Versioning
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub historical_roots: VariableList<B256, U16777216>,
+
+    // Eth1
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: VariableList<Eth1Data, SlotsPerEth1VotingPeriod>,
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: VariableList<Validator, U1099511627776>,
+    pub balances: VariableList<u64, U1099511627776>,
+
+    // Randomness
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
+
+    // Slashings
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
+
+    // Participation
+    pub previous_epoch_participation: VariableList<u8, U1099511627776>,
+    pub current_epoch_participation: VariableList<u8, U1099511627776>,
+
+    // Finality
+    pub justification_bits: BitVector<U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    pub inactivity_scores: VariableList<u64, U1099511627776>,
+
+    // Sync
+    pub current_sync_committee: Arc<SyncCommittee>,
+    pub next_sync_committee: Arc<SyncCommittee>,
+
+    // Execution
+    pub latest_execution_payload_header: ExecutionPayloadHeader,
+
+    // Withdrawals
+    pub next_withdrawal_index: u64,
+    pub next_withdrawal_validator_index: u64,
+
+    // Deep history valid from Capella onwards.
+    pub historical_summaries: VariableList<HistoricalSummary, U16777216>,
+}
+
+impl BeaconState {
+    pub fn get_expected_withdrawals(&self) -> Vec<Withdrawal> {
+        compute_expected_withdrawals(
+            &self.validators,
+            &self.balances,
+            compute_epoch_at_slot(self.slot),
+            self.next_withdrawal_index,
+            self.next_withdrawal_validator_index,
+        )
+    }
+}
+
+/// Return the withdrawals of the sweep over ``validators`` starting at
+/// ``next_withdrawal_validator_index``, as of Capella.
+pub fn compute_expected_withdrawals(
+    validators: &[Validator],
+    balances: &[u64],
+    epoch: u64,
+    next_withdrawal_index: u64,
+    next_withdrawal_validator_index: u64,
+) -> Vec<Withdrawal> {
+    let mut withdrawal_index = next_withdrawal_index;
+    let mut validator_index = next_withdrawal_validator_index;
+    let mut withdrawals: Vec<Withdrawal> = vec![];
+    let bound = min(validators.len(), MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP);
+    for _ in 0..bound {
+        let validator = &validators[validator_index as usize];
+        let balance = balances[validator_index as usize];
+        if validator.is_fully_withdrawable_validator(balance, epoch) {
+            withdrawals.push(Withdrawal {
+                index: withdrawal_index,
+                validator_index,
+                address: Address::from_slice(&validator.withdrawal_credentials[12..]),
+                amount: balance,
+            });
+            withdrawal_index += 1
+        } else if validator.is_partially_withdrawable_validator(balance) {
+            withdrawals.push(Withdrawal {
+                index: withdrawal_index,
+                validator_index,
+                address: Address::from_slice(&validator.withdrawal_credentials[12..]),
+                amount: balance - MAX_EFFECTIVE_BALANCE,
+            });
+            withdrawal_index += 1
+        }
+        if withdrawals.len() == MAX_WITHDRAWALS_PER_PAYLOAD as usize {
+            break;
+        }
+        validator_index = (validator_index + 1) % validators.len() as u64
+    }
+    withdrawals
+}
@@ -0,0 +1,340 @@
+use std::{fmt::Display, fs, path::Path, str::FromStr, sync::OnceLock};
+
+use alloy_primitives::{Address, address, aliases::B32, fixed_bytes};
+use anyhow::{anyhow, ensure};
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    constants::{FAR_FUTURE_EPOCH, GENESIS_EPOCH},
+    fork_name::ForkName,
+    fork_schedule::{ForkSchedule, ScheduledFork},
+    preset::{PRESET_BASE, Preset},
+};
+
+static CHAIN_SPEC: OnceLock<ChainSpec> = OnceLock::new();
+
+/// Return the chain spec of the process, the default config of the compiled preset until
+/// [`set_chain_spec`] is called.
+pub fn chain_spec() -> &'static ChainSpec {
+    CHAIN_SPEC.get_or_init(ChainSpec::default)
+}
+
+/// Set the chain spec of the process. This must happen at startup, before anything reads it, as
+/// the spec can't change once it is in use.
+pub fn set_chain_spec(chain_spec: ChainSpec) -> anyhow::Result<()> {
+    ensure!(
+        chain_spec.preset_base == PRESET_BASE,
+        "Config is for the {} preset but this binary was compiled with the {PRESET_BASE} preset",
+        chain_spec.preset_base
+    );
+    if let Err(chain_spec) = CHAIN_SPEC.set(chain_spec) {
+        ensure!(
+            CHAIN_SPEC.get() == Some(&chain_spec),
+            "Chain spec is already set to {}",
+            self::chain_spec().config_name
+        );
+    }
+    Ok(())
+}
+
+/// The runtime configuration of a network, as found in the ``config.yaml`` of the consensus specs
+/// and of testnet directories. Fields missing from a config take the value of the default config
+/// of the compiled preset.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct ChainSpec {
+    pub preset_base: String,
+    pub config_name: String,
+
+    // Genesis
+    pub min_genesis_active_validator_count: u64,
+    pub min_genesis_time: u64,
+    #[serde(deserialize_with = "from_str")]
+    pub genesis_fork_version: B32,
+    pub genesis_delay: u64,
+
+    // Forking
+    #[serde(deserialize_with = "from_str")]
+    pub altair_fork_version: B32,
+    pub altair_fork_epoch: u64,
+    #[serde(deserialize_with = "from_str")]
+    pub bellatrix_fork_version: B32,
+    pub bellatrix_fork_epoch: u64,
+    #[serde(deserialize_with = "from_str")]
+    pub capella_fork_version: B32,
+    pub capella_fork_epoch: u64,
+    #[serde(deserialize_with = "from_str")]
+    pub deneb_fork_version: B32,
+    pub deneb_fork_epoch: u64,
+    #[serde(deserialize_with = "from_str")]
+    pub electra_fork_version: B32,
+    pub electra_fork_epoch: u64,
+
+    // Time parameters
+    pub seconds_per_slot: u64,
+    pub seconds_per_eth1_block: u64,
+    pub min_validator_withdrawability_delay: u64,
+    pub shard_committee_period: u64,
+    pub eth1_follow_distance: u64,
+
+    // Validator cycle
+    pub inactivity_score_bias: u64,
+    pub inactivity_score_recovery_rate: u64,
+    pub ejection_balance: u64,
+    pub min_per_epoch_churn_limit: u64,
+    pub churn_limit_quotient: u64,
+    pub max_per_epoch_activation_churn_limit: u64,
+
+    // Fork choice
+    pub proposer_score_boost: u64,
+    pub reorg_head_weight_threshold: u64,
+    pub reorg_parent_weight_threshold: u64,
+    pub reorg_max_epochs_since_finalization: u64,
+
+    // Deposit contract
+    pub deposit_chain_id: u64,
+    pub deposit_network_id: u64,
+    #[serde(deserialize_with = "from_str")]
+    pub deposit_contract_address: Address,
+
+    // Networking
+    pub min_epochs_for_block_requests: u64,
+
+    // Deneb
+    pub max_blobs_per_block: u64,
+
+    // Electra
+    pub min_per_epoch_churn_limit_electra: u64,
+    pub max_per_epoch_activation_exit_churn_limit: u64,
+    pub max_blobs_per_block_electra: u64,
+}
+
+impl ChainSpec {
+    pub fn mainnet() -> Self {
+        Self {
+            preset_base: "mainnet".to_string(),
+            config_name: "mainnet".to_string(),
+            min_genesis_active_validator_count: 16384,
+            min_genesis_time: 1606824000,
+            genesis_fork_version: fixed_bytes!("0x00000000"),
+            genesis_delay: 604800,
+            altair_fork_version: fixed_bytes!("0x01000000"),
+            altair_fork_epoch: 74240,
+            bellatrix_fork_version: fixed_bytes!("0x02000000"),
+            bellatrix_fork_epoch: 144896,
+            capella_fork_version: fixed_bytes!("0x03000000"),
+            capella_fork_epoch: 194048,
+            deneb_fork_version: fixed_bytes!("0x04000000"),
+            deneb_fork_epoch: 269568,
+            electra_fork_version: fixed_bytes!("0x05000000"),
+            electra_fork_epoch: 364032,
+            seconds_per_slot: 12,
+            seconds_per_eth1_block: 14,
+            min_validator_withdrawability_delay: 256,
+            shard_committee_period: 256,
+            eth1_follow_distance: 2048,
+            inactivity_score_bias: 4,
+            inactivity_score_recovery_rate: 16,
+            ejection_balance: 16_000_000_000,
+            min_per_epoch_churn_limit: 4,
+            churn_limit_quotient: 65536,
+            max_per_epoch_activation_churn_limit: 8,
+            proposer_score_boost: 40,
+            reorg_head_weight_threshold: 20,
+            reorg_parent_weight_threshold: 160,
+            reorg_max_epochs_since_finalization: 2,
+            deposit_chain_id: 1,
+            deposit_network_id: 1,
+            deposit_contract_address: address!("0x00000000219ab540356cBB839Cbe05303d7705Fa"),
+            min_epochs_for_block_requests: 33024,
+            max_blobs_per_block: 6,
+            min_per_epoch_churn_limit_electra: 128_000_000_000,
+            max_per_epoch_activation_exit_churn_limit: 256_000_000_000,
+            max_blobs_per_block_electra: 9,
+        }
+    }
+
+    /// The ``minimal`` config of the consensus specs, no fork is scheduled past phase0.
+    pub fn minimal() -> Self {
+        Self {
+            preset_base: "minimal".to_string(),
+            config_name: "minimal".to_string(),
+            min_genesis_active_validator_count: 64,
+            min_genesis_time: 1578009600,
+            genesis_fork_version: fixed_bytes!("0x00000001"),
+            genesis_delay: 300,
+            altair_fork_version: fixed_bytes!("0x01000001"),
+            altair_fork_epoch: FAR_FUTURE_EPOCH,
+            bellatrix_fork_version: fixed_bytes!("0x02000001"),
+            bellatrix_fork_epoch: FAR_FUTURE_EPOCH,
+            capella_fork_version: fixed_bytes!("0x03000001"),
+            capella_fork_epoch: FAR_FUTURE_EPOCH,
+            deneb_fork_version: fixed_bytes!("0x04000001"),
+            deneb_fork_epoch: FAR_FUTURE_EPOCH,
+            electra_fork_version: fixed_bytes!("0x05000001"),
+            electra_fork_epoch: FAR_FUTURE_EPOCH,
+            seconds_per_slot: 6,
+            shard_committee_period: 64,
+            eth1_follow_distance: 16,
+            min_per_epoch_churn_limit: 2,
+            churn_limit_quotient: 32,
+            max_per_epoch_activation_churn_limit: 4,
+            deposit_chain_id: 5,
+            deposit_network_id: 5,
+            deposit_contract_address: address!("0x1234567890123456789012345678901234567890"),
+            min_epochs_for_block_requests: 272,
+            min_per_epoch_churn_limit_electra: 64_000_000_000,
+            max_per_epoch_activation_exit_churn_limit: 128_000_000_000,
+            ..Self::mainnet()
+        }
+    }
+
+    /// Load a ``config.yaml``, which must be for the compiled preset.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let chain_spec: Self = serde_yaml::from_str(&fs::read_to_string(path)?)
+            .map_err(|err| anyhow!("Failed to parse config {}: {err}", path.display()))?;
+        ensure!(
+            chain_spec.preset_base == PRESET_BASE,
+            "Config {} is for the {} preset but this binary was compiled with the {PRESET_BASE} \
+             preset",
+            path.display(),
+            chain_spec.preset_base
+        );
+        Ok(chain_spec)
+    }
+
+    /// Load a ``config.yaml`` along with the preset files it is meant to be used with, which must
+    /// match the compiled preset.
+    pub fn from_files(config_path: &Path, preset_path: &Path) -> anyhow::Result<Self> {
+        Preset::from_dir(preset_path)?.ensure_compiled()?;
+        Self::from_file(config_path)
+    }
+
+    pub fn fork_version(&self, fork_name: ForkName) -> B32 {
+        match fork_name {
+            ForkName::Phase0 => self.genesis_fork_version,
+            ForkName::Altair => self.altair_fork_version,
+            ForkName::Bellatrix => self.bellatrix_fork_version,
+            ForkName::Capella => self.capella_fork_version,
+            ForkName::Deneb => self.deneb_fork_version,
+            ForkName::Electra => self.electra_fork_version,
+        }
+    }
+
+    pub fn fork_epoch(&self, fork_name: ForkName) -> u64 {
+        match fork_name {
+            ForkName::Phase0 => GENESIS_EPOCH,
+            ForkName::Altair => self.altair_fork_epoch,
+            ForkName::Bellatrix => self.bellatrix_fork_epoch,
+            ForkName::Capella => self.capella_fork_epoch,
+            ForkName::Deneb => self.deneb_fork_epoch,
+            ForkName::Electra => self.electra_fork_epoch,
+        }
+    }
+
+    /// Return the maximum number of blobs of a block of ``epoch``.
+    pub fn max_blobs_per_block_at_epoch(&self, epoch: u64) -> u64 {
+        match epoch >= self.electra_fork_epoch {
+            true => self.max_blobs_per_block_electra,
+            false => self.max_blobs_per_block,
+        }
+    }
+
+    /// Return the fork schedule of the config, forks set to ``FAR_FUTURE_EPOCH`` are included but
+    /// never active.
+    pub fn fork_schedule(&self) -> ForkSchedule {
+        ForkSchedule::new(
+            [
+                ForkName::Phase0,
+                ForkName::Altair,
+                ForkName::Bellatrix,
+                ForkName::Capella,
+                ForkName::Deneb,
+                ForkName::Electra,
+            ]
+            .into_iter()
+            .map(|fork_name| ScheduledFork {
+                fork_name,
+                version: self.fork_version(fork_name),
+                epoch: self.fork_epoch(fork_name),
+            })
+            .collect(),
+        )
+    }
+}
+
+impl Default for ChainSpec {
+    #[cfg(not(feature = "minimal"))]
+    fn default() -> Self {
+        Self::mainnet()
+    }
+
+    #[cfg(feature = "minimal")]
+    fn default() -> Self {
+        Self::minimal()
+    }
+}
+
+/// Deserialize a value through its string form, fork versions and addresses in ``config.yaml``
+/// are unquoted hex which YAML would otherwise read as integers.
+fn from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_config_yaml() {
+        let config = "PRESET_BASE: 'mainnet'\nCONFIG_NAME: 'hoodi'\nGENESIS_FORK_VERSION: \
+                      0x10000910\nELECTRA_FORK_EPOCH: 2048\nSECONDS_PER_SLOT: 12\n\
+                      DEPOSIT_CONTRACT_ADDRESS: 0x00000000219ab540356cBB839Cbe05303d7705Fa\n\
+                      TERMINAL_TOTAL_DIFFICULTY: 0\n";
+        let chain_spec: ChainSpec = serde_yaml::from_str(config).unwrap();
+
+        assert_eq!(chain_spec.config_name, "hoodi");
+        assert_eq!(chain_spec.genesis_fork_version, fixed_bytes!("0x10000910"));
+        assert_eq!(chain_spec.electra_fork_epoch, 2048);
+        assert_eq!(
+            chain_spec.deposit_contract_address,
+            ChainSpec::mainnet().deposit_contract_address
+        );
+        assert_eq!(
+            chain_spec.churn_limit_quotient,
+            ChainSpec::default().churn_limit_quotient
+        );
+    }
+
+    #[test]
+    fn fork_schedule_skips_unscheduled_forks() {
+        let fork_schedule = ChainSpec::minimal().fork_schedule();
+
+        assert_eq!(
+            fork_schedule.fork_name_at_epoch(1_000_000),
+            ForkName::Phase0
+        );
+        assert_eq!(
+            ChainSpec::mainnet()
+                .fork_schedule()
+                .fork_name_at_epoch(364032),
+            ForkName::Electra
+        );
+    }
+
+    #[test]
+    fn max_blobs_per_block_follows_the_fork() {
+        let chain_spec = ChainSpec::mainnet();
+
+        assert_eq!(chain_spec.max_blobs_per_block_at_epoch(364031), 6);
+        assert_eq!(chain_spec.max_blobs_per_block_at_epoch(364032), 9);
+    }
+}
@@ -5,6 +5,7 @@ use tree_hash_derive::TreeHash;
 
 #[derive(
     Debug,
+    Default,
     Eq,
     Hash,
     PartialEq,
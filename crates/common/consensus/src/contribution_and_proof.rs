@@ -0,0 +1,19 @@
+use ream_bls::BLSSignature;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+use crate::sync_committee_contribution::SyncCommitteeContribution;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SignedContributionAndProof {
+    pub message: ContributionAndProof,
+    pub signature: BLSSignature,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct ContributionAndProof {
+    pub aggregator_index: u64,
+    pub contribution: SyncCommitteeContribution,
+    pub selection_proof: BLSSignature,
+}
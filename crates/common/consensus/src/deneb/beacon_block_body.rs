@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
     VariableList,
-    typenum::{U2, U16, U128, U4096},
+    typenum::{U2, U16, U128},
 };
 use tree_hash_derive::TreeHash;
 
@@ -12,8 +12,9 @@ use super::execution_payload::ExecutionPayload;
 use crate::{
     attestation::Attestation, attester_slashing::AttesterSlashing,
     bls_to_execution_change::SignedBLSToExecutionChange, deposit::Deposit, eth_1_data::Eth1Data,
-    kzg_commitment::KZGCommitment, proposer_slashing::ProposerSlashing,
-    sync_aggregate::SyncAggregate, voluntary_exit::SignedVoluntaryExit,
+    kzg_commitment::KZGCommitment, preset::MaxBlobCommitmentsPerBlock,
+    proposer_slashing::ProposerSlashing, sync_aggregate::SyncAggregate,
+    voluntary_exit::SignedVoluntaryExit,
 };
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
@@ -35,5 +36,5 @@ pub struct BeaconBlockBody {
     pub sync_aggregate: SyncAggregate,
     pub execution_payload: ExecutionPayload,
     pub bls_to_execution_changes: VariableList<SignedBLSToExecutionChange, U16>,
-    pub blob_kzg_commitments: VariableList<KZGCommitment, U4096>,
+    pub blob_kzg_commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
 }
@@ -3,9 +3,10 @@ use std::{
     collections::HashSet,
     ops::Deref,
     sync::Arc,
+    time::Instant,
 };
 
-use alloy_primitives::{Address, B256, aliases::B32};
+use alloy_primitives::{B256, aliases::B32};
 use anyhow::{anyhow, bail, ensure};
 use ethereum_hashing::{hash, hash_fixed};
 use itertools::Itertools;
@@ -17,7 +18,7 @@ use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
     BitVector, FixedVector, VariableList,
-    typenum::{U4, U2048, U8192, U65536, U16777216, U1099511627776},
+    typenum::{U4, U16777216, U1099511627776},
 };
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
@@ -33,28 +34,26 @@ use crate::{
     attestation_data::AttestationData,
     attester_slashing::AttesterSlashing,
     beacon_block_header::BeaconBlockHeader,
+    beacon_state_helpers::impl_beacon_state_helpers,
     bls_to_execution_change::SignedBLSToExecutionChange,
+    capella::beacon_state::compute_expected_withdrawals,
+    chain_spec::chain_spec,
     checkpoint::Checkpoint,
     constants::{
-        BASE_REWARD_FACTOR, BLS_WITHDRAWAL_PREFIX, CAPELLA_FORK_VERSION, CHURN_LIMIT_QUOTIENT,
-        DEPOSIT_CONTRACT_TREE_DEPTH, DOMAIN_BEACON_ATTESTER, DOMAIN_BEACON_PROPOSER,
-        DOMAIN_BLS_TO_EXECUTION_CHANGE, DOMAIN_DEPOSIT, DOMAIN_RANDAO, DOMAIN_SYNC_COMMITTEE,
-        DOMAIN_VOLUNTARY_EXIT, EFFECTIVE_BALANCE_INCREMENT, EJECTION_BALANCE,
-        EPOCHS_PER_ETH1_VOTING_PERIOD, EPOCHS_PER_HISTORICAL_VECTOR, EPOCHS_PER_SLASHINGS_VECTOR,
-        EPOCHS_PER_SYNC_COMMITTEE_PERIOD, ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH,
-        GENESIS_EPOCH, GENESIS_SLOT, HYSTERESIS_DOWNWARD_MULTIPLIER, HYSTERESIS_QUOTIENT,
-        HYSTERESIS_UPWARD_MULTIPLIER, INACTIVITY_PENALTY_QUOTIENT_BELLATRIX, INACTIVITY_SCORE_BIAS,
-        INACTIVITY_SCORE_RECOVERY_RATE, JUSTIFICATION_BITS_LENGTH, MAX_BLOBS_PER_BLOCK,
-        MAX_COMMITTEES_PER_SLOT, MAX_DEPOSITS, MAX_EFFECTIVE_BALANCE,
-        MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT, MAX_RANDOM_BYTE,
-        MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP, MAX_WITHDRAWALS_PER_PAYLOAD,
-        MIN_ATTESTATION_INCLUSION_DELAY, MIN_EPOCHS_TO_INACTIVITY_PENALTY,
-        MIN_GENESIS_ACTIVE_VALIDATOR_COUNT, MIN_GENESIS_TIME, MIN_PER_EPOCH_CHURN_LIMIT,
-        MIN_SEED_LOOKAHEAD, MIN_SLASHING_PENALTY_QUOTIENT, MIN_VALIDATOR_WITHDRAWABILITY_DELAY,
+        BASE_REWARD_FACTOR, BLS_WITHDRAWAL_PREFIX, DEPOSIT_CONTRACT_TREE_DEPTH,
+        DOMAIN_BEACON_ATTESTER, DOMAIN_BEACON_PROPOSER, DOMAIN_BLS_TO_EXECUTION_CHANGE,
+        DOMAIN_DEPOSIT, DOMAIN_RANDAO, DOMAIN_SYNC_COMMITTEE, DOMAIN_VOLUNTARY_EXIT,
+        EFFECTIVE_BALANCE_INCREMENT, EPOCHS_PER_ETH1_VOTING_PERIOD, EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR, EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH, GENESIS_EPOCH, GENESIS_SLOT,
+        HYSTERESIS_DOWNWARD_MULTIPLIER, HYSTERESIS_QUOTIENT, HYSTERESIS_UPWARD_MULTIPLIER,
+        INACTIVITY_PENALTY_QUOTIENT_BELLATRIX, JUSTIFICATION_BITS_LENGTH, MAX_DEPOSITS,
+        MAX_EFFECTIVE_BALANCE, MAX_RANDOM_BYTE, MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP,
+        MAX_WITHDRAWALS_PER_PAYLOAD, MIN_ATTESTATION_INCLUSION_DELAY,
+        MIN_EPOCHS_TO_INACTIVITY_PENALTY, MIN_SEED_LOOKAHEAD, MIN_SLASHING_PENALTY_QUOTIENT,
         PARTICIPATION_FLAG_WEIGHTS, PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX,
-        PROPOSER_REWARD_QUOTIENT, PROPOSER_WEIGHT, SECONDS_PER_SLOT, SHARD_COMMITTEE_PERIOD,
-        SLOTS_PER_EPOCH, SLOTS_PER_HISTORICAL_ROOT, SYNC_COMMITTEE_SIZE, SYNC_REWARD_WEIGHT,
-        TARGET_COMMITTEE_SIZE, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX,
+        PROPOSER_REWARD_QUOTIENT, PROPOSER_WEIGHT, SLOTS_PER_EPOCH, SLOTS_PER_HISTORICAL_ROOT,
+        SYNC_COMMITTEE_SIZE, SYNC_REWARD_WEIGHT, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX,
         TIMELY_TARGET_FLAG_INDEX, UINT64_MAX, UINT64_MAX_SQRT, WEIGHT_DENOMINATOR,
         WHISTLEBLOWER_REWARD_QUOTIENT,
     },
@@ -68,14 +67,26 @@ use crate::{
     indexed_attestation::IndexedAttestation,
     kzg_commitment::{KZGCommitment, VERSIONED_HASH_VERSION_KZG},
     misc::{
-        compute_activation_exit_epoch, compute_committee, compute_domain, compute_epoch_at_slot,
+        compute_activation_exit_epoch, compute_domain, compute_epoch_at_slot,
         compute_shuffled_index, compute_signing_root, compute_start_slot_at_epoch,
-        is_sorted_and_unique,
+        compute_weak_subjectivity_period, is_sorted_and_unique,
     },
     predicates::is_slashable_attestation_data,
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, SlotsPerEth1VotingPeriod,
+        SlotsPerHistoricalRoot,
+    },
     proposer_slashing::ProposerSlashing,
+    shuffling_cache::{
+        Shuffling, ShufflingKey, get_or_compute_proposers, get_or_compute_shuffling,
+    },
+    signature_sets::{SignatureSet, SignatureVerifier},
+    state_transition::{BlockSignatureStrategy, StateTransitionTimings},
     sync_aggregate::SyncAggregate,
     sync_committee::SyncCommittee,
+    tree_hash_cache::{
+        BeaconStateTreeHashCache, BeaconStateTreeHashCacheCell, merkleize_field_roots,
+    },
     validator::Validator,
     voluntary_exit::SignedVoluntaryExit,
     withdrawal::Withdrawal,
@@ -91,14 +102,14 @@ pub struct BeaconState {
 
     // History
     pub latest_block_header: BeaconBlockHeader,
-    pub block_roots: FixedVector<B256, U8192>,
-    pub state_roots: FixedVector<B256, U8192>,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
     /// Frozen in Capella, replaced by historical_summaries
     pub historical_roots: VariableList<B256, U16777216>,
 
     // Eth1
     pub eth1_data: Eth1Data,
-    pub eth1_data_votes: VariableList<Eth1Data, U2048>,
+    pub eth1_data_votes: VariableList<Eth1Data, SlotsPerEth1VotingPeriod>,
     pub eth1_deposit_index: u64,
 
     // Registry
@@ -106,10 +117,10 @@ pub struct BeaconState {
     pub balances: VariableList<u64, U1099511627776>,
 
     // Randomness
-    pub randao_mixes: FixedVector<B256, U65536>,
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
 
     // Slashings
-    pub slashings: FixedVector<u64, U8192>,
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
 
     // Participation
     pub previous_epoch_participation: VariableList<u8, U1099511627776>,
@@ -137,84 +148,26 @@ pub struct BeaconState {
 
     // Deep history valid from Capella onwards.
     pub historical_summaries: VariableList<HistoricalSummary, U16777216>,
-}
-
-impl BeaconState {
-    /// Return the current epoch.
-    pub fn get_current_epoch(&self) -> u64 {
-        compute_epoch_at_slot(self.slot)
-    }
-
-    /// Return the previous epoch (unless the current epoch is ``GENESIS_EPOCH``).
-    pub fn get_previous_epoch(&self) -> u64 {
-        let current_epoch = self.get_current_epoch();
-        if current_epoch == GENESIS_EPOCH {
-            GENESIS_EPOCH
-        } else {
-            current_epoch - 1
-        }
-    }
-
-    /// Return the block root at the start of a recent ``epoch``.
-    pub fn get_block_root(&self, epoch: u64) -> anyhow::Result<B256> {
-        self.get_block_root_at_slot(compute_start_slot_at_epoch(epoch))
-    }
-
-    /// Return the block root at a recent ``slot``.
-    pub fn get_block_root_at_slot(&self, slot: u64) -> anyhow::Result<B256> {
-        ensure!(
-            slot < self.slot && self.slot <= slot + SLOTS_PER_HISTORICAL_ROOT,
-            "slot given was outside of block_roots range"
-        );
-        Ok(self.block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT) as usize])
-    }
 
-    /// Return the randao mix at a recent ``epoch``.
-    pub fn get_randao_mix(&self, epoch: u64) -> B256 {
-        self.randao_mixes[(epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize]
-    }
+    /// Not part of the state, see ``BeaconStateTreeHashCacheCell``.
+    #[serde(skip)]
+    #[ssz(skip_serializing, skip_deserializing)]
+    #[tree_hash(skip_hashing)]
+    pub tree_hash_cache: BeaconStateTreeHashCacheCell,
+}
 
-    /// Return the sequence of active validator indices at ``epoch``.
-    pub fn get_active_validator_indices(&self, epoch: u64) -> Vec<u64> {
-        self.validators
-            .iter()
-            .enumerate()
-            .filter_map(|(i, v)| {
-                if v.is_active_validator(epoch) {
-                    Some(i as u64)
-                } else {
-                    None
-                }
-            })
-            .collect()
-    }
+impl_beacon_state_helpers!();
 
+impl BeaconState {
     /// Return the validator churn limit for the current epoch.
     pub fn get_validator_churn_limit(&self) -> u64 {
         let active_validator_indices = self.get_active_validator_indices(self.get_current_epoch());
         max(
-            MIN_PER_EPOCH_CHURN_LIMIT,
-            active_validator_indices.len() as u64 / CHURN_LIMIT_QUOTIENT,
+            chain_spec().min_per_epoch_churn_limit,
+            active_validator_indices.len() as u64 / chain_spec().churn_limit_quotient,
         )
     }
 
-    /// Return the seed at ``epoch``.
-    pub fn get_seed(&self, epoch: u64, domain_type: B32) -> B256 {
-        let mix =
-            self.get_randao_mix(epoch + EPOCHS_PER_HISTORICAL_VECTOR - MIN_SEED_LOOKAHEAD - 1);
-        let epoch_with_index =
-            [domain_type.as_slice(), &epoch.to_le_bytes(), mix.as_slice()].concat();
-        B256::from(hash_fixed(&epoch_with_index))
-    }
-
-    /// Return the number of committees in each slot for the given ``epoch``.
-    pub fn get_committee_count_per_slot(&self, epoch: u64) -> u64 {
-        (self.get_active_validator_indices(epoch).len() as u64
-            / SLOTS_PER_EPOCH
-            / TARGET_COMMITTEE_SIZE)
-            .clamp(1, MAX_COMMITTEES_PER_SLOT)
-    }
-
     /// Return from ``indices`` a random index sampled by effective balance
     pub fn compute_proposer_index(&self, indices: &[u64], seed: B256) -> anyhow::Result<u64> {
         ensure!(!indices.is_empty(), "Index must be less than index_count");
@@ -240,79 +193,22 @@ impl BeaconState {
         }
     }
 
-    /// Return the beacon proposer index at the current slot.
-    pub fn get_beacon_proposer_index(&self) -> anyhow::Result<u64> {
-        let epoch = self.get_current_epoch();
-        let seed = B256::from(hash_fixed(
-            &[
-                self.get_seed(epoch, DOMAIN_BEACON_PROPOSER).as_slice(),
-                &self.slot.to_le_bytes(),
-            ]
-            .concat(),
-        ));
-        let indices = self.get_active_validator_indices(epoch);
-        self.compute_proposer_index(&indices, seed)
-    }
-
-    /// Return the combined effective balance of the ``indices``.
-    /// ``EFFECTIVE_BALANCE_INCREMENT`` Gwei minimum to avoid divisions by zero.
-    /// Math safe up to ~10B ETH, after which this overflows uint64.
-    pub fn get_total_balance(&self, indices: HashSet<u64>) -> u64 {
-        max(
-            EFFECTIVE_BALANCE_INCREMENT,
-            indices
-                .iter()
-                .map(|index| self.validators[*index as usize].effective_balance)
-                .sum(),
-        )
-    }
-
-    /// Return the combined effective balance of the active validators.
-    /// Note: ``get_total_balance`` returns ``EFFECTIVE_BALANCE_INCREMENT`` Gwei minimum to avoid
-    /// divisions by zero.
-    pub fn get_total_active_balance(&self) -> u64 {
-        self.get_total_balance(
-            self.get_active_validator_indices(self.get_current_epoch())
-                .into_iter()
-                .collect::<HashSet<_>>(),
-        )
-    }
-
-    /// Return the signature domain (fork version concatenated with domain type) of a message.
-    pub fn get_domain(&self, domain_type: B32, epoch: Option<u64>) -> B256 {
-        let epoch = match epoch {
-            Some(epoch) => epoch,
-            None => self.get_current_epoch(),
-        };
-        let fork_version = if epoch < self.fork.epoch {
-            self.fork.previous_version
-        } else {
-            self.fork.current_version
-        };
-        compute_domain(
-            domain_type,
-            Some(fork_version),
-            Some(self.genesis_validators_root),
-        )
-    }
-
-    /// Return the beacon committee at ``slot`` for ``index``.
-    pub fn get_beacon_committee(&self, slot: u64, index: u64) -> anyhow::Result<Vec<u64>> {
-        let epoch = compute_epoch_at_slot(slot);
-        let committees_per_slot = self.get_committee_count_per_slot(epoch);
-        compute_committee(
-            &self.get_active_validator_indices(epoch),
-            self.get_seed(epoch, DOMAIN_BEACON_ATTESTER),
-            (slot % SLOTS_PER_EPOCH) * committees_per_slot + index,
-            committees_per_slot * SLOTS_PER_EPOCH,
-        )
-    }
-
     /// Check if ``indexed_attestation`` is not empty, has sorted and unique indices and has a valid
     /// aggregate signature.
     pub fn is_valid_indexed_attestation(
         &self,
         indexed_attestation: &IndexedAttestation,
+    ) -> anyhow::Result<bool> {
+        self.is_valid_indexed_attestation_with_verifier(
+            indexed_attestation,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn is_valid_indexed_attestation_with_verifier(
+        &self,
+        indexed_attestation: &IndexedAttestation,
+        signature_verifier: &mut SignatureVerifier,
     ) -> anyhow::Result<bool> {
         let indices: Vec<usize> = indexed_attestation
             .attesting_indices
@@ -330,21 +226,20 @@ impl BeaconState {
         );
         let signing_root = compute_signing_root(&indexed_attestation.data, domain);
 
-        indexed_attestation
-            .signature
-            .fast_aggregate_verify(
-                indices
-                    .iter()
-                    .map(|&index| {
-                        self.validators
-                            .get(index)
-                            .map(|validator| &validator.pubkey)
-                            .ok_or(anyhow!("Invalid index"))
-                    })
-                    .collect::<anyhow::Result<Vec<_>>>()?,
-                signing_root.as_ref(),
-            )
-            .map_err(|e| anyhow!("Invalid indexed attestation: {:?}", e))
+        let pubkeys = indices
+            .iter()
+            .map(|&index| {
+                self.validators
+                    .get(index)
+                    .map(|validator| validator.pubkey.clone())
+                    .ok_or(anyhow!("Invalid index"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        signature_verifier.verify(SignatureSet::multiple_pubkeys(
+            indexed_attestation.signature.clone(),
+            pubkeys,
+            signing_root,
+        ))
     }
 
     /// Return the set of attesting indices corresponding to ``data`` and ``bits``.
@@ -380,26 +275,6 @@ impl BeaconState {
         })
     }
 
-    /// Increase the validator balance at index ``index`` by ``delta``.
-    pub fn increase_balance(&mut self, index: u64, delta: u64) -> anyhow::Result<()> {
-        if let Some(balance) = self.balances.get_mut(index as usize) {
-            *balance += delta;
-            Ok(())
-        } else {
-            Err(anyhow!("failed to increase balance"))
-        }
-    }
-
-    /// Decrease the validator balance at index ``index`` by ``delta`` with underflow protection.
-    pub fn decrease_balance(&mut self, index: u64, delta: u64) -> anyhow::Result<()> {
-        if let Some(balance) = self.balances.get_mut(index as usize) {
-            *balance = balance.saturating_sub(delta);
-            Ok(())
-        } else {
-            Err(anyhow!("failed to decrease balance"))
-        }
-    }
-
     /// Initiate if validator already initiated exit.
     pub fn initiate_validator_exit(&mut self, index: u64) -> anyhow::Result<()> {
         // Return if validator already initiated exit
@@ -449,7 +324,7 @@ impl BeaconState {
         validator.exit_epoch = exit_queue_epoch;
         validator.withdrawable_epoch = validator
             .exit_epoch
-            .checked_add(MIN_VALIDATOR_WITHDRAWABILITY_DELAY)
+            .checked_add(chain_spec().min_validator_withdrawability_delay)
             .ok_or(anyhow!("Failed to set withdrawable epoch"))?;
 
         Ok(())
@@ -499,183 +374,21 @@ impl BeaconState {
     }
 
     pub fn is_valid_genesis_state(&self) -> bool {
-        if self.genesis_time < MIN_GENESIS_TIME {
+        if self.genesis_time < chain_spec().min_genesis_time {
             return false;
         }
         if self.get_active_validator_indices(GENESIS_EPOCH).len()
-            < MIN_GENESIS_ACTIVE_VALIDATOR_COUNT as usize
+            < chain_spec().min_genesis_active_validator_count as usize
         {
             return false;
         }
         true
     }
 
-    pub fn add_flag(flags: u8, flag_index: u8) -> u8 {
-        let flag = 1 << flag_index;
-        flags | flag
-    }
-
-    pub fn has_flag(flags: u8, flag_index: u8) -> bool {
-        let flag = 1 << flag_index;
-        flags & flag == flag
-    }
-
-    /// Return the set of validator indices that are both active and unslashed for the given
-    /// ``flag_index`` and ``epoch``.
-    pub fn get_unslashed_participating_indices(
-        &self,
-        flag_index: u8,
-        epoch: u64,
-    ) -> anyhow::Result<HashSet<u64>> {
-        ensure!(
-            epoch == self.get_previous_epoch() || epoch == self.get_current_epoch(),
-            "Epoch must be either the previous or current epoch"
-        );
-        let epoch_participation = if epoch == self.get_current_epoch() {
-            &self.current_epoch_participation
-        } else {
-            &self.previous_epoch_participation
-        };
-        let active_validator_indices = self.get_active_validator_indices(epoch);
-        let mut participating_indices = vec![];
-        for i in active_validator_indices {
-            if Self::has_flag(epoch_participation[i as usize], flag_index) {
-                participating_indices.push(i);
-            }
-        }
-        let filtered_indices: HashSet<u64> = participating_indices
-            .into_iter()
-            .filter(|&index| !self.validators[index as usize].slashed)
-            .collect();
-        Ok(filtered_indices)
-    }
-
-    pub fn process_inactivity_updates(&mut self) -> anyhow::Result<()> {
-        // Skip the genesis epoch as score updates are based on the previous epoch participation
-        if self.get_current_epoch() == GENESIS_EPOCH {
-            return Ok(());
-        }
-        for index in self.get_eligible_validator_indices()? {
-            // Increase the inactivity score of inactive validators
-            if self
-                .get_unslashed_participating_indices(
-                    TIMELY_TARGET_FLAG_INDEX,
-                    self.get_previous_epoch(),
-                )?
-                .contains(&index)
-            {
-                self.inactivity_scores[index as usize] -=
-                    min(1, self.inactivity_scores[index as usize])
-            } else {
-                self.inactivity_scores[index as usize] += INACTIVITY_SCORE_BIAS
-            }
-
-            // Decrease the inactivity score of all eligible validators during a leak-free epoch
-            if !self.is_in_inactivity_leak() {
-                self.inactivity_scores[index as usize] -= min(
-                    INACTIVITY_SCORE_RECOVERY_RATE,
-                    self.inactivity_scores[index as usize],
-                )
-            }
-        }
-        Ok(())
-    }
-
-    pub fn get_base_reward_per_increment(&self) -> u64 {
-        EFFECTIVE_BALANCE_INCREMENT * BASE_REWARD_FACTOR
-            / integer_squareroot(self.get_total_active_balance())
-    }
-
-    /// Return the base reward for the validator defined by ``index`` with respect to the current
-    /// ``state``.
-    pub fn get_base_reward(&self, index: u64) -> u64 {
-        let increments =
-            self.validators[index as usize].effective_balance / EFFECTIVE_BALANCE_INCREMENT;
-        increments * self.get_base_reward_per_increment()
-    }
-
-    pub fn get_proposer_reward(&self, attesting_index: u64) -> u64 {
-        self.get_base_reward(attesting_index) / PROPOSER_REWARD_QUOTIENT
-    }
-
-    pub fn get_finality_delay(&self) -> u64 {
-        self.get_previous_epoch() - self.finalized_checkpoint.epoch
-    }
-
-    pub fn is_in_inactivity_leak(&self) -> bool {
-        self.get_finality_delay() > MIN_EPOCHS_TO_INACTIVITY_PENALTY
-    }
-
-    pub fn get_eligible_validator_indices(&self) -> anyhow::Result<Vec<u64>> {
-        let previous_epoch = self.get_previous_epoch();
-        let mut validator_indices = vec![];
-        for (index, v) in self.validators.iter().enumerate() {
-            if v.is_active_validator(previous_epoch)
-                || (v.slashed && previous_epoch + 1 < v.withdrawable_epoch)
-            {
-                validator_indices.push(index as u64)
-            }
-        }
-        Ok(validator_indices)
-    }
-
     pub fn get_index_for_new_validator(&self) -> u64 {
         self.validators.len() as u64
     }
 
-    /// Return the flag indices that are satisfied by an attestation.
-    pub fn get_attestation_participation_flag_indices(
-        &self,
-        data: &AttestationData,
-        inclusion_delay: u64,
-    ) -> anyhow::Result<Vec<u8>> {
-        let justified_checkpoint = if data.target.epoch == self.get_current_epoch() {
-            self.current_justified_checkpoint
-        } else {
-            self.previous_justified_checkpoint
-        };
-        let is_matching_source = data.source == justified_checkpoint;
-        let is_matching_target =
-            is_matching_source && data.target.root == self.get_block_root(data.target.epoch)?;
-        let is_matching_head = is_matching_target
-            && data.beacon_block_root == self.get_block_root_at_slot(data.slot)?;
-        ensure!(is_matching_source);
-
-        let mut participation_flag_indices = vec![];
-
-        if is_matching_source && inclusion_delay <= integer_squareroot(SLOTS_PER_EPOCH) {
-            participation_flag_indices.push(TIMELY_SOURCE_FLAG_INDEX);
-        }
-        if is_matching_target {
-            participation_flag_indices.push(TIMELY_TARGET_FLAG_INDEX);
-        }
-        if is_matching_head && inclusion_delay == MIN_ATTESTATION_INCLUSION_DELAY {
-            participation_flag_indices.push(TIMELY_HEAD_FLAG_INDEX);
-        }
-
-        Ok(participation_flag_indices)
-    }
-
-    /// Return the inactivity penalty deltas by considering timely target participation flags and
-    /// inactivity scores.
-    pub fn get_inactivity_penalty_deltas(&self) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
-        let rewards = vec![0; self.validators.len()];
-        let mut penalties = vec![0; self.validators.len()];
-        let previous_epoch = self.get_previous_epoch();
-        let matching_target_indices =
-            self.get_unslashed_participating_indices(TIMELY_TARGET_FLAG_INDEX, previous_epoch)?;
-        for index in self.get_eligible_validator_indices()? {
-            if !matching_target_indices.contains(&index) {
-                let penalty_numerator = self.validators[index as usize].effective_balance
-                    * self.inactivity_scores[index as usize];
-                let penalty_denominator =
-                    INACTIVITY_SCORE_BIAS * INACTIVITY_PENALTY_QUOTIENT_BELLATRIX;
-                penalties[index as usize] += penalty_numerator / penalty_denominator;
-            }
-        }
-        Ok((rewards, penalties))
-    }
-
     pub fn process_block_header(&mut self, block: &BeaconBlock) -> anyhow::Result<()> {
         // Verify that the slots match
         ensure!(
@@ -715,37 +428,13 @@ impl BeaconState {
     }
 
     pub fn get_expected_withdrawals(&self) -> Vec<Withdrawal> {
-        let epoch = self.get_current_epoch();
-        let mut withdrawal_index = self.next_withdrawal_index;
-        let mut validator_index = self.next_withdrawal_validator_index;
-        let mut withdrawals: Vec<Withdrawal> = vec![];
-        let bound = min(self.validators.len(), MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP);
-        for _ in 0..bound {
-            let validator = &self.validators[validator_index as usize];
-            let balance = self.balances[validator_index as usize];
-            if validator.is_fully_withdrawable_validator(balance, epoch) {
-                withdrawals.push(Withdrawal {
-                    index: withdrawal_index,
-                    validator_index,
-                    address: Address::from_slice(&validator.withdrawal_credentials[12..]),
-                    amount: balance,
-                });
-                withdrawal_index += 1
-            } else if validator.is_partially_withdrawable_validator(balance) {
-                withdrawals.push(Withdrawal {
-                    index: withdrawal_index,
-                    validator_index,
-                    address: Address::from_slice(&validator.withdrawal_credentials[12..]),
-                    amount: balance - MAX_EFFECTIVE_BALANCE,
-                });
-                withdrawal_index += 1
-            }
-            if withdrawals.len() == MAX_WITHDRAWALS_PER_PAYLOAD as usize {
-                break;
-            }
-            validator_index = (validator_index + 1) % self.validators.len() as u64
-        }
-        withdrawals
+        compute_expected_withdrawals(
+            &self.validators,
+            &self.balances,
+            self.get_current_epoch(),
+            self.next_withdrawal_index,
+            self.next_withdrawal_validator_index,
+        )
     }
 
     pub fn process_withdrawals(&mut self, payload: &ExecutionPayload) -> anyhow::Result<()> {
@@ -876,6 +565,17 @@ impl BeaconState {
     pub fn process_bls_to_execution_change(
         &mut self,
         signed_address_change: &SignedBLSToExecutionChange,
+    ) -> anyhow::Result<()> {
+        self.process_bls_to_execution_change_with_verifier(
+            signed_address_change,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn process_bls_to_execution_change_with_verifier(
+        &mut self,
+        signed_address_change: &SignedBLSToExecutionChange,
+        signature_verifier: &mut SignatureVerifier,
     ) -> anyhow::Result<()> {
         let address_change = &signed_address_change.message;
 
@@ -898,9 +598,11 @@ impl BeaconState {
 
         let signing_root = compute_signing_root(address_change, domain);
         ensure!(
-            signed_address_change
-                .signature
-                .verify(&address_change.from_bls_pubkey, signing_root.as_ref())?,
+            signature_verifier.verify(SignatureSet::single_pubkey(
+                signed_address_change.signature.clone(),
+                address_change.from_bls_pubkey.clone(),
+                signing_root,
+            ))?,
             "BLS Signature verification failed!"
         );
 
@@ -918,12 +620,23 @@ impl BeaconState {
 
     pub fn compute_timestamp_at_slot(&self, slot: u64) -> u64 {
         let slots_since_genesis = slot - GENESIS_SLOT;
-        self.genesis_time + slots_since_genesis * SECONDS_PER_SLOT
+        self.genesis_time + slots_since_genesis * chain_spec().seconds_per_slot
     }
 
     pub fn process_voluntary_exit(
         &mut self,
         signed_voluntary_exit: &SignedVoluntaryExit,
+    ) -> anyhow::Result<()> {
+        self.process_voluntary_exit_with_verifier(
+            signed_voluntary_exit,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn process_voluntary_exit_with_verifier(
+        &mut self,
+        signed_voluntary_exit: &SignedVoluntaryExit,
+        signature_verifier: &mut SignatureVerifier,
     ) -> anyhow::Result<()> {
         let voluntary_exit = &signed_voluntary_exit.message;
         let validator_index = voluntary_exit.validator_index as usize;
@@ -954,7 +667,7 @@ impl BeaconState {
         // Verify the validator has been active long enough
         let earlist_exit_epoch = validator
             .activation_epoch
-            .checked_add(SHARD_COMMITTEE_PERIOD)
+            .checked_add(chain_spec().shard_committee_period)
             .ok_or(anyhow!("Failed to calculate earliest exit epoch"))?;
         ensure!(
             self.get_current_epoch() >= earlist_exit_epoch,
@@ -964,15 +677,17 @@ impl BeaconState {
         // Compute signature domain
         let domain = compute_domain(
             DOMAIN_VOLUNTARY_EXIT,
-            Some(CAPELLA_FORK_VERSION),
+            Some(chain_spec().capella_fork_version),
             Some(self.genesis_validators_root),
         );
         let signing_root = compute_signing_root(voluntary_exit, domain);
 
         ensure!(
-            signed_voluntary_exit
-                .signature
-                .verify(&validator.pubkey, signing_root.as_ref())?,
+            signature_verifier.verify(SignatureSet::single_pubkey(
+                signed_voluntary_exit.signature.clone(),
+                validator.pubkey.clone(),
+                signing_root,
+            ))?,
             "BLS Signature verification failed!"
         );
 
@@ -1010,6 +725,17 @@ impl BeaconState {
     pub fn process_proposer_slashing(
         &mut self,
         proposer_slashing: &ProposerSlashing,
+    ) -> anyhow::Result<()> {
+        self.process_proposer_slashing_with_verifier(
+            proposer_slashing,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn process_proposer_slashing_with_verifier(
+        &mut self,
+        proposer_slashing: &ProposerSlashing,
+        signature_verifier: &mut SignatureVerifier,
     ) -> anyhow::Result<()> {
         let header_1 = &proposer_slashing.signed_header_1.message;
         let header_2 = &proposer_slashing.signed_header_2.message;
@@ -1051,9 +777,11 @@ impl BeaconState {
             let signing_root = compute_signing_root(&signed_header.message, domain);
 
             ensure!(
-                signed_header
-                    .signature
-                    .verify(&proposer.pubkey, signing_root.as_ref())?,
+                signature_verifier.verify(SignatureSet::single_pubkey(
+                    signed_header.signature.clone(),
+                    proposer.pubkey.clone(),
+                    signing_root,
+                ))?,
                 "BLS Signature verification failed!"
             );
         }
@@ -1080,6 +808,17 @@ impl BeaconState {
     pub fn process_attester_slashing(
         &mut self,
         attester_slashing: &AttesterSlashing,
+    ) -> anyhow::Result<()> {
+        self.process_attester_slashing_with_verifier(
+            attester_slashing,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn process_attester_slashing_with_verifier(
+        &mut self,
+        attester_slashing: &AttesterSlashing,
+        signature_verifier: &mut SignatureVerifier,
     ) -> anyhow::Result<()> {
         let attestation_1 = &attester_slashing.attestation_1;
         let attestation_2 = &attester_slashing.attestation_2;
@@ -1092,11 +831,11 @@ impl BeaconState {
 
         // Validate both attestations
         ensure!(
-            self.is_valid_indexed_attestation(attestation_1)?,
+            self.is_valid_indexed_attestation_with_verifier(attestation_1, signature_verifier)?,
             "First attestation is invalid"
         );
         ensure!(
-            self.is_valid_indexed_attestation(attestation_2)?,
+            self.is_valid_indexed_attestation_with_verifier(attestation_2, signature_verifier)?,
             "Second attestation is invalid"
         );
 
@@ -1119,6 +858,14 @@ impl BeaconState {
     }
 
     pub fn process_sync_aggregate(&mut self, sync_aggregate: &SyncAggregate) -> anyhow::Result<()> {
+        self.process_sync_aggregate_with_verifier(sync_aggregate, &mut SignatureVerifier::default())
+    }
+
+    pub fn process_sync_aggregate_with_verifier(
+        &mut self,
+        sync_aggregate: &SyncAggregate,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
         // Verify sync committee aggregate signature signing over the previous slot block root
         let committee_pubkeys = &self.current_sync_committee.pubkeys;
         let mut participant_pubkeys = vec![];
@@ -1141,11 +888,11 @@ impl BeaconState {
             compute_signing_root(self.get_block_root_at_slot(previous_slot)?, domain);
 
         ensure!(
-            eth_fast_aggregate_verify(
-                &participant_pubkeys,
+            signature_verifier.verify(SignatureSet::multiple_pubkeys(
+                sync_aggregate.sync_committee_signature.clone(),
+                participant_pubkeys.into_iter().cloned().collect(),
                 signing_root,
-                &sync_aggregate.sync_committee_signature,
-            )?,
+            ))?,
             "Sync aggregate signature verification failed."
         );
 
@@ -1173,13 +920,14 @@ impl BeaconState {
             committee_indices.push(index);
         }
 
+        let proposer_index = self.get_beacon_proposer_index()?;
         for (participant_index, participation_bit) in committee_indices
             .iter()
             .zip(sync_aggregate.sync_committee_bits.iter())
         {
             if participation_bit {
                 self.increase_balance(*participant_index as u64, participant_reward)?;
-                self.increase_balance(self.get_beacon_proposer_index()?, proposer_reward)?;
+                self.increase_balance(proposer_index, proposer_reward)?;
             } else {
                 self.decrease_balance(*participant_index as u64, participant_reward)?;
             }
@@ -1329,6 +1077,14 @@ impl BeaconState {
     }
 
     pub fn process_randao(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        self.process_randao_with_verifier(body, &mut SignatureVerifier::default())
+    }
+
+    pub fn process_randao_with_verifier(
+        &mut self,
+        body: &BeaconBlockBody,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
         let epoch = self.get_current_epoch();
 
         // Verify RANDAO reveal
@@ -1339,8 +1095,11 @@ impl BeaconState {
             let signing_root =
                 compute_signing_root(epoch, self.get_domain(DOMAIN_RANDAO, Some(epoch)));
             ensure!(
-                body.randao_reveal
-                    .verify(&proposer.pubkey, signing_root.as_ref())?,
+                signature_verifier.verify(SignatureSet::single_pubkey(
+                    body.randao_reveal.clone(),
+                    proposer.pubkey.clone(),
+                    signing_root,
+                ))?,
                 "BLS Signature verification failed!"
             );
 
@@ -1374,6 +1133,14 @@ impl BeaconState {
     }
 
     pub fn process_attestation(&mut self, attestation: &Attestation) -> anyhow::Result<()> {
+        self.process_attestation_with_verifier(attestation, &mut SignatureVerifier::default())
+    }
+
+    pub fn process_attestation_with_verifier(
+        &mut self,
+        attestation: &Attestation,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
         ensure!(
             attestation.data.target.epoch == self.get_previous_epoch()
                 || attestation.data.target.epoch == self.get_current_epoch(),
@@ -1408,7 +1175,10 @@ impl BeaconState {
         )?;
 
         ensure!(
-            self.is_valid_indexed_attestation(&self.get_indexed_attestation(attestation)?)?,
+            self.is_valid_indexed_attestation_with_verifier(
+                &self.get_indexed_attestation(attestation)?,
+                signature_verifier,
+            )?,
             "Attestation signature must be valid"
         );
 
@@ -1493,6 +1263,14 @@ impl BeaconState {
     }
 
     pub fn process_operations(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        self.process_operations_with_verifier(body, &mut SignatureVerifier::default())
+    }
+
+    pub fn process_operations_with_verifier(
+        &mut self,
+        body: &BeaconBlockBody,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
         // Verify that outstanding deposits are processed up to the maximum number of deposits
         ensure!(
             body.deposits.len()
@@ -1503,38 +1281,53 @@ impl BeaconState {
         );
 
         for proposer_slashing in body.proposer_slashings.iter() {
-            self.process_proposer_slashing(proposer_slashing)?;
+            self.process_proposer_slashing_with_verifier(proposer_slashing, signature_verifier)?;
         }
         for attester_slashing in body.attester_slashings.iter() {
-            self.process_attester_slashing(attester_slashing)?;
+            self.process_attester_slashing_with_verifier(attester_slashing, signature_verifier)?;
         }
         for attestation in body.attestations.iter() {
-            self.process_attestation(attestation)?;
+            self.process_attestation_with_verifier(attestation, signature_verifier)?;
         }
         for deposit in body.deposits.iter() {
             self.process_deposit(deposit)?;
         }
         for voluntary_exit in body.voluntary_exits.iter() {
-            self.process_voluntary_exit(voluntary_exit)?;
+            self.process_voluntary_exit_with_verifier(voluntary_exit, signature_verifier)?;
         }
         for bls_to_execution_change in body.bls_to_execution_changes.iter() {
-            self.process_bls_to_execution_change(bls_to_execution_change)?;
+            self.process_bls_to_execution_change_with_verifier(
+                bls_to_execution_change,
+                signature_verifier,
+            )?;
         }
 
         Ok(())
     }
 
     pub fn verify_block_signature(&self, signed_block: &SignedBeaconBlock) -> anyhow::Result<bool> {
-        let proposer = &self.validators[signed_block.message.proposer_index as usize];
+        self.verify_block_signature_with_verifier(signed_block, &mut SignatureVerifier::default())
+    }
+
+    pub fn verify_block_signature_with_verifier(
+        &self,
+        signed_block: &SignedBeaconBlock,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<bool> {
+        let proposer = self
+            .validators
+            .get(signed_block.message.proposer_index as usize)
+            .ok_or_else(|| anyhow!("Invalid proposer index"))?;
         let signing_root = compute_signing_root(
             signed_block.message.clone(),
             self.get_domain(DOMAIN_BEACON_PROPOSER, None),
         );
 
-        signed_block
-            .signature
-            .verify(&proposer.pubkey, signing_root.as_ref())
-            .map_err(|e| anyhow!("Invalid block signature: {:?}", e))
+        signature_verifier.verify(SignatureSet::single_pubkey(
+            signed_block.signature.clone(),
+            proposer.pubkey.clone(),
+            signing_root,
+        ))
     }
 
     /// Check if ``validator`` is eligible for activation.
@@ -1547,7 +1340,7 @@ impl BeaconState {
     /// Return the validator activation churn limit for the current epoch.
     pub fn get_validator_activation_churn_limit(&self) -> u64 {
         min(
-            MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT,
+            chain_spec().max_per_epoch_activation_churn_limit,
             self.get_validator_churn_limit(),
         )
     }
@@ -1566,7 +1359,7 @@ impl BeaconState {
             }
 
             if validator.is_active_validator(current_epoch)
-                && validator.effective_balance <= EJECTION_BALANCE
+                && validator.effective_balance <= chain_spec().ejection_balance
             {
                 initiate_validator.push(index as u64);
             }
@@ -1607,93 +1400,6 @@ impl BeaconState {
         Ok(())
     }
 
-    /// Return the deltas for a given ``flag_index`` by scanning through the participation flags.
-    pub fn get_flag_index_deltas(&self, flag_index: u8) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
-        let mut rewards = vec![0; self.validators.len()];
-        let mut penalties = vec![0; self.validators.len()];
-
-        let previous_epoch = self.get_previous_epoch();
-        let unslashed_participating_indices =
-            self.get_unslashed_participating_indices(flag_index, previous_epoch)?;
-        let weight = PARTICIPATION_FLAG_WEIGHTS[flag_index as usize];
-        let unslashed_participating_balance =
-            self.get_total_balance(unslashed_participating_indices.clone());
-        let unslashed_participating_increments =
-            unslashed_participating_balance / EFFECTIVE_BALANCE_INCREMENT;
-        let active_increments = self.get_total_active_balance() / EFFECTIVE_BALANCE_INCREMENT;
-
-        for index in self.get_eligible_validator_indices()? {
-            let base_reward = self.get_base_reward(index);
-
-            if unslashed_participating_indices.contains(&index) {
-                if !self.is_in_inactivity_leak() {
-                    let reward_numerator =
-                        base_reward * weight * unslashed_participating_increments;
-                    rewards[index as usize] +=
-                        reward_numerator / (active_increments * WEIGHT_DENOMINATOR);
-                }
-            } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
-                penalties[index as usize] += base_reward * weight / WEIGHT_DENOMINATOR;
-            }
-        }
-
-        Ok((rewards, penalties))
-    }
-
-    pub fn process_rewards_and_penalties(&mut self) -> anyhow::Result<()> {
-        // No rewards are applied at the end of `GENESIS_EPOCH` because rewards are for work done in
-        // the previous epoch
-        if self.get_current_epoch() == GENESIS_EPOCH {
-            return Ok(());
-        }
-
-        // Get deltas for each flag index and inactivity penalties
-        let mut deltas = vec![];
-
-        // Collect the flag deltas for each participation flag index
-        for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
-            deltas.push(self.get_flag_index_deltas(flag_index as u8)?);
-        }
-
-        // Add the inactivity penalties
-        deltas.push(self.get_inactivity_penalty_deltas()?);
-
-        // Iterate over rewards and penalties for each delta
-        for (rewards, penalties) in deltas {
-            for index in 0..self.validators.len() {
-                self.increase_balance(index as u64, rewards[index])?;
-                self.decrease_balance(index as u64, penalties[index])?;
-            }
-        }
-        Ok(())
-    }
-
-    /// Return the next sync committee, with possible pubkey duplicates.
-    pub fn get_next_sync_committee(&self) -> anyhow::Result<SyncCommittee> {
-        let indices = self.get_next_sync_committee_indices()?;
-        let mut pubkeys = vec![];
-
-        for index in indices {
-            pubkeys.push(self.validators[index as usize].pubkey.clone());
-        }
-
-        let aggregate_pubkey = eth_aggregate_pubkeys(&pubkeys.iter().collect::<Vec<_>>())?;
-
-        Ok(SyncCommittee {
-            pubkeys: FixedVector::from(pubkeys),
-            aggregate_pubkey,
-        })
-    }
-
-    pub fn process_sync_committee_updates(&mut self) -> anyhow::Result<()> {
-        let next_epoch = self.get_current_epoch() + 1;
-        if next_epoch % EPOCHS_PER_SYNC_COMMITTEE_PERIOD == 0 {
-            self.current_sync_committee = self.next_sync_committee.clone();
-            self.next_sync_committee = Arc::new(self.get_next_sync_committee()?);
-        }
-        Ok(())
-    }
-
     pub fn process_participation_flag_updates(&mut self) -> anyhow::Result<()> {
         self.previous_epoch_participation = self.current_epoch_participation.clone();
         self.current_epoch_participation = vec![0; self.validators.len()].into();
@@ -1730,9 +1436,58 @@ impl BeaconState {
         Ok(())
     }
 
+    /// Return ``hash_tree_root(state)``, only rehashing the parts of the large fields which changed
+    /// since ``cache`` was last used.
+    pub fn tree_hash_root_with_cache(&self, cache: &mut BeaconStateTreeHashCache) -> B256 {
+        merkleize_field_roots(&[
+            self.genesis_time.tree_hash_root(),
+            self.genesis_validators_root.tree_hash_root(),
+            self.slot.tree_hash_root(),
+            self.fork.tree_hash_root(),
+            self.latest_block_header.tree_hash_root(),
+            cache.block_roots.recalculate_merkle_root(&self.block_roots),
+            cache.state_roots.recalculate_merkle_root(&self.state_roots),
+            self.historical_roots.tree_hash_root(),
+            self.eth1_data.tree_hash_root(),
+            self.eth1_data_votes.tree_hash_root(),
+            self.eth1_deposit_index.tree_hash_root(),
+            cache.validators.recalculate_list_root(&self.validators),
+            cache.balances.recalculate_list_root(&self.balances),
+            cache
+                .randao_mixes
+                .recalculate_merkle_root(&self.randao_mixes),
+            self.slashings.tree_hash_root(),
+            cache
+                .previous_epoch_participation
+                .recalculate_list_root(&self.previous_epoch_participation),
+            cache
+                .current_epoch_participation
+                .recalculate_list_root(&self.current_epoch_participation),
+            self.justification_bits.tree_hash_root(),
+            self.previous_justified_checkpoint.tree_hash_root(),
+            self.current_justified_checkpoint.tree_hash_root(),
+            self.finalized_checkpoint.tree_hash_root(),
+            cache
+                .inactivity_scores
+                .recalculate_list_root(&self.inactivity_scores),
+            self.current_sync_committee.tree_hash_root(),
+            self.next_sync_committee.tree_hash_root(),
+            self.latest_execution_payload_header.tree_hash_root(),
+            self.next_withdrawal_index.tree_hash_root(),
+            self.next_withdrawal_validator_index.tree_hash_root(),
+            self.historical_summaries.tree_hash_root(),
+        ])
+    }
+
+    /// Return ``hash_tree_root(state)`` using the ``tree_hash_cache`` of the state.
+    pub fn cached_tree_hash_root(&self) -> B256 {
+        self.tree_hash_cache
+            .with(|cache| self.tree_hash_root_with_cache(cache))
+    }
+
     pub fn process_slot(&mut self) -> anyhow::Result<()> {
         // Cache state root
-        let previous_state_root = self.tree_hash_root();
+        let previous_state_root = self.cached_tree_hash_root();
         self.state_roots[(self.slot % SLOTS_PER_HISTORICAL_ROOT) as usize] = previous_state_root;
         // Cache latest block header state root
         if self.latest_block_header.state_root == B256::default() {
@@ -1744,6 +1499,22 @@ impl BeaconState {
         Ok(())
     }
 
+    /// Return the weak subjectivity period for the current ``state``.
+    pub fn compute_weak_subjectivity_period(&self) -> u64 {
+        compute_weak_subjectivity_period(
+            self.get_active_validator_indices(self.get_current_epoch())
+                .len() as u64,
+            self.get_total_active_balance(),
+        )
+    }
+
+    /// Return ``true`` if ``current_epoch`` is within the weak subjectivity period of the state,
+    /// i.e. a node starting from this state is still safe from long range attacks.
+    pub fn is_within_weak_subjectivity_period(&self, current_epoch: u64) -> bool {
+        let ws_state_epoch = compute_epoch_at_slot(self.slot);
+        current_epoch <= ws_state_epoch + self.compute_weak_subjectivity_period()
+    }
+
     pub async fn process_execution_payload(
         &mut self,
         body: &BeaconBlockBody,
@@ -1759,7 +1530,7 @@ impl BeaconState {
         // Verify timestamp
         ensure!(payload.timestamp == self.compute_timestamp_at_slot(self.slot));
         // Verify commitments are under limit
-        ensure!(body.blob_kzg_commitments.len() <= MAX_BLOBS_PER_BLOCK as usize);
+        ensure!(body.blob_kzg_commitments.len() <= chain_spec().max_blobs_per_block as usize);
 
         // Verify the execution payload is valid
         let mut versioned_hashes = vec![];
@@ -1772,30 +1543,13 @@ impl BeaconState {
                     execution_payload: payload.clone(),
                     versioned_hashes,
                     parent_beacon_block_root: self.latest_block_header.parent_root,
+                    execution_requests: None,
                 })
                 .await?
         );
 
         // Cache execution payload header
-        self.latest_execution_payload_header = ExecutionPayloadHeader {
-            parent_hash: payload.parent_hash,
-            fee_recipient: payload.fee_recipient,
-            state_root: payload.state_root,
-            receipts_root: payload.receipts_root,
-            logs_bloom: payload.logs_bloom.clone(),
-            prev_randao: payload.prev_randao,
-            block_number: payload.block_number,
-            gas_limit: payload.gas_limit,
-            gas_used: payload.gas_used,
-            timestamp: payload.timestamp,
-            extra_data: payload.extra_data.clone(),
-            base_fee_per_gas: payload.base_fee_per_gas,
-            block_hash: payload.block_hash,
-            transactions_root: payload.transactions.tree_hash_root(),
-            withdrawals_root: payload.withdrawals.tree_hash_root(),
-            blob_gas_used: payload.blob_gas_used,
-            excess_blob_gas: payload.excess_blob_gas,
-        };
+        self.latest_execution_payload_header = payload.to_execution_payload_header();
 
         Ok(())
     }
@@ -1804,42 +1558,97 @@ impl BeaconState {
         &mut self,
         block: &BeaconBlock,
         execution_engine: &impl ExecutionApi,
+    ) -> anyhow::Result<()> {
+        self.process_block_with_verifier(block, execution_engine, &mut SignatureVerifier::default())
+            .await
+    }
+
+    pub async fn process_block_with_verifier(
+        &mut self,
+        block: &BeaconBlock,
+        execution_engine: &impl ExecutionApi,
+        signature_verifier: &mut SignatureVerifier,
     ) -> anyhow::Result<()> {
         self.process_block_header(block)?;
         self.process_withdrawals(&block.body.execution_payload)?;
         self.process_execution_payload(&block.body, execution_engine)
             .await?;
-        self.process_randao(&block.body)?;
+        self.process_randao_with_verifier(&block.body, signature_verifier)?;
         self.process_eth1_data(&block.body)?;
-        self.process_operations(&block.body)?;
-        self.process_sync_aggregate(&block.body.sync_aggregate)?;
+        self.process_operations_with_verifier(&block.body, signature_verifier)?;
+        self.process_sync_aggregate_with_verifier(&block.body.sync_aggregate, signature_verifier)?;
         Ok(())
     }
 
+    /// The signatures of the operations are always verified, as a single batch. The proposer
+    /// signature and the post state root are only checked when ``validate_result`` is set.
     pub async fn state_transition(
         &mut self,
         signed_block: &SignedBeaconBlock,
         validate_result: bool,
         execution_engine: &impl ExecutionApi,
     ) -> anyhow::Result<()> {
+        self.state_transition_with_strategy(
+            signed_block,
+            BlockSignatureStrategy::VerifyBulk,
+            validate_result,
+            execution_engine,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Apply ``signed_block`` checking the signatures of its operations with ``strategy``. As in
+    /// ``state_transition``, the proposer signature and the post state root are only checked when
+    /// ``validate_result`` is set. Returns the time spent in each phase.
+    pub async fn state_transition_with_strategy(
+        &mut self,
+        signed_block: &SignedBeaconBlock,
+        strategy: BlockSignatureStrategy,
+        validate_result: bool,
+        execution_engine: &impl ExecutionApi,
+    ) -> anyhow::Result<StateTransitionTimings> {
         let block = &signed_block.message;
+        let mut signature_verifier = SignatureVerifier::new(strategy);
+        let mut timings = StateTransitionTimings::default();
 
         // Process slots (including those with no blocks) since block
+        let start = Instant::now();
         self.process_slots(block.slot)?;
+        timings.process_slots = start.elapsed();
 
         // Verify signature
         if validate_result {
-            ensure!(self.verify_block_signature(signed_block)?)
+            let start = Instant::now();
+            ensure!(
+                self.verify_block_signature_with_verifier(signed_block, &mut signature_verifier)?,
+                "Block signature verification failed"
+            );
+            timings.verify_signatures = start.elapsed();
         }
 
         // Process block
-        self.process_block(block, execution_engine).await?;
+        let start = Instant::now();
+        self.process_block_with_verifier(block, execution_engine, &mut signature_verifier)
+            .await?;
+        timings.process_block = start.elapsed();
+
+        // Verify the signatures deferred while processing the block
+        let start = Instant::now();
+        signature_verifier.finish()?;
+        timings.verify_signatures += start.elapsed();
 
         // Verify state root
         if validate_result {
-            ensure!(block.state_root == self.tree_hash_root())
+            let start = Instant::now();
+            ensure!(
+                block.state_root == self.tree_hash_root(),
+                "State root mismatch"
+            );
+            timings.state_root = start.elapsed();
         }
-        Ok(())
+
+        Ok(timings)
     }
 }
 
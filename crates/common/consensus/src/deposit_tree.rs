@@ -0,0 +1,411 @@
+use std::sync::LazyLock;
+
+use alloy_primitives::B256;
+use anyhow::{bail, ensure};
+use ethereum_hashing::hash32_concat;
+use ssz_types::{FixedVector, VariableList, typenum::U33};
+
+use crate::{
+    constants::DEPOSIT_CONTRACT_TREE_DEPTH, deposit_tree_snapshot::DepositTreeSnapshot,
+    eth_1_data::Eth1Data,
+};
+
+const DEPTH: usize = DEPOSIT_CONTRACT_TREE_DEPTH as usize;
+
+/// The roots of the empty subtrees of each height of the deposit contract tree.
+pub static ZERO_HASHES: LazyLock<[B256; DEPTH + 1]> = LazyLock::new(|| {
+    let mut zero_hashes = [B256::ZERO; DEPTH + 1];
+    for height in 1..=DEPTH {
+        zero_hashes[height] = B256::from(hash32_concat(
+            zero_hashes[height - 1].as_slice(),
+            zero_hashes[height - 1].as_slice(),
+        ));
+    }
+    zero_hashes
+});
+
+/// A subtree of the deposit contract tree as described in EIP-4881.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MerkleTree {
+    /// A pruned full subtree of ``deposit_count`` finalized deposits
+    Finalized {
+        root: B256,
+        deposit_count: u64,
+    },
+    Leaf(B256),
+    Node {
+        root: B256,
+        left: Box<MerkleTree>,
+        right: Box<MerkleTree>,
+    },
+    /// An empty subtree of the given height
+    Zero(usize),
+}
+
+impl MerkleTree {
+    fn node(left: MerkleTree, right: MerkleTree) -> Self {
+        Self::Node {
+            root: B256::from(hash32_concat(
+                left.root().as_slice(),
+                right.root().as_slice(),
+            )),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Rebuild the tree of height ``height`` from the roots of its finalized subtrees.
+    fn from_finalized(finalized: &[B256], deposit_count: u64, height: usize) -> Self {
+        if finalized.is_empty() || deposit_count == 0 {
+            return Self::Zero(height);
+        }
+        if deposit_count == 1 << height {
+            return Self::Finalized {
+                root: finalized[0],
+                deposit_count,
+            };
+        }
+
+        let left_deposits = 1 << (height - 1);
+        if deposit_count <= left_deposits {
+            Self::node(
+                Self::from_finalized(finalized, deposit_count, height - 1),
+                Self::Zero(height - 1),
+            )
+        } else {
+            Self::node(
+                Self::Finalized {
+                    root: finalized[0],
+                    deposit_count: left_deposits,
+                },
+                Self::from_finalized(&finalized[1..], deposit_count - left_deposits, height - 1),
+            )
+        }
+    }
+
+    fn root(&self) -> B256 {
+        match self {
+            Self::Finalized { root, .. } | Self::Leaf(root) | Self::Node { root, .. } => *root,
+            Self::Zero(height) => ZERO_HASHES[*height],
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        match self {
+            Self::Finalized { .. } | Self::Leaf(_) => true,
+            Self::Node { right, .. } => right.is_full(),
+            Self::Zero(_) => false,
+        }
+    }
+
+    fn push_leaf(&mut self, leaf: B256, height: usize) -> anyhow::Result<()> {
+        match self {
+            Self::Finalized { .. } | Self::Leaf(_) => bail!("Deposit subtree is full"),
+            Self::Zero(_) if height == 0 => *self = Self::Leaf(leaf),
+            Self::Zero(_) => {
+                let mut left = Self::Zero(height - 1);
+                left.push_leaf(leaf, height - 1)?;
+                *self = Self::node(left, Self::Zero(height - 1));
+            }
+            Self::Node { root, left, right } => {
+                if left.is_full() {
+                    right.push_leaf(leaf, height - 1)?;
+                } else {
+                    left.push_leaf(leaf, height - 1)?;
+                }
+                *root = B256::from(hash32_concat(
+                    left.root().as_slice(),
+                    right.root().as_slice(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Prune the subtrees holding only the first ``deposits_to_finalize`` deposits.
+    fn finalize(&mut self, deposits_to_finalize: u64, height: usize) {
+        let deposits = 1 << height;
+        if deposits <= deposits_to_finalize {
+            *self = Self::Finalized {
+                root: self.root(),
+                deposit_count: deposits,
+            };
+            return;
+        }
+        if let Self::Node { left, right, .. } = self {
+            left.finalize(deposits_to_finalize, height - 1);
+            if deposits_to_finalize > deposits / 2 {
+                right.finalize(deposits_to_finalize - deposits / 2, height - 1);
+            }
+        }
+    }
+
+    /// Append the roots of the finalized subtrees to ``result`` and return their deposit count.
+    fn get_finalized(&self, result: &mut Vec<B256>) -> u64 {
+        match self {
+            Self::Finalized {
+                root,
+                deposit_count,
+            } => {
+                result.push(*root);
+                *deposit_count
+            }
+            Self::Node { left, right, .. } => {
+                left.get_finalized(result) + right.get_finalized(result)
+            }
+            Self::Leaf(_) | Self::Zero(_) => 0,
+        }
+    }
+
+    /// Return the leaf at ``index`` and its branch, starting from the sibling of the leaf.
+    fn generate_proof(&self, index: u64, height: usize) -> anyhow::Result<(B256, Vec<B256>)> {
+        let mut branch = vec![];
+        let mut node = self;
+        for level in (0..height).rev() {
+            let Self::Node { left, right, .. } = node else {
+                bail!("Deposit {index} is finalized or missing");
+            };
+            node = match (index >> level) & 1 {
+                1 => {
+                    branch.push(left.root());
+                    right
+                }
+                _ => {
+                    branch.push(right.root());
+                    left
+                }
+            };
+        }
+        let Self::Leaf(leaf) = node else {
+            bail!("Deposit {index} is finalized or missing");
+        };
+        branch.reverse();
+        Ok((*leaf, branch))
+    }
+}
+
+/// The deposit contract Merkle tree, with the finalization and snapshots of EIP-4881 so only the
+/// deposits which may still be included in blocks have to be kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepositTree {
+    tree: MerkleTree,
+    deposit_count: u64,
+    /// The ``Eth1Data`` and block height of the last finalized execution block
+    finalized_execution_block: Option<(Eth1Data, u64)>,
+}
+
+impl Default for DepositTree {
+    fn default() -> Self {
+        Self {
+            tree: MerkleTree::Zero(DEPTH),
+            deposit_count: 0,
+            finalized_execution_block: None,
+        }
+    }
+}
+
+impl DepositTree {
+    /// Restore the tree from a snapshot, only deposits after the snapshot can be proven.
+    pub fn from_snapshot(snapshot: &DepositTreeSnapshot) -> anyhow::Result<Self> {
+        ensure!(
+            snapshot.finalized.len() == snapshot.deposit_count.count_ones() as usize,
+            "Snapshot of {} deposits has {} finalized roots",
+            snapshot.deposit_count,
+            snapshot.finalized.len()
+        );
+        ensure!(
+            snapshot.calculate_root() == snapshot.deposit_root,
+            "Snapshot deposit root mismatch"
+        );
+
+        Ok(Self {
+            tree: MerkleTree::from_finalized(&snapshot.finalized, snapshot.deposit_count, DEPTH),
+            deposit_count: snapshot.deposit_count,
+            finalized_execution_block: Some((
+                Eth1Data {
+                    deposit_root: snapshot.deposit_root,
+                    deposit_count: snapshot.deposit_count,
+                    block_hash: snapshot.execution_block_hash,
+                },
+                snapshot.execution_block_height,
+            )),
+        })
+    }
+
+    /// Return the snapshot of the tree as of the last finalization.
+    pub fn get_snapshot(&self) -> anyhow::Result<DepositTreeSnapshot> {
+        let Some((eth1_data, execution_block_height)) = &self.finalized_execution_block else {
+            bail!("Deposit tree has not been finalized");
+        };
+        let mut finalized = vec![];
+        let deposit_count = self.tree.get_finalized(&mut finalized);
+
+        Ok(DepositTreeSnapshot {
+            finalized: VariableList::from(finalized),
+            deposit_root: eth1_data.deposit_root,
+            deposit_count,
+            execution_block_hash: eth1_data.block_hash,
+            execution_block_height: *execution_block_height,
+        })
+    }
+
+    pub fn deposit_count(&self) -> u64 {
+        self.deposit_count
+    }
+
+    /// Return the number of deposits pruned by the last finalization.
+    pub fn finalized_deposit_count(&self) -> u64 {
+        self.finalized_execution_block
+            .as_ref()
+            .map_or(0, |(eth1_data, _)| eth1_data.deposit_count)
+    }
+
+    /// Append the ``hash_tree_root`` of a ``DepositData``.
+    pub fn push_leaf(&mut self, leaf: B256) -> anyhow::Result<()> {
+        ensure!(
+            self.deposit_count < (1 << DEPOSIT_CONTRACT_TREE_DEPTH) - 1,
+            "Deposit tree is full"
+        );
+        self.tree.push_leaf(leaf, DEPTH)?;
+        self.deposit_count += 1;
+        Ok(())
+    }
+
+    /// Prune the deposits included in ``eth1_data``, which was read from the finalized
+    /// execution block at ``execution_block_height``.
+    pub fn finalize(
+        &mut self,
+        eth1_data: Eth1Data,
+        execution_block_height: u64,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            eth1_data.deposit_count <= self.deposit_count,
+            "Cannot finalize {} deposits of {}",
+            eth1_data.deposit_count,
+            self.deposit_count
+        );
+        ensure!(
+            eth1_data.deposit_count >= self.finalized_deposit_count(),
+            "Finalized deposit count cannot decrease"
+        );
+        self.tree.finalize(eth1_data.deposit_count, DEPTH);
+        self.finalized_execution_block = Some((eth1_data, execution_block_height));
+        Ok(())
+    }
+
+    /// Return the root of the deposit contract, the Merkle root mixed in with the deposit count,
+    /// which is what ``Eth1Data.deposit_root`` holds.
+    pub fn get_root(&self) -> B256 {
+        B256::from(hash32_concat(
+            self.tree.root().as_slice(),
+            self.length_mix_in().as_slice(),
+        ))
+    }
+
+    /// Return the leaf of the deposit at ``index`` and its ``Deposit.proof`` against
+    /// [`Self::get_root`].
+    pub fn get_proof(&self, index: u64) -> anyhow::Result<(B256, FixedVector<B256, U33>)> {
+        ensure!(
+            index < self.deposit_count,
+            "Deposit {index} is out of range of {} deposits",
+            self.deposit_count
+        );
+        ensure!(
+            index >= self.finalized_deposit_count(),
+            "Deposit {index} is finalized"
+        );
+        let (leaf, mut proof) = self.tree.generate_proof(index, DEPTH)?;
+        proof.push(self.length_mix_in());
+        Ok((leaf, FixedVector::from(proof)))
+    }
+
+    /// Return the ``Deposit.proof`` of the last pushed leaf against [`Self::get_root`].
+    pub fn get_last_proof(&self) -> anyhow::Result<FixedVector<B256, U33>> {
+        ensure!(self.deposit_count > 0, "Deposit tree is empty");
+        Ok(self.get_proof(self.deposit_count - 1)?.1)
+    }
+
+    fn length_mix_in(&self) -> B256 {
+        let mut length = B256::ZERO;
+        length[..8].copy_from_slice(&self.deposit_count.to_le_bytes());
+        length
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ssz_types::typenum::U4294967296;
+    use tree_hash::TreeHash;
+
+    use super::*;
+    use crate::deneb::beacon_state::is_valid_merkle_branch;
+
+    #[test]
+    fn root_and_proofs_match_the_ssz_list() {
+        let mut deposit_tree = DepositTree::default();
+        let mut leaves = vec![];
+        assert_eq!(
+            deposit_tree.get_root(),
+            VariableList::<B256, U4294967296>::empty().tree_hash_root()
+        );
+
+        for index in 0..20u8 {
+            let leaf = B256::repeat_byte(index + 1);
+            leaves.push(leaf);
+            deposit_tree.push_leaf(leaf).unwrap();
+
+            let root = VariableList::<B256, U4294967296>::from(leaves.clone()).tree_hash_root();
+            assert_eq!(deposit_tree.get_root(), root);
+            assert!(is_valid_merkle_branch(
+                leaf,
+                &deposit_tree.get_last_proof().unwrap(),
+                DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+                index as u64,
+                root,
+            ));
+        }
+    }
+
+    #[test]
+    fn finalization_keeps_root_and_later_proofs() {
+        let leaves = (1..=11u8).map(B256::repeat_byte).collect::<Vec<_>>();
+        let mut deposit_tree = DepositTree::default();
+        for leaf in &leaves[..7] {
+            deposit_tree.push_leaf(*leaf).unwrap();
+        }
+        let eth1_data = Eth1Data {
+            deposit_root: deposit_tree.get_root(),
+            deposit_count: 7,
+            block_hash: B256::repeat_byte(0xaa),
+        };
+        for leaf in &leaves[7..] {
+            deposit_tree.push_leaf(*leaf).unwrap();
+        }
+
+        let mut finalized_tree = deposit_tree.clone();
+        finalized_tree.finalize(eth1_data, 100).unwrap();
+        assert_eq!(finalized_tree.get_root(), deposit_tree.get_root());
+        assert!(finalized_tree.get_proof(6).is_err());
+        for index in 7..11 {
+            assert_eq!(
+                finalized_tree.get_proof(index).unwrap(),
+                deposit_tree.get_proof(index).unwrap()
+            );
+        }
+
+        let snapshot = finalized_tree.get_snapshot().unwrap();
+        assert_eq!(snapshot.finalized.len(), 3);
+        assert_eq!(snapshot.calculate_root(), snapshot.deposit_root);
+
+        let mut restored_tree = DepositTree::from_snapshot(&snapshot).unwrap();
+        for leaf in &leaves[7..] {
+            restored_tree.push_leaf(*leaf).unwrap();
+        }
+        assert_eq!(restored_tree.get_root(), deposit_tree.get_root());
+        assert_eq!(
+            restored_tree.get_proof(9).unwrap(),
+            deposit_tree.get_proof(9).unwrap()
+        );
+    }
+}
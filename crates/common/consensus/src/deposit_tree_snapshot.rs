@@ -0,0 +1,50 @@
+use alloy_primitives::B256;
+use ethereum_hashing::hash32_concat;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{VariableList, typenum::U32};
+use tree_hash_derive::TreeHash;
+
+use crate::{constants::DEPOSIT_CONTRACT_TREE_DEPTH, deposit_tree::ZERO_HASHES};
+
+/// The finalized part of the deposit contract tree as described in EIP-4881, enough to resume
+/// following the deposit contract from ``execution_block_height``.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct DepositTreeSnapshot {
+    /// The roots of the finalized subtrees, from left to right
+    pub finalized: VariableList<B256, U32>,
+    pub deposit_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub deposit_count: u64,
+    pub execution_block_hash: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub execution_block_height: u64,
+}
+
+impl DepositTreeSnapshot {
+    /// Return the deposit root of the ``deposit_count`` deposits proven by ``finalized``.
+    pub fn calculate_root(&self) -> B256 {
+        let mut size = self.deposit_count;
+        let mut index = self.finalized.len();
+        let mut root = ZERO_HASHES[0];
+        for height in 0..DEPOSIT_CONTRACT_TREE_DEPTH as usize {
+            root = match size & 1 {
+                1 => {
+                    // Missing roots make the result differ from ``deposit_root``
+                    index = index.saturating_sub(1);
+                    let finalized = self.finalized.get(index).copied().unwrap_or_default();
+                    B256::from(hash32_concat(finalized.as_slice(), root.as_slice()))
+                }
+                _ => B256::from(hash32_concat(
+                    root.as_slice(),
+                    ZERO_HASHES[height].as_slice(),
+                )),
+            };
+            size >>= 1;
+        }
+
+        let mut length = B256::ZERO;
+        length[..8].copy_from_slice(&self.deposit_count.to_le_bytes());
+        B256::from(hash32_concat(root.as_slice(), length.as_slice()))
+    }
+}
@@ -0,0 +1,240 @@
+use anyhow::{anyhow, bail, ensure};
+use ream_bls::{BLSSignature, traits::SignatureAggregatable};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{BitList, BitVector};
+use tree_hash_derive::TreeHash;
+
+use crate::{
+    attestation_data::AttestationData,
+    preset::{MaxCommitteesPerSlot, MaxValidatorsPerSlot},
+};
+
+/// An attestation aggregating over all committees of a slot, the committees taking part are
+/// selected by ``committee_bits`` (EIP-7549).
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct Attestation {
+    /// [Modified in Electra:EIP7549]
+    pub aggregation_bits: BitList<MaxValidatorsPerSlot>,
+    pub data: AttestationData,
+    pub signature: BLSSignature,
+
+    /// [New in Electra:EIP7549]
+    pub committee_bits: BitVector<MaxCommitteesPerSlot>,
+}
+
+impl Attestation {
+    /// Return the indices of the committees set in ``committee_bits``.
+    pub fn get_committee_indices(&self) -> Vec<u64> {
+        self.committee_bits
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bit)| bit.then_some(index as u64))
+            .collect()
+    }
+
+    /// Aggregate ``other`` into a new attestation. Both must attest to the same data with the
+    /// same committees, and have no participant in common.
+    pub fn aggregate(&self, other: &Attestation) -> anyhow::Result<Attestation> {
+        ensure!(
+            self.data == other.data,
+            "Can't aggregate attestations with different data"
+        );
+        ensure!(
+            self.committee_bits == other.committee_bits,
+            "Can't aggregate attestations of different committees"
+        );
+        ensure!(
+            self.aggregation_bits.len() == other.aggregation_bits.len(),
+            "Can't aggregate attestations with aggregation bits of different lengths"
+        );
+        ensure!(
+            self.aggregation_bits
+                .intersection(&other.aggregation_bits)
+                .is_zero(),
+            "Can't aggregate attestations with overlapping participants"
+        );
+
+        Ok(Attestation {
+            aggregation_bits: self.aggregation_bits.union(&other.aggregation_bits),
+            data: self.data.clone(),
+            signature: BLSSignature::aggregate(&[&self.signature, &other.signature])?,
+            committee_bits: self.committee_bits.clone(),
+        })
+    }
+
+    /// Combine ``network_aggregates``, aggregates of distinct committees attesting to the same
+    /// data, into an attestation to include in a block.
+    pub fn compute_on_chain_aggregate(network_aggregates: &[Attestation]) -> anyhow::Result<Self> {
+        let mut aggregates = network_aggregates
+            .iter()
+            .map(|aggregate| {
+                let committee_indices = aggregate.get_committee_indices();
+                ensure!(
+                    committee_indices.len() == 1,
+                    "Network aggregates must be of a single committee"
+                );
+                Ok((committee_indices[0], aggregate))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        aggregates.sort_by_key(|(committee_index, _)| *committee_index);
+        let Some((_, first)) = aggregates.first() else {
+            bail!("Can't compute the on chain aggregate of no aggregates");
+        };
+
+        let mut aggregation_bits = vec![];
+        let mut committee_bits = BitVector::new();
+        for (committee_index, aggregate) in aggregates.iter() {
+            ensure!(
+                aggregate.data == first.data,
+                "Can't aggregate attestations with different data"
+            );
+            ensure!(
+                !committee_bits
+                    .get(*committee_index as usize)
+                    .unwrap_or(false),
+                "Multiple aggregates of committee {committee_index}"
+            );
+            committee_bits
+                .set(*committee_index as usize, true)
+                .map_err(|err| anyhow!("Invalid committee index {committee_index}: {err:?}"))?;
+            aggregation_bits.extend(aggregate.aggregation_bits.iter());
+        }
+        let mut bits = BitList::with_capacity(aggregation_bits.len())
+            .map_err(|err| anyhow!("Too many aggregation bits: {err:?}"))?;
+        for (index, bit) in aggregation_bits.into_iter().enumerate() {
+            bits.set(index, bit)
+                .map_err(|err| anyhow!("Failed to set aggregation bit {index}: {err:?}"))?;
+        }
+
+        Ok(Attestation {
+            aggregation_bits: bits,
+            data: first.data.clone(),
+            signature: BLSSignature::aggregate(
+                &aggregates
+                    .iter()
+                    .map(|(_, aggregate)| &aggregate.signature)
+                    .collect::<Vec<_>>(),
+            )?,
+            committee_bits,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::traits::{Signable, Verifiable};
+
+    use super::*;
+    use crate::{checkpoint::Checkpoint, genesis::interop_private_key};
+
+    fn attestation(validator_index: u64, position: usize, data: &AttestationData) -> Attestation {
+        let mut aggregation_bits = BitList::with_capacity(4).unwrap();
+        aggregation_bits.set(position, true).unwrap();
+        let mut committee_bits = BitVector::new();
+        committee_bits.set(0, true).unwrap();
+        Attestation {
+            aggregation_bits,
+            data: data.clone(),
+            signature: interop_private_key(validator_index)
+                .sign(B256::repeat_byte(1).as_slice())
+                .unwrap(),
+            committee_bits,
+        }
+    }
+
+    #[test]
+    fn aggregate_merges_bits_and_signatures() {
+        let data = AttestationData {
+            slot: 1,
+            index: 0,
+            beacon_block_root: B256::repeat_byte(2),
+            source: Checkpoint::default(),
+            target: Checkpoint::default(),
+        };
+        let first = attestation(0, 0, &data);
+        let second = attestation(1, 2, &data);
+
+        let aggregate = first.aggregate(&second).unwrap();
+
+        assert_eq!(
+            aggregate.aggregation_bits.iter().collect::<Vec<_>>(),
+            vec![true, false, true, false]
+        );
+        let pubkeys = [
+            interop_private_key(0).public_key().unwrap(),
+            interop_private_key(1).public_key().unwrap(),
+        ];
+        assert!(
+            aggregate
+                .signature
+                .fast_aggregate_verify(
+                    pubkeys.iter().collect::<Vec<_>>(),
+                    B256::repeat_byte(1).as_slice()
+                )
+                .unwrap()
+        );
+        assert!(aggregate.aggregate(&first).is_err());
+    }
+
+    #[test]
+    fn on_chain_aggregate_concatenates_committees() {
+        let data = AttestationData {
+            slot: 1,
+            index: 0,
+            beacon_block_root: B256::repeat_byte(2),
+            source: Checkpoint::default(),
+            target: Checkpoint::default(),
+        };
+        let first_committee = attestation(0, 0, &data);
+        let mut third_committee = attestation(1, 1, &data);
+        third_committee.committee_bits = BitVector::new();
+        third_committee.committee_bits.set(2, true).unwrap();
+
+        // The aggregates are combined in the order of their committees
+        let on_chain_aggregate = Attestation::compute_on_chain_aggregate(&[
+            third_committee.clone(),
+            first_committee.clone(),
+        ])
+        .unwrap();
+
+        assert_eq!(on_chain_aggregate.get_committee_indices(), vec![0, 2]);
+        assert_eq!(
+            on_chain_aggregate
+                .aggregation_bits
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![true, false, false, false, false, true, false, false]
+        );
+        let pubkeys = [
+            interop_private_key(0).public_key().unwrap(),
+            interop_private_key(1).public_key().unwrap(),
+        ];
+        assert!(
+            on_chain_aggregate
+                .signature
+                .fast_aggregate_verify(
+                    pubkeys.iter().collect::<Vec<_>>(),
+                    B256::repeat_byte(1).as_slice()
+                )
+                .unwrap()
+        );
+
+        // Each network aggregate must be of a distinct single committee
+        assert!(Attestation::compute_on_chain_aggregate(&[]).is_err());
+        assert!(
+            Attestation::compute_on_chain_aggregate(&[
+                first_committee.clone(),
+                first_committee.clone()
+            ])
+            .is_err()
+        );
+        assert!(Attestation::compute_on_chain_aggregate(&[on_chain_aggregate]).is_err());
+
+        let mut other_data = third_committee;
+        other_data.data.slot = 2;
+        assert!(Attestation::compute_on_chain_aggregate(&[first_committee, other_data]).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use alloy_primitives::B256;
+use anyhow::anyhow;
+use ream_bls::BLSSignature;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    FixedVector, VariableList,
+    typenum::{U1, U4, U8, U16},
+};
+use tree_hash_derive::TreeHash;
+
+use super::{
+    attestation::Attestation, attester_slashing::AttesterSlashing,
+    execution_requests::ExecutionRequests,
+};
+use crate::{
+    bls_to_execution_change::SignedBLSToExecutionChange, constants::EXECUTION_PAYLOAD_GINDEX,
+    deneb::execution_payload::ExecutionPayload, deposit::Deposit, eth_1_data::Eth1Data,
+    kzg_commitment::KZGCommitment, merkle_proof::MerkleProof, preset::MaxBlobCommitmentsPerBlock,
+    proposer_slashing::ProposerSlashing, sync_aggregate::SyncAggregate,
+    voluntary_exit::SignedVoluntaryExit,
+};
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconBlockBody {
+    pub randao_reveal: BLSSignature,
+
+    /// Eth1 data vote
+    pub eth1_data: Eth1Data,
+
+    /// Arbitrary data
+    pub graffiti: B256,
+
+    // Operations
+    pub proposer_slashings: VariableList<ProposerSlashing, U16>,
+    /// [Modified in Electra:EIP7549]
+    pub attester_slashings: VariableList<AttesterSlashing, U1>,
+    /// [Modified in Electra:EIP7549]
+    pub attestations: VariableList<Attestation, U8>,
+    pub deposits: VariableList<Deposit, U16>,
+    pub voluntary_exits: VariableList<SignedVoluntaryExit, U16>,
+    pub sync_aggregate: SyncAggregate,
+    pub execution_payload: ExecutionPayload,
+    pub bls_to_execution_changes: VariableList<SignedBLSToExecutionChange, U16>,
+    pub blob_kzg_commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
+
+    /// [New in Electra]
+    pub execution_requests: ExecutionRequests,
+}
+
+impl BeaconBlockBody {
+    /// Return the branch of ``execution_payload``, at ``EXECUTION_PAYLOAD_GINDEX``.
+    pub fn execution_payload_branch(&self) -> anyhow::Result<FixedVector<B256, U4>> {
+        FixedVector::new(self.compute_merkle_proof(EXECUTION_PAYLOAD_GINDEX)?.branch)
+            .map_err(|err| anyhow!("Unexpected execution payload branch depth: {err:?}"))
+    }
+}
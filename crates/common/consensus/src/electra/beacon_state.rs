@@ -0,0 +1,2822 @@
+use std::{
+    cmp::{Reverse, max, min},
+    collections::{HashMap, HashSet},
+    ops::Deref,
+    sync::Arc,
+    time::Instant,
+};
+
+use alloy_primitives::{Address, B256, aliases::B32};
+use anyhow::{anyhow, bail, ensure};
+use ethereum_hashing::{hash, hash_fixed};
+use itertools::Itertools;
+use ream_bls::{BLSSignature, PubKey, traits::Verifiable};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    BitVector, FixedVector, VariableList,
+    typenum::{U4, U6, U7, U16777216, U134217728, U1099511627776},
+};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::{
+    attestation::Attestation,
+    attester_slashing::AttesterSlashing,
+    beacon_block::{BeaconBlock, SignedBeaconBlock},
+    beacon_block_body::BeaconBlockBody,
+    consolidation_request::ConsolidationRequest,
+    deposit_request::DepositRequest,
+    indexed_attestation::IndexedAttestation,
+    pending_consolidation::PendingConsolidation,
+    pending_deposit::PendingDeposit,
+    pending_partial_withdrawal::PendingPartialWithdrawal,
+    withdrawal_request::WithdrawalRequest,
+};
+use crate::{
+    attestation_data::AttestationData,
+    beacon_block_header::BeaconBlockHeader,
+    beacon_state_helpers::impl_beacon_state_helpers,
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    chain_spec::chain_spec,
+    checkpoint::Checkpoint,
+    constants::{
+        BASE_REWARD_FACTOR, BLS_WITHDRAWAL_PREFIX, COMPOUNDING_WITHDRAWAL_PREFIX,
+        CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA, DEPOSIT_CONTRACT_TREE_DEPTH, DOMAIN_BEACON_ATTESTER,
+        DOMAIN_BEACON_PROPOSER, DOMAIN_BLS_TO_EXECUTION_CHANGE, DOMAIN_DEPOSIT, DOMAIN_RANDAO,
+        DOMAIN_SYNC_COMMITTEE, DOMAIN_VOLUNTARY_EXIT, EFFECTIVE_BALANCE_INCREMENT,
+        EPOCHS_PER_ETH1_VOTING_PERIOD, EPOCHS_PER_HISTORICAL_VECTOR, EPOCHS_PER_SLASHINGS_VECTOR,
+        EPOCHS_PER_SYNC_COMMITTEE_PERIOD, ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH,
+        FINALIZED_ROOT_GINDEX_ELECTRA, FULL_EXIT_REQUEST_AMOUNT, GENESIS_EPOCH, GENESIS_SLOT,
+        HYSTERESIS_DOWNWARD_MULTIPLIER, HYSTERESIS_QUOTIENT, HYSTERESIS_UPWARD_MULTIPLIER,
+        INACTIVITY_PENALTY_QUOTIENT_BELLATRIX, JUSTIFICATION_BITS_LENGTH, MAX_DEPOSITS,
+        MAX_EFFECTIVE_BALANCE_ELECTRA, MAX_PENDING_DEPOSITS_PER_EPOCH,
+        MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP, MAX_RANDOM_VALUE,
+        MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP, MAX_WITHDRAWALS_PER_PAYLOAD, MIN_ACTIVATION_BALANCE,
+        MIN_ATTESTATION_INCLUSION_DELAY, MIN_EPOCHS_TO_INACTIVITY_PENALTY, MIN_SEED_LOOKAHEAD,
+        MIN_SLASHING_PENALTY_QUOTIENT_ELECTRA, NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+        PARTICIPATION_FLAG_WEIGHTS, PENDING_CONSOLIDATIONS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT, PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX,
+        PROPOSER_REWARD_QUOTIENT, PROPOSER_WEIGHT, SAFETY_DECAY, SLOTS_PER_EPOCH,
+        SLOTS_PER_HISTORICAL_ROOT, SYNC_COMMITTEE_SIZE, SYNC_COMMITTEE_SUBNET_COUNT,
+        SYNC_REWARD_WEIGHT, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX,
+        TIMELY_TARGET_FLAG_INDEX, UNSET_DEPOSIT_REQUESTS_START_INDEX, WEIGHT_DENOMINATOR,
+        WHISTLEBLOWER_REWARD_QUOTIENT_ELECTRA,
+    },
+    deneb::{
+        self,
+        beacon_state::{
+            eth_aggregate_pubkeys, integer_squareroot, is_valid_merkle_branch,
+            kzg_commitment_to_versioned_hash,
+        },
+        execution_payload::ExecutionPayload,
+        execution_payload_header::ExecutionPayloadHeader,
+    },
+    deposit::Deposit,
+    deposit_message::DepositMessage,
+    deposit_tree::DepositTree,
+    eth_1_block::Eth1Block,
+    eth_1_data::Eth1Data,
+    execution_engine::{engine_trait::ExecutionApi, new_payload_request::NewPayloadRequest},
+    fork::Fork,
+    helpers::xor,
+    historical_summary::HistoricalSummary,
+    merkle_proof::MerkleProof,
+    misc::{
+        compute_activation_exit_epoch, compute_domain, compute_epoch_at_slot,
+        compute_shuffled_index, compute_signing_root, compute_start_slot_at_epoch,
+        compute_sync_committee_period, is_aggregator, is_sorted_and_unique,
+    },
+    predicates::is_slashable_attestation_data,
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, PendingConsolidationsLimit,
+        PendingPartialWithdrawalsLimit, SlotsPerEth1VotingPeriod, SlotsPerHistoricalRoot,
+    },
+    proposer_slashing::ProposerSlashing,
+    shuffling_cache::{
+        Shuffling, ShufflingKey, get_or_compute_proposers, get_or_compute_shuffling,
+    },
+    signature_sets::{SignatureSet, SignatureVerifier},
+    state_transition::{BlockSignatureStrategy, StateTransitionTimings},
+    sync_aggregate::SyncAggregate,
+    sync_committee::SyncCommittee,
+    tree_hash_cache::{
+        BeaconStateTreeHashCache, BeaconStateTreeHashCacheCell, merkleize_field_roots,
+    },
+    validator::Validator,
+    voluntary_exit::SignedVoluntaryExit,
+    withdrawal::Withdrawal,
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconState {
+    // Versioning
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    /// Frozen in Capella, replaced by historical_summaries
+    pub historical_roots: VariableList<B256, U16777216>,
+
+    // Eth1
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: VariableList<Eth1Data, SlotsPerEth1VotingPeriod>,
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: VariableList<Validator, U1099511627776>,
+    pub balances: VariableList<u64, U1099511627776>,
+
+    // Randomness
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
+
+    // Slashings
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
+
+    // Participation
+    pub previous_epoch_participation: VariableList<u8, U1099511627776>,
+    pub current_epoch_participation: VariableList<u8, U1099511627776>,
+
+    // Finality
+    pub justification_bits: BitVector<U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    pub inactivity_scores: VariableList<u64, U1099511627776>,
+
+    // Sync
+    pub current_sync_committee: Arc<SyncCommittee>,
+    pub next_sync_committee: Arc<SyncCommittee>,
+
+    // Execution
+    pub latest_execution_payload_header: ExecutionPayloadHeader,
+
+    // Withdrawals
+    pub next_withdrawal_index: u64,
+    pub next_withdrawal_validator_index: u64,
+
+    // Deep history valid from Capella onwards.
+    pub historical_summaries: VariableList<HistoricalSummary, U16777216>,
+
+    // Electra
+    /// [New in Electra:EIP6110]
+    pub deposit_requests_start_index: u64,
+    /// [New in Electra:EIP7251]
+    pub deposit_balance_to_consume: u64,
+    /// [New in Electra:EIP7251]
+    pub exit_balance_to_consume: u64,
+    /// [New in Electra:EIP7251]
+    pub earliest_exit_epoch: u64,
+    /// [New in Electra:EIP7251]
+    pub consolidation_balance_to_consume: u64,
+    /// [New in Electra:EIP7251]
+    pub earliest_consolidation_epoch: u64,
+    /// [New in Electra:EIP7251]
+    pub pending_deposits: VariableList<PendingDeposit, U134217728>,
+    /// [New in Electra:EIP7251]
+    pub pending_partial_withdrawals:
+        VariableList<PendingPartialWithdrawal, PendingPartialWithdrawalsLimit>,
+    /// [New in Electra:EIP7251]
+    pub pending_consolidations: VariableList<PendingConsolidation, PendingConsolidationsLimit>,
+
+    /// Not part of the state, see ``BeaconStateTreeHashCacheCell``.
+    #[serde(skip)]
+    #[ssz(skip_serializing, skip_deserializing)]
+    #[tree_hash(skip_hashing)]
+    pub tree_hash_cache: BeaconStateTreeHashCacheCell,
+}
+
+impl_beacon_state_helpers!();
+
+impl BeaconState {
+    /// Return the churn limit for the current epoch.
+    pub fn get_balance_churn_limit(&self) -> u64 {
+        let churn = max(
+            chain_spec().min_per_epoch_churn_limit_electra,
+            self.get_total_active_balance() / chain_spec().churn_limit_quotient,
+        );
+        churn - churn % EFFECTIVE_BALANCE_INCREMENT
+    }
+
+    /// Return the churn limit for the current epoch dedicated to activations and exits.
+    pub fn get_activation_exit_churn_limit(&self) -> u64 {
+        min(
+            chain_spec().max_per_epoch_activation_exit_churn_limit,
+            self.get_balance_churn_limit(),
+        )
+    }
+
+    /// Return the churn limit for the current epoch dedicated to consolidations.
+    pub fn get_consolidation_churn_limit(&self) -> u64 {
+        self.get_balance_churn_limit() - self.get_activation_exit_churn_limit()
+    }
+
+    /// Return the amount queued in pending partial withdrawals for ``validator_index``.
+    pub fn get_pending_balance_to_withdraw(&self, validator_index: u64) -> u64 {
+        self.pending_partial_withdrawals
+            .iter()
+            .filter(|withdrawal| withdrawal.validator_index == validator_index)
+            .map(|withdrawal| withdrawal.amount)
+            .sum()
+    }
+
+    /// Return from ``indices`` a random index sampled by effective balance
+    pub fn compute_proposer_index(&self, indices: &[u64], seed: B256) -> anyhow::Result<u64> {
+        ensure!(!indices.is_empty(), "Index must be less than index_count");
+
+        let mut i: usize = 0;
+        let total = indices.len();
+
+        loop {
+            let candidate_index = indices[compute_shuffled_index(i % total, total, seed)?];
+
+            // [Modified in Electra] Sample with 16 bit random values
+            let seed_with_index = [seed.as_slice(), &(i / 16).to_le_bytes()].concat();
+            let random_bytes = hash(&seed_with_index);
+            let offset = i % 16 * 2;
+            let random_value =
+                u16::from_le_bytes([random_bytes[offset], random_bytes[offset + 1]]) as u64;
+
+            let effective_balance = self.validators[candidate_index as usize].effective_balance;
+
+            if effective_balance * MAX_RANDOM_VALUE >= MAX_EFFECTIVE_BALANCE_ELECTRA * random_value
+            {
+                return Ok(candidate_index);
+            }
+
+            i += 1;
+        }
+    }
+
+    /// Check if the ``selection_proof`` over ``slot`` makes its signer an aggregator of the
+    /// committee ``index`` at ``slot``.
+    pub fn is_aggregator(
+        &self,
+        slot: u64,
+        index: u64,
+        selection_proof: &BLSSignature,
+    ) -> anyhow::Result<bool> {
+        let committee = self.get_beacon_committee(slot, index)?;
+        Ok(is_aggregator(committee.len(), selection_proof))
+    }
+
+    /// Return the time of the start of the current eth1 voting period.
+    pub fn voting_period_start_time(&self) -> u64 {
+        let eth1_voting_period_start_slot =
+            self.slot - self.slot % (EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH);
+        self.compute_timestamp_at_slot(eth1_voting_period_start_slot)
+    }
+
+    /// Return the ``Eth1Data`` to vote for in a block on top of this state, out of the blocks of
+    /// ``eth1_chain`` in ascending order.
+    pub fn get_eth1_vote(&self, eth1_chain: &[Eth1Block]) -> Eth1Data {
+        // Once the transition to deposit requests is complete there is nothing left to vote on
+        if self.eth1_deposit_index == self.deposit_requests_start_index {
+            return self.eth1_data.clone();
+        }
+
+        let period_start = self.voting_period_start_time();
+        let votes_to_consider = eth1_chain
+            .iter()
+            .filter(|block| {
+                block.is_candidate_block(period_start)
+                    && block.deposit_count >= self.eth1_data.deposit_count
+            })
+            .map(Eth1Block::eth1_data)
+            .collect::<Vec<_>>();
+        let valid_votes = self
+            .eth1_data_votes
+            .iter()
+            .filter(|vote| votes_to_consider.contains(vote))
+            .collect::<Vec<_>>();
+
+        // Tiebreak by the earliest vote
+        valid_votes
+            .iter()
+            .max_by_key(|vote| {
+                let count = valid_votes.iter().filter(|other| other == vote).count();
+                let first_index = valid_votes.iter().position(|other| other == *vote);
+                (count, Reverse(first_index))
+            })
+            .map(|vote| (**vote).clone())
+            .or_else(|| votes_to_consider.last().cloned())
+            .unwrap_or_else(|| self.eth1_data.clone())
+    }
+
+    /// Check if ``indexed_attestation`` is not empty, has sorted and unique indices and has a valid
+    /// aggregate signature.
+    pub fn is_valid_indexed_attestation(
+        &self,
+        indexed_attestation: &IndexedAttestation,
+    ) -> anyhow::Result<bool> {
+        self.is_valid_indexed_attestation_with_verifier(
+            indexed_attestation,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn is_valid_indexed_attestation_with_verifier(
+        &self,
+        indexed_attestation: &IndexedAttestation,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<bool> {
+        let indices: Vec<usize> = indexed_attestation
+            .attesting_indices
+            .iter()
+            .map(|&i| i as usize)
+            .collect();
+        // Verify indices are sorted and unique
+        if indices.is_empty() || !is_sorted_and_unique(&indices) {
+            return Ok(false);
+        }
+
+        let domain = self.get_domain(
+            DOMAIN_BEACON_ATTESTER,
+            Some(indexed_attestation.data.target.epoch),
+        );
+        let signing_root = compute_signing_root(&indexed_attestation.data, domain);
+
+        let pubkeys = indices
+            .iter()
+            .map(|&index| {
+                self.validators
+                    .get(index)
+                    .map(|validator| validator.pubkey.clone())
+                    .ok_or(anyhow!("Invalid index"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        signature_verifier.verify(SignatureSet::multiple_pubkeys(
+            indexed_attestation.signature.clone(),
+            pubkeys,
+            signing_root,
+        ))
+    }
+
+    /// Return the set of attesting indices corresponding to ``aggregation_bits`` and
+    /// ``committee_bits``.
+    pub fn get_attesting_indices(&self, attestation: &Attestation) -> anyhow::Result<Vec<u64>> {
+        let mut output = vec![];
+        let mut committee_offset = 0;
+        for committee_index in attestation.get_committee_indices() {
+            let committee = self.get_beacon_committee(attestation.data.slot, committee_index)?;
+            for (i, attester_index) in committee.iter().enumerate() {
+                if attestation
+                    .aggregation_bits
+                    .get(committee_offset + i)
+                    .unwrap_or(false)
+                {
+                    output.push(*attester_index);
+                }
+            }
+            committee_offset += committee.len();
+        }
+        Ok(output.into_iter().unique().collect())
+    }
+
+    /// Return the indexed attestation corresponding to ``attestation``.
+    pub fn get_indexed_attestation(
+        &self,
+        attestation: &Attestation,
+    ) -> anyhow::Result<IndexedAttestation> {
+        let mut attesting_indices = self.get_attesting_indices(attestation)?;
+        attesting_indices.sort();
+        Ok(IndexedAttestation {
+            attesting_indices: attesting_indices.into(),
+            data: attestation.data.clone(),
+            signature: attestation.signature.clone(),
+        })
+    }
+
+    /// Initiate the exit of the validator with index ``index``.
+    pub fn initiate_validator_exit(&mut self, index: u64) -> anyhow::Result<()> {
+        let Some(validator) = self.validators.get(index as usize) else {
+            bail!("could not get validator")
+        };
+
+        // Return if validator already initiated exit
+        if validator.exit_epoch != FAR_FUTURE_EPOCH {
+            return Ok(());
+        }
+
+        // [Modified in Electra:EIP7251] Compute exit queue epoch
+        let exit_queue_epoch =
+            self.compute_exit_epoch_and_update_churn(validator.effective_balance);
+
+        // Set validator exit epoch and withdrawable epoch
+        let validator = &mut self.validators[index as usize];
+        validator.exit_epoch = exit_queue_epoch;
+        validator.withdrawable_epoch = exit_queue_epoch
+            .checked_add(chain_spec().min_validator_withdrawability_delay)
+            .ok_or(anyhow!("Failed to set withdrawable epoch"))?;
+
+        Ok(())
+    }
+
+    /// Return the epoch at which an exit of ``exit_balance`` is processed, consuming the exit
+    /// churn.
+    pub fn compute_exit_epoch_and_update_churn(&mut self, exit_balance: u64) -> u64 {
+        let (earliest_exit_epoch, exit_balance_to_consume) =
+            self.get_exit_epoch_and_churn(exit_balance);
+
+        // Consume the balance and update state variables
+        self.exit_balance_to_consume = exit_balance_to_consume - exit_balance;
+        self.earliest_exit_epoch = earliest_exit_epoch;
+
+        self.earliest_exit_epoch
+    }
+
+    /// Return the epoch at which an exit of ``exit_balance`` would be processed, and the exit
+    /// churn available to it, without consuming the churn.
+    pub fn get_exit_epoch_and_churn(&self, exit_balance: u64) -> (u64, u64) {
+        let mut earliest_exit_epoch = max(
+            self.earliest_exit_epoch,
+            compute_activation_exit_epoch(self.get_current_epoch()),
+        );
+        let per_epoch_churn = self.get_activation_exit_churn_limit();
+
+        // New epoch for exits
+        let mut exit_balance_to_consume = if self.earliest_exit_epoch < earliest_exit_epoch {
+            per_epoch_churn
+        } else {
+            self.exit_balance_to_consume
+        };
+
+        // Exit doesn't fit in the current earliest epoch
+        if exit_balance > exit_balance_to_consume {
+            let balance_to_process = exit_balance - exit_balance_to_consume;
+            let additional_epochs = (balance_to_process - 1) / per_epoch_churn + 1;
+            earliest_exit_epoch += additional_epochs;
+            exit_balance_to_consume += additional_epochs * per_epoch_churn;
+        }
+
+        (earliest_exit_epoch, exit_balance_to_consume)
+    }
+
+    /// Return the epoch at which a consolidation of ``consolidation_balance`` is processed,
+    /// consuming the consolidation churn.
+    pub fn compute_consolidation_epoch_and_update_churn(
+        &mut self,
+        consolidation_balance: u64,
+    ) -> u64 {
+        let mut earliest_consolidation_epoch = max(
+            self.earliest_consolidation_epoch,
+            compute_activation_exit_epoch(self.get_current_epoch()),
+        );
+        let per_epoch_consolidation_churn = self.get_consolidation_churn_limit();
+
+        // New epoch for consolidations
+        let mut consolidation_balance_to_consume =
+            if self.earliest_consolidation_epoch < earliest_consolidation_epoch {
+                per_epoch_consolidation_churn
+            } else {
+                self.consolidation_balance_to_consume
+            };
+
+        // Consolidation doesn't fit in the current earliest epoch
+        if consolidation_balance > consolidation_balance_to_consume {
+            let balance_to_process = consolidation_balance - consolidation_balance_to_consume;
+            let additional_epochs = (balance_to_process - 1) / per_epoch_consolidation_churn + 1;
+            earliest_consolidation_epoch += additional_epochs;
+            consolidation_balance_to_consume += additional_epochs * per_epoch_consolidation_churn;
+        }
+
+        // Consume the balance and update state variables
+        self.consolidation_balance_to_consume =
+            consolidation_balance_to_consume - consolidation_balance;
+        self.earliest_consolidation_epoch = earliest_consolidation_epoch;
+
+        self.earliest_consolidation_epoch
+    }
+
+    /// Switch the validator with index ``index`` to compounding withdrawal credentials, queueing
+    /// its balance above ``MIN_ACTIVATION_BALANCE``.
+    pub fn switch_to_compounding_validator(&mut self, index: u64) -> anyhow::Result<()> {
+        let validator = self
+            .validators
+            .get_mut(index as usize)
+            .ok_or(anyhow!("Invalid validator index"))?;
+        validator.withdrawal_credentials[..1].copy_from_slice(&COMPOUNDING_WITHDRAWAL_PREFIX);
+        self.queue_excess_active_balance(index)
+    }
+
+    /// Move the balance of the validator with index ``index`` above ``MIN_ACTIVATION_BALANCE``
+    /// into the pending deposits queue.
+    pub fn queue_excess_active_balance(&mut self, index: u64) -> anyhow::Result<()> {
+        let balance = self.balances[index as usize];
+        if balance > MIN_ACTIVATION_BALANCE {
+            let excess_balance = balance - MIN_ACTIVATION_BALANCE;
+            self.balances[index as usize] = MIN_ACTIVATION_BALANCE;
+            let validator = &self.validators[index as usize];
+            // Use bls.G2_POINT_AT_INFINITY as a signature field placeholder and GENESIS_SLOT to
+            // distinguish from a pending deposit request
+            self.pending_deposits
+                .push(PendingDeposit {
+                    pubkey: validator.pubkey.clone(),
+                    withdrawal_credentials: validator.withdrawal_credentials,
+                    amount: excess_balance,
+                    signature: BLSSignature::infinity(),
+                    slot: GENESIS_SLOT,
+                })
+                .map_err(|err| anyhow!("Couldn't push to pending_deposits {err:?}"))?;
+        }
+        Ok(())
+    }
+
+    /// Slash the validator with index ``slashed_index``
+    pub fn slash_validator(
+        &mut self,
+        slashed_index: u64,
+        whistleblower_index: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let epoch = self.get_current_epoch();
+
+        // Initiate validator exit
+        self.initiate_validator_exit(slashed_index)?;
+
+        let validator_effective_balance =
+            if let Some(validator) = self.validators.get_mut(slashed_index as usize) {
+                validator.slashed = true;
+                validator.withdrawable_epoch = std::cmp::max(
+                    validator.withdrawable_epoch,
+                    epoch + EPOCHS_PER_SLASHINGS_VECTOR,
+                );
+                validator.effective_balance
+            } else {
+                bail!("Validator at index {slashed_index} not found")
+            };
+        // Add slashed effective balance to the slashings vector
+        self.slashings[(epoch % EPOCHS_PER_SLASHINGS_VECTOR) as usize] +=
+            validator_effective_balance;
+        // Decrease validator balance
+        self.decrease_balance(
+            slashed_index,
+            validator_effective_balance / MIN_SLASHING_PENALTY_QUOTIENT_ELECTRA,
+        )?;
+
+        // Apply proposer and whistleblower rewards
+        let proposer_index = self.get_beacon_proposer_index()?;
+        let whistleblower_index = whistleblower_index.unwrap_or(proposer_index);
+
+        let whistleblower_reward =
+            validator_effective_balance / WHISTLEBLOWER_REWARD_QUOTIENT_ELECTRA;
+        let proposer_reward = whistleblower_reward * PROPOSER_WEIGHT / WEIGHT_DENOMINATOR;
+        self.increase_balance(proposer_index, proposer_reward)?;
+        self.increase_balance(whistleblower_index, whistleblower_reward - proposer_reward)?;
+
+        Ok(())
+    }
+
+    pub fn is_valid_genesis_state(&self) -> bool {
+        if self.genesis_time < chain_spec().min_genesis_time {
+            return false;
+        }
+        if self.get_active_validator_indices(GENESIS_EPOCH).len()
+            < chain_spec().min_genesis_active_validator_count as usize
+        {
+            return false;
+        }
+        true
+    }
+
+    pub fn get_index_for_new_validator(&self) -> u64 {
+        self.validators.len() as u64
+    }
+
+    pub fn process_block_header(&mut self, block: &BeaconBlock) -> anyhow::Result<()> {
+        // Verify that the slots match
+        ensure!(
+            self.slot == block.slot,
+            "State slot must be equal to block slot"
+        );
+        // Verify that the block is newer than latest block header
+        ensure!(
+            block.slot > self.latest_block_header.slot,
+            "Block slot must be greater than latest block header slot of state"
+        );
+        // Verify that proposer index is the correct index
+        ensure!(
+            block.proposer_index == self.get_beacon_proposer_index()?,
+            "Block proposer index must be equal to beacon proposer index"
+        );
+        // Verify that the parent matches
+        ensure!(
+            block.parent_root == self.latest_block_header.tree_hash_root(),
+            "Block Parent Root must be equal root of latest block header"
+        );
+
+        // Cache current block as the new latest block
+        self.latest_block_header = BeaconBlockHeader {
+            slot: block.slot,
+            proposer_index: block.proposer_index,
+            parent_root: block.parent_root,
+            state_root: B256::default(), // Overwritten in the next process_slot call
+            body_root: block.body.tree_hash_root(),
+        };
+
+        // Verify proposer is not slashed
+        let proposer = &self.validators[block.proposer_index as usize];
+        ensure!(!proposer.slashed, "Block proposer must not be slashed");
+
+        Ok(())
+    }
+
+    /// Return the withdrawals of the next payload and the number of pending partial withdrawals
+    /// they consume.
+    pub fn get_expected_withdrawals(&self) -> (Vec<Withdrawal>, u64) {
+        let epoch = self.get_current_epoch();
+        let mut withdrawal_index = self.next_withdrawal_index;
+        let mut validator_index = self.next_withdrawal_validator_index;
+        let mut withdrawals: Vec<Withdrawal> = vec![];
+        let mut processed_partial_withdrawals_count = 0;
+
+        // [New in Electra:EIP7251] Consume pending partial withdrawals
+        for withdrawal in self.pending_partial_withdrawals.iter() {
+            if withdrawal.withdrawable_epoch > epoch
+                || withdrawals.len() == MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP as usize
+            {
+                break;
+            }
+
+            let validator = &self.validators[withdrawal.validator_index as usize];
+            let has_sufficient_effective_balance =
+                validator.effective_balance >= MIN_ACTIVATION_BALANCE;
+            let total_withdrawn = get_total_withdrawn(&withdrawals, withdrawal.validator_index);
+            let balance =
+                self.balances[withdrawal.validator_index as usize].saturating_sub(total_withdrawn);
+            let has_excess_balance = balance > MIN_ACTIVATION_BALANCE;
+            if validator.exit_epoch == FAR_FUTURE_EPOCH
+                && has_sufficient_effective_balance
+                && has_excess_balance
+            {
+                withdrawals.push(Withdrawal {
+                    index: withdrawal_index,
+                    validator_index: withdrawal.validator_index,
+                    address: Address::from_slice(&validator.withdrawal_credentials[12..]),
+                    amount: min(balance - MIN_ACTIVATION_BALANCE, withdrawal.amount),
+                });
+                withdrawal_index += 1;
+            }
+            processed_partial_withdrawals_count += 1;
+        }
+
+        // Sweep for remaining
+        let bound = min(self.validators.len(), MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP);
+        for _ in 0..bound {
+            let validator = &self.validators[validator_index as usize];
+            // [Modified in Electra:EIP7251]
+            let total_withdrawn = get_total_withdrawn(&withdrawals, validator_index);
+            let balance = self.balances[validator_index as usize].saturating_sub(total_withdrawn);
+            if validator.is_fully_withdrawable_validator_electra(balance, epoch) {
+                withdrawals.push(Withdrawal {
+                    index: withdrawal_index,
+                    validator_index,
+                    address: Address::from_slice(&validator.withdrawal_credentials[12..]),
+                    amount: balance,
+                });
+                withdrawal_index += 1
+            } else if validator.is_partially_withdrawable_validator_electra(balance) {
+                withdrawals.push(Withdrawal {
+                    index: withdrawal_index,
+                    validator_index,
+                    address: Address::from_slice(&validator.withdrawal_credentials[12..]),
+                    amount: balance - validator.get_max_effective_balance(),
+                });
+                withdrawal_index += 1
+            }
+            if withdrawals.len() == MAX_WITHDRAWALS_PER_PAYLOAD as usize {
+                break;
+            }
+            validator_index = (validator_index + 1) % self.validators.len() as u64
+        }
+        (withdrawals, processed_partial_withdrawals_count)
+    }
+
+    pub fn process_withdrawals(&mut self, payload: &ExecutionPayload) -> anyhow::Result<()> {
+        let (expected_withdrawals, processed_partial_withdrawals_count) =
+            self.get_expected_withdrawals();
+        ensure!(
+            payload.withdrawals.deref() == expected_withdrawals,
+            "Payload withdrawals must match the expected withdrawals"
+        );
+
+        for withdrawal in &expected_withdrawals {
+            self.decrease_balance(withdrawal.validator_index, withdrawal.amount)?;
+        }
+
+        // [New in Electra:EIP7251] Update pending partial withdrawals
+        self.pending_partial_withdrawals = self.pending_partial_withdrawals
+            [processed_partial_withdrawals_count as usize..]
+            .to_vec()
+            .into();
+
+        // Update the next withdrawal index if this block contained withdrawals
+        if let Some(latest_withdrawal) = expected_withdrawals.last() {
+            self.next_withdrawal_index = latest_withdrawal.index + 1
+        }
+
+        // Update the next validator index to start the next withdrawal sweep
+        if expected_withdrawals.len() == MAX_WITHDRAWALS_PER_PAYLOAD as usize {
+            // Next sweep starts after the latest withdrawal's validator index
+            let next_validator_index =
+                (expected_withdrawals[expected_withdrawals.len() - 1].validator_index + 1)
+                    % self.validators.len() as u64;
+            self.next_withdrawal_validator_index = next_validator_index
+        } else {
+            // Advance sweep by the max length of the sweep if there was not a full set of
+            // withdrawals
+            let next_index =
+                self.next_withdrawal_validator_index + MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP as u64;
+            let next_validator_index = next_index % self.validators.len() as u64;
+            self.next_withdrawal_validator_index = next_validator_index
+        }
+
+        Ok(())
+    }
+
+    pub fn add_validator_to_registry(
+        &mut self,
+        pubkey: PubKey,
+        withdrawal_credentials: B256,
+        amount: u64,
+    ) -> anyhow::Result<()> {
+        self.validators
+            .push(get_validator_from_deposit(
+                pubkey,
+                withdrawal_credentials,
+                amount,
+            ))
+            .map_err(|err| anyhow!("Couldn't push to validators {:?}", err))?;
+        self.balances
+            .push(amount)
+            .map_err(|err| anyhow!("Couldn't push to balances {:?}", err))?;
+        self.previous_epoch_participation
+            .push(0)
+            .map_err(|err| anyhow!("Couldn't push to previous_epoch_participation {:?}", err))?;
+        self.current_epoch_participation
+            .push(0)
+            .map_err(|err| anyhow!("Couldn't push to current_epoch_participation {:?}", err))?;
+        self.inactivity_scores
+            .push(0)
+            .map_err(|err| anyhow!("Couldn't push to inactivity_scores {:?}", err))?;
+        Ok(())
+    }
+
+    pub fn apply_deposit(
+        &mut self,
+        pubkey: PubKey,
+        withdrawal_credentials: B256,
+        amount: u64,
+        signature: BLSSignature,
+    ) -> anyhow::Result<()> {
+        if !self
+            .validators
+            .iter()
+            .any(|validator| validator.pubkey == pubkey)
+        {
+            // Verify the deposit signature (proof of possession) which is not checked by the
+            // deposit contract
+            if !is_valid_deposit_signature(&pubkey, withdrawal_credentials, amount, &signature) {
+                return Ok(());
+            }
+            // [Modified in Electra:EIP7251]
+            self.add_validator_to_registry(pubkey.clone(), withdrawal_credentials, 0)?;
+        }
+
+        // [Modified in Electra:EIP7251] Increase balance by deposit amount
+        self.pending_deposits
+            .push(PendingDeposit {
+                pubkey,
+                withdrawal_credentials,
+                amount,
+                signature,
+                slot: GENESIS_SLOT, /* Use GENESIS_SLOT to distinguish from a pending deposit
+                                     * request */
+            })
+            .map_err(|err| anyhow!("Couldn't push to pending_deposits {err:?}"))
+    }
+
+    /// Apply a pending ``deposit``, creating a validator if its pubkey is not yet known and the
+    /// deposit signature is valid.
+    pub fn apply_pending_deposit(&mut self, deposit: &PendingDeposit) -> anyhow::Result<()> {
+        match self
+            .validators
+            .iter()
+            .position(|validator| validator.pubkey == deposit.pubkey)
+        {
+            Some(validator_index) => self.increase_balance(validator_index as u64, deposit.amount),
+            None => {
+                // Verify the deposit signature (proof of possession) which is not checked by the
+                // deposit contract
+                if is_valid_deposit_signature(
+                    &deposit.pubkey,
+                    deposit.withdrawal_credentials,
+                    deposit.amount,
+                    &deposit.signature,
+                ) {
+                    self.add_validator_to_registry(
+                        deposit.pubkey.clone(),
+                        deposit.withdrawal_credentials,
+                        deposit.amount,
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn process_deposit(&mut self, deposit: &Deposit) -> anyhow::Result<()> {
+        // Verify the Merkle branch
+        ensure!(is_valid_merkle_branch(
+            deposit.data.tree_hash_root(),
+            &deposit.proof,
+            DEPOSIT_CONTRACT_TREE_DEPTH + 1, // Add 1 for the List length mix-in
+            self.eth1_deposit_index,
+            self.eth1_data.deposit_root,
+        ));
+
+        // Deposits must be processed in order
+        self.eth1_deposit_index += 1;
+
+        self.apply_deposit(
+            deposit.data.pubkey.clone(),
+            deposit.data.withdrawal_credentials,
+            deposit.data.amount,
+            deposit.data.signature.clone(),
+        )
+    }
+
+    pub fn process_bls_to_execution_change(
+        &mut self,
+        signed_address_change: &SignedBLSToExecutionChange,
+    ) -> anyhow::Result<()> {
+        self.process_bls_to_execution_change_with_verifier(
+            signed_address_change,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn process_bls_to_execution_change_with_verifier(
+        &mut self,
+        signed_address_change: &SignedBLSToExecutionChange,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        self.verify_bls_to_execution_change_with_verifier(
+            signed_address_change,
+            signature_verifier,
+        )?;
+
+        let address_change = &signed_address_change.message;
+        let withdrawal_credentials = [
+            ETH1_ADDRESS_WITHDRAWAL_PREFIX.as_slice(),
+            vec![0x00; 11].as_slice(),
+            address_change.to_execution_address.as_slice(),
+        ]
+        .concat();
+        self.validators[address_change.validator_index as usize].withdrawal_credentials =
+            B256::from_slice(&withdrawal_credentials);
+
+        Ok(())
+    }
+
+    /// Check that ``signed_address_change`` may be processed, without changing the state.
+    pub fn verify_bls_to_execution_change(
+        &self,
+        signed_address_change: &SignedBLSToExecutionChange,
+    ) -> anyhow::Result<()> {
+        self.verify_bls_to_execution_change_with_verifier(
+            signed_address_change,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn verify_bls_to_execution_change_with_verifier(
+        &self,
+        signed_address_change: &SignedBLSToExecutionChange,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        let address_change = &signed_address_change.message;
+
+        ensure!(address_change.validator_index < self.validators.len() as u64);
+
+        let validator: &Validator = &self.validators[address_change.validator_index as usize];
+
+        ensure!(&validator.withdrawal_credentials[..1] == BLS_WITHDRAWAL_PREFIX);
+        ensure!(
+            validator.withdrawal_credentials[1..]
+                == hash(address_change.from_bls_pubkey.to_bytes())[1..]
+        );
+
+        // Fork-agnostic domain since address changes are valid across forks
+        let domain = compute_domain(
+            DOMAIN_BLS_TO_EXECUTION_CHANGE,
+            None,
+            Some(self.genesis_validators_root),
+        );
+
+        let signing_root = compute_signing_root(address_change, domain);
+        ensure!(
+            signature_verifier.verify(SignatureSet::single_pubkey(
+                signed_address_change.signature.clone(),
+                address_change.from_bls_pubkey.clone(),
+                signing_root,
+            ))?,
+            "BLS Signature verification failed!"
+        );
+
+        Ok(())
+    }
+
+    pub fn compute_timestamp_at_slot(&self, slot: u64) -> u64 {
+        let slots_since_genesis = slot - GENESIS_SLOT;
+        self.genesis_time + slots_since_genesis * chain_spec().seconds_per_slot
+    }
+
+    pub fn process_voluntary_exit(
+        &mut self,
+        signed_voluntary_exit: &SignedVoluntaryExit,
+    ) -> anyhow::Result<()> {
+        self.process_voluntary_exit_with_verifier(
+            signed_voluntary_exit,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn process_voluntary_exit_with_verifier(
+        &mut self,
+        signed_voluntary_exit: &SignedVoluntaryExit,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        self.verify_voluntary_exit_with_verifier(signed_voluntary_exit, signature_verifier)?;
+
+        // Initiate exit
+        self.initiate_validator_exit(signed_voluntary_exit.message.validator_index)?;
+
+        Ok(())
+    }
+
+    /// Check that ``signed_voluntary_exit`` may be processed, without changing the state.
+    pub fn verify_voluntary_exit(
+        &self,
+        signed_voluntary_exit: &SignedVoluntaryExit,
+    ) -> anyhow::Result<()> {
+        self.verify_voluntary_exit_with_verifier(
+            signed_voluntary_exit,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn verify_voluntary_exit_with_verifier(
+        &self,
+        signed_voluntary_exit: &SignedVoluntaryExit,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        let voluntary_exit = &signed_voluntary_exit.message;
+        let validator_index = voluntary_exit.validator_index as usize;
+
+        let validator = self
+            .validators
+            .get(validator_index)
+            .ok_or(anyhow!("Invalid validator index"))?;
+
+        // Verify the validator is active
+        ensure!(
+            validator.is_active_validator(self.get_current_epoch()),
+            "Validator is not active"
+        );
+
+        // Verify exit has not been initiated
+        ensure!(
+            validator.exit_epoch == FAR_FUTURE_EPOCH,
+            "Exit has already been initiated"
+        );
+
+        // Exits must specify an epoch when they become valid; they are not valid before then
+        ensure!(
+            self.get_current_epoch() >= voluntary_exit.epoch,
+            "Exit is not yet valid"
+        );
+
+        // Verify the validator has been active long enough
+        let earlist_exit_epoch = validator
+            .activation_epoch
+            .checked_add(chain_spec().shard_committee_period)
+            .ok_or(anyhow!("Failed to calculate earliest exit epoch"))?;
+        ensure!(
+            self.get_current_epoch() >= earlist_exit_epoch,
+            "Validator has not been active long enough"
+        );
+
+        // [New in Electra:EIP7251] Only exit validator if it has no pending withdrawals in the
+        // queue
+        ensure!(
+            self.get_pending_balance_to_withdraw(voluntary_exit.validator_index) == 0,
+            "Validator has pending withdrawals in the queue"
+        );
+
+        // Compute signature domain
+        let domain = compute_domain(
+            DOMAIN_VOLUNTARY_EXIT,
+            Some(chain_spec().capella_fork_version),
+            Some(self.genesis_validators_root),
+        );
+        let signing_root = compute_signing_root(voluntary_exit, domain);
+
+        ensure!(
+            signature_verifier.verify(SignatureSet::single_pubkey(
+                signed_voluntary_exit.signature.clone(),
+                validator.pubkey.clone(),
+                signing_root,
+            ))?,
+            "BLS Signature verification failed!"
+        );
+
+        Ok(())
+    }
+
+    /// Return the sync committee indices, with possible duplicates, for the next sync committee.
+    pub fn get_next_sync_committee_indices(&self) -> anyhow::Result<Vec<u64>> {
+        let epoch = self.get_current_epoch() + 1;
+        let active_validator_indices = self.get_active_validator_indices(epoch);
+        let active_validator_count = active_validator_indices.len();
+        let seed = self.get_seed(epoch, DOMAIN_SYNC_COMMITTEE);
+        let mut i = 0;
+        let mut sync_committee_indices: Vec<u64> = vec![];
+        while sync_committee_indices.len() < SYNC_COMMITTEE_SIZE as usize {
+            let shuffled_index =
+                compute_shuffled_index(i % active_validator_count, active_validator_count, seed)?;
+            let candidate_index = active_validator_indices[shuffled_index];
+            // [Modified in Electra] Sample with 16 bit random values
+            let seed_with_index = [seed.as_slice(), &(i / 16).to_le_bytes()].concat();
+            let random_bytes = hash(&seed_with_index);
+            let offset = i % 16 * 2;
+            let random_value =
+                u16::from_le_bytes([random_bytes[offset], random_bytes[offset + 1]]) as u64;
+            let effective_balance = self.validators[candidate_index as usize].effective_balance;
+            if effective_balance * MAX_RANDOM_VALUE >= MAX_EFFECTIVE_BALANCE_ELECTRA * random_value
+            {
+                sync_committee_indices.push(candidate_index)
+            }
+            i += 1
+        }
+
+        Ok(sync_committee_indices)
+    }
+
+    pub fn process_proposer_slashing(
+        &mut self,
+        proposer_slashing: &ProposerSlashing,
+    ) -> anyhow::Result<()> {
+        self.process_proposer_slashing_with_verifier(
+            proposer_slashing,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn process_proposer_slashing_with_verifier(
+        &mut self,
+        proposer_slashing: &ProposerSlashing,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        self.verify_proposer_slashing_with_verifier(proposer_slashing, signature_verifier)?;
+
+        // Slash the validator
+        self.slash_validator(
+            proposer_slashing.signed_header_1.message.proposer_index,
+            None,
+        )
+    }
+
+    /// Check that ``proposer_slashing`` may be processed, without changing the state.
+    pub fn verify_proposer_slashing(
+        &self,
+        proposer_slashing: &ProposerSlashing,
+    ) -> anyhow::Result<()> {
+        self.verify_proposer_slashing_with_verifier(
+            proposer_slashing,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn verify_proposer_slashing_with_verifier(
+        &self,
+        proposer_slashing: &ProposerSlashing,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        let header_1 = &proposer_slashing.signed_header_1.message;
+        let header_2 = &proposer_slashing.signed_header_2.message;
+
+        // Verify header slots match
+        ensure!(header_1.slot == header_2.slot, "Header slots must match");
+
+        // Verify header proposer indices match
+        ensure!(
+            header_1.proposer_index == header_2.proposer_index,
+            "Proposer indices must match"
+        );
+
+        // Verify the headers are different
+        ensure!(header_1 != header_2, "Headers must be different");
+
+        // Get the proposer and verify they are slashable
+        let proposer_index = header_1.proposer_index;
+        let proposer = self
+            .validators
+            .get(proposer_index as usize)
+            .ok_or_else(|| anyhow::anyhow!("Invalid proposer index"))?;
+
+        ensure!(
+            proposer.is_slashable_validator(self.get_current_epoch()),
+            "Proposer is not slashable"
+        );
+
+        // Verify signatures
+        for signed_header in [
+            &proposer_slashing.signed_header_1,
+            &proposer_slashing.signed_header_2,
+        ] {
+            let domain = self.get_domain(
+                DOMAIN_BEACON_PROPOSER,
+                Some(compute_epoch_at_slot(signed_header.message.slot)),
+            );
+
+            let signing_root = compute_signing_root(&signed_header.message, domain);
+
+            ensure!(
+                signature_verifier.verify(SignatureSet::single_pubkey(
+                    signed_header.signature.clone(),
+                    proposer.pubkey.clone(),
+                    signing_root,
+                ))?,
+                "BLS Signature verification failed!"
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn process_historical_summaries_update(&mut self) -> anyhow::Result<()> {
+        // Set historical block root accumulator.
+        let next_epoch = self.get_current_epoch() + 1;
+        if next_epoch % (SLOTS_PER_HISTORICAL_ROOT / SLOTS_PER_EPOCH) == 0 {
+            let historical_summary = HistoricalSummary {
+                block_summary_root: self.block_roots.tree_hash_root(),
+                state_summary_root: self.state_roots.tree_hash_root(),
+            };
+            self.historical_summaries
+                .push(historical_summary)
+                .map_err(|err| anyhow!("Failed to push historical summary: {err:?}"))?;
+        }
+        Ok(())
+    }
+
+    pub fn process_attester_slashing(
+        &mut self,
+        attester_slashing: &AttesterSlashing,
+    ) -> anyhow::Result<()> {
+        self.process_attester_slashing_with_verifier(
+            attester_slashing,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn process_attester_slashing_with_verifier(
+        &mut self,
+        attester_slashing: &AttesterSlashing,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        for index in
+            self.verify_attester_slashing_with_verifier(attester_slashing, signature_verifier)?
+        {
+            self.slash_validator(index, None)?;
+        }
+        Ok(())
+    }
+
+    /// Check that ``attester_slashing`` may be processed, without changing the state. Returns the
+    /// indices of the validators it slashes, in ascending order.
+    pub fn verify_attester_slashing(
+        &self,
+        attester_slashing: &AttesterSlashing,
+    ) -> anyhow::Result<Vec<u64>> {
+        self.verify_attester_slashing_with_verifier(
+            attester_slashing,
+            &mut SignatureVerifier::default(),
+        )
+    }
+
+    pub fn verify_attester_slashing_with_verifier(
+        &self,
+        attester_slashing: &AttesterSlashing,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<Vec<u64>> {
+        let attestation_1 = &attester_slashing.attestation_1;
+        let attestation_2 = &attester_slashing.attestation_2;
+
+        // Ensure the two attestations are slashable
+        ensure!(
+            is_slashable_attestation_data(&attestation_1.data, &attestation_2.data),
+            "Attestations are not slashable"
+        );
+
+        // Validate both attestations
+        ensure!(
+            self.is_valid_indexed_attestation_with_verifier(attestation_1, signature_verifier)?,
+            "First attestation is invalid"
+        );
+        ensure!(
+            self.is_valid_indexed_attestation_with_verifier(attestation_2, signature_verifier)?,
+            "Second attestation is invalid"
+        );
+
+        let current_epoch = self.get_current_epoch();
+        let indices_1: HashSet<_> = attestation_1.attesting_indices.iter().cloned().collect();
+        let indices_2: HashSet<_> = attestation_2.attesting_indices.iter().cloned().collect();
+
+        // Find the common attesting indices which are slashable
+        let slashable_indices = indices_1
+            .intersection(&indices_2)
+            .copied()
+            .filter(|&index| self.validators[index as usize].is_slashable_validator(current_epoch))
+            .sorted()
+            .collect::<Vec<_>>();
+
+        ensure!(!slashable_indices.is_empty(), "No validator was slashed");
+        Ok(slashable_indices)
+    }
+
+    pub fn process_sync_aggregate(&mut self, sync_aggregate: &SyncAggregate) -> anyhow::Result<()> {
+        self.process_sync_aggregate_with_verifier(sync_aggregate, &mut SignatureVerifier::default())
+    }
+
+    pub fn process_sync_aggregate_with_verifier(
+        &mut self,
+        sync_aggregate: &SyncAggregate,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        // Verify sync committee aggregate signature signing over the previous slot block root
+        let committee_pubkeys = &self.current_sync_committee.pubkeys;
+        let mut participant_pubkeys = vec![];
+
+        for (pubkey, bit) in committee_pubkeys
+            .iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+        {
+            if bit {
+                participant_pubkeys.push(pubkey);
+            }
+        }
+
+        let previous_slot = max(self.slot, 1) - 1;
+        let domain = self.get_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            Some(compute_epoch_at_slot(previous_slot)),
+        );
+        let signing_root =
+            compute_signing_root(self.get_block_root_at_slot(previous_slot)?, domain);
+
+        ensure!(
+            signature_verifier.verify(SignatureSet::multiple_pubkeys(
+                sync_aggregate.sync_committee_signature.clone(),
+                participant_pubkeys.into_iter().cloned().collect(),
+                signing_root,
+            ))?,
+            "Sync aggregate signature verification failed."
+        );
+
+        // Compute participant and proposer rewards
+        let (participant_reward, proposer_reward) = self.get_sync_committee_rewards();
+
+        // Apply participant and proposer rewards
+        let committee_indices = self.get_sync_committee_indices()?;
+        let proposer_index = self.get_beacon_proposer_index()?;
+        for (participant_index, participation_bit) in committee_indices
+            .iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+        {
+            if participation_bit {
+                self.increase_balance(*participant_index, participant_reward)?;
+                self.increase_balance(proposer_index, proposer_reward)?;
+            } else {
+                self.decrease_balance(*participant_index, participant_reward)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the reward of a sync committee participant, and the reward of the proposer for each
+    /// participant included.
+    pub fn get_sync_committee_rewards(&self) -> (u64, u64) {
+        let total_active_increments = self.get_total_active_balance() / EFFECTIVE_BALANCE_INCREMENT;
+        let total_base_rewards = self.get_base_reward_per_increment() * total_active_increments;
+        let max_participant_rewards =
+            total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / SLOTS_PER_EPOCH;
+        let participant_reward = max_participant_rewards / SYNC_COMMITTEE_SIZE;
+        let proposer_reward =
+            participant_reward * PROPOSER_WEIGHT / (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT);
+        (participant_reward, proposer_reward)
+    }
+
+    /// Return the validator indices of the members of ``current_sync_committee``, with possible
+    /// duplicates.
+    pub fn get_sync_committee_indices(&self) -> anyhow::Result<Vec<u64>> {
+        let validator_indices = self
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(index, validator)| (validator.pubkey.to_bytes(), index as u64))
+            .collect::<HashMap<_, _>>();
+        self.current_sync_committee
+            .pubkeys
+            .iter()
+            .map(|pubkey| {
+                validator_indices
+                    .get(pubkey.to_bytes())
+                    .copied()
+                    .ok_or_else(|| anyhow!("Sync committee member {pubkey:?} is not a validator"))
+            })
+            .collect()
+    }
+
+    pub fn process_justification_and_finalization(&mut self) -> anyhow::Result<()> {
+        // Initial FFG checkpoint values have a `0x00` stub for `root`.
+        // Skip FFG updates in the first two epochs to avoid corner cases that might result in
+        // modifying this stub.
+        if self.get_current_epoch() <= GENESIS_EPOCH + 1 {
+            return Ok(());
+        }
+
+        let previous_indices = self.get_unslashed_participating_indices(
+            TIMELY_TARGET_FLAG_INDEX,
+            self.get_previous_epoch(),
+        )?;
+        let current_indices = self.get_unslashed_participating_indices(
+            TIMELY_TARGET_FLAG_INDEX,
+            self.get_current_epoch(),
+        )?;
+
+        let total_active_balance = self.get_total_active_balance();
+        let previous_target_balance = self.get_total_balance(previous_indices);
+        let current_target_balance = self.get_total_balance(current_indices);
+
+        self.weigh_justification_and_finalization(
+            total_active_balance,
+            previous_target_balance,
+            current_target_balance,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn weigh_justification_and_finalization(
+        &mut self,
+        total_active_balance: u64,
+        previous_epoch_target_balance: u64,
+        current_epoch_target_balance: u64,
+    ) -> anyhow::Result<()> {
+        let previous_epoch = self.get_previous_epoch();
+        let current_epoch = self.get_current_epoch();
+        let old_previous_justified_checkpoint = self.previous_justified_checkpoint;
+        let old_current_justified_checkpoint = self.current_justified_checkpoint;
+
+        self.previous_justified_checkpoint = self.current_justified_checkpoint;
+
+        for i in (1..JUSTIFICATION_BITS_LENGTH).rev() {
+            let bit = self
+                .justification_bits
+                .get(i - 1)
+                .map_err(|err| anyhow!("Failed to get justification bit {err:?}"))?;
+            self.justification_bits
+                .set(i, bit)
+                .map_err(|err| anyhow!("Failed to set justification bit {err:?}"))?;
+        }
+
+        self.justification_bits
+            .set(0, false)
+            .map_err(|err| anyhow!("Failed to set justification bit 0: {err:?}"))?;
+
+        if previous_epoch_target_balance * 3 >= total_active_balance * 2 {
+            self.current_justified_checkpoint = Checkpoint {
+                epoch: previous_epoch,
+                root: self.get_block_root(previous_epoch)?,
+            };
+            self.justification_bits
+                .set(1, true)
+                .map_err(|err| anyhow!("Failed to set justification bit 1: {err:?}"))?;
+        }
+
+        if current_epoch_target_balance * 3 >= total_active_balance * 2 {
+            self.current_justified_checkpoint = Checkpoint {
+                epoch: current_epoch,
+                root: self.get_block_root(current_epoch)?,
+            };
+            self.justification_bits
+                .set(0, true)
+                .map_err(|err| anyhow!("Failed to set justification bit 0: {err:?}"))?;
+        }
+
+        // Process finalizations
+        let bits: Vec<bool> = self.justification_bits.iter().collect();
+
+        // The 2nd/3rd/4th most recent epochs are justified, the 2nd using the 4th as source
+        if bits[1..4].iter().all(|&b| b)
+            && old_previous_justified_checkpoint.epoch + 3 == current_epoch
+        {
+            self.finalized_checkpoint = old_previous_justified_checkpoint;
+        }
+
+        // The 2nd/3rd most recent epochs are justified, the 2nd using the 3rd as source
+        if bits[1..3].iter().all(|&b| b)
+            && old_previous_justified_checkpoint.epoch + 2 == current_epoch
+        {
+            self.finalized_checkpoint = old_previous_justified_checkpoint;
+        }
+
+        // The 1st/2nd/3rd most recent epochs are justified, the 1st using the 3rd as source
+        if bits[0..3].iter().all(|&b| b)
+            && old_current_justified_checkpoint.epoch + 2 == current_epoch
+        {
+            self.finalized_checkpoint = old_current_justified_checkpoint;
+        }
+
+        // The 1st/2nd most recent epochs are justified, the 1st using the 2nd as source
+        if bits[0..2].iter().all(|&b| b)
+            && old_current_justified_checkpoint.epoch + 1 == current_epoch
+        {
+            self.finalized_checkpoint = old_current_justified_checkpoint;
+        }
+
+        Ok(())
+    }
+
+    pub fn process_eth1_data_reset(&mut self) -> anyhow::Result<()> {
+        let next_epoch = self.get_current_epoch() + 1;
+
+        // Reset eth1 data votes
+        if next_epoch % EPOCHS_PER_ETH1_VOTING_PERIOD == 0 {
+            self.eth1_data_votes = VariableList::default();
+        }
+
+        Ok(())
+    }
+
+    pub fn process_effective_balance_updates(&mut self) -> anyhow::Result<()> {
+        // Update effective balances with hysteresis
+        for (index, validator) in self.validators.iter_mut().enumerate() {
+            let balance = self.balances[index];
+            let hysteresis_increment = EFFECTIVE_BALANCE_INCREMENT / HYSTERESIS_QUOTIENT;
+            let downward_threshold = hysteresis_increment * HYSTERESIS_DOWNWARD_MULTIPLIER;
+            let upward_threshold = hysteresis_increment * HYSTERESIS_UPWARD_MULTIPLIER;
+
+            if balance + downward_threshold < validator.effective_balance
+                || validator.effective_balance + upward_threshold < balance
+            {
+                // [Modified in Electra:EIP7251]
+                validator.effective_balance = (balance - balance % EFFECTIVE_BALANCE_INCREMENT)
+                    .min(validator.get_max_effective_balance());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn process_randao(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        self.process_randao_with_verifier(body, &mut SignatureVerifier::default())
+    }
+
+    pub fn process_randao_with_verifier(
+        &mut self,
+        body: &BeaconBlockBody,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        let epoch = self.get_current_epoch();
+
+        // Verify RANDAO reveal
+        if let Some(proposer) = self
+            .validators
+            .get(self.get_beacon_proposer_index()? as usize)
+        {
+            let signing_root =
+                compute_signing_root(epoch, self.get_domain(DOMAIN_RANDAO, Some(epoch)));
+            ensure!(
+                signature_verifier.verify(SignatureSet::single_pubkey(
+                    body.randao_reveal.clone(),
+                    proposer.pubkey.clone(),
+                    signing_root,
+                ))?,
+                "BLS Signature verification failed!"
+            );
+
+            // Mix in RANDAO reveal
+            let mix = xor(
+                self.get_randao_mix(epoch).as_slice(),
+                hash(body.randao_reveal.to_bytes()).as_slice(),
+            );
+            self.randao_mixes[(epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize] = mix;
+        }
+
+        Ok(())
+    }
+
+    pub fn process_eth1_data(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        self.eth1_data_votes
+            .push(body.eth1_data.clone())
+            .map_err(|err| anyhow!("Can't push eth1_data {err:?}"))?;
+
+        let count = self
+            .eth1_data_votes
+            .iter()
+            .filter(|data| **data == body.eth1_data)
+            .count() as u64;
+
+        if count * 2 > (EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH) {
+            self.eth1_data = body.eth1_data.clone();
+        }
+
+        Ok(())
+    }
+
+    pub fn process_attestation(&mut self, attestation: &Attestation) -> anyhow::Result<()> {
+        self.process_attestation_with_verifier(attestation, &mut SignatureVerifier::default())
+    }
+
+    pub fn process_attestation_with_verifier(
+        &mut self,
+        attestation: &Attestation,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            attestation.data.target.epoch == self.get_previous_epoch()
+                || attestation.data.target.epoch == self.get_current_epoch(),
+            "Target epoch must be the previous or current epoch"
+        );
+
+        ensure!(
+            attestation.data.target.epoch == compute_epoch_at_slot(attestation.data.slot),
+            "Target epoch must match the computed epoch at slot"
+        );
+
+        ensure!(
+            attestation.data.slot + MIN_ATTESTATION_INCLUSION_DELAY <= self.slot,
+            "Attestation must be included after the minimum delay"
+        );
+
+        // [Modified in Electra:EIP7549]
+        ensure!(
+            attestation.data.index == 0,
+            "Attestation data index must be zero"
+        );
+        let mut committee_offset = 0;
+        for committee_index in attestation.get_committee_indices() {
+            ensure!(
+                committee_index < self.get_committee_count_per_slot(attestation.data.target.epoch),
+                "Committee index must be within bounds"
+            );
+            let committee = self.get_beacon_committee(attestation.data.slot, committee_index)?;
+            let has_committee_attesters = (0..committee.len()).any(|i| {
+                attestation
+                    .aggregation_bits
+                    .get(committee_offset + i)
+                    .unwrap_or(false)
+            });
+            ensure!(
+                has_committee_attesters,
+                "Committee must have at least one attester"
+            );
+            committee_offset += committee.len();
+        }
+
+        // Bitfield length matches total number of participants
+        ensure!(
+            attestation.aggregation_bits.len() == committee_offset,
+            "Aggregation bits length must match the total size of the committees"
+        );
+
+        let participation_flag_indices = self.get_attestation_participation_flag_indices(
+            &attestation.data,
+            self.slot - attestation.data.slot,
+        )?;
+
+        ensure!(
+            self.is_valid_indexed_attestation_with_verifier(
+                &self.get_indexed_attestation(attestation)?,
+                signature_verifier,
+            )?,
+            "Attestation signature must be valid"
+        );
+
+        let attesting_indices = self.get_attesting_indices(attestation)?;
+        let base_rewards: Vec<_> = attesting_indices
+            .iter()
+            .map(|&index| (index, self.get_base_reward(index)))
+            .collect();
+
+        // Update epoch participation flags
+        let epoch_participation = if attestation.data.target.epoch == self.get_current_epoch() {
+            &mut self.current_epoch_participation
+        } else {
+            &mut self.previous_epoch_participation
+        };
+
+        let mut proposer_reward_numerator = 0;
+
+        for (index, base_reward) in base_rewards {
+            for (flag_index, &weight) in PARTICIPATION_FLAG_WEIGHTS.iter().enumerate() {
+                let flag_index = flag_index as u8;
+
+                if participation_flag_indices.contains(&flag_index) {
+                    let epoch_part =
+                        epoch_participation.get_mut(index as usize).ok_or_else(|| {
+                            anyhow!("Index {} out of bounds in epoch_participation", index)
+                        })?;
+
+                    if !Self::has_flag(*epoch_part, flag_index) {
+                        *epoch_part = Self::add_flag(*epoch_part, flag_index);
+                        proposer_reward_numerator += base_reward * weight;
+                    }
+                }
+            }
+        }
+
+        let proposer_reward_denominator =
+            (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT) * WEIGHT_DENOMINATOR / PROPOSER_WEIGHT;
+        let proposer_reward = proposer_reward_numerator / proposer_reward_denominator;
+        self.increase_balance(self.get_beacon_proposer_index()?, proposer_reward)?;
+        Ok(())
+    }
+
+    pub fn process_randao_mixes_reset(&mut self) -> anyhow::Result<()> {
+        let current_epoch = self.get_current_epoch();
+        let next_epoch = current_epoch + 1;
+        // Set randao mix
+        self.randao_mixes[(next_epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize] =
+            self.get_randao_mix(current_epoch);
+        Ok(())
+    }
+
+    pub fn process_slashings_reset(&mut self) -> anyhow::Result<()> {
+        let next_epoch = self.get_current_epoch() + 1;
+        // Reset slashings
+        self.slashings[(next_epoch % EPOCHS_PER_SLASHINGS_VECTOR) as usize] = 0;
+        Ok(())
+    }
+
+    pub fn process_slashings(&mut self) -> anyhow::Result<()> {
+        let epoch = self.get_current_epoch();
+        let total_balance = self.get_total_active_balance();
+        let adjusted_total_slashing_balance = (self.slashings.iter().sum::<u64>()
+            * PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX)
+            .min(total_balance);
+        let increment = EFFECTIVE_BALANCE_INCREMENT; // Factored out from total balance to avoid uint64 overflow
+        let penalty_per_effective_balance_increment =
+            adjusted_total_slashing_balance / (total_balance / increment);
+
+        for index in 0..self.validators.len() {
+            let validator = &self.validators[index];
+            if validator.slashed
+                && epoch + EPOCHS_PER_SLASHINGS_VECTOR / 2 == validator.withdrawable_epoch
+            {
+                // [Modified in Electra:EIP7251]
+                let effective_balance_increments = validator.effective_balance / increment;
+                let penalty =
+                    penalty_per_effective_balance_increment * effective_balance_increments;
+
+                self.decrease_balance(index as u64, penalty)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn process_operations(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        self.process_operations_with_verifier(body, &mut SignatureVerifier::default())
+    }
+
+    pub fn process_operations_with_verifier(
+        &mut self,
+        body: &BeaconBlockBody,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        // [Modified in Electra:EIP6110]
+        // Disable former deposit mechanism once all prior deposits are processed
+        let eth1_deposit_index_limit = min(
+            self.eth1_data.deposit_count,
+            self.deposit_requests_start_index,
+        );
+        if self.eth1_deposit_index < eth1_deposit_index_limit {
+            // Verify that outstanding deposits are processed up to the maximum number of deposits
+            ensure!(
+                body.deposits.len() as u64
+                    == min(
+                        MAX_DEPOSITS,
+                        eth1_deposit_index_limit - self.eth1_deposit_index
+                    ),
+                "Outstanding deposits must be processed up to the maximum number of deposits"
+            );
+        } else {
+            ensure!(
+                body.deposits.is_empty(),
+                "Deposits are no longer processed through the Eth1 bridge"
+            );
+        }
+
+        for proposer_slashing in body.proposer_slashings.iter() {
+            self.process_proposer_slashing_with_verifier(proposer_slashing, signature_verifier)?;
+        }
+        for attester_slashing in body.attester_slashings.iter() {
+            self.process_attester_slashing_with_verifier(attester_slashing, signature_verifier)?;
+        }
+        for attestation in body.attestations.iter() {
+            self.process_attestation_with_verifier(attestation, signature_verifier)?;
+        }
+        for deposit in body.deposits.iter() {
+            self.process_deposit(deposit)?;
+        }
+        for voluntary_exit in body.voluntary_exits.iter() {
+            self.process_voluntary_exit_with_verifier(voluntary_exit, signature_verifier)?;
+        }
+        for bls_to_execution_change in body.bls_to_execution_changes.iter() {
+            self.process_bls_to_execution_change_with_verifier(
+                bls_to_execution_change,
+                signature_verifier,
+            )?;
+        }
+        // [New in Electra:EIP6110]
+        for deposit_request in body.execution_requests.deposits.iter() {
+            self.process_deposit_request(deposit_request)?;
+        }
+        // [New in Electra:EIP7002:EIP7251]
+        for withdrawal_request in body.execution_requests.withdrawals.iter() {
+            self.process_withdrawal_request(withdrawal_request)?;
+        }
+        // [New in Electra:EIP7251]
+        for consolidation_request in body.execution_requests.consolidations.iter() {
+            self.process_consolidation_request(consolidation_request)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn process_deposit_request(
+        &mut self,
+        deposit_request: &DepositRequest,
+    ) -> anyhow::Result<()> {
+        // Set deposit request start index
+        if self.deposit_requests_start_index == UNSET_DEPOSIT_REQUESTS_START_INDEX {
+            self.deposit_requests_start_index = deposit_request.index;
+        }
+
+        // Create pending deposit
+        self.pending_deposits
+            .push(PendingDeposit {
+                pubkey: deposit_request.pubkey.clone(),
+                withdrawal_credentials: deposit_request.withdrawal_credentials,
+                amount: deposit_request.amount,
+                signature: deposit_request.signature.clone(),
+                slot: self.slot,
+            })
+            .map_err(|err| anyhow!("Couldn't push to pending_deposits {err:?}"))
+    }
+
+    pub fn process_withdrawal_request(
+        &mut self,
+        withdrawal_request: &WithdrawalRequest,
+    ) -> anyhow::Result<()> {
+        let amount = withdrawal_request.amount;
+        let is_full_exit_request = amount == FULL_EXIT_REQUEST_AMOUNT;
+
+        // If partial withdrawal queue is full, only full exits are processed
+        if self.pending_partial_withdrawals.len() as u64 == PENDING_PARTIAL_WITHDRAWALS_LIMIT
+            && !is_full_exit_request
+        {
+            return Ok(());
+        }
+
+        // Verify pubkey exists
+        let Some(index) = self
+            .validators
+            .iter()
+            .position(|validator| validator.pubkey == withdrawal_request.validator_pubkey)
+        else {
+            return Ok(());
+        };
+        let validator = &self.validators[index];
+
+        // Verify withdrawal credentials
+        let has_correct_credential = validator.has_execution_withdrawal_credential();
+        let is_correct_source_address =
+            &validator.withdrawal_credentials[12..] == withdrawal_request.source_address.as_slice();
+        if !(has_correct_credential && is_correct_source_address) {
+            return Ok(());
+        }
+
+        // Verify the validator is active
+        if !validator.is_active_validator(self.get_current_epoch()) {
+            return Ok(());
+        }
+
+        // Verify exit has not been initiated
+        if validator.exit_epoch != FAR_FUTURE_EPOCH {
+            return Ok(());
+        }
+
+        // Verify the validator has been active long enough
+        if self.get_current_epoch()
+            < validator.activation_epoch + chain_spec().shard_committee_period
+        {
+            return Ok(());
+        }
+
+        let pending_balance_to_withdraw = self.get_pending_balance_to_withdraw(index as u64);
+
+        if is_full_exit_request {
+            // Only exit validator if it has no pending withdrawals in the queue
+            if pending_balance_to_withdraw == 0 {
+                self.initiate_validator_exit(index as u64)?;
+            }
+            return Ok(());
+        }
+
+        let balance = self.balances[index];
+        let has_sufficient_effective_balance =
+            validator.effective_balance >= MIN_ACTIVATION_BALANCE;
+        let has_excess_balance = balance > MIN_ACTIVATION_BALANCE + pending_balance_to_withdraw;
+
+        // Only allow partial withdrawals with compounding withdrawal credentials
+        if validator.has_compounding_withdrawal_credential()
+            && has_sufficient_effective_balance
+            && has_excess_balance
+        {
+            let to_withdraw = min(
+                balance - MIN_ACTIVATION_BALANCE - pending_balance_to_withdraw,
+                amount,
+            );
+            let exit_queue_epoch = self.compute_exit_epoch_and_update_churn(to_withdraw);
+            self.pending_partial_withdrawals
+                .push(PendingPartialWithdrawal {
+                    validator_index: index as u64,
+                    amount: to_withdraw,
+                    withdrawable_epoch: exit_queue_epoch
+                        + chain_spec().min_validator_withdrawability_delay,
+                })
+                .map_err(|err| anyhow!("Couldn't push to pending_partial_withdrawals {err:?}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if ``consolidation_request`` is a request to switch its source validator to
+    /// compounding withdrawal credentials.
+    pub fn is_valid_switch_to_compounding_request(
+        &self,
+        consolidation_request: &ConsolidationRequest,
+    ) -> bool {
+        // Switch to compounding requires source and target be equal
+        if consolidation_request.source_pubkey != consolidation_request.target_pubkey {
+            return false;
+        }
+
+        // Verify pubkey exists
+        let Some(source_validator) = self
+            .validators
+            .iter()
+            .find(|validator| validator.pubkey == consolidation_request.source_pubkey)
+        else {
+            return false;
+        };
+
+        // Verify request has been authorized
+        if &source_validator.withdrawal_credentials[12..]
+            != consolidation_request.source_address.as_slice()
+        {
+            return false;
+        }
+
+        // Verify source withdrawal credentials
+        if !source_validator.has_eth1_withdrawal_credential() {
+            return false;
+        }
+
+        // Verify the source is active
+        if !source_validator.is_active_validator(self.get_current_epoch()) {
+            return false;
+        }
+
+        // Verify exit for source has not been initiated
+        source_validator.exit_epoch == FAR_FUTURE_EPOCH
+    }
+
+    pub fn process_consolidation_request(
+        &mut self,
+        consolidation_request: &ConsolidationRequest,
+    ) -> anyhow::Result<()> {
+        let source_index = self
+            .validators
+            .iter()
+            .position(|validator| validator.pubkey == consolidation_request.source_pubkey);
+
+        if self.is_valid_switch_to_compounding_request(consolidation_request) {
+            let source_index = source_index.ok_or(anyhow!("Source validator not found"))?;
+            return self.switch_to_compounding_validator(source_index as u64);
+        }
+
+        // Verify that source != target, so a consolidation cannot be used as an exit
+        if consolidation_request.source_pubkey == consolidation_request.target_pubkey {
+            return Ok(());
+        }
+
+        // If the pending consolidations queue is full, consolidation requests are ignored
+        if self.pending_consolidations.len() as u64 == PENDING_CONSOLIDATIONS_LIMIT {
+            return Ok(());
+        }
+
+        // If there is too little available consolidation churn limit, consolidation requests are
+        // ignored
+        if self.get_consolidation_churn_limit() <= MIN_ACTIVATION_BALANCE {
+            return Ok(());
+        }
+
+        // Verify pubkeys exists
+        let Some(source_index) = source_index else {
+            return Ok(());
+        };
+        let Some(target_index) = self
+            .validators
+            .iter()
+            .position(|validator| validator.pubkey == consolidation_request.target_pubkey)
+        else {
+            return Ok(());
+        };
+        let source_validator = &self.validators[source_index];
+        let target_validator = &self.validators[target_index];
+
+        // Verify source withdrawal credentials
+        let has_correct_credential = source_validator.has_execution_withdrawal_credential();
+        let is_correct_source_address = &source_validator.withdrawal_credentials[12..]
+            == consolidation_request.source_address.as_slice();
+        if !(has_correct_credential && is_correct_source_address) {
+            return Ok(());
+        }
+
+        // Verify that target has compounding withdrawal credentials
+        if !target_validator.has_compounding_withdrawal_credential() {
+            return Ok(());
+        }
+
+        // Verify the source and the target are active
+        let current_epoch = self.get_current_epoch();
+        if !source_validator.is_active_validator(current_epoch)
+            || !target_validator.is_active_validator(current_epoch)
+        {
+            return Ok(());
+        }
+
+        // Verify exits for source and target have not been initiated
+        if source_validator.exit_epoch != FAR_FUTURE_EPOCH
+            || target_validator.exit_epoch != FAR_FUTURE_EPOCH
+        {
+            return Ok(());
+        }
+
+        // Verify the source has been active long enough
+        if current_epoch < source_validator.activation_epoch + chain_spec().shard_committee_period {
+            return Ok(());
+        }
+
+        // Verify the source has no pending withdrawals in the queue
+        if self.get_pending_balance_to_withdraw(source_index as u64) > 0 {
+            return Ok(());
+        }
+
+        // Initiate source validator exit and append pending consolidation
+        let source_effective_balance = source_validator.effective_balance;
+        let exit_epoch =
+            self.compute_consolidation_epoch_and_update_churn(source_effective_balance);
+        let source_validator = &mut self.validators[source_index];
+        source_validator.exit_epoch = exit_epoch;
+        source_validator.withdrawable_epoch =
+            exit_epoch + chain_spec().min_validator_withdrawability_delay;
+        self.pending_consolidations
+            .push(PendingConsolidation {
+                source_index: source_index as u64,
+                target_index: target_index as u64,
+            })
+            .map_err(|err| anyhow!("Couldn't push to pending_consolidations {err:?}"))
+    }
+
+    pub fn verify_block_signature(&self, signed_block: &SignedBeaconBlock) -> anyhow::Result<bool> {
+        self.verify_block_signature_with_verifier(signed_block, &mut SignatureVerifier::default())
+    }
+
+    pub fn verify_block_signature_with_verifier(
+        &self,
+        signed_block: &SignedBeaconBlock,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<bool> {
+        let proposer = self
+            .validators
+            .get(signed_block.message.proposer_index as usize)
+            .ok_or_else(|| anyhow!("Invalid proposer index"))?;
+        let signing_root = compute_signing_root(
+            signed_block.message.clone(),
+            self.get_domain(DOMAIN_BEACON_PROPOSER, None),
+        );
+
+        signature_verifier.verify(SignatureSet::single_pubkey(
+            signed_block.signature.clone(),
+            proposer.pubkey.clone(),
+            signing_root,
+        ))
+    }
+
+    /// Check if ``validator`` is eligible for activation.
+    pub fn is_eligible_for_activation(&self, validator: &Validator) -> bool {
+        // Placement in queue is finalized
+        validator.activation_eligibility_epoch <= self.finalized_checkpoint.epoch
+            && validator.activation_epoch == FAR_FUTURE_EPOCH
+    }
+
+    pub fn process_registry_updates(&mut self) -> anyhow::Result<()> {
+        let current_epoch = self.get_current_epoch();
+        let activation_epoch = compute_activation_exit_epoch(current_epoch);
+
+        // [Modified in Electra:EIP7251]
+        // Process activation eligibility, ejections, and activations
+        for index in 0..self.validators.len() {
+            let validator = &self.validators[index];
+            if validator.is_eligible_for_activation_queue_electra() {
+                self.validators[index].activation_eligibility_epoch = current_epoch + 1;
+            } else if validator.is_active_validator(current_epoch)
+                && validator.effective_balance <= chain_spec().ejection_balance
+            {
+                self.initiate_validator_exit(index as u64)?;
+            } else if self.is_eligible_for_activation(validator) {
+                self.validators[index].activation_epoch = activation_epoch;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn process_pending_deposits(&mut self) -> anyhow::Result<()> {
+        let next_epoch = self.get_current_epoch() + 1;
+        let available_for_processing =
+            self.deposit_balance_to_consume + self.get_activation_exit_churn_limit();
+        let mut processed_amount = 0;
+        let mut next_deposit_index = 0;
+        let mut deposits_to_postpone = vec![];
+        let mut is_churn_limit_reached = false;
+        let finalized_slot = compute_start_slot_at_epoch(self.finalized_checkpoint.epoch);
+
+        while next_deposit_index < self.pending_deposits.len() {
+            let deposit = self.pending_deposits[next_deposit_index].clone();
+
+            // Do not process deposit requests if Eth1 bridge deposits are not yet applied.
+            if deposit.slot > GENESIS_SLOT
+                && self.eth1_deposit_index < self.deposit_requests_start_index
+            {
+                break;
+            }
+
+            // Check if deposit has been finalized, otherwise, stop processing.
+            if deposit.slot > finalized_slot {
+                break;
+            }
+
+            // Check if number of processed deposits has not reached the limit, otherwise, stop
+            // processing.
+            if next_deposit_index >= MAX_PENDING_DEPOSITS_PER_EPOCH as usize {
+                break;
+            }
+
+            // Read validator state
+            let (is_validator_exited, is_validator_withdrawn) = match self
+                .validators
+                .iter()
+                .find(|validator| validator.pubkey == deposit.pubkey)
+            {
+                Some(validator) => (
+                    validator.exit_epoch < FAR_FUTURE_EPOCH,
+                    validator.withdrawable_epoch < next_epoch,
+                ),
+                None => (false, false),
+            };
+
+            if is_validator_withdrawn {
+                // Deposited balance will never become active. Increase balance but do not consume
+                // churn
+                self.apply_pending_deposit(&deposit)?;
+            } else if is_validator_exited {
+                // Validator is exiting, postpone the deposit until after withdrawable epoch
+                deposits_to_postpone.push(deposit);
+            } else {
+                // Check if deposit fits in the churn, otherwise, do no more deposit processing in
+                // this epoch.
+                is_churn_limit_reached =
+                    processed_amount + deposit.amount > available_for_processing;
+                if is_churn_limit_reached {
+                    break;
+                }
+
+                // Consume churn and apply deposit.
+                processed_amount += deposit.amount;
+                self.apply_pending_deposit(&deposit)?;
+            }
+
+            // Regardless of how the deposit was handled, we move on in the queue.
+            next_deposit_index += 1;
+        }
+
+        let mut pending_deposits = self.pending_deposits[next_deposit_index..].to_vec();
+        pending_deposits.extend(deposits_to_postpone);
+        self.pending_deposits = pending_deposits.into();
+
+        // Accumulate churn only if the churn limit has been hit.
+        self.deposit_balance_to_consume = if is_churn_limit_reached {
+            available_for_processing - processed_amount
+        } else {
+            0
+        };
+
+        Ok(())
+    }
+
+    pub fn process_pending_consolidations(&mut self) -> anyhow::Result<()> {
+        let next_epoch = self.get_current_epoch() + 1;
+        let mut next_pending_consolidation = 0;
+
+        while next_pending_consolidation < self.pending_consolidations.len() {
+            let PendingConsolidation {
+                source_index,
+                target_index,
+            } = self.pending_consolidations[next_pending_consolidation];
+            let source_validator = &self.validators[source_index as usize];
+            if source_validator.slashed {
+                next_pending_consolidation += 1;
+                continue;
+            }
+            if source_validator.withdrawable_epoch > next_epoch {
+                break;
+            }
+
+            // Calculate the consolidated balance
+            let source_effective_balance = min(
+                self.balances[source_index as usize],
+                source_validator.effective_balance,
+            );
+
+            // Move active balance to target. Excess balance is withdrawable.
+            self.decrease_balance(source_index, source_effective_balance)?;
+            self.increase_balance(target_index, source_effective_balance)?;
+            next_pending_consolidation += 1;
+        }
+
+        self.pending_consolidations = self.pending_consolidations[next_pending_consolidation..]
+            .to_vec()
+            .into();
+
+        Ok(())
+    }
+
+    /// Return the sync committee validators sign for at the next slot, the next committee on
+    /// the last slot of a sync committee period.
+    pub fn get_sync_committee_for_next_slot(&self) -> &Arc<SyncCommittee> {
+        let next_slot_epoch = compute_epoch_at_slot(self.slot + 1);
+        if compute_sync_committee_period(self.get_current_epoch())
+            == compute_sync_committee_period(next_slot_epoch)
+        {
+            &self.current_sync_committee
+        } else {
+            &self.next_sync_committee
+        }
+    }
+
+    /// Return the pubkeys of the sync subcommittee ``subcommittee_index``.
+    pub fn get_sync_subcommittee_pubkeys(&self, subcommittee_index: u64) -> Vec<PubKey> {
+        let sync_subcommittee_size = (SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT) as usize;
+        let start = subcommittee_index as usize * sync_subcommittee_size;
+        self.get_sync_committee_for_next_slot()
+            .pubkeys
+            .iter()
+            .skip(start)
+            .take(sync_subcommittee_size)
+            .cloned()
+            .collect()
+    }
+
+    /// Return the sync committee subnets of ``validator_index``, one per seat it holds.
+    pub fn compute_subnets_for_sync_committee(
+        &self,
+        validator_index: u64,
+    ) -> anyhow::Result<HashSet<u64>> {
+        let target_pubkey = &self
+            .validators
+            .get(validator_index as usize)
+            .ok_or_else(|| anyhow!("Validator index {validator_index} out of bounds"))?
+            .pubkey;
+        let sync_subcommittee_size = SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT;
+
+        Ok(self
+            .get_sync_committee_for_next_slot()
+            .pubkeys
+            .iter()
+            .enumerate()
+            .filter(|(_, pubkey)| *pubkey == target_pubkey)
+            .map(|(index, _)| index as u64 / sync_subcommittee_size)
+            .collect())
+    }
+
+    pub fn process_participation_flag_updates(&mut self) -> anyhow::Result<()> {
+        self.previous_epoch_participation = self.current_epoch_participation.clone();
+        self.current_epoch_participation = vec![0; self.validators.len()].into();
+        Ok(())
+    }
+
+    pub fn process_epoch(&mut self) -> anyhow::Result<()> {
+        self.process_justification_and_finalization()?;
+        self.process_inactivity_updates()?;
+        self.process_rewards_and_penalties()?;
+        self.process_registry_updates()?;
+        self.process_slashings()?;
+        self.process_eth1_data_reset()?;
+        self.process_pending_deposits()?;
+        self.process_pending_consolidations()?;
+        self.process_effective_balance_updates()?;
+        self.process_slashings_reset()?;
+        self.process_randao_mixes_reset()?;
+        self.process_historical_summaries_update()?;
+        self.process_participation_flag_updates()?;
+        self.process_sync_committee_updates()?;
+        Ok(())
+    }
+
+    pub fn process_slots(&mut self, slot: u64) -> anyhow::Result<()> {
+        ensure!(self.slot < slot);
+
+        while self.slot < slot {
+            self.process_slot()?;
+            // Process epoch on the start slot of the next epoch
+            if (self.slot + 1) % SLOTS_PER_EPOCH == 0 {
+                self.process_epoch()?;
+            }
+            self.slot += 1
+        }
+        Ok(())
+    }
+
+    /// Return ``hash_tree_root(state)``, only rehashing the parts of the large fields which changed
+    /// since ``cache`` was last used.
+    pub fn tree_hash_root_with_cache(&self, cache: &mut BeaconStateTreeHashCache) -> B256 {
+        merkleize_field_roots(&[
+            self.genesis_time.tree_hash_root(),
+            self.genesis_validators_root.tree_hash_root(),
+            self.slot.tree_hash_root(),
+            self.fork.tree_hash_root(),
+            self.latest_block_header.tree_hash_root(),
+            cache.block_roots.recalculate_merkle_root(&self.block_roots),
+            cache.state_roots.recalculate_merkle_root(&self.state_roots),
+            self.historical_roots.tree_hash_root(),
+            self.eth1_data.tree_hash_root(),
+            self.eth1_data_votes.tree_hash_root(),
+            self.eth1_deposit_index.tree_hash_root(),
+            cache.validators.recalculate_list_root(&self.validators),
+            cache.balances.recalculate_list_root(&self.balances),
+            cache
+                .randao_mixes
+                .recalculate_merkle_root(&self.randao_mixes),
+            self.slashings.tree_hash_root(),
+            cache
+                .previous_epoch_participation
+                .recalculate_list_root(&self.previous_epoch_participation),
+            cache
+                .current_epoch_participation
+                .recalculate_list_root(&self.current_epoch_participation),
+            self.justification_bits.tree_hash_root(),
+            self.previous_justified_checkpoint.tree_hash_root(),
+            self.current_justified_checkpoint.tree_hash_root(),
+            self.finalized_checkpoint.tree_hash_root(),
+            cache
+                .inactivity_scores
+                .recalculate_list_root(&self.inactivity_scores),
+            self.current_sync_committee.tree_hash_root(),
+            self.next_sync_committee.tree_hash_root(),
+            self.latest_execution_payload_header.tree_hash_root(),
+            self.next_withdrawal_index.tree_hash_root(),
+            self.next_withdrawal_validator_index.tree_hash_root(),
+            self.historical_summaries.tree_hash_root(),
+            self.deposit_requests_start_index.tree_hash_root(),
+            self.deposit_balance_to_consume.tree_hash_root(),
+            self.exit_balance_to_consume.tree_hash_root(),
+            self.earliest_exit_epoch.tree_hash_root(),
+            self.consolidation_balance_to_consume.tree_hash_root(),
+            self.earliest_consolidation_epoch.tree_hash_root(),
+            self.pending_deposits.tree_hash_root(),
+            self.pending_partial_withdrawals.tree_hash_root(),
+            self.pending_consolidations.tree_hash_root(),
+        ])
+    }
+
+    /// Return ``hash_tree_root(state)`` using the ``tree_hash_cache`` of the state.
+    pub fn cached_tree_hash_root(&self) -> B256 {
+        self.tree_hash_cache
+            .with(|cache| self.tree_hash_root_with_cache(cache))
+    }
+
+    /// Return the branch of ``current_sync_committee``, at
+    /// ``CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA``.
+    pub fn current_sync_committee_branch(&self) -> anyhow::Result<FixedVector<B256, U6>> {
+        FixedVector::new(
+            self.compute_merkle_proof(CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA)?
+                .branch,
+        )
+        .map_err(|err| anyhow!("Unexpected current sync committee branch depth: {err:?}"))
+    }
+
+    /// Return the branch of ``next_sync_committee``, at ``NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA``.
+    pub fn next_sync_committee_branch(&self) -> anyhow::Result<FixedVector<B256, U6>> {
+        FixedVector::new(
+            self.compute_merkle_proof(NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA)?
+                .branch,
+        )
+        .map_err(|err| anyhow!("Unexpected next sync committee branch depth: {err:?}"))
+    }
+
+    /// Return the branch of ``finalized_checkpoint.root``, at ``FINALIZED_ROOT_GINDEX_ELECTRA``.
+    pub fn finality_branch(&self) -> anyhow::Result<FixedVector<B256, U7>> {
+        FixedVector::new(
+            self.compute_merkle_proof(FINALIZED_ROOT_GINDEX_ELECTRA)?
+                .branch,
+        )
+        .map_err(|err| anyhow!("Unexpected finality branch depth: {err:?}"))
+    }
+
+    pub fn process_slot(&mut self) -> anyhow::Result<()> {
+        // Cache state root
+        let previous_state_root = self.cached_tree_hash_root();
+        self.state_roots[(self.slot % SLOTS_PER_HISTORICAL_ROOT) as usize] = previous_state_root;
+        // Cache latest block header state root
+        if self.latest_block_header.state_root == B256::default() {
+            self.latest_block_header.state_root = previous_state_root;
+        }
+        // Cache block root
+        let previous_block_root = self.latest_block_header.tree_hash_root();
+        self.block_roots[(self.slot % SLOTS_PER_HISTORICAL_ROOT) as usize] = previous_block_root;
+        Ok(())
+    }
+
+    /// Return the weak subjectivity period for the current ``state``.
+    /// Since Electra the validator set churn is bounded by ``get_balance_churn_limit()`` per epoch,
+    /// which also bounds balance top-ups.
+    pub fn compute_weak_subjectivity_period(&self) -> u64 {
+        let t = self.get_total_active_balance();
+        let delta = self.get_balance_churn_limit();
+        let epochs_for_validator_set_churn = SAFETY_DECAY * t / (2 * delta * 100);
+        chain_spec().min_validator_withdrawability_delay + epochs_for_validator_set_churn
+    }
+
+    /// Return ``true`` if ``current_epoch`` is within the weak subjectivity period of the state,
+    /// i.e. a node starting from this state is still safe from long range attacks.
+    pub fn is_within_weak_subjectivity_period(&self, current_epoch: u64) -> bool {
+        let ws_state_epoch = compute_epoch_at_slot(self.slot);
+        current_epoch <= ws_state_epoch + self.compute_weak_subjectivity_period()
+    }
+
+    pub async fn process_execution_payload(
+        &mut self,
+        body: &BeaconBlockBody,
+        execution_engine: &impl ExecutionApi,
+    ) -> anyhow::Result<()> {
+        let payload = &body.execution_payload;
+
+        // Verify consistency of the parent hash with respect to the previous execution payload
+        // header
+        ensure!(payload.parent_hash == self.latest_execution_payload_header.block_hash);
+        // Verify prev_randao
+        ensure!(payload.prev_randao == self.get_randao_mix(self.get_current_epoch()));
+        // Verify timestamp
+        ensure!(payload.timestamp == self.compute_timestamp_at_slot(self.slot));
+        // Verify commitments are under limit
+        // [Modified in Electra:EIP7691]
+        ensure!(
+            body.blob_kzg_commitments.len() <= chain_spec().max_blobs_per_block_electra as usize
+        );
+
+        // Verify the execution payload is valid
+        let mut versioned_hashes = vec![];
+        for commitment in body.blob_kzg_commitments.iter() {
+            versioned_hashes.push(kzg_commitment_to_versioned_hash(commitment));
+        }
+        ensure!(
+            execution_engine
+                .verify_and_notify_new_payload(NewPayloadRequest {
+                    execution_payload: payload.clone(),
+                    versioned_hashes,
+                    parent_beacon_block_root: self.latest_block_header.parent_root,
+                    execution_requests: Some(body.execution_requests.clone()),
+                })
+                .await?
+        );
+
+        // Cache execution payload header
+        self.latest_execution_payload_header = payload.to_execution_payload_header();
+
+        Ok(())
+    }
+
+    pub async fn process_block(
+        &mut self,
+        block: &BeaconBlock,
+        execution_engine: &impl ExecutionApi,
+    ) -> anyhow::Result<()> {
+        self.process_block_with_verifier(block, execution_engine, &mut SignatureVerifier::default())
+            .await
+    }
+
+    pub async fn process_block_with_verifier(
+        &mut self,
+        block: &BeaconBlock,
+        execution_engine: &impl ExecutionApi,
+        signature_verifier: &mut SignatureVerifier,
+    ) -> anyhow::Result<()> {
+        self.process_block_header(block)?;
+        self.process_withdrawals(&block.body.execution_payload)?;
+        self.process_execution_payload(&block.body, execution_engine)
+            .await?;
+        self.process_randao_with_verifier(&block.body, signature_verifier)?;
+        self.process_eth1_data(&block.body)?;
+        self.process_operations_with_verifier(&block.body, signature_verifier)?;
+        self.process_sync_aggregate_with_verifier(&block.body.sync_aggregate, signature_verifier)?;
+        Ok(())
+    }
+
+    /// The signatures of the operations are always verified, as a single batch. The proposer
+    /// signature and the post state root are only checked when ``validate_result`` is set.
+    pub async fn state_transition(
+        &mut self,
+        signed_block: &SignedBeaconBlock,
+        validate_result: bool,
+        execution_engine: &impl ExecutionApi,
+    ) -> anyhow::Result<()> {
+        self.state_transition_with_strategy(
+            signed_block,
+            BlockSignatureStrategy::VerifyBulk,
+            validate_result,
+            execution_engine,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Apply ``signed_block`` checking the signatures of its operations with ``strategy``. As in
+    /// ``state_transition``, the proposer signature and the post state root are only checked when
+    /// ``validate_result`` is set. Returns the time spent in each phase.
+    pub async fn state_transition_with_strategy(
+        &mut self,
+        signed_block: &SignedBeaconBlock,
+        strategy: BlockSignatureStrategy,
+        validate_result: bool,
+        execution_engine: &impl ExecutionApi,
+    ) -> anyhow::Result<StateTransitionTimings> {
+        let block = &signed_block.message;
+        let mut signature_verifier = SignatureVerifier::new(strategy);
+        let mut timings = StateTransitionTimings::default();
+
+        // Process slots (including those with no blocks) since block
+        let start = Instant::now();
+        self.process_slots(block.slot)?;
+        timings.process_slots = start.elapsed();
+
+        // Verify signature
+        if validate_result {
+            let start = Instant::now();
+            ensure!(
+                self.verify_block_signature_with_verifier(signed_block, &mut signature_verifier)?,
+                "Block signature verification failed"
+            );
+            timings.verify_signatures = start.elapsed();
+        }
+
+        // Process block
+        let start = Instant::now();
+        self.process_block_with_verifier(block, execution_engine, &mut signature_verifier)
+            .await?;
+        timings.process_block = start.elapsed();
+
+        // Verify the signatures deferred while processing the block
+        let start = Instant::now();
+        signature_verifier.finish()?;
+        timings.verify_signatures += start.elapsed();
+
+        // Verify state root
+        if validate_result {
+            let start = Instant::now();
+            ensure!(
+                block.state_root == self.tree_hash_root(),
+                "State root mismatch"
+            );
+            timings.state_root = start.elapsed();
+        }
+
+        Ok(timings)
+    }
+}
+
+pub fn get_validator_from_deposit(
+    pubkey: PubKey,
+    withdrawal_credentials: B256,
+    amount: u64,
+) -> Validator {
+    let mut validator = Validator {
+        pubkey,
+        withdrawal_credentials,
+        effective_balance: 0,
+        slashed: false,
+        activation_eligibility_epoch: FAR_FUTURE_EPOCH,
+        activation_epoch: FAR_FUTURE_EPOCH,
+        exit_epoch: FAR_FUTURE_EPOCH,
+        withdrawable_epoch: FAR_FUTURE_EPOCH,
+    };
+
+    // [Modified in Electra:EIP7251]
+    let max_effective_balance = validator.get_max_effective_balance();
+    validator.effective_balance = min(
+        amount - amount % EFFECTIVE_BALANCE_INCREMENT,
+        max_effective_balance,
+    );
+
+    validator
+}
+
+/// Check the deposit signature (proof of possession), which is not checked by the deposit
+/// contract.
+pub fn is_valid_deposit_signature(
+    pubkey: &PubKey,
+    withdrawal_credentials: B256,
+    amount: u64,
+    signature: &BLSSignature,
+) -> bool {
+    let deposit_message = DepositMessage {
+        pubkey: pubkey.clone(),
+        withdrawal_credentials,
+        amount,
+    };
+    let domain = compute_domain(DOMAIN_DEPOSIT, None, None); // Fork-agnostic domain since deposits are valid across forks
+    let signing_root = compute_signing_root(deposit_message, domain);
+    signature
+        .verify(pubkey, signing_root.as_ref())
+        .unwrap_or(false)
+}
+
+/// Return the amount already withdrawn from ``validator_index`` in ``withdrawals``.
+fn get_total_withdrawn(withdrawals: &[Withdrawal], validator_index: u64) -> u64 {
+    withdrawals
+        .iter()
+        .filter(|withdrawal| withdrawal.validator_index == validator_index)
+        .map(|withdrawal| withdrawal.amount)
+        .sum()
+}
+
+/// Upgrade the Deneb state ``pre`` to Electra at the fork epoch.
+pub fn upgrade_to_electra(pre: deneb::beacon_state::BeaconState) -> anyhow::Result<BeaconState> {
+    let epoch = pre.get_current_epoch();
+    let activation_exit_epoch = compute_activation_exit_epoch(epoch);
+    let earliest_exit_epoch = pre
+        .validators
+        .iter()
+        .map(|validator| validator.exit_epoch)
+        .filter(|&exit_epoch| exit_epoch != FAR_FUTURE_EPOCH)
+        .fold(activation_exit_epoch, max)
+        + 1;
+
+    let mut post = BeaconState {
+        // Versioning
+        genesis_time: pre.genesis_time,
+        genesis_validators_root: pre.genesis_validators_root,
+        slot: pre.slot,
+        fork: Fork {
+            previous_version: pre.fork.current_version,
+            current_version: chain_spec().electra_fork_version,
+            epoch,
+        },
+
+        // History
+        latest_block_header: pre.latest_block_header,
+        block_roots: pre.block_roots,
+        state_roots: pre.state_roots,
+        historical_roots: pre.historical_roots,
+
+        // Eth1
+        eth1_data: pre.eth1_data,
+        eth1_data_votes: pre.eth1_data_votes,
+        eth1_deposit_index: pre.eth1_deposit_index,
+
+        // Registry
+        validators: pre.validators,
+        balances: pre.balances,
+
+        // Randomness
+        randao_mixes: pre.randao_mixes,
+
+        // Slashings
+        slashings: pre.slashings,
+
+        // Participation
+        previous_epoch_participation: pre.previous_epoch_participation,
+        current_epoch_participation: pre.current_epoch_participation,
+
+        // Finality
+        justification_bits: pre.justification_bits,
+        previous_justified_checkpoint: pre.previous_justified_checkpoint,
+        current_justified_checkpoint: pre.current_justified_checkpoint,
+        finalized_checkpoint: pre.finalized_checkpoint,
+
+        // Inactivity
+        inactivity_scores: pre.inactivity_scores,
+
+        // Sync
+        current_sync_committee: pre.current_sync_committee,
+        next_sync_committee: pre.next_sync_committee,
+
+        // Execution
+        latest_execution_payload_header: pre.latest_execution_payload_header,
+
+        // Withdrawals
+        next_withdrawal_index: pre.next_withdrawal_index,
+        next_withdrawal_validator_index: pre.next_withdrawal_validator_index,
+
+        // Deep history valid from Capella onwards.
+        historical_summaries: pre.historical_summaries,
+
+        // Electra
+        deposit_requests_start_index: UNSET_DEPOSIT_REQUESTS_START_INDEX,
+        deposit_balance_to_consume: 0,
+        exit_balance_to_consume: 0,
+        earliest_exit_epoch,
+        consolidation_balance_to_consume: 0,
+        earliest_consolidation_epoch: activation_exit_epoch,
+        pending_deposits: VariableList::default(),
+        pending_partial_withdrawals: VariableList::default(),
+        pending_consolidations: VariableList::default(),
+        tree_hash_cache: BeaconStateTreeHashCacheCell::default(),
+    };
+
+    post.exit_balance_to_consume = post.get_activation_exit_churn_limit();
+    post.consolidation_balance_to_consume = post.get_consolidation_churn_limit();
+
+    // [New in Electra:EIP7251]
+    // Add validators that are not yet active to pending balance deposits
+    let pre_activation: Vec<usize> = post
+        .validators
+        .iter()
+        .enumerate()
+        .filter(|(_, validator)| validator.activation_epoch == FAR_FUTURE_EPOCH)
+        .sorted_by_key(|(index, validator)| (validator.activation_eligibility_epoch, *index))
+        .map(|(index, _)| index)
+        .collect();
+
+    for index in pre_activation {
+        let balance = post.balances[index];
+        post.balances[index] = 0;
+        let validator = &mut post.validators[index];
+        validator.effective_balance = 0;
+        validator.activation_eligibility_epoch = FAR_FUTURE_EPOCH;
+        // Use bls.G2_POINT_AT_INFINITY as a signature field placeholder and GENESIS_SLOT to
+        // distinguish from a pending deposit request
+        let pending_deposit = PendingDeposit {
+            pubkey: validator.pubkey.clone(),
+            withdrawal_credentials: validator.withdrawal_credentials,
+            amount: balance,
+            signature: BLSSignature::infinity(),
+            slot: GENESIS_SLOT,
+        };
+        post.pending_deposits
+            .push(pending_deposit)
+            .map_err(|err| anyhow!("Couldn't push to pending_deposits {err:?}"))?;
+    }
+
+    // Ensure early adopters of compounding credentials go through the activation churn
+    for index in 0..post.validators.len() {
+        if post.validators[index].has_compounding_withdrawal_credential() {
+            post.queue_excess_active_balance(index as u64)?;
+        }
+    }
+
+    Ok(post)
+}
+
+/// Build the Electra genesis state from the eth1 block the deposits were taken from.
+///
+/// ``deposits`` are processed in order, each one proven against the deposit root of the deposits
+/// up to and including it.
+pub fn initialize_beacon_state_from_eth1(
+    eth1_block_hash: B256,
+    eth1_timestamp: u64,
+    deposits: &[Deposit],
+    execution_payload_header: ExecutionPayloadHeader,
+) -> anyhow::Result<BeaconState> {
+    let fork = Fork {
+        previous_version: chain_spec().electra_fork_version,
+        current_version: chain_spec().electra_fork_version,
+        epoch: GENESIS_EPOCH,
+    };
+    let mut state = BeaconState {
+        genesis_time: eth1_timestamp + chain_spec().genesis_delay,
+        genesis_validators_root: B256::ZERO,
+        slot: GENESIS_SLOT,
+        fork,
+        latest_block_header: BeaconBlockHeader {
+            body_root: BeaconBlockBody::default().tree_hash_root(),
+            ..Default::default()
+        },
+        block_roots: FixedVector::default(),
+        state_roots: FixedVector::default(),
+        historical_roots: VariableList::default(),
+        eth1_data: Eth1Data {
+            block_hash: eth1_block_hash,
+            deposit_count: deposits.len() as u64,
+            ..Default::default()
+        },
+        eth1_data_votes: VariableList::default(),
+        eth1_deposit_index: 0,
+        validators: VariableList::default(),
+        balances: VariableList::default(),
+        randao_mixes: FixedVector::from(vec![
+            eth1_block_hash;
+            EPOCHS_PER_HISTORICAL_VECTOR as usize
+        ]),
+        slashings: FixedVector::default(),
+        previous_epoch_participation: VariableList::default(),
+        current_epoch_participation: VariableList::default(),
+        justification_bits: BitVector::default(),
+        previous_justified_checkpoint: Checkpoint::default(),
+        current_justified_checkpoint: Checkpoint::default(),
+        finalized_checkpoint: Checkpoint::default(),
+        inactivity_scores: VariableList::default(),
+        current_sync_committee: Arc::new(SyncCommittee::default()),
+        next_sync_committee: Arc::new(SyncCommittee::default()),
+        latest_execution_payload_header: ExecutionPayloadHeader::default(),
+        next_withdrawal_index: 0,
+        next_withdrawal_validator_index: 0,
+        historical_summaries: VariableList::default(),
+        deposit_requests_start_index: UNSET_DEPOSIT_REQUESTS_START_INDEX,
+        deposit_balance_to_consume: 0,
+        exit_balance_to_consume: 0,
+        earliest_exit_epoch: 0,
+        consolidation_balance_to_consume: 0,
+        earliest_consolidation_epoch: 0,
+        pending_deposits: VariableList::default(),
+        pending_partial_withdrawals: VariableList::default(),
+        pending_consolidations: VariableList::default(),
+        tree_hash_cache: BeaconStateTreeHashCacheCell::default(),
+    };
+
+    // Process deposits
+    let mut deposit_tree = DepositTree::default();
+    for deposit in deposits {
+        deposit_tree.push_leaf(deposit.data.tree_hash_root())?;
+        state.eth1_data.deposit_root = deposit_tree.get_root();
+        state.process_deposit(deposit)?;
+    }
+
+    // Process deposit balance updates
+    for deposit in std::mem::take(&mut state.pending_deposits).iter() {
+        let validator_index = state
+            .validators
+            .iter()
+            .position(|validator| validator.pubkey == deposit.pubkey)
+            .ok_or_else(|| anyhow!("Pending deposit for an unknown validator"))?;
+        state.increase_balance(validator_index as u64, deposit.amount)?;
+    }
+
+    // Process activations
+    for (index, validator) in state.validators.iter_mut().enumerate() {
+        let balance = state.balances[index];
+        validator.effective_balance = min(
+            balance - balance % EFFECTIVE_BALANCE_INCREMENT,
+            validator.get_max_effective_balance(),
+        );
+        if validator.effective_balance >= MIN_ACTIVATION_BALANCE {
+            validator.activation_eligibility_epoch = GENESIS_EPOCH;
+            validator.activation_epoch = GENESIS_EPOCH;
+        }
+    }
+
+    // Set genesis validators root for domain separation and chain versioning
+    state.genesis_validators_root = state.validators.tree_hash_root();
+
+    // Fill in sync committees
+    // Note: A duplicate committee is assigned for the current and next committee at genesis
+    state.current_sync_committee = Arc::new(state.get_next_sync_committee()?);
+    state.next_sync_committee = state.current_sync_committee.clone();
+
+    // Initialize the execution payload header
+    state.latest_execution_payload_header = execution_payload_header;
+
+    Ok(state)
+}
@@ -0,0 +1,139 @@
+use alloy_primitives::Bytes;
+use anyhow::{anyhow, bail, ensure};
+use serde::{Deserialize, Serialize};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{VariableList, typenum::U2};
+use tree_hash_derive::TreeHash;
+
+use super::{
+    consolidation_request::ConsolidationRequest, deposit_request::DepositRequest,
+    withdrawal_request::WithdrawalRequest,
+};
+use crate::{
+    constants::{CONSOLIDATION_REQUEST_TYPE, DEPOSIT_REQUEST_TYPE, WITHDRAWAL_REQUEST_TYPE},
+    preset::{MaxDepositRequestsPerPayload, MaxWithdrawalRequestsPerPayload},
+};
+
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct ExecutionRequests {
+    /// [New in Electra:EIP6110]
+    pub deposits: VariableList<DepositRequest, MaxDepositRequestsPerPayload>,
+
+    /// [New in Electra:EIP7002:EIP7251]
+    pub withdrawals: VariableList<WithdrawalRequest, MaxWithdrawalRequestsPerPayload>,
+
+    /// [New in Electra:EIP7251]
+    pub consolidations: VariableList<ConsolidationRequest, U2>,
+}
+
+impl ExecutionRequests {
+    /// Return the requests in the form passed to the execution engine, each non-empty list of
+    /// requests is SSZ encoded and prefixed with its request type.
+    pub fn get_execution_requests_list(&self) -> Vec<Bytes> {
+        [
+            (DEPOSIT_REQUEST_TYPE, self.deposits.as_ssz_bytes()),
+            (WITHDRAWAL_REQUEST_TYPE, self.withdrawals.as_ssz_bytes()),
+            (
+                CONSOLIDATION_REQUEST_TYPE,
+                self.consolidations.as_ssz_bytes(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, request_data)| !request_data.is_empty())
+        .map(|(request_type, request_data)| {
+            Bytes::from([&[request_type], request_data.as_slice()].concat())
+        })
+        .collect()
+    }
+
+    /// Return the requests of the list returned by the execution engine,
+    /// ``get_execution_requests`` in the specs.
+    pub fn from_execution_requests_list(execution_requests_list: &[Bytes]) -> anyhow::Result<Self> {
+        let mut execution_requests = Self::default();
+        let mut previous_request_type = None;
+        for request in execution_requests_list {
+            let (&request_type, request_data) = request
+                .split_first()
+                .ok_or_else(|| anyhow!("Execution request must have a type"))?;
+            ensure!(
+                !request_data.is_empty(),
+                "Execution request of type {request_type} must not be empty"
+            );
+            ensure!(
+                previous_request_type.is_none_or(|previous| previous < request_type),
+                "Execution requests must be in strictly ascending order of type"
+            );
+            previous_request_type = Some(request_type);
+
+            let decode_error =
+                |err| anyhow!("Invalid execution requests of type {request_type}: {err:?}");
+            match request_type {
+                DEPOSIT_REQUEST_TYPE => {
+                    execution_requests.deposits =
+                        VariableList::from_ssz_bytes(request_data).map_err(decode_error)?
+                }
+                WITHDRAWAL_REQUEST_TYPE => {
+                    execution_requests.withdrawals =
+                        VariableList::from_ssz_bytes(request_data).map_err(decode_error)?
+                }
+                CONSOLIDATION_REQUEST_TYPE => {
+                    execution_requests.consolidations =
+                        VariableList::from_ssz_bytes(request_data).map_err(decode_error)?
+                }
+                _ => bail!("Unknown execution request type {request_type}"),
+            }
+        }
+        Ok(execution_requests)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::{Address, B256};
+
+    use super::*;
+
+    #[test]
+    fn execution_requests_list_round_trips() {
+        let execution_requests = ExecutionRequests {
+            withdrawals: VariableList::from(vec![WithdrawalRequest {
+                source_address: Address::repeat_byte(1),
+                validator_pubkey: Default::default(),
+                amount: 32,
+            }]),
+            consolidations: VariableList::from(vec![ConsolidationRequest {
+                source_address: Address::repeat_byte(2),
+                source_pubkey: Default::default(),
+                target_pubkey: Default::default(),
+            }]),
+            ..Default::default()
+        };
+        let execution_requests_list = execution_requests.get_execution_requests_list();
+        assert_eq!(execution_requests_list.len(), 2);
+        assert_eq!(
+            ExecutionRequests::from_execution_requests_list(&execution_requests_list).unwrap(),
+            execution_requests
+        );
+
+        // The types must be ascending and the requests not empty
+        let reversed = execution_requests_list
+            .into_iter()
+            .rev()
+            .collect::<Vec<_>>();
+        assert!(ExecutionRequests::from_execution_requests_list(&reversed).is_err());
+        assert!(
+            ExecutionRequests::from_execution_requests_list(&[Bytes::from(vec![
+                DEPOSIT_REQUEST_TYPE
+            ])])
+            .is_err()
+        );
+        assert!(
+            ExecutionRequests::from_execution_requests_list(&[Bytes::from(
+                [&[3], B256::ZERO.as_slice()].concat()
+            )])
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use ream_bls::BLSSignature;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::VariableList;
+use tree_hash_derive::TreeHash;
+
+use crate::{attestation_data::AttestationData, preset::MaxValidatorsPerSlot};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct IndexedAttestation {
+    /// [Modified in Electra:EIP7549]
+    pub attesting_indices: VariableList<u64, MaxValidatorsPerSlot>,
+    pub data: AttestationData,
+    pub signature: BLSSignature,
+}
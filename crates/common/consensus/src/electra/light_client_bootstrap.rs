@@ -0,0 +1,47 @@
+use alloy_primitives::B256;
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{FixedVector, typenum::U6};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::{
+    beacon_block::SignedBeaconBlock, beacon_state::BeaconState,
+    light_client_header::LightClientHeader,
+};
+use crate::sync_committee::SyncCommittee;
+
+/// The trusted starting point of a light client, the current sync committee proven against the
+/// state of a block.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct LightClientBootstrap {
+    /// Header matching the requested beacon block root
+    pub header: LightClientHeader,
+    /// Current sync committee corresponding to ``header.beacon.state_root``
+    pub current_sync_committee: SyncCommittee,
+    pub current_sync_committee_branch: FixedVector<B256, U6>,
+}
+
+impl LightClientBootstrap {
+    /// Create the bootstrap of ``block`` from its post state ``state``,
+    /// ``create_light_client_bootstrap`` in the specs.
+    pub fn new(state: &BeaconState, block: &SignedBeaconBlock) -> anyhow::Result<Self> {
+        ensure!(
+            state.slot == state.latest_block_header.slot,
+            "State must be at the slot of its latest block header"
+        );
+        let mut header = state.latest_block_header.clone();
+        header.state_root = state.cached_tree_hash_root();
+        ensure!(
+            header.tree_hash_root() == block.message.tree_hash_root(),
+            "State is not the post state of the block"
+        );
+
+        Ok(Self {
+            header: LightClientHeader::new(block)?,
+            current_sync_committee: (*state.current_sync_committee).clone(),
+            current_sync_committee_branch: state.current_sync_committee_branch()?,
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{FixedVector, typenum::U7};
+use tree_hash_derive::TreeHash;
+
+use super::{light_client_header::LightClientHeader, light_client_update::LightClientUpdate};
+use crate::sync_aggregate::SyncAggregate;
+
+/// The latest finalized header known to the node, gossiped to light clients.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct LightClientFinalityUpdate {
+    /// Header attested to by the sync committee
+    pub attested_header: LightClientHeader,
+    /// Finalized header corresponding to ``attested_header.beacon.state_root``
+    pub finalized_header: LightClientHeader,
+    pub finality_branch: FixedVector<B256, U7>,
+    /// Sync committee aggregate signature
+    pub sync_aggregate: SyncAggregate,
+    /// Slot at which the aggregate signature was created (untrusted)
+    pub signature_slot: u64,
+}
+
+impl From<&LightClientUpdate> for LightClientFinalityUpdate {
+    fn from(update: &LightClientUpdate) -> Self {
+        Self {
+            attested_header: update.attested_header.clone(),
+            finalized_header: update.finalized_header.clone(),
+            finality_branch: update.finality_branch.clone(),
+            sync_aggregate: update.sync_aggregate.clone(),
+            signature_slot: update.signature_slot,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{FixedVector, typenum::U4};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::beacon_block::SignedBeaconBlock;
+use crate::{
+    beacon_block_header::BeaconBlockHeader,
+    constants::EXECUTION_PAYLOAD_GINDEX,
+    deneb::{
+        beacon_state::is_valid_merkle_branch, execution_payload_header::ExecutionPayloadHeader,
+    },
+};
+
+/// The header of a block as seen by light clients, with the execution payload header proven
+/// against the body root.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct LightClientHeader {
+    pub beacon: BeaconBlockHeader,
+    pub execution: ExecutionPayloadHeader,
+    pub execution_branch: FixedVector<B256, U4>,
+}
+
+impl LightClientHeader {
+    /// Return the light client header of ``block``, ``block_to_light_client_header`` in the specs.
+    pub fn new(block: &SignedBeaconBlock) -> anyhow::Result<Self> {
+        Ok(Self {
+            beacon: block.message.block_header(),
+            execution: block
+                .message
+                .body
+                .execution_payload
+                .to_execution_payload_header(),
+            execution_branch: block.message.body.execution_payload_branch()?,
+        })
+    }
+
+    /// Check the execution payload header is part of the block body.
+    pub fn is_valid(&self) -> bool {
+        is_valid_merkle_branch(
+            self.execution.tree_hash_root(),
+            &self.execution_branch,
+            EXECUTION_PAYLOAD_GINDEX.ilog2() as u64,
+            EXECUTION_PAYLOAD_GINDEX % 2u64.pow(EXECUTION_PAYLOAD_GINDEX.ilog2()),
+            self.beacon.body_root,
+        )
+    }
+}
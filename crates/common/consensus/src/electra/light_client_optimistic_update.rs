@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+use super::{light_client_header::LightClientHeader, light_client_update::LightClientUpdate};
+use crate::sync_aggregate::SyncAggregate;
+
+/// The latest header attested to by the sync committee, gossiped to light clients.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct LightClientOptimisticUpdate {
+    /// Header attested to by the sync committee
+    pub attested_header: LightClientHeader,
+    /// Sync committee aggregate signature
+    pub sync_aggregate: SyncAggregate,
+    /// Slot at which the aggregate signature was created (untrusted)
+    pub signature_slot: u64,
+}
+
+impl From<&LightClientUpdate> for LightClientOptimisticUpdate {
+    fn from(update: &LightClientUpdate) -> Self {
+        Self {
+            attested_header: update.attested_header.clone(),
+            sync_aggregate: update.sync_aggregate.clone(),
+            signature_slot: update.signature_slot,
+        }
+    }
+}
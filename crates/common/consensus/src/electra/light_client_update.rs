@@ -0,0 +1,233 @@
+use alloy_primitives::B256;
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    FixedVector,
+    typenum::{U6, U7},
+};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::{
+    beacon_block::SignedBeaconBlock, beacon_state::BeaconState,
+    light_client_header::LightClientHeader,
+};
+use crate::{
+    constants::{GENESIS_SLOT, MIN_SYNC_COMMITTEE_PARTICIPANTS},
+    misc::compute_sync_committee_period_at_slot,
+    sync_aggregate::SyncAggregate,
+    sync_committee::SyncCommittee,
+};
+
+/// An update of the light client, the sync committee signature over an attested header along
+/// with the next sync committee and the finalized header it commits to when known.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct LightClientUpdate {
+    /// Header attested to by the sync committee
+    pub attested_header: LightClientHeader,
+    /// Next sync committee corresponding to ``attested_header.beacon.state_root``
+    pub next_sync_committee: SyncCommittee,
+    pub next_sync_committee_branch: FixedVector<B256, U6>,
+    /// Finalized header corresponding to ``attested_header.beacon.state_root``
+    pub finalized_header: LightClientHeader,
+    pub finality_branch: FixedVector<B256, U7>,
+    /// Sync committee aggregate signature
+    pub sync_aggregate: SyncAggregate,
+    /// Slot at which the aggregate signature was created (untrusted)
+    pub signature_slot: u64,
+}
+
+impl LightClientUpdate {
+    /// Create the update signed by the sync aggregate of ``block``, attesting to its parent
+    /// ``attested_block``. ``finalized_block`` is the block of the finalized checkpoint of
+    /// ``attested_state`` if it is known, ``create_light_client_update`` in the specs.
+    pub fn new(
+        state: &BeaconState,
+        block: &SignedBeaconBlock,
+        attested_state: &BeaconState,
+        attested_block: &SignedBeaconBlock,
+        finalized_block: Option<&SignedBeaconBlock>,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            block
+                .message
+                .body
+                .sync_aggregate
+                .sync_committee_bits
+                .num_set_bits() as u64
+                >= MIN_SYNC_COMMITTEE_PARTICIPANTS,
+            "Not enough sync committee participants"
+        );
+
+        ensure!(
+            state.slot == state.latest_block_header.slot,
+            "State must be at the slot of its latest block header"
+        );
+        let mut header = state.latest_block_header.clone();
+        header.state_root = state.cached_tree_hash_root();
+        ensure!(
+            header.tree_hash_root() == block.message.tree_hash_root(),
+            "State is not the post state of the block"
+        );
+        let update_signature_period = compute_sync_committee_period_at_slot(block.message.slot);
+
+        ensure!(
+            attested_state.slot == attested_state.latest_block_header.slot,
+            "Attested state must be at the slot of its latest block header"
+        );
+        let mut attested_header = attested_state.latest_block_header.clone();
+        attested_header.state_root = attested_state.cached_tree_hash_root();
+        let attested_block_root = attested_block.message.tree_hash_root();
+        ensure!(
+            attested_header.tree_hash_root() == attested_block_root
+                && attested_block_root == block.message.parent_root,
+            "Attested state is not the post state of the parent block"
+        );
+        let update_attested_period =
+            compute_sync_committee_period_at_slot(attested_block.message.slot);
+
+        let mut update = Self {
+            attested_header: LightClientHeader::new(attested_block)?,
+            sync_aggregate: block.message.body.sync_aggregate.clone(),
+            signature_slot: block.message.slot,
+            ..Default::default()
+        };
+
+        // The next sync committee is only useful if the message is signed by the current sync
+        // committee
+        if update_attested_period == update_signature_period {
+            update.next_sync_committee = (*attested_state.next_sync_committee).clone();
+            update.next_sync_committee_branch = attested_state.next_sync_committee_branch()?;
+        }
+
+        // Indicate finality whenever possible
+        if let Some(finalized_block) = finalized_block {
+            if finalized_block.message.slot != GENESIS_SLOT {
+                update.finalized_header = LightClientHeader::new(finalized_block)?;
+                ensure!(
+                    update.finalized_header.beacon.tree_hash_root()
+                        == attested_state.finalized_checkpoint.root,
+                    "Finalized block does not match the finalized checkpoint"
+                );
+            } else {
+                ensure!(
+                    attested_state.finalized_checkpoint.root == B256::ZERO,
+                    "Finalized checkpoint of genesis must have a zero root"
+                );
+            }
+            update.finality_branch = attested_state.finality_branch()?;
+        }
+
+        Ok(update)
+    }
+
+    pub fn is_sync_committee_update(&self) -> bool {
+        self.next_sync_committee_branch
+            .iter()
+            .any(|node| !node.is_zero())
+    }
+
+    pub fn is_finality_update(&self) -> bool {
+        self.finality_branch.iter().any(|node| !node.is_zero())
+    }
+
+    /// Return whether ``self`` should replace ``old`` as the best update of a sync committee
+    /// period, ``is_better_update`` in the specs.
+    pub fn is_better_update(&self, old: &LightClientUpdate) -> bool {
+        // Compare supermajority (> 2/3) sync committee participation
+        let max_active_participants = self.sync_aggregate.sync_committee_bits.len();
+        let new_num_active_participants = self.sync_aggregate.sync_committee_bits.num_set_bits();
+        let old_num_active_participants = old.sync_aggregate.sync_committee_bits.num_set_bits();
+        let new_has_supermajority = new_num_active_participants * 3 >= max_active_participants * 2;
+        let old_has_supermajority = old_num_active_participants * 3 >= max_active_participants * 2;
+        if new_has_supermajority != old_has_supermajority {
+            return new_has_supermajority;
+        }
+        if !new_has_supermajority && new_num_active_participants != old_num_active_participants {
+            return new_num_active_participants > old_num_active_participants;
+        }
+
+        // Compare presence of relevant sync committee
+        let new_has_relevant_sync_committee = self.is_sync_committee_update()
+            && compute_sync_committee_period_at_slot(self.attested_header.beacon.slot)
+                == compute_sync_committee_period_at_slot(self.signature_slot);
+        let old_has_relevant_sync_committee = old.is_sync_committee_update()
+            && compute_sync_committee_period_at_slot(old.attested_header.beacon.slot)
+                == compute_sync_committee_period_at_slot(old.signature_slot);
+        if new_has_relevant_sync_committee != old_has_relevant_sync_committee {
+            return new_has_relevant_sync_committee;
+        }
+
+        // Compare indication of any finality
+        let new_has_finality = self.is_finality_update();
+        let old_has_finality = old.is_finality_update();
+        if new_has_finality != old_has_finality {
+            return new_has_finality;
+        }
+
+        // Compare sync committee finality
+        if new_has_finality {
+            let new_has_sync_committee_finality =
+                compute_sync_committee_period_at_slot(self.finalized_header.beacon.slot)
+                    == compute_sync_committee_period_at_slot(self.attested_header.beacon.slot);
+            let old_has_sync_committee_finality =
+                compute_sync_committee_period_at_slot(old.finalized_header.beacon.slot)
+                    == compute_sync_committee_period_at_slot(old.attested_header.beacon.slot);
+            if new_has_sync_committee_finality != old_has_sync_committee_finality {
+                return new_has_sync_committee_finality;
+            }
+        }
+
+        // Tiebreaker 1: Sync committee participation beyond supermajority
+        if new_num_active_participants != old_num_active_participants {
+            return new_num_active_participants > old_num_active_participants;
+        }
+
+        // Tiebreaker 2: Prefer older data (fewer changes to best)
+        if self.attested_header.beacon.slot != old.attested_header.beacon.slot {
+            return self.attested_header.beacon.slot < old.attested_header.beacon.slot;
+        }
+
+        // Tiebreaker 3: Prefer updates with earlier signature slots
+        self.signature_slot < old.signature_slot
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+
+    fn update(participants: usize, attested_slot: u64) -> LightClientUpdate {
+        let mut update = LightClientUpdate {
+            signature_slot: attested_slot + 1,
+            ..Default::default()
+        };
+        update.attested_header.beacon.slot = attested_slot;
+        for index in 0..participants {
+            update
+                .sync_aggregate
+                .sync_committee_bits
+                .set(index, true)
+                .unwrap();
+        }
+        update
+    }
+
+    #[test]
+    fn is_better_update_prefers_participation_then_finality() {
+        let size = update(0, 0).sync_aggregate.sync_committee_bits.len();
+        let supermajority = update(size, 10);
+        let minority = update(size / 2, 10);
+        assert!(supermajority.is_better_update(&minority));
+        assert!(!minority.is_better_update(&supermajority));
+
+        let mut finalized = update(size, 10);
+        finalized.finality_branch = FixedVector::from(vec![B256::repeat_byte(1); 7]);
+        assert!(finalized.is_better_update(&supermajority));
+
+        // Prefer older data on a tie
+        assert!(update(size, 9).is_better_update(&supermajority));
+    }
+}
@@ -0,0 +1,22 @@
+pub mod aggregate_and_proof;
+pub mod attestation;
+pub mod attester_slashing;
+pub mod beacon_block;
+pub mod beacon_block_body;
+pub mod beacon_state;
+pub mod consolidation_request;
+pub mod deposit_request;
+pub mod execution_requests;
+pub mod indexed_attestation;
+pub mod light_client_bootstrap;
+pub mod light_client_finality_update;
+pub mod light_client_header;
+pub mod light_client_optimistic_update;
+pub mod light_client_update;
+pub mod pending_consolidation;
+pub mod pending_deposit;
+pub mod pending_partial_withdrawal;
+pub mod rewards;
+pub mod single_attestation;
+pub mod validator_queue;
+pub mod withdrawal_request;
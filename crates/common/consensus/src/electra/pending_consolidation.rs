@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct PendingConsolidation {
+    pub source_index: u64,
+    pub target_index: u64,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct PendingPartialWithdrawal {
+    pub validator_index: u64,
+    pub amount: u64,
+    pub withdrawable_epoch: u64,
+}
@@ -0,0 +1,447 @@
+use std::collections::{BTreeSet, HashSet};
+
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+
+use super::{beacon_block::BeaconBlock, beacon_state::BeaconState};
+use crate::{
+    constants::{
+        EFFECTIVE_BALANCE_INCREMENT, NUM_FLAG_INDICES, PARTICIPATION_FLAG_WEIGHTS,
+        TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX,
+        WEIGHT_DENOMINATOR,
+    },
+    signature_sets::SignatureVerifier,
+    state_transition::BlockSignatureStrategy,
+};
+
+/// The rewards of the proposer of a block, in Gwei.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockRewards {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub total: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attestations: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub sync_aggregate: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_slashings: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attester_slashings: u64,
+}
+
+/// The rewards of a validator with ``effective_balance`` attesting perfectly, in Gwei.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct IdealAttestationRewards {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub effective_balance: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub head: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub target: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub source: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub inactivity: u64,
+}
+
+/// The rewards of a validator for its attestation, in Gwei. Penalties are negative.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TotalAttestationRewards {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "serde_utils::quoted_i64")]
+    pub head: i64,
+    #[serde(with = "serde_utils::quoted_i64")]
+    pub target: i64,
+    #[serde(with = "serde_utils::quoted_i64")]
+    pub source: i64,
+    #[serde(with = "serde_utils::quoted_i64")]
+    pub inactivity: i64,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AttestationRewards {
+    pub ideal_rewards: Vec<IdealAttestationRewards>,
+    pub total_rewards: Vec<TotalAttestationRewards>,
+}
+
+/// The reward of a sync committee member for a block, in Gwei. Penalties are negative.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SyncCommitteeReward {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "serde_utils::quoted_i64")]
+    pub reward: i64,
+}
+
+impl BeaconState {
+    /// Return the rewards of the proposer of ``block``, by replaying the operations of the block
+    /// on this state, the post state of its parent.
+    pub fn compute_block_rewards(&self, block: &BeaconBlock) -> anyhow::Result<BlockRewards> {
+        let mut state = self.clone();
+        if state.slot < block.slot {
+            state.process_slots(block.slot)?;
+        }
+        state.process_block_header(block)?;
+
+        // The block is already known to be valid
+        let mut signature_verifier = SignatureVerifier::new(BlockSignatureStrategy::NoVerification);
+        let proposer_index = block.proposer_index;
+        let proposer_slashings = state.get_balance_increase(proposer_index, |state| {
+            for proposer_slashing in block.body.proposer_slashings.iter() {
+                state.process_proposer_slashing_with_verifier(
+                    proposer_slashing,
+                    &mut signature_verifier,
+                )?;
+            }
+            Ok(())
+        })?;
+        let attester_slashings = state.get_balance_increase(proposer_index, |state| {
+            for attester_slashing in block.body.attester_slashings.iter() {
+                state.process_attester_slashing_with_verifier(
+                    attester_slashing,
+                    &mut signature_verifier,
+                )?;
+            }
+            Ok(())
+        })?;
+        let attestations = state.get_balance_increase(proposer_index, |state| {
+            for attestation in block.body.attestations.iter() {
+                state.process_attestation_with_verifier(attestation, &mut signature_verifier)?;
+            }
+            Ok(())
+        })?;
+
+        // The proposer may be a member of the sync committee, so its reward is computed rather
+        // than read from its balance
+        let (_, proposer_reward) = state.get_sync_committee_rewards();
+        let sync_aggregate =
+            proposer_reward * block.body.sync_aggregate.sync_committee_bits.num_set_bits() as u64;
+
+        Ok(BlockRewards {
+            proposer_index,
+            total: attestations + sync_aggregate + proposer_slashings + attester_slashings,
+            attestations,
+            sync_aggregate,
+            proposer_slashings,
+            attester_slashings,
+        })
+    }
+
+    /// Return the rewards of the sync committee members for ``block``, in the order of the
+    /// committee. This state is the post state of the parent of ``block``.
+    pub fn compute_sync_committee_rewards(
+        &self,
+        block: &BeaconBlock,
+    ) -> anyhow::Result<Vec<SyncCommitteeReward>> {
+        let mut state = self.clone();
+        if state.slot < block.slot {
+            state.process_slots(block.slot)?;
+        }
+
+        let (participant_reward, _) = state.get_sync_committee_rewards();
+        let mut rewards: Vec<SyncCommitteeReward> = vec![];
+        for (validator_index, participated) in state
+            .get_sync_committee_indices()?
+            .into_iter()
+            .zip(block.body.sync_aggregate.sync_committee_bits.iter())
+        {
+            let reward = match participated {
+                true => participant_reward as i64,
+                false => -(participant_reward as i64),
+            };
+            // Validators may appear several times in the committee
+            match rewards
+                .iter_mut()
+                .find(|reward| reward.validator_index == validator_index)
+            {
+                Some(validator_reward) => validator_reward.reward += reward,
+                None => rewards.push(SyncCommitteeReward {
+                    validator_index,
+                    reward,
+                }),
+            }
+        }
+        Ok(rewards)
+    }
+
+    /// Return the rewards for the attestations of the previous epoch, as applied by the next
+    /// ``process_rewards_and_penalties``, of ``validator_indices`` or of every validator if it is
+    /// empty. This state must be in the epoch after the attestations.
+    pub fn compute_attestation_rewards(
+        &self,
+        validator_indices: &[u64],
+    ) -> anyhow::Result<AttestationRewards> {
+        let validator_indices = match validator_indices.is_empty() {
+            true => (0..self.validators.len() as u64).collect(),
+            false => validator_indices.to_vec(),
+        };
+        for &index in validator_indices.iter() {
+            ensure!(
+                index < self.validators.len() as u64,
+                "Validator {index} does not exist"
+            );
+        }
+
+        // Sums over the validators are computed once, unlike in ``get_flag_index_deltas``
+        let previous_epoch = self.get_previous_epoch();
+        let base_reward_per_increment = self.get_base_reward_per_increment();
+        let active_increments = self.get_total_active_balance() / EFFECTIVE_BALANCE_INCREMENT;
+        let is_in_inactivity_leak = self.is_in_inactivity_leak();
+        let mut participating_indices = vec![];
+        let mut participating_increments = vec![];
+        for flag_index in 0..NUM_FLAG_INDICES {
+            let indices =
+                self.get_unslashed_participating_indices(flag_index as u8, previous_epoch)?;
+            participating_increments
+                .push(self.get_total_balance(indices.clone()) / EFFECTIVE_BALANCE_INCREMENT);
+            participating_indices.push(indices);
+        }
+        let flag_reward = |flag_index: u8, effective_balance: u64| {
+            if is_in_inactivity_leak {
+                return 0;
+            }
+            let base_reward =
+                effective_balance / EFFECTIVE_BALANCE_INCREMENT * base_reward_per_increment;
+            let reward_numerator = base_reward
+                * PARTICIPATION_FLAG_WEIGHTS[flag_index as usize]
+                * participating_increments[flag_index as usize];
+            reward_numerator / (active_increments * WEIGHT_DENOMINATOR)
+        };
+        let (_, inactivity_penalties) = self.get_inactivity_penalty_deltas()?;
+
+        let eligible_indices = self
+            .get_eligible_validator_indices()?
+            .into_iter()
+            .collect::<HashSet<_>>();
+        let mut effective_balances = BTreeSet::new();
+        let mut total_rewards = vec![];
+        for index in validator_indices {
+            if !eligible_indices.contains(&index) {
+                continue;
+            }
+            let effective_balance = self.validators[index as usize].effective_balance;
+            effective_balances.insert(effective_balance);
+            let flag_delta = |flag_index: u8| {
+                if participating_indices[flag_index as usize].contains(&index) {
+                    flag_reward(flag_index, effective_balance) as i64
+                } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
+                    let base_reward =
+                        effective_balance / EFFECTIVE_BALANCE_INCREMENT * base_reward_per_increment;
+                    -((base_reward * PARTICIPATION_FLAG_WEIGHTS[flag_index as usize]
+                        / WEIGHT_DENOMINATOR) as i64)
+                } else {
+                    0
+                }
+            };
+            total_rewards.push(TotalAttestationRewards {
+                validator_index: index,
+                head: flag_delta(TIMELY_HEAD_FLAG_INDEX),
+                target: flag_delta(TIMELY_TARGET_FLAG_INDEX),
+                source: flag_delta(TIMELY_SOURCE_FLAG_INDEX),
+                inactivity: -(inactivity_penalties[index as usize] as i64),
+            });
+        }
+
+        let ideal_rewards = effective_balances
+            .into_iter()
+            .map(|effective_balance| IdealAttestationRewards {
+                effective_balance,
+                head: flag_reward(TIMELY_HEAD_FLAG_INDEX, effective_balance),
+                target: flag_reward(TIMELY_TARGET_FLAG_INDEX, effective_balance),
+                source: flag_reward(TIMELY_SOURCE_FLAG_INDEX, effective_balance),
+                inactivity: 0,
+            })
+            .collect();
+
+        Ok(AttestationRewards {
+            ideal_rewards,
+            total_rewards,
+        })
+    }
+
+    /// Return how much ``process`` increases the balance of the validator ``index``.
+    fn get_balance_increase(
+        &mut self,
+        index: u64,
+        process: impl FnOnce(&mut Self) -> anyhow::Result<()>,
+    ) -> anyhow::Result<u64> {
+        let balance_before = self.balances[index as usize];
+        process(self)?;
+        Ok(self.balances[index as usize].saturating_sub(balance_before))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::BLSSignature;
+    use ssz_types::{BitList, BitVector, VariableList};
+    use tree_hash::TreeHash;
+
+    use super::*;
+    use crate::{
+        attestation_data::AttestationData,
+        checkpoint::Checkpoint,
+        constants::{PROPOSER_WEIGHT, SYNC_COMMITTEE_SIZE},
+        electra::{attestation::Attestation, beacon_block_body::BeaconBlockBody},
+        genesis::interop_genesis_state,
+        sync_aggregate::SyncAggregate,
+    };
+
+    /// Return the interop state at slot 2 and a block of slot 2 on top of it, with the
+    /// ``sync_committee_bits`` of ``participation``. Signatures are not checked by the rewards.
+    fn state_and_block(participation: impl Fn(usize) -> bool) -> (BeaconState, BeaconBlock) {
+        let mut state = interop_genesis_state(64, 0).unwrap();
+        state.process_slots(2).unwrap();
+
+        let mut sync_committee_bits = BitVector::new();
+        for index in 0..SYNC_COMMITTEE_SIZE as usize {
+            sync_committee_bits
+                .set(index, participation(index))
+                .unwrap();
+        }
+        let block = BeaconBlock {
+            slot: 2,
+            proposer_index: state.get_beacon_proposer_index().unwrap(),
+            parent_root: state.latest_block_header.tree_hash_root(),
+            state_root: B256::ZERO,
+            body: BeaconBlockBody {
+                sync_aggregate: SyncAggregate {
+                    sync_committee_bits,
+                    sync_committee_signature: BLSSignature::infinity(),
+                },
+                ..Default::default()
+            },
+        };
+        (state, block)
+    }
+
+    #[test]
+    fn attestation_rewards_match_the_flag_index_deltas() {
+        let mut state = interop_genesis_state(64, 0).unwrap();
+        state.slot = 2 * crate::constants::SLOTS_PER_EPOCH;
+        for (index, flags) in state.previous_epoch_participation.iter_mut().enumerate() {
+            // Every flag for the first half, the source and target flags for the rest
+            *flags = match index < 32 {
+                true => 0b111,
+                false => 0b011,
+            };
+        }
+        state.previous_epoch_participation[63] = 0;
+
+        let rewards = state.compute_attestation_rewards(&[]).unwrap();
+
+        let deltas = (0..NUM_FLAG_INDICES as u8)
+            .map(|flag_index| state.get_flag_index_deltas(flag_index).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rewards.total_rewards.len(), 64);
+        for reward in rewards.total_rewards.iter() {
+            let index = reward.validator_index as usize;
+            let delta = |flag_index: u8| {
+                let (rewards, penalties) = &deltas[flag_index as usize];
+                rewards[index] as i64 - penalties[index] as i64
+            };
+            assert_eq!(reward.source, delta(TIMELY_SOURCE_FLAG_INDEX));
+            assert_eq!(reward.target, delta(TIMELY_TARGET_FLAG_INDEX));
+            assert_eq!(reward.head, delta(TIMELY_HEAD_FLAG_INDEX));
+        }
+        assert!(rewards.total_rewards[63].source < 0);
+
+        let ideal_rewards = &rewards.ideal_rewards[0];
+        assert_eq!(ideal_rewards.head as i64, rewards.total_rewards[0].head);
+        assert_eq!(rewards.total_rewards[40].head, 0);
+    }
+
+    #[test]
+    fn block_rewards_count_new_attesters_and_sync_participants() {
+        let (state, mut block) = state_and_block(|_| true);
+        let committee = state.get_beacon_committee(1, 0).unwrap();
+        let mut aggregation_bits = BitList::with_capacity(committee.len()).unwrap();
+        for position in 0..committee.len() {
+            aggregation_bits.set(position, true).unwrap();
+        }
+        let mut committee_bits = BitVector::new();
+        committee_bits.set(0, true).unwrap();
+        let attestation = Attestation {
+            aggregation_bits,
+            data: AttestationData {
+                slot: 1,
+                index: 0,
+                beacon_block_root: state.get_block_root_at_slot(1).unwrap(),
+                source: state.current_justified_checkpoint,
+                target: Checkpoint {
+                    epoch: 0,
+                    root: state.get_block_root(0).unwrap(),
+                },
+            },
+            signature: BLSSignature::infinity(),
+            committee_bits,
+        };
+        // The second copy of the attestation earns nothing
+        block.body.attestations = VariableList::from(vec![attestation.clone(), attestation]);
+
+        let rewards = state.compute_block_rewards(&block).unwrap();
+
+        // Every member is timely for the source, the target and the head
+        let proposer_reward_numerator = committee
+            .iter()
+            .map(|&index| {
+                state.get_base_reward(index) * PARTICIPATION_FLAG_WEIGHTS.iter().sum::<u64>()
+            })
+            .sum::<u64>();
+        let proposer_reward_denominator =
+            (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT) * WEIGHT_DENOMINATOR / PROPOSER_WEIGHT;
+        let (_, sync_proposer_reward) = state.get_sync_committee_rewards();
+        assert_eq!(
+            rewards,
+            BlockRewards {
+                proposer_index: block.proposer_index,
+                total: proposer_reward_numerator / proposer_reward_denominator
+                    + sync_proposer_reward * SYNC_COMMITTEE_SIZE,
+                attestations: proposer_reward_numerator / proposer_reward_denominator,
+                sync_aggregate: sync_proposer_reward * SYNC_COMMITTEE_SIZE,
+                proposer_slashings: 0,
+                attester_slashings: 0,
+            }
+        );
+        assert!(rewards.attestations > 0);
+    }
+
+    #[test]
+    fn sync_committee_rewards_match_the_balance_changes() {
+        // The first half of the committee participates
+        let (state, block) = state_and_block(|index| index < SYNC_COMMITTEE_SIZE as usize / 2);
+
+        let rewards = state.compute_sync_committee_rewards(&block).unwrap();
+
+        let mut post_state = state.clone();
+        post_state
+            .process_sync_aggregate_with_verifier(
+                &block.body.sync_aggregate,
+                &mut SignatureVerifier::new(BlockSignatureStrategy::NoVerification),
+            )
+            .unwrap();
+        let (_, proposer_reward) = state.get_sync_committee_rewards();
+        let unique_members = state
+            .get_sync_committee_indices()
+            .unwrap()
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(rewards.len(), unique_members.len());
+        for reward in rewards.iter() {
+            let index = reward.validator_index as usize;
+            let mut balance_change =
+                post_state.balances[index] as i64 - state.balances[index] as i64;
+            if reward.validator_index == block.proposer_index {
+                balance_change -= (proposer_reward * SYNC_COMMITTEE_SIZE / 2) as i64;
+            }
+            assert_eq!(reward.reward, balance_change);
+        }
+        assert!(rewards.iter().any(|reward| reward.reward > 0));
+        assert!(rewards.iter().any(|reward| reward.reward < 0));
+    }
+}
@@ -0,0 +1,15 @@
+use ream_bls::BLSSignature;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+use crate::attestation_data::AttestationData;
+
+/// An unaggregated attestation as sent on the attestation subnets.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SingleAttestation {
+    pub committee_index: u64,
+    pub attester_index: u64,
+    pub data: AttestationData,
+    pub signature: BLSSignature,
+}
@@ -0,0 +1,174 @@
+//! Estimates of when validators enter and leave the active set under the current churn.
+//!
+//! The estimates assume the chain finalizes every epoch and that deposits and exits submitted
+//! after the state don't get ahead of the ones it knows of.
+
+use anyhow::anyhow;
+use ream_bls::PubKey;
+
+use super::beacon_state::BeaconState;
+use crate::{
+    constants::{FAR_FUTURE_EPOCH, MAX_PENDING_DEPOSITS_PER_EPOCH, MIN_ACTIVATION_BALANCE},
+    misc::compute_activation_exit_epoch,
+};
+
+impl BeaconState {
+    /// Return the epoch the validator ``index`` is, or is expected to be, activated at, or
+    /// ``None`` if its effective balance is too low for it to ever be.
+    pub fn get_expected_activation_epoch(&self, index: u64) -> anyhow::Result<Option<u64>> {
+        let validator = self
+            .validators
+            .get(index as usize)
+            .ok_or_else(|| anyhow!("Validator {index} does not exist"))?;
+        if validator.activation_epoch != FAR_FUTURE_EPOCH {
+            return Ok(Some(validator.activation_epoch));
+        }
+
+        let activation_eligibility_epoch = match validator.activation_eligibility_epoch {
+            // Eligibility is granted by the registry updates at the end of the current epoch
+            FAR_FUTURE_EPOCH if validator.effective_balance >= MIN_ACTIVATION_BALANCE => {
+                self.get_current_epoch() + 1
+            }
+            FAR_FUTURE_EPOCH => return Ok(None),
+            epoch => epoch,
+        };
+        Ok(Some(self.get_expected_activation_epoch_from_eligibility(
+            activation_eligibility_epoch,
+        )))
+    }
+
+    /// Return the epoch the validator of ``pubkey``, not yet in the registry, is expected to be
+    /// activated at once its ``pending_deposits`` are processed, or ``None`` if they don't add up
+    /// to ``MIN_ACTIVATION_BALANCE``.
+    pub fn get_expected_deposit_activation_epoch(&self, pubkey: &PubKey) -> Option<u64> {
+        let mut deposited_amount = 0;
+        self.pending_deposits
+            .iter()
+            .zip(self.get_expected_pending_deposit_epochs())
+            .find_map(|(deposit, epoch)| {
+                if deposit.pubkey != *pubkey {
+                    return None;
+                }
+                deposited_amount += deposit.amount;
+                // The validator is added at the end of ``epoch`` and made eligible at the end of
+                // the next one
+                (deposited_amount >= MIN_ACTIVATION_BALANCE)
+                    .then(|| self.get_expected_activation_epoch_from_eligibility(epoch + 2))
+            })
+    }
+
+    /// Return the epoch at the end of which each of the ``pending_deposits`` is expected to be
+    /// processed, every deposit consuming churn.
+    pub fn get_expected_pending_deposit_epochs(&self) -> Vec<u64> {
+        let per_epoch_churn = self.get_activation_exit_churn_limit();
+        let mut epoch = self.get_current_epoch();
+        let mut available_for_processing = self.deposit_balance_to_consume + per_epoch_churn;
+        let mut processed_count = 0;
+        self.pending_deposits
+            .iter()
+            .map(|deposit| {
+                while processed_count == MAX_PENDING_DEPOSITS_PER_EPOCH
+                    || deposit.amount > available_for_processing
+                {
+                    // The churn left is only carried over when the churn limit is reached
+                    if processed_count == MAX_PENDING_DEPOSITS_PER_EPOCH {
+                        available_for_processing = 0;
+                    }
+                    epoch += 1;
+                    available_for_processing += per_epoch_churn;
+                    processed_count = 0;
+                }
+                available_for_processing -= deposit.amount;
+                processed_count += 1;
+                epoch
+            })
+            .collect()
+    }
+
+    /// Return the epoch the validator ``index`` exits at, or would exit at if it initiated its
+    /// exit now.
+    pub fn get_expected_exit_epoch(&self, index: u64) -> anyhow::Result<u64> {
+        let validator = self
+            .validators
+            .get(index as usize)
+            .ok_or_else(|| anyhow!("Validator {index} does not exist"))?;
+        if validator.exit_epoch != FAR_FUTURE_EPOCH {
+            return Ok(validator.exit_epoch);
+        }
+        let (exit_epoch, _) = self.get_exit_epoch_and_churn(validator.effective_balance);
+        Ok(exit_epoch)
+    }
+
+    /// Return the epoch a validator eligible at ``activation_eligibility_epoch`` is expected to
+    /// be activated at, once that epoch is finalized at the end of the next one.
+    fn get_expected_activation_epoch_from_eligibility(
+        &self,
+        activation_eligibility_epoch: u64,
+    ) -> u64 {
+        compute_activation_exit_epoch(
+            self.get_current_epoch()
+                .max(activation_eligibility_epoch + 1),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::BLSSignature;
+
+    use super::*;
+    use crate::{
+        electra::pending_deposit::PendingDeposit,
+        genesis::{interop_genesis_state, interop_private_key},
+    };
+
+    #[test]
+    fn pending_deposits_are_processed_within_the_churn() {
+        let mut state = interop_genesis_state(64, 0).unwrap();
+        let deposits_per_epoch = state.get_activation_exit_churn_limit() / MIN_ACTIVATION_BALANCE;
+        let pubkeys = (64..64 + deposits_per_epoch + 1)
+            .map(|index| interop_private_key(index).public_key().unwrap())
+            .collect::<Vec<_>>();
+        state.pending_deposits = pubkeys
+            .iter()
+            .map(|pubkey| PendingDeposit {
+                pubkey: pubkey.clone(),
+                withdrawal_credentials: B256::ZERO,
+                amount: MIN_ACTIVATION_BALANCE,
+                signature: BLSSignature::default(),
+                slot: 0,
+            })
+            .collect::<Vec<_>>()
+            .into();
+
+        let epochs = state.get_expected_pending_deposit_epochs();
+
+        assert_eq!(epochs[0], 0);
+        assert_eq!(epochs[deposits_per_epoch as usize - 1], 0);
+        assert_eq!(epochs[deposits_per_epoch as usize], 1);
+        assert_eq!(
+            state.get_expected_deposit_activation_epoch(&pubkeys[deposits_per_epoch as usize]),
+            Some(compute_activation_exit_epoch(4))
+        );
+        assert_eq!(
+            state.get_expected_deposit_activation_epoch(&PubKey::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn exits_are_estimated_from_the_exit_churn() {
+        let mut state = interop_genesis_state(64, 0).unwrap();
+        assert_eq!(
+            state.get_expected_exit_epoch(0).unwrap(),
+            compute_activation_exit_epoch(0)
+        );
+
+        let exit_epoch = state.get_expected_exit_epoch(1).unwrap();
+        state.initiate_validator_exit(1).unwrap();
+        assert_eq!(state.validators[1].exit_epoch, exit_epoch);
+        assert_eq!(state.get_expected_exit_epoch(1).unwrap(), exit_epoch);
+    }
+}
@@ -0,0 +1,12 @@
+use alloy_primitives::Address;
+use ream_bls::PubKey;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct WithdrawalRequest {
+    pub source_address: Address,
+    pub validator_pubkey: PubKey,
+    pub amount: u64,
+}
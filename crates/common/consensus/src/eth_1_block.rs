@@ -0,0 +1,32 @@
+use alloy_primitives::B256;
+
+use crate::{chain_spec::chain_spec, eth_1_data::Eth1Data};
+
+/// A block of the execution chain along with the state of the deposit contract at that block.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Eth1Block {
+    pub hash: B256,
+    pub number: u64,
+    pub timestamp: u64,
+    pub deposit_root: B256,
+    pub deposit_count: u64,
+}
+
+impl Eth1Block {
+    /// Return the ``Eth1Data`` a validator would vote for with this block.
+    pub fn eth1_data(&self) -> Eth1Data {
+        Eth1Data {
+            deposit_root: self.deposit_root,
+            deposit_count: self.deposit_count,
+            block_hash: self.hash,
+        }
+    }
+
+    /// Check if the block is old enough to be voted for in the voting period starting at
+    /// ``period_start``, but not too old.
+    pub fn is_candidate_block(&self, period_start: u64) -> bool {
+        let follow_time = chain_spec().seconds_per_eth1_block * chain_spec().eth1_follow_distance;
+        self.timestamp + follow_time <= period_start
+            && self.timestamp + follow_time * 2 >= period_start
+    }
+}
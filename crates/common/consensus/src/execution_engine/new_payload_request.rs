@@ -2,11 +2,16 @@ use alloy_primitives::B256;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 
-use crate::deneb::execution_payload::ExecutionPayload;
+use crate::{
+    deneb::execution_payload::ExecutionPayload, electra::execution_requests::ExecutionRequests,
+};
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct NewPayloadRequest {
     pub execution_payload: ExecutionPayload,
     pub versioned_hashes: Vec<B256>,
     pub parent_beacon_block_root: B256,
+
+    /// The execution requests of the block, ``None`` before Electra.
+    pub execution_requests: Option<ExecutionRequests>,
 }
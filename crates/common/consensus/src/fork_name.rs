@@ -0,0 +1,47 @@
+use std::fmt::{self, Display};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+/// The consensus forks, in activation order. The discriminant is used as the fork tag of values
+/// persisted to disk, so variants must only ever be appended.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum ForkName {
+    Phase0 = 0,
+    Altair = 1,
+    Bellatrix = 2,
+    Capella = 3,
+    Deneb = 4,
+    Electra = 5,
+}
+
+impl Display for ForkName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForkName::Phase0 => write!(f, "phase0"),
+            ForkName::Altair => write!(f, "altair"),
+            ForkName::Bellatrix => write!(f, "bellatrix"),
+            ForkName::Capella => write!(f, "capella"),
+            ForkName::Deneb => write!(f, "deneb"),
+            ForkName::Electra => write!(f, "electra"),
+        }
+    }
+}
+
+impl TryFrom<u8> for ForkName {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => ForkName::Phase0,
+            1 => ForkName::Altair,
+            2 => ForkName::Bellatrix,
+            3 => ForkName::Capella,
+            4 => ForkName::Deneb,
+            5 => ForkName::Electra,
+            _ => bail!("Unknown fork tag: {value}"),
+        })
+    }
+}
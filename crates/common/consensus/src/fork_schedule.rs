@@ -0,0 +1,156 @@
+use alloy_primitives::{B256, aliases::B32};
+
+use crate::{
+    constants::FAR_FUTURE_EPOCH, fork::Fork, fork_data::ForkData, fork_name::ForkName,
+    misc::compute_epoch_at_slot,
+};
+
+/// A fork and the epoch it activates at on a given network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledFork {
+    pub fork_name: ForkName,
+    pub version: B32,
+    pub epoch: u64,
+}
+
+/// The forks of a network, in activation order. The first fork always activates at genesis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkSchedule {
+    pub forks: Vec<ScheduledFork>,
+}
+
+impl ForkSchedule {
+    pub fn new(forks: Vec<ScheduledFork>) -> Self {
+        Self { forks }
+    }
+
+    /// Return the fork active at ``epoch``.
+    pub fn fork_at_epoch(&self, epoch: u64) -> &ScheduledFork {
+        self.forks
+            .iter()
+            .rev()
+            .find(|fork| fork.epoch <= epoch)
+            .unwrap_or(&self.forks[0])
+    }
+
+    pub fn fork_name_at_epoch(&self, epoch: u64) -> ForkName {
+        self.fork_at_epoch(epoch).fork_name
+    }
+
+    pub fn fork_name_at_slot(&self, slot: u64) -> ForkName {
+        self.fork_name_at_epoch(compute_epoch_at_slot(slot))
+    }
+
+    pub fn fork_version_at_epoch(&self, epoch: u64) -> B32 {
+        self.fork_at_epoch(epoch).version
+    }
+
+    /// Return the first scheduled fork activating after ``epoch``, if any.
+    pub fn next_fork_at_epoch(&self, epoch: u64) -> Option<&ScheduledFork> {
+        self.forks
+            .iter()
+            .find(|fork| fork.epoch > epoch && fork.epoch != FAR_FUTURE_EPOCH)
+    }
+
+    /// Return the version of the next scheduled fork, or the current version if no fork is
+    /// scheduled, as advertised in the ``ENRForkID``.
+    pub fn next_fork_version_at_epoch(&self, epoch: u64) -> B32 {
+        self.next_fork_at_epoch(epoch)
+            .map_or_else(|| self.fork_version_at_epoch(epoch), |fork| fork.version)
+    }
+
+    /// Return the epoch of the next scheduled fork, or ``FAR_FUTURE_EPOCH`` if no fork is
+    /// scheduled.
+    pub fn next_fork_epoch_at_epoch(&self, epoch: u64) -> u64 {
+        self.next_fork_at_epoch(epoch)
+            .map_or(FAR_FUTURE_EPOCH, |fork| fork.epoch)
+    }
+
+    /// Return the fork digest of the fork active at ``epoch``.
+    pub fn fork_digest_at_epoch(&self, epoch: u64, genesis_validators_root: B256) -> B32 {
+        ForkData {
+            current_version: self.fork_version_at_epoch(epoch),
+            genesis_validators_root,
+        }
+        .compute_fork_digest()
+    }
+
+    /// Return the scheduled forks as ``Fork``s, each pointing back to the version it replaces.
+    /// Forks which are not scheduled are left out.
+    pub fn scheduled_forks(&self) -> Vec<Fork> {
+        let mut previous_version = self.forks[0].version;
+        self.forks
+            .iter()
+            .filter(|fork| fork.epoch != FAR_FUTURE_EPOCH)
+            .map(|fork| {
+                let scheduled_fork = Fork {
+                    previous_version,
+                    current_version: fork.version,
+                    epoch: fork.epoch,
+                };
+                previous_version = fork.version;
+                scheduled_fork
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::fixed_bytes;
+
+    use super::*;
+
+    fn fork_schedule() -> ForkSchedule {
+        ForkSchedule::new(vec![
+            ScheduledFork {
+                fork_name: ForkName::Phase0,
+                version: fixed_bytes!("0x00000000"),
+                epoch: 0,
+            },
+            ScheduledFork {
+                fork_name: ForkName::Altair,
+                version: fixed_bytes!("0x01000000"),
+                epoch: 10,
+            },
+            ScheduledFork {
+                fork_name: ForkName::Bellatrix,
+                version: fixed_bytes!("0x02000000"),
+                epoch: FAR_FUTURE_EPOCH,
+            },
+        ])
+    }
+
+    #[test]
+    fn next_fork_skips_unscheduled_forks() {
+        let fork_schedule = fork_schedule();
+
+        assert_eq!(
+            fork_schedule.next_fork_version_at_epoch(0),
+            fixed_bytes!("0x01000000")
+        );
+        assert_eq!(fork_schedule.next_fork_epoch_at_epoch(0), 10);
+        assert_eq!(
+            fork_schedule.next_fork_version_at_epoch(10),
+            fixed_bytes!("0x01000000")
+        );
+        assert_eq!(fork_schedule.next_fork_epoch_at_epoch(10), FAR_FUTURE_EPOCH);
+        assert_eq!(fork_schedule.scheduled_forks().len(), 2);
+        assert_eq!(
+            fork_schedule.scheduled_forks()[1].previous_version,
+            fixed_bytes!("0x00000000")
+        );
+    }
+
+    #[test]
+    fn fork_digest_matches_mainnet() {
+        // The mainnet phase0 fork digest, ``0xb5303f2a``.
+        let genesis_validators_root =
+            fixed_bytes!("0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95");
+
+        assert_eq!(
+            fork_schedule().fork_digest_at_epoch(0, genesis_validators_root),
+            fixed_bytes!("0xb5303f2a")
+        );
+    }
+}
@@ -1,5 +1,33 @@
-use alloy_primitives::{B256, aliases::B32};
+use alloy_primitives::{B256, U256, aliases::B32, uint};
+use anyhow::ensure;
+use ethereum_hashing::hash;
+use ream_bls::{PrivateKey, traits::Signable};
 use serde::{Deserialize, Serialize};
+use tree_hash::TreeHash;
+
+use crate::{
+    constants::{BLS_WITHDRAWAL_PREFIX, DOMAIN_DEPOSIT, GENESIS_EPOCH, MAX_EFFECTIVE_BALANCE},
+    deneb::execution_payload_header::ExecutionPayloadHeader,
+    deposit::Deposit,
+    deposit_data::DepositData,
+    deposit_message::DepositMessage,
+    deposit_tree::DepositTree,
+    electra::beacon_state::{BeaconState, initialize_beacon_state_from_eth1},
+    fork_name::ForkName,
+    fork_schedule::ForkSchedule,
+    misc::{compute_domain, compute_signing_root},
+};
+
+/// The eth1 block hash interop genesis states are built from.
+pub const INTEROP_ETH1_BLOCK_HASH: B256 = B256::repeat_byte(0x42);
+
+/// The eth1 timestamp interop genesis states are built from, the genesis time is overridden
+/// afterwards.
+pub const INTEROP_ETH1_TIMESTAMP: u64 = 1 << 40;
+
+/// The order of the BLS12-381 scalar field.
+const CURVE_ORDER: U256 =
+    uint!(0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001_U256);
 
 /// Genesis Config store.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -9,3 +37,142 @@ pub struct Genesis {
     pub genesis_validator_root: B256,
     pub genesis_fork_version: B32,
 }
+
+/// Return the interop private key of validator ``index``, the little-endian sha256 of the
+/// little-endian index reduced modulo the curve order.
+pub fn interop_private_key(index: u64) -> PrivateKey {
+    let mut preimage = [0; 32];
+    preimage[..8].copy_from_slice(&index.to_le_bytes());
+    let key = U256::from_le_slice(&hash(&preimage)) % CURVE_ORDER;
+
+    PrivateKey {
+        inner: B256::from(key.to_be_bytes()),
+    }
+}
+
+/// Return a ``MAX_EFFECTIVE_BALANCE`` deposit with BLS withdrawal credentials for each of the
+/// first ``validator_count`` interop validators, proven against the deposits before them.
+pub fn interop_deposits(validator_count: u64) -> anyhow::Result<Vec<Deposit>> {
+    let domain = compute_domain(DOMAIN_DEPOSIT, None, None);
+    let mut deposit_tree = DepositTree::default();
+    let mut deposits = vec![];
+    for index in 0..validator_count {
+        let private_key = interop_private_key(index);
+        let pubkey = private_key.public_key()?;
+        let withdrawal_credentials =
+            B256::from_slice(&[BLS_WITHDRAWAL_PREFIX, &hash(pubkey.to_bytes())[1..]].concat());
+        let signing_root = compute_signing_root(
+            DepositMessage {
+                pubkey: pubkey.clone(),
+                withdrawal_credentials,
+                amount: MAX_EFFECTIVE_BALANCE,
+            },
+            domain,
+        );
+        let data = DepositData {
+            pubkey,
+            withdrawal_credentials,
+            amount: MAX_EFFECTIVE_BALANCE,
+            signature: private_key.sign(signing_root.as_ref())?,
+        };
+
+        deposit_tree.push_leaf(data.tree_hash_root())?;
+        deposits.push(Deposit {
+            proof: deposit_tree.get_last_proof()?,
+            data,
+        });
+    }
+    Ok(deposits)
+}
+
+/// Ensure the network of ``fork_schedule`` starts at Electra, the only fork interop genesis
+/// states are built for.
+pub fn ensure_interop_genesis_fork(fork_schedule: &ForkSchedule) -> anyhow::Result<()> {
+    let genesis_fork = fork_schedule.fork_name_at_epoch(GENESIS_EPOCH);
+    ensure!(
+        genesis_fork == ForkName::Electra,
+        "Interop genesis states are Electra states but the network starts at {genesis_fork}"
+    );
+    Ok(())
+}
+
+/// Build the interop genesis state of ``validator_count`` validators starting at
+/// ``genesis_time``.
+///
+/// The state is always an Electra state, callers starting a network from it must check its fork
+/// schedule with ``ensure_interop_genesis_fork``. The state has an empty execution payload header,
+/// so the execution layer has to be started from a genesis without a terminal block.
+pub fn interop_genesis_state(
+    validator_count: u64,
+    genesis_time: u64,
+) -> anyhow::Result<BeaconState> {
+    let mut state = initialize_beacon_state_from_eth1(
+        INTEROP_ETH1_BLOCK_HASH,
+        INTEROP_ETH1_TIMESTAMP,
+        &interop_deposits(validator_count)?,
+        ExecutionPayloadHeader::default(),
+    )?;
+    state.genesis_time = genesis_time;
+    Ok(state)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::b256;
+    use ream_bls::PubKey;
+
+    use super::*;
+    use crate::chain_spec::ChainSpec;
+
+    #[test]
+    fn interop_keys_match_the_standard_scheme() {
+        let private_key = interop_private_key(0);
+
+        assert_eq!(
+            private_key.inner,
+            b256!("0x25295f0d1d592a90b333e26e85149708208e9f8e8bc18f6c77bd62f8ad7a6866")
+        );
+        assert_eq!(
+            private_key.public_key().unwrap(),
+            "0xa99a76ed7796f7be22d5b7e85deeb7c5677e88e511e0b337618f8c4eb61349b4bf2d153f649f7b53359fe8b94a38e44c"
+                .parse::<PubKey>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn interop_genesis_state_activates_every_validator() {
+        let state = interop_genesis_state(4, 1_700_000_000).unwrap();
+
+        assert_eq!(state.genesis_time, 1_700_000_000);
+        assert_eq!(state.eth1_deposit_index, 4);
+        assert_eq!(state.get_active_validator_indices(GENESIS_EPOCH).len(), 4);
+        assert!(
+            state
+                .balances
+                .iter()
+                .all(|&balance| balance == MAX_EFFECTIVE_BALANCE)
+        );
+        assert!(state.pending_deposits.is_empty());
+        assert_eq!(
+            state.genesis_validators_root,
+            state.validators.tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn interop_genesis_requires_an_electra_network() {
+        let mut chain_spec = ChainSpec::default();
+        assert!(ensure_interop_genesis_fork(&chain_spec.fork_schedule()).is_err());
+
+        chain_spec.altair_fork_epoch = GENESIS_EPOCH;
+        chain_spec.bellatrix_fork_epoch = GENESIS_EPOCH;
+        chain_spec.capella_fork_epoch = GENESIS_EPOCH;
+        chain_spec.deneb_fork_epoch = GENESIS_EPOCH;
+        assert!(ensure_interop_genesis_fork(&chain_spec.fork_schedule()).is_err());
+
+        chain_spec.electra_fork_epoch = GENESIS_EPOCH;
+        ensure_interop_genesis_fork(&chain_spec.fork_schedule()).unwrap();
+    }
+}
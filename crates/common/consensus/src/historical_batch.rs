@@ -1,12 +1,14 @@
 use alloy_primitives::B256;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{FixedVector, typenum::U8192};
+use ssz_types::FixedVector;
 use tree_hash_derive::TreeHash;
 
+use crate::preset::SlotsPerHistoricalRoot;
+
 // todo: add tests
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct HistoricalBatch {
-    pub block_roots: FixedVector<B256, U8192>,
-    pub state_roots: FixedVector<B256, U8192>,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
 }
@@ -1,35 +1,58 @@
 #![warn(clippy::unwrap_used)]
 
+pub mod altair;
 pub mod attestation;
 pub mod attestation_data;
 pub mod attester_slashing;
 pub mod beacon_block_header;
+pub mod beacon_state_helpers;
+pub mod bellatrix;
+pub mod blob_sidecar;
 pub mod bls_to_execution_change;
+pub mod capella;
+pub mod chain_spec;
 pub mod checkpoint;
 pub mod constants;
+pub mod contribution_and_proof;
 pub mod deneb;
 pub mod deposit;
 pub mod deposit_data;
 pub mod deposit_message;
+pub mod deposit_tree;
+pub mod deposit_tree_snapshot;
+pub mod electra;
+pub mod eth_1_block;
 pub mod eth_1_data;
 pub mod execution_engine;
 pub mod fork;
 pub mod fork_choice;
 pub mod fork_data;
+pub mod fork_name;
+pub mod fork_schedule;
 pub mod genesis;
 pub mod helpers;
 pub mod historical_batch;
 pub mod historical_summary;
 pub mod indexed_attestation;
 pub mod kzg_commitment;
+pub mod merkle_proof;
 pub mod misc;
 pub mod pending_attestation;
+pub mod phase0;
 pub mod polynomial_commitments;
 pub mod predicates;
+pub mod preset;
 pub mod proposer_slashing;
+pub mod shuffling_cache;
+pub mod signature_sets;
 pub mod signing_data;
+pub mod state_transition;
 pub mod sync_aggregate;
 pub mod sync_committee;
+pub mod sync_committee_contribution;
+pub mod sync_committee_message;
+pub mod tree_hash_cache;
 pub mod validator;
+pub mod versioned;
 pub mod voluntary_exit;
 pub mod withdrawal;
@@ -0,0 +1,709 @@
+//! Merkle proofs of the nodes of SSZ objects, addressed by generalized index.
+//!
+//! The node at generalized index ``1`` is the root of an object, the children of the node at
+//! ``index`` are at ``2 * index`` and ``2 * index + 1``. Containers are trees over the roots of
+//! their fields, vectors over the roots of their elements (or their packed chunks for basic
+//! types), and lists the same tree mixed in with their length at index ``3``.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use alloy_primitives::{Address, B256, U256, aliases::B32};
+use anyhow::{anyhow, bail, ensure};
+use ethereum_hashing::hash32_concat;
+use ream_bls::{BLSSignature, PubKey};
+use ssz_types::{BitList, BitVector, FixedVector, VariableList, typenum::Unsigned};
+use tree_hash::{BYTES_PER_CHUNK, TreeHash, TreeHashType, merkle_root};
+
+use crate::{
+    beacon_block_header::BeaconBlockHeader,
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    checkpoint::Checkpoint,
+    deneb::{
+        execution_payload::ExecutionPayload, execution_payload_header::ExecutionPayloadHeader,
+    },
+    deposit::Deposit,
+    electra::{
+        self, beacon_block_body::BeaconBlockBody, beacon_state::BeaconState,
+        execution_requests::ExecutionRequests, pending_consolidation::PendingConsolidation,
+        pending_deposit::PendingDeposit, pending_partial_withdrawal::PendingPartialWithdrawal,
+    },
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    historical_summary::HistoricalSummary,
+    kzg_commitment::KZGCommitment,
+    proposer_slashing::ProposerSlashing,
+    sync_aggregate::SyncAggregate,
+    sync_committee::SyncCommittee,
+    validator::Validator,
+    voluntary_exit::SignedVoluntaryExit,
+};
+
+/// A Merkle branch and the leaf it proves, the branch starts from the sibling of the leaf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SingleMerkleProof {
+    pub leaf: B256,
+    pub branch: Vec<B256>,
+}
+
+/// An SSZ object whose nodes can be proven against its root.
+pub trait MerkleProof {
+    /// Return the generalized index of the node at ``path``, the field names and element indices
+    /// leading to it from the root of ``self``. ``__len__`` addresses the length of a list.
+    fn get_generalized_index(&self, path: &[&str]) -> anyhow::Result<u64>;
+
+    /// Return the node at ``gindex`` and the branch proving it against the root of ``self``.
+    fn compute_merkle_proof(&self, gindex: u64) -> anyhow::Result<SingleMerkleProof>;
+
+    /// Return the node at ``gindex``.
+    fn get_node(&self, gindex: u64) -> anyhow::Result<B256> {
+        Ok(self.compute_merkle_proof(gindex)?.leaf)
+    }
+
+    /// Return the nodes proving all of ``gindices`` at once against the root of ``self``, in the
+    /// order of ``get_helper_indices``.
+    fn compute_merkle_multiproof(&self, gindices: &[u64]) -> anyhow::Result<Vec<B256>> {
+        get_helper_indices(gindices)
+            .into_iter()
+            .map(|gindex| self.get_node(gindex))
+            .collect()
+    }
+}
+
+/// Split a path such as ``validators[42].effective_balance`` into its field names and indices.
+pub fn parse_path(path: &str) -> Vec<&str> {
+    path.split(['.', '[', ']'])
+        .filter(|element| !element.is_empty())
+        .collect()
+}
+
+/// Return the generalized index of ``b`` within the subtree at ``a``.
+pub fn concat_generalized_indices(a: u64, b: u64) -> u64 {
+    let depth = b.ilog2();
+    (a << depth) | (b - (1 << depth))
+}
+
+/// Return the generalized indices of the siblings of the nodes on the path of ``gindex``.
+pub fn get_branch_indices(gindex: u64) -> Vec<u64> {
+    let mut indices = vec![];
+    let mut index = gindex;
+    while index > 1 {
+        indices.push(index ^ 1);
+        index /= 2;
+    }
+    indices
+}
+
+/// Return the generalized indices of the nodes on the path from ``gindex`` to the root.
+pub fn get_path_indices(gindex: u64) -> Vec<u64> {
+    let mut indices = vec![];
+    let mut index = gindex;
+    while index > 1 {
+        indices.push(index);
+        index /= 2;
+    }
+    indices
+}
+
+/// Return the generalized indices of the nodes needed to prove all of ``gindices``, in
+/// decreasing order.
+pub fn get_helper_indices(gindices: &[u64]) -> Vec<u64> {
+    let mut helper_indices = gindices
+        .iter()
+        .flat_map(|gindex| get_branch_indices(*gindex))
+        .collect::<Vec<_>>();
+    let path_indices = gindices
+        .iter()
+        .flat_map(|gindex| get_path_indices(*gindex))
+        .collect::<Vec<_>>();
+    helper_indices.retain(|index| !path_indices.contains(index));
+    helper_indices.sort_unstable_by(|a, b| b.cmp(a));
+    helper_indices.dedup();
+    helper_indices
+}
+
+/// Return the root proven by the ``leaves`` at ``gindices`` and the multiproof ``proof``.
+pub fn calculate_multi_merkle_root(
+    leaves: &[B256],
+    proof: &[B256],
+    gindices: &[u64],
+) -> anyhow::Result<B256> {
+    ensure!(
+        leaves.len() == gindices.len(),
+        "Expected a leaf per generalized index"
+    );
+    let helper_indices = get_helper_indices(gindices);
+    ensure!(
+        proof.len() == helper_indices.len(),
+        "Expected {} proof nodes, got {}",
+        helper_indices.len(),
+        proof.len()
+    );
+
+    let mut objects = gindices
+        .iter()
+        .copied()
+        .zip(leaves.iter().copied())
+        .chain(helper_indices.into_iter().zip(proof.iter().copied()))
+        .collect::<BTreeMap<_, _>>();
+    let mut keys = objects.keys().rev().copied().collect::<Vec<_>>();
+    let mut position = 0;
+    while position < keys.len() {
+        let key = keys[position];
+        if key > 1 && objects.contains_key(&(key ^ 1)) && !objects.contains_key(&(key / 2)) {
+            let left = objects[&(key & !1)];
+            let right = objects[&(key | 1)];
+            objects.insert(
+                key / 2,
+                B256::from(hash32_concat(left.as_slice(), right.as_slice())),
+            );
+            keys.push(key / 2);
+        }
+        position += 1;
+    }
+    objects
+        .get(&1)
+        .copied()
+        .ok_or_else(|| anyhow!("The proof does not reach the root"))
+}
+
+/// Check the ``leaves`` at ``gindices`` are part of ``root`` with the multiproof ``proof``.
+pub fn verify_merkle_multiproof(
+    leaves: &[B256],
+    proof: &[B256],
+    gindices: &[u64],
+    root: B256,
+) -> bool {
+    calculate_multi_merkle_root(leaves, proof, gindices).is_ok_and(|result| result == root)
+}
+
+/// Return the root of the subtree of height ``height`` at ``index`` among the subtrees of that
+/// height over ``leaves``, missing leaves are zero chunks.
+fn subtree_root(leaves: &[B256], height: u32, index: usize) -> B256 {
+    let start = (index << height).min(leaves.len());
+    let end = (start + (1 << height)).min(leaves.len());
+    let bytes = leaves[start..end]
+        .iter()
+        .flat_map(|leaf| leaf.0)
+        .collect::<Vec<u8>>();
+    merkle_root(&bytes, 1 << height)
+}
+
+/// Prove the node at ``gindex`` of a tree of depth ``depth`` over ``leaves``, calling
+/// ``descend`` with the leaf index and the remaining generalized index for nodes below the
+/// leaves.
+fn compute_tree_proof(
+    leaves: &[B256],
+    depth: u32,
+    gindex: u64,
+    descend: impl FnOnce(usize, u64) -> anyhow::Result<SingleMerkleProof>,
+) -> anyhow::Result<SingleMerkleProof> {
+    ensure!(gindex > 0, "Generalized indices start at 1");
+    let level = gindex.ilog2();
+    let (mut proof, height, index) = if level <= depth {
+        let height = depth - level;
+        let index = (gindex - (1 << level)) as usize;
+        let proof = SingleMerkleProof {
+            leaf: subtree_root(leaves, height, index),
+            branch: vec![],
+        };
+        (proof, height, index)
+    } else {
+        let below = level - depth;
+        let index = ((gindex >> below) - (1 << depth)) as usize;
+        let proof = descend(index, (gindex & ((1 << below) - 1)) | (1 << below))?;
+        (proof, 0, index)
+    };
+    for level_height in height..depth {
+        let sibling = (index >> (level_height - height)) ^ 1;
+        proof
+            .branch
+            .push(subtree_root(leaves, level_height, sibling));
+    }
+    Ok(proof)
+}
+
+/// Return the depth of a tree with ``chunk_count`` leaves.
+fn tree_depth(chunk_count: usize) -> u32 {
+    chunk_count.next_power_of_two().trailing_zeros()
+}
+
+/// Return the leaves of the tree of a sequence of ``values`` and the number of values per leaf.
+fn sequence_leaves<T: TreeHash>(values: &[T]) -> (Vec<B256>, usize) {
+    if T::tree_hash_type() != TreeHashType::Basic {
+        return (values.iter().map(TreeHash::tree_hash_root).collect(), 1);
+    }
+    let bytes = values
+        .iter()
+        .flat_map(|value| value.tree_hash_packed_encoding().to_vec())
+        .collect::<Vec<u8>>();
+    let leaves = bytes
+        .chunks(BYTES_PER_CHUNK)
+        .map(|chunk| {
+            let mut leaf = B256::ZERO;
+            leaf[..chunk.len()].copy_from_slice(chunk);
+            leaf
+        })
+        .collect();
+    (leaves, T::tree_hash_packing_factor())
+}
+
+/// Return the generalized index of ``path`` in a sequence of ``values`` stored in a tree of depth
+/// ``depth``.
+fn sequence_generalized_index<T: MerkleProof + TreeHash>(
+    values: &[T],
+    depth: u32,
+    path: &[&str],
+) -> anyhow::Result<u64> {
+    let Some((element, rest)) = path.split_first() else {
+        return Ok(1);
+    };
+    let index = element
+        .parse::<usize>()
+        .map_err(|_| anyhow!("Expected an element index, got {element}"))?;
+    if T::tree_hash_type() == TreeHashType::Basic {
+        ensure!(index < values.len(), "Index {index} is out of bounds");
+        ensure!(
+            rest.is_empty(),
+            "Basic values have no fields, got {}",
+            rest.join(".")
+        );
+        return Ok((1 << depth) + (index / T::tree_hash_packing_factor()) as u64);
+    }
+    let value = values
+        .get(index)
+        .ok_or_else(|| anyhow!("Index {index} is out of bounds"))?;
+    Ok(concat_generalized_indices(
+        (1 << depth) + index as u64,
+        value.get_generalized_index(rest)?,
+    ))
+}
+
+/// Prove the node at ``gindex`` of the tree of depth ``depth`` over a sequence of ``values``.
+fn compute_sequence_proof<T: MerkleProof + TreeHash>(
+    values: &[T],
+    depth: u32,
+    gindex: u64,
+) -> anyhow::Result<SingleMerkleProof> {
+    let (leaves, _) = sequence_leaves(values);
+    compute_tree_proof(&leaves, depth, gindex, |index, gindex| {
+        ensure!(
+            T::tree_hash_type() != TreeHashType::Basic,
+            "Basic values have no fields"
+        );
+        values
+            .get(index)
+            .ok_or_else(|| anyhow!("Index {index} is out of bounds"))?
+            .compute_merkle_proof(gindex)
+    })
+}
+
+/// Return the depth of the tree of a sequence of at most ``limit`` values of ``T``.
+fn sequence_depth<T: TreeHash>(limit: usize) -> u32 {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => tree_depth(limit.div_ceil(T::tree_hash_packing_factor())),
+        _ => tree_depth(limit),
+    }
+}
+
+impl<T: MerkleProof + TreeHash, N: Unsigned> MerkleProof for FixedVector<T, N> {
+    fn get_generalized_index(&self, path: &[&str]) -> anyhow::Result<u64> {
+        sequence_generalized_index(self, sequence_depth::<T>(N::to_usize()), path)
+    }
+
+    fn compute_merkle_proof(&self, gindex: u64) -> anyhow::Result<SingleMerkleProof> {
+        compute_sequence_proof(self, sequence_depth::<T>(N::to_usize()), gindex)
+    }
+}
+
+impl<T: MerkleProof + TreeHash, N: Unsigned> MerkleProof for VariableList<T, N> {
+    fn get_generalized_index(&self, path: &[&str]) -> anyhow::Result<u64> {
+        match path {
+            [] => Ok(1),
+            ["__len__"] => Ok(3),
+            _ => Ok(concat_generalized_indices(
+                2,
+                sequence_generalized_index(self, sequence_depth::<T>(N::to_usize()), path)?,
+            )),
+        }
+    }
+
+    fn compute_merkle_proof(&self, gindex: u64) -> anyhow::Result<SingleMerkleProof> {
+        let mut length = B256::ZERO;
+        length[..8].copy_from_slice(&(self.len() as u64).to_le_bytes());
+        match gindex {
+            0 => bail!("Generalized indices start at 1"),
+            1 => Ok(SingleMerkleProof {
+                leaf: self.tree_hash_root(),
+                branch: vec![],
+            }),
+            3 => Ok(SingleMerkleProof {
+                leaf: length,
+                branch: vec![
+                    compute_sequence_proof(self, sequence_depth::<T>(N::to_usize()), 1)?.leaf,
+                ],
+            }),
+            _ => {
+                // Strip the first step of the path, which must go into the data tree
+                let level = gindex.ilog2();
+                ensure!(
+                    gindex >> (level - 1) == 2,
+                    "The length of a list has no children"
+                );
+                let data_gindex = (gindex & ((1 << (level - 1)) - 1)) | (1 << (level - 1));
+                let mut proof =
+                    compute_sequence_proof(self, sequence_depth::<T>(N::to_usize()), data_gindex)?;
+                proof.branch.push(length);
+                Ok(proof)
+            }
+        }
+    }
+}
+
+impl<T: MerkleProof> MerkleProof for Arc<T> {
+    fn get_generalized_index(&self, path: &[&str]) -> anyhow::Result<u64> {
+        (**self).get_generalized_index(path)
+    }
+
+    fn compute_merkle_proof(&self, gindex: u64) -> anyhow::Result<SingleMerkleProof> {
+        (**self).compute_merkle_proof(gindex)
+    }
+}
+
+/// Implement ``MerkleProof`` for types whose inner nodes are not addressable, only their root.
+macro_rules! impl_merkle_proof_for_leaf {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl MerkleProof for $type {
+                fn get_generalized_index(&self, path: &[&str]) -> anyhow::Result<u64> {
+                    ensure!(
+                        path.is_empty(),
+                        "{} has no addressable fields, got {}",
+                        stringify!($type),
+                        path.join(".")
+                    );
+                    Ok(1)
+                }
+
+                fn compute_merkle_proof(&self, gindex: u64) -> anyhow::Result<SingleMerkleProof> {
+                    ensure!(
+                        gindex == 1,
+                        "{} has no addressable nodes below its root",
+                        stringify!($type)
+                    );
+                    Ok(SingleMerkleProof {
+                        leaf: self.tree_hash_root(),
+                        branch: vec![],
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_merkle_proof_for_leaf!(
+    bool,
+    u8,
+    u64,
+    B32,
+    B256,
+    U256,
+    Address,
+    PubKey,
+    BLSSignature,
+    KZGCommitment,
+    Deposit,
+    ExecutionPayload,
+    ExecutionRequests,
+    ProposerSlashing,
+    SignedBLSToExecutionChange,
+    SignedVoluntaryExit,
+    electra::attestation::Attestation,
+    electra::attester_slashing::AttesterSlashing,
+);
+
+impl<N: Unsigned + Clone> MerkleProof for BitVector<N> {
+    fn get_generalized_index(&self, path: &[&str]) -> anyhow::Result<u64> {
+        ensure!(path.is_empty(), "Bitvectors have no addressable fields");
+        Ok(1)
+    }
+
+    fn compute_merkle_proof(&self, gindex: u64) -> anyhow::Result<SingleMerkleProof> {
+        ensure!(gindex == 1, "Bitvectors have no addressable nodes");
+        Ok(SingleMerkleProof {
+            leaf: self.tree_hash_root(),
+            branch: vec![],
+        })
+    }
+}
+
+impl<N: Unsigned + Clone> MerkleProof for BitList<N> {
+    fn get_generalized_index(&self, path: &[&str]) -> anyhow::Result<u64> {
+        ensure!(path.is_empty(), "Bitlists have no addressable fields");
+        Ok(1)
+    }
+
+    fn compute_merkle_proof(&self, gindex: u64) -> anyhow::Result<SingleMerkleProof> {
+        ensure!(gindex == 1, "Bitlists have no addressable nodes");
+        Ok(SingleMerkleProof {
+            leaf: self.tree_hash_root(),
+            branch: vec![],
+        })
+    }
+}
+
+/// Implement ``MerkleProof`` for a container from its fields, in declaration order.
+macro_rules! impl_merkle_proof_for_container {
+    ($type:ty { $($field:ident),* $(,)? }) => {
+        impl MerkleProof for $type {
+            fn get_generalized_index(&self, path: &[&str]) -> anyhow::Result<u64> {
+                let fields: &[(&str, &dyn MerkleProof)] = &[$((stringify!($field), &self.$field)),*];
+                let Some((name, rest)) = path.split_first() else {
+                    return Ok(1);
+                };
+                let position = fields
+                    .iter()
+                    .position(|(field, _)| field == name)
+                    .ok_or_else(|| anyhow!("{} has no field {name}", stringify!($type)))?;
+                Ok(concat_generalized_indices(
+                    (1 << tree_depth(fields.len())) + position as u64,
+                    fields[position].1.get_generalized_index(rest)?,
+                ))
+            }
+
+            fn compute_merkle_proof(&self, gindex: u64) -> anyhow::Result<SingleMerkleProof> {
+                let fields: &[&dyn MerkleProof] = &[$(&self.$field),*];
+                let field_roots = [$(self.$field.tree_hash_root()),*];
+                compute_tree_proof(&field_roots, tree_depth(fields.len()), gindex, |index, gindex| {
+                    fields[index].compute_merkle_proof(gindex)
+                })
+            }
+        }
+    };
+}
+
+impl_merkle_proof_for_container!(Fork {
+    previous_version,
+    current_version,
+    epoch,
+});
+impl_merkle_proof_for_container!(Checkpoint { epoch, root });
+impl_merkle_proof_for_container!(BeaconBlockHeader {
+    slot,
+    proposer_index,
+    parent_root,
+    state_root,
+    body_root,
+});
+impl_merkle_proof_for_container!(Eth1Data {
+    deposit_root,
+    deposit_count,
+    block_hash,
+});
+impl_merkle_proof_for_container!(Validator {
+    pubkey,
+    withdrawal_credentials,
+    effective_balance,
+    slashed,
+    activation_eligibility_epoch,
+    activation_epoch,
+    exit_epoch,
+    withdrawable_epoch,
+});
+impl_merkle_proof_for_container!(HistoricalSummary {
+    block_summary_root,
+    state_summary_root,
+});
+impl_merkle_proof_for_container!(SyncCommittee {
+    pubkeys,
+    aggregate_pubkey,
+});
+impl_merkle_proof_for_container!(SyncAggregate {
+    sync_committee_bits,
+    sync_committee_signature,
+});
+impl_merkle_proof_for_container!(PendingDeposit {
+    pubkey,
+    withdrawal_credentials,
+    amount,
+    signature,
+    slot,
+});
+impl_merkle_proof_for_container!(PendingPartialWithdrawal {
+    validator_index,
+    amount,
+    withdrawable_epoch,
+});
+impl_merkle_proof_for_container!(PendingConsolidation {
+    source_index,
+    target_index,
+});
+impl_merkle_proof_for_container!(ExecutionPayloadHeader {
+    parent_hash,
+    fee_recipient,
+    state_root,
+    receipts_root,
+    logs_bloom,
+    prev_randao,
+    block_number,
+    gas_limit,
+    gas_used,
+    timestamp,
+    extra_data,
+    base_fee_per_gas,
+    block_hash,
+    transactions_root,
+    withdrawals_root,
+    blob_gas_used,
+    excess_blob_gas,
+});
+impl_merkle_proof_for_container!(BeaconBlockBody {
+    randao_reveal,
+    eth1_data,
+    graffiti,
+    proposer_slashings,
+    attester_slashings,
+    attestations,
+    deposits,
+    voluntary_exits,
+    sync_aggregate,
+    execution_payload,
+    bls_to_execution_changes,
+    blob_kzg_commitments,
+    execution_requests,
+});
+impl_merkle_proof_for_container!(BeaconState {
+    genesis_time,
+    genesis_validators_root,
+    slot,
+    fork,
+    latest_block_header,
+    block_roots,
+    state_roots,
+    historical_roots,
+    eth1_data,
+    eth1_data_votes,
+    eth1_deposit_index,
+    validators,
+    balances,
+    randao_mixes,
+    slashings,
+    previous_epoch_participation,
+    current_epoch_participation,
+    justification_bits,
+    previous_justified_checkpoint,
+    current_justified_checkpoint,
+    finalized_checkpoint,
+    inactivity_scores,
+    current_sync_committee,
+    next_sync_committee,
+    latest_execution_payload_header,
+    next_withdrawal_index,
+    next_withdrawal_validator_index,
+    historical_summaries,
+    deposit_requests_start_index,
+    deposit_balance_to_consume,
+    exit_balance_to_consume,
+    earliest_exit_epoch,
+    consolidation_balance_to_consume,
+    earliest_consolidation_epoch,
+    pending_deposits,
+    pending_partial_withdrawals,
+    pending_consolidations,
+});
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::{
+        constants::{
+            CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA, EXECUTION_PAYLOAD_GINDEX,
+            FINALIZED_ROOT_GINDEX_ELECTRA, NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+        },
+        deneb::beacon_state::is_valid_merkle_branch,
+        genesis::interop_genesis_state,
+    };
+
+    fn assert_valid_proof(proof: &SingleMerkleProof, gindex: u64, root: B256) {
+        let depth = gindex.ilog2();
+        assert_eq!(proof.branch.len(), depth as usize);
+        assert!(is_valid_merkle_branch(
+            proof.leaf,
+            &proof.branch,
+            depth as u64,
+            gindex % (1 << depth),
+            root
+        ));
+    }
+
+    #[test]
+    fn generalized_indices_match_constants() {
+        let state = interop_genesis_state(4, 0).unwrap();
+        let gindex = |path: &str| state.get_generalized_index(&parse_path(path)).unwrap();
+        assert_eq!(
+            gindex("current_sync_committee"),
+            CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA
+        );
+        assert_eq!(
+            gindex("next_sync_committee"),
+            NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA
+        );
+        assert_eq!(
+            gindex("finalized_checkpoint.root"),
+            FINALIZED_ROOT_GINDEX_ELECTRA
+        );
+        assert_eq!(
+            BeaconBlockBody::default()
+                .get_generalized_index(&["execution_payload"])
+                .unwrap(),
+            EXECUTION_PAYLOAD_GINDEX
+        );
+    }
+
+    #[test]
+    fn single_proofs_verify_against_the_state_root() {
+        let state = interop_genesis_state(4, 0).unwrap();
+        let root = state.tree_hash_root();
+        for path in [
+            "finalized_checkpoint.root",
+            "validators[2].effective_balance",
+            "validators.__len__",
+            "balances[3]",
+            "block_roots[100]",
+            "latest_execution_payload_header.extra_data",
+        ] {
+            let gindex = state.get_generalized_index(&parse_path(path)).unwrap();
+            let proof = state.compute_merkle_proof(gindex).unwrap();
+            assert_valid_proof(&proof, gindex, root);
+        }
+        assert_eq!(
+            state
+                .compute_merkle_proof(FINALIZED_ROOT_GINDEX_ELECTRA)
+                .unwrap()
+                .branch,
+            state.finality_branch().unwrap().to_vec()
+        );
+    }
+
+    #[test]
+    fn multiproof_verifies_against_the_state_root() {
+        let state = interop_genesis_state(4, 0).unwrap();
+        let gindices = ["slot", "validators[0].pubkey", "validators[1].slashed"]
+            .map(|path| state.get_generalized_index(&parse_path(path)).unwrap());
+        let leaves = gindices.map(|gindex| state.get_node(gindex).unwrap());
+        let proof = state.compute_merkle_multiproof(&gindices).unwrap();
+
+        assert!(verify_merkle_multiproof(
+            &leaves,
+            &proof,
+            &gindices,
+            state.tree_hash_root()
+        ));
+        assert!(!verify_merkle_multiproof(
+            &leaves,
+            &proof,
+            &gindices,
+            B256::ZERO
+        ));
+    }
+}
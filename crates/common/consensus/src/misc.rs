@@ -3,10 +3,17 @@ use std::cmp::max;
 use alloy_primitives::{B256, aliases::B32};
 use anyhow::ensure;
 use ethereum_hashing::hash;
+use ream_bls::BLSSignature;
 use tree_hash::TreeHash;
 
 use crate::{
-    constants::{GENESIS_FORK_VERSION, MAX_SEED_LOOKAHEAD, SHUFFLE_ROUND_COUNT, SLOTS_PER_EPOCH},
+    chain_spec::chain_spec,
+    constants::{
+        ATTESTATION_SUBNET_COUNT, BLOB_SIDECAR_SUBNET_COUNT, BLOB_SIDECAR_SUBNET_COUNT_ELECTRA,
+        EPOCHS_PER_SYNC_COMMITTEE_PERIOD, ETH_TO_GWEI, MAX_DEPOSITS, MAX_EFFECTIVE_BALANCE,
+        MAX_SEED_LOOKAHEAD, SAFETY_DECAY, SHUFFLE_ROUND_COUNT, SLOTS_PER_EPOCH,
+        TARGET_AGGREGATORS_PER_COMMITTEE,
+    },
     fork_data::ForkData,
     signing_data::SigningData,
 };
@@ -45,6 +52,43 @@ pub fn compute_shuffled_index(
     Ok(index)
 }
 
+/// Return ``indices`` reordered so that position ``i`` holds
+/// ``indices[compute_shuffled_index(i, len(indices), seed)]``.
+///
+/// Runs the swap-or-not rounds over the whole list at once, hashing each 256 position chunk once
+/// per round instead of once per index and round.
+pub fn compute_shuffled_list(indices: &[u64], seed: B256) -> Vec<u64> {
+    let index_count = indices.len();
+    let mut shuffled_positions: Vec<usize> = (0..index_count).collect();
+    if index_count == 0 {
+        return vec![];
+    }
+
+    for round in 0..SHUFFLE_ROUND_COUNT {
+        let seed_with_round = [seed.as_slice(), &round.to_le_bytes()].concat();
+        let pivot = (bytes_to_int64(&hash(&seed_with_round)[..]) % index_count as u64) as usize;
+        let sources = (0..index_count.div_ceil(256))
+            .map(|chunk: usize| {
+                hash(&[seed_with_round.as_slice(), &chunk.to_le_bytes()[0..4]].concat())
+            })
+            .collect::<Vec<_>>();
+
+        for index in shuffled_positions.iter_mut() {
+            let flip = (pivot + (index_count - *index)) % index_count;
+            let position = max(*index, flip);
+            let byte = sources[position / 256][(position % 256) / 8];
+            if (byte >> (position % 8)) % 2 == 1 {
+                *index = flip;
+            }
+        }
+    }
+
+    shuffled_positions
+        .into_iter()
+        .map(|position| indices[position])
+        .collect()
+}
+
 fn bytes_to_int64(slice: &[u8]) -> u64 {
     let mut bytes = [0; 8];
     bytes.copy_from_slice(&slice[0..8]);
@@ -85,12 +129,60 @@ pub fn compute_start_slot_at_epoch(epoch: u64) -> u64 {
     epoch * SLOTS_PER_EPOCH
 }
 
+/// Return the sync committee period at ``epoch``.
+pub fn compute_sync_committee_period(epoch: u64) -> u64 {
+    epoch / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// Return the sync committee period of ``slot``.
+pub fn compute_sync_committee_period_at_slot(slot: u64) -> u64 {
+    compute_sync_committee_period(compute_epoch_at_slot(slot))
+}
+
 /// Return the epoch during which validator activations and exits initiated in ``epoch`` take
 /// effect.
 pub fn compute_activation_exit_epoch(epoch: u64) -> u64 {
     epoch + 1 + MAX_SEED_LOOKAHEAD
 }
 
+/// Return the weak subjectivity period, in epochs, of a state before Electra with
+/// ``active_validator_count`` active validators holding ``total_active_balance`` Gwei.
+///
+/// This computation takes into account the effect of:
+///     - validator set churn (bounded by ``get_validator_churn_limit()`` per epoch), and
+///     - validator balance top-ups (bounded by ``MAX_DEPOSITS * SLOTS_PER_EPOCH`` per epoch).
+/// A detailed calculation can be found at:
+/// <https://github.com/runtimeverification/beacon-chain-verification/blob/master/weak-subjectivity/weak-subjectivity-analysis.pdf>
+pub fn compute_weak_subjectivity_period(
+    active_validator_count: u64,
+    total_active_balance: u64,
+) -> u64 {
+    let mut ws_period = chain_spec().min_validator_withdrawability_delay;
+    let n = active_validator_count;
+    if n == 0 {
+        return ws_period;
+    }
+    let t = total_active_balance / n / ETH_TO_GWEI;
+    let total = MAX_EFFECTIVE_BALANCE / ETH_TO_GWEI;
+    let delta = max(
+        chain_spec().min_per_epoch_churn_limit,
+        n / chain_spec().churn_limit_quotient,
+    );
+    let max_balance_top_ups = MAX_DEPOSITS * SLOTS_PER_EPOCH;
+    let d = SAFETY_DECAY;
+
+    if total * (200 + 3 * d) < t * (200 + 12 * d) {
+        let epochs_for_validator_set_churn =
+            n * (t * (200 + 12 * d) - total * (200 + 3 * d)) / (600 * delta * (2 * t + total));
+        let epochs_for_balance_top_ups = n * (200 + 3 * d) / (600 * max_balance_top_ups);
+        ws_period += max(epochs_for_validator_set_churn, epochs_for_balance_top_ups);
+    } else {
+        ws_period += 3 * n * d * t / (200 * max_balance_top_ups * (total - t));
+    }
+
+    ws_period
+}
+
 /// Return the domain for the ``domain_type`` and ``fork_version``
 pub fn compute_domain(
     domain_type: B32,
@@ -98,8 +190,7 @@ pub fn compute_domain(
     genesis_validators_root: Option<B256>,
 ) -> B256 {
     let fork_data = ForkData {
-        current_version: fork_version.unwrap_or(GENESIS_FORK_VERSION), /* Fork version for
-                                                                        * Ethereum mainnet */
+        current_version: fork_version.unwrap_or(chain_spec().genesis_fork_version),
         genesis_validators_root: genesis_validators_root.unwrap_or_default(),
     };
     let fork_data_root = fork_data.compute_fork_data_root();
@@ -107,6 +198,41 @@ pub fn compute_domain(
     B256::from_slice(&domain_bytes)
 }
 
+/// Return the subnet an attestation of committee ``committee_index`` at ``slot`` is gossiped on.
+pub fn compute_subnet_for_attestation(
+    committees_per_slot: u64,
+    slot: u64,
+    committee_index: u64,
+) -> u64 {
+    let slots_since_epoch_start = slot % SLOTS_PER_EPOCH;
+    let committees_since_epoch_start = committees_per_slot * slots_since_epoch_start;
+    (committees_since_epoch_start + committee_index) % ATTESTATION_SUBNET_COUNT
+}
+
+/// Return the subnet the blob sidecar of index ``blob_index`` of a block of ``slot`` is gossiped
+/// on. Electra adds subnets for its higher blob limit.
+pub fn compute_subnet_for_blob_sidecar(blob_index: u64, slot: u64) -> u64 {
+    let blob_sidecar_subnet_count =
+        match compute_epoch_at_slot(slot) >= chain_spec().electra_fork_epoch {
+            true => BLOB_SIDECAR_SUBNET_COUNT_ELECTRA,
+            false => BLOB_SIDECAR_SUBNET_COUNT,
+        };
+    blob_index % blob_sidecar_subnet_count
+}
+
+/// Check if the ``selection_proof`` of a member of a committee of ``committee_size`` validators
+/// makes it an aggregator of the committee.
+pub fn is_aggregator(committee_size: usize, selection_proof: &BLSSignature) -> bool {
+    let modulo = max(1, committee_size as u64 / TARGET_AGGREGATORS_PER_COMMITTEE);
+    let selection_proof_hash = hash(selection_proof.to_bytes());
+    u64::from_le_bytes(
+        selection_proof_hash[..8]
+            .try_into()
+            .expect("hash is 32 bytes"),
+    ) % modulo
+        == 0
+}
+
 pub fn is_sorted_and_unique(indices: &[usize]) -> bool {
     indices.windows(2).all(|w| w[0] < w[1])
 }
@@ -0,0 +1,32 @@
+use alloy_primitives::B256;
+use ream_bls::BLSSignature;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    VariableList,
+    typenum::{U2, U16, U128},
+};
+use tree_hash_derive::TreeHash;
+
+use crate::{
+    attestation::Attestation, attester_slashing::AttesterSlashing, deposit::Deposit,
+    eth_1_data::Eth1Data, proposer_slashing::ProposerSlashing, voluntary_exit::SignedVoluntaryExit,
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconBlockBody {
+    pub randao_reveal: BLSSignature,
+
+    /// Eth1 data vote
+    pub eth1_data: Eth1Data,
+
+    /// Arbitrary data
+    pub graffiti: B256,
+
+    // Operations
+    pub proposer_slashings: VariableList<ProposerSlashing, U16>,
+    pub attester_slashings: VariableList<AttesterSlashing, U2>,
+    pub attestations: VariableList<Attestation, U128>,
+    pub deposits: VariableList<Deposit, U16>,
+    pub voluntary_exits: VariableList<SignedVoluntaryExit, U16>,
+}
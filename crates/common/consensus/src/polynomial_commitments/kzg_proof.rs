@@ -1,15 +1,4 @@
-use serde::Deserialize;
-use ssz_types::{VariableList, typenum};
+use alloy_primitives::FixedBytes;
+use kzg::eip_4844::BYTES_PER_PROOF;
 
-#[derive(Deserialize, Debug)]
-pub struct KZGProof {
-    pub bytes: VariableList<u8, typenum::U48>,
-}
-
-impl KZGProof {
-    pub fn to_fixed_bytes(&self) -> [u8; 48] {
-        let mut fixed_array = [0u8; 48];
-        fixed_array.copy_from_slice(&self.bytes);
-        fixed_array
-    }
-}
+pub type KZGProof = FixedBytes<BYTES_PER_PROOF>;
@@ -0,0 +1,46 @@
+use ssz_types::typenum::{
+    U16, U17, U64, U128, U512, U2048, U4096, U8192, U65536, U131072, U262144, U134217728, Unsigned,
+};
+
+pub const PRESET_BASE: &str = "mainnet";
+
+pub type SlotsPerHistoricalRoot = U8192;
+pub type EpochsPerHistoricalVector = U65536;
+pub type EpochsPerSlashingsVector = U8192;
+/// ``EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH``
+pub type SlotsPerEth1VotingPeriod = U2048;
+/// ``MAX_ATTESTATIONS * SLOTS_PER_EPOCH``
+pub type MaxPendingAttestations = U4096;
+pub type SyncCommitteeSize = U512;
+/// ``SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT``
+pub type SyncSubcommitteeSize = U128;
+pub type MaxWithdrawalsPerPayload = U16;
+pub type KzgCommitmentInclusionProofDepth = U17;
+pub type MaxBlobCommitmentsPerBlock = U4096;
+pub type MaxCommitteesPerSlot = U64;
+/// ``MAX_VALIDATORS_PER_COMMITTEE * MAX_COMMITTEES_PER_SLOT``
+pub type MaxValidatorsPerSlot = U131072;
+pub type PendingPartialWithdrawalsLimit = U134217728;
+pub type PendingConsolidationsLimit = U262144;
+pub type MaxDepositRequestsPerPayload = U8192;
+pub type MaxWithdrawalRequestsPerPayload = U16;
+
+pub const SLOTS_PER_EPOCH: u64 = 32;
+pub const MAX_COMMITTEES_PER_SLOT: u64 = MaxCommitteesPerSlot::U64;
+pub const TARGET_COMMITTEE_SIZE: u64 = 128;
+pub const SHUFFLE_ROUND_COUNT: u8 = 90;
+pub const EPOCHS_PER_ETH1_VOTING_PERIOD: u64 = 64;
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = SlotsPerHistoricalRoot::U64;
+pub const EPOCHS_PER_HISTORICAL_VECTOR: u64 = EpochsPerHistoricalVector::U64;
+pub const EPOCHS_PER_SLASHINGS_VECTOR: u64 = EpochsPerSlashingsVector::U64;
+pub const SYNC_COMMITTEE_SIZE: u64 = SyncCommitteeSize::U64;
+pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+pub const MAX_WITHDRAWALS_PER_PAYLOAD: u64 = MaxWithdrawalsPerPayload::U64;
+pub const MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP: usize = 16384;
+pub const MAX_BLOB_COMMITMENTS_PER_BLOCK: u64 = MaxBlobCommitmentsPerBlock::U64;
+pub const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: u64 = KzgCommitmentInclusionProofDepth::U64;
+pub const PENDING_PARTIAL_WITHDRAWALS_LIMIT: u64 = PendingPartialWithdrawalsLimit::U64;
+pub const PENDING_CONSOLIDATIONS_LIMIT: u64 = PendingConsolidationsLimit::U64;
+pub const MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP: u64 = 8;
+pub const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: u64 = MaxDepositRequestsPerPayload::U64;
+pub const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: u64 = MaxWithdrawalRequestsPerPayload::U64;
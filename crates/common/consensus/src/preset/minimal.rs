@@ -0,0 +1,44 @@
+use ssz_types::typenum::{U2, U4, U8, U10, U32, U64, U1024, U8192, Unsigned};
+
+pub const PRESET_BASE: &str = "minimal";
+
+pub type SlotsPerHistoricalRoot = U64;
+pub type EpochsPerHistoricalVector = U64;
+pub type EpochsPerSlashingsVector = U64;
+/// ``EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH``
+pub type SlotsPerEth1VotingPeriod = U32;
+/// ``MAX_ATTESTATIONS * SLOTS_PER_EPOCH``
+pub type MaxPendingAttestations = U1024;
+pub type SyncCommitteeSize = U32;
+/// ``SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT``
+pub type SyncSubcommitteeSize = U8;
+pub type MaxWithdrawalsPerPayload = U4;
+pub type KzgCommitmentInclusionProofDepth = U10;
+pub type MaxBlobCommitmentsPerBlock = U32;
+pub type MaxCommitteesPerSlot = U4;
+/// ``MAX_VALIDATORS_PER_COMMITTEE * MAX_COMMITTEES_PER_SLOT``
+pub type MaxValidatorsPerSlot = U8192;
+pub type PendingPartialWithdrawalsLimit = U64;
+pub type PendingConsolidationsLimit = U64;
+pub type MaxDepositRequestsPerPayload = U4;
+pub type MaxWithdrawalRequestsPerPayload = U2;
+
+pub const SLOTS_PER_EPOCH: u64 = 8;
+pub const MAX_COMMITTEES_PER_SLOT: u64 = MaxCommitteesPerSlot::U64;
+pub const TARGET_COMMITTEE_SIZE: u64 = 4;
+pub const SHUFFLE_ROUND_COUNT: u8 = 10;
+pub const EPOCHS_PER_ETH1_VOTING_PERIOD: u64 = 4;
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = SlotsPerHistoricalRoot::U64;
+pub const EPOCHS_PER_HISTORICAL_VECTOR: u64 = EpochsPerHistoricalVector::U64;
+pub const EPOCHS_PER_SLASHINGS_VECTOR: u64 = EpochsPerSlashingsVector::U64;
+pub const SYNC_COMMITTEE_SIZE: u64 = SyncCommitteeSize::U64;
+pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 8;
+pub const MAX_WITHDRAWALS_PER_PAYLOAD: u64 = MaxWithdrawalsPerPayload::U64;
+pub const MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP: usize = 16;
+pub const MAX_BLOB_COMMITMENTS_PER_BLOCK: u64 = MaxBlobCommitmentsPerBlock::U64;
+pub const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: u64 = KzgCommitmentInclusionProofDepth::U64;
+pub const PENDING_PARTIAL_WITHDRAWALS_LIMIT: u64 = PendingPartialWithdrawalsLimit::U64;
+pub const PENDING_CONSOLIDATIONS_LIMIT: u64 = PendingConsolidationsLimit::U64;
+pub const MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP: u64 = 2;
+pub const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: u64 = MaxDepositRequestsPerPayload::U64;
+pub const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: u64 = MaxWithdrawalRequestsPerPayload::U64;
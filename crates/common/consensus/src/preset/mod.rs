@@ -0,0 +1,127 @@
+//! The preset values of the consensus specs.
+//!
+//! Presets size the SSZ containers, so unlike the [`ChainSpec`](crate::chain_spec::ChainSpec)
+//! they are fixed at compile time. ``mainnet`` is the default, the ``minimal`` feature selects the
+//! ``minimal`` preset used by the EF minimal tests and local devnets.
+
+#[cfg(not(feature = "minimal"))]
+mod mainnet;
+#[cfg(feature = "minimal")]
+mod minimal;
+
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, ensure};
+#[cfg(not(feature = "minimal"))]
+pub use mainnet::*;
+#[cfg(feature = "minimal")]
+pub use minimal::*;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::constants::{MAX_SEED_LOOKAHEAD, MIN_EPOCHS_TO_INACTIVITY_PENALTY, MIN_SEED_LOOKAHEAD};
+
+/// The preset values this client depends on, as found in the ``presets/<name>/*.yaml`` files of
+/// the consensus specs.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct Preset {
+    pub slots_per_epoch: u64,
+    pub max_committees_per_slot: u64,
+    pub target_committee_size: u64,
+    pub shuffle_round_count: u64,
+    pub min_seed_lookahead: u64,
+    pub max_seed_lookahead: u64,
+    pub epochs_per_eth1_voting_period: u64,
+    pub slots_per_historical_root: u64,
+    pub min_epochs_to_inactivity_penalty: u64,
+    pub epochs_per_historical_vector: u64,
+    pub epochs_per_slashings_vector: u64,
+    pub sync_committee_size: u64,
+    pub epochs_per_sync_committee_period: u64,
+    pub max_withdrawals_per_payload: u64,
+    pub max_validators_per_withdrawals_sweep: u64,
+    pub max_blob_commitments_per_block: u64,
+    pub pending_partial_withdrawals_limit: u64,
+    pub pending_consolidations_limit: u64,
+    pub max_pending_partials_per_withdrawals_sweep: u64,
+    pub max_deposit_requests_per_payload: u64,
+    pub max_withdrawal_requests_per_payload: u64,
+}
+
+impl Preset {
+    /// Return the preset this binary was compiled with.
+    pub fn compiled() -> Self {
+        Self {
+            slots_per_epoch: SLOTS_PER_EPOCH,
+            max_committees_per_slot: MAX_COMMITTEES_PER_SLOT,
+            target_committee_size: TARGET_COMMITTEE_SIZE,
+            shuffle_round_count: SHUFFLE_ROUND_COUNT as u64,
+            min_seed_lookahead: MIN_SEED_LOOKAHEAD,
+            max_seed_lookahead: MAX_SEED_LOOKAHEAD,
+            epochs_per_eth1_voting_period: EPOCHS_PER_ETH1_VOTING_PERIOD,
+            slots_per_historical_root: SLOTS_PER_HISTORICAL_ROOT,
+            min_epochs_to_inactivity_penalty: MIN_EPOCHS_TO_INACTIVITY_PENALTY,
+            epochs_per_historical_vector: EPOCHS_PER_HISTORICAL_VECTOR,
+            epochs_per_slashings_vector: EPOCHS_PER_SLASHINGS_VECTOR,
+            sync_committee_size: SYNC_COMMITTEE_SIZE,
+            epochs_per_sync_committee_period: EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+            max_withdrawals_per_payload: MAX_WITHDRAWALS_PER_PAYLOAD,
+            max_validators_per_withdrawals_sweep: MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP as u64,
+            max_blob_commitments_per_block: MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            pending_partial_withdrawals_limit: PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            pending_consolidations_limit: PENDING_CONSOLIDATIONS_LIMIT,
+            max_pending_partials_per_withdrawals_sweep: MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP,
+            max_deposit_requests_per_payload: MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            max_withdrawal_requests_per_payload: MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        }
+    }
+
+    /// Load a preset from a directory holding one YAML file per fork, such as
+    /// ``presets/minimal`` of the consensus specs.
+    pub fn from_dir(path: &Path) -> anyhow::Result<Self> {
+        let mut values = Mapping::new();
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "yaml") {
+                continue;
+            }
+            let fork_values: Mapping = serde_yaml::from_str(&fs::read_to_string(&path)?)
+                .map_err(|err| anyhow!("Failed to parse preset file {}: {err}", path.display()))?;
+            values.extend(fork_values);
+        }
+        serde_yaml::from_value(Value::Mapping(values))
+            .map_err(|err| anyhow!("Failed to parse preset {}: {err}", path.display()))
+    }
+
+    /// Ensure a loaded preset matches the one compiled in, the sizes of the SSZ containers can't
+    /// change at runtime.
+    pub fn ensure_compiled(&self) -> anyhow::Result<()> {
+        let compiled = Self::compiled();
+        ensure!(
+            *self == compiled,
+            "Preset does not match the compiled {PRESET_BASE} preset, expected {compiled:?} got \
+             {self:?}"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ssz_types::typenum::Unsigned;
+
+    use super::*;
+
+    #[test]
+    fn type_level_lengths_match_preset() {
+        assert_eq!(
+            SlotsPerEth1VotingPeriod::U64,
+            EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH
+        );
+        assert_eq!(MaxValidatorsPerSlot::U64, 2048 * MAX_COMMITTEES_PER_SLOT);
+        Preset::compiled()
+            .ensure_compiled()
+            .expect("compiled preset is consistent");
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use alloy_primitives::{B256, aliases::B32};
+
+use crate::{
+    constants::{MAX_COMMITTEES_PER_SLOT, SLOTS_PER_EPOCH, TARGET_COMMITTEE_SIZE},
+    misc::compute_shuffled_list,
+};
+
+/// The number of shufflings kept, enough for the previous, current and next epoch of a handful of
+/// competing forks.
+pub const SHUFFLING_CACHE_SIZE: usize = 16;
+
+/// The attester shufflings shared by every ``BeaconState`` of the process, so states loaded by
+/// the RPC and the fork choice reuse each other's work.
+pub static SHUFFLING_CACHE: LazyLock<RwLock<ShufflingCache<Shuffling>>> =
+    LazyLock::new(|| RwLock::new(ShufflingCache::default()));
+
+/// The proposers of each slot of an epoch, shared like [`SHUFFLING_CACHE`].
+pub static PROPOSER_CACHE: LazyLock<RwLock<ShufflingCache<Vec<u64>>>> =
+    LazyLock::new(|| RwLock::new(ShufflingCache::default()));
+
+/// Identifies a shuffling of ``epoch`` across states.
+///
+/// ``decision_root`` is the root of the last block before the shuffling can't change anymore,
+/// so states of competing forks only share a shuffling when they share its history. The fork
+/// version is included as the proposer selection changes between forks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShufflingKey {
+    pub epoch: u64,
+    pub seed: B256,
+    pub decision_root: B256,
+    pub fork_version: B32,
+}
+
+/// The attester shuffling of an epoch, the committees of the epoch are consecutive slices of
+/// ``shuffled_indices``.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Shuffling {
+    pub active_validator_indices: Vec<u64>,
+    pub shuffled_indices: Vec<u64>,
+    pub committees_per_slot: u64,
+}
+
+impl Shuffling {
+    pub fn new(seed: B256, active_validator_indices: Vec<u64>) -> Self {
+        let shuffled_indices = compute_shuffled_list(&active_validator_indices, seed);
+        let committees_per_slot =
+            (active_validator_indices.len() as u64 / SLOTS_PER_EPOCH / TARGET_COMMITTEE_SIZE)
+                .clamp(1, MAX_COMMITTEES_PER_SLOT);
+        Self {
+            active_validator_indices,
+            shuffled_indices,
+            committees_per_slot,
+        }
+    }
+
+    /// Return committee ``index`` out of ``count``, the same committee as ``compute_committee``.
+    pub fn committee(&self, index: u64, count: u64) -> &[u64] {
+        let start = (self.shuffled_indices.len() as u64 * index) / count;
+        let end = (self.shuffled_indices.len() as u64 * (index + 1)) / count;
+        &self.shuffled_indices[start as usize..end as usize]
+    }
+}
+
+/// Values of an epoch keyed by their ``ShufflingKey``, oldest first.
+#[derive(Debug)]
+pub struct ShufflingCache<T> {
+    entries: VecDeque<(ShufflingKey, Arc<T>)>,
+}
+
+impl<T> Default for ShufflingCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> ShufflingCache<T> {
+    pub fn get(&self, key: &ShufflingKey) -> Option<Arc<T>> {
+        self.entries
+            .iter()
+            .find(|(cached_key, _)| cached_key == key)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn insert(&mut self, key: ShufflingKey, value: Arc<T>) {
+        self.entries.retain(|(cached_key, _)| *cached_key != key);
+        if self.entries.len() == SHUFFLING_CACHE_SIZE {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, value));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Return the proposers of each slot of the epoch of ``key`` from [`PROPOSER_CACHE`], computing
+/// them on a miss.
+pub fn get_or_compute_proposers(
+    key: ShufflingKey,
+    compute: impl FnOnce() -> anyhow::Result<Vec<u64>>,
+) -> anyhow::Result<Arc<Vec<u64>>> {
+    if let Some(proposers) = PROPOSER_CACHE.read().ok().and_then(|cache| cache.get(&key)) {
+        return Ok(proposers);
+    }
+
+    let proposers = Arc::new(compute()?);
+    if let Ok(mut cache) = PROPOSER_CACHE.write() {
+        cache.insert(key, proposers.clone());
+    }
+    Ok(proposers)
+}
+
+/// Return the attester shuffling of ``key`` from [`SHUFFLING_CACHE`], shuffling the
+/// ``active_validator_indices`` on a miss.
+pub fn get_or_compute_shuffling(
+    key: ShufflingKey,
+    active_validator_indices: impl FnOnce() -> Vec<u64>,
+) -> Arc<Shuffling> {
+    if let Some(shuffling) = SHUFFLING_CACHE
+        .read()
+        .ok()
+        .and_then(|cache| cache.get(&key))
+    {
+        return shuffling;
+    }
+
+    let shuffling = Arc::new(Shuffling::new(key.seed, active_validator_indices()));
+    if let Ok(mut cache) = SHUFFLING_CACHE.write() {
+        cache.insert(key, shuffling.clone());
+    }
+    shuffling
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::misc::compute_committee;
+
+    fn key(epoch: u64, decision_root: B256) -> ShufflingKey {
+        ShufflingKey {
+            epoch,
+            seed: B256::repeat_byte(0x01),
+            decision_root,
+            fork_version: B32::ZERO,
+        }
+    }
+
+    #[test]
+    fn committees_match_compute_committee() {
+        let seed = B256::repeat_byte(0x2a);
+        let active_validator_indices: Vec<u64> = (0..1000).filter(|i| i % 7 != 0).collect();
+        let shuffling = Shuffling::new(seed, active_validator_indices.clone());
+
+        let count = 12;
+        for index in 0..count {
+            assert_eq!(
+                shuffling.committee(index, count),
+                compute_committee(&active_validator_indices, seed, index, count)
+                    .unwrap()
+                    .as_slice()
+            );
+        }
+    }
+
+    #[test]
+    fn cache_is_keyed_by_decision_root() {
+        let mut cache = ShufflingCache::default();
+        cache.insert(
+            key(1, B256::ZERO),
+            Arc::new(Shuffling::new(B256::ZERO, vec![0, 1, 2, 3])),
+        );
+
+        assert!(cache.get(&key(1, B256::ZERO)).is_some());
+        assert!(cache.get(&key(1, B256::repeat_byte(0x02))).is_none());
+        assert!(cache.get(&key(2, B256::ZERO)).is_none());
+
+        cache.insert(
+            key(1, B256::ZERO),
+            Arc::new(Shuffling::new(B256::ZERO, vec![0, 1, 2])),
+        );
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn cache_evicts_oldest_shuffling() {
+        let mut cache = ShufflingCache::default();
+        for epoch in 0..=SHUFFLING_CACHE_SIZE as u64 {
+            cache.insert(
+                key(epoch, B256::ZERO),
+                Arc::new(Shuffling::new(B256::ZERO, vec![0, 1])),
+            );
+        }
+
+        assert_eq!(cache.len(), SHUFFLING_CACHE_SIZE);
+        assert!(cache.get(&key(0, B256::ZERO)).is_none());
+        assert!(cache.get(&key(1, B256::ZERO)).is_some());
+    }
+}
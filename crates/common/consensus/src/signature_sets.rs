@@ -0,0 +1,181 @@
+use alloy_primitives::B256;
+use anyhow::{anyhow, bail};
+use ream_bls::{
+    BLSSignature, PubKey,
+    traits::{BatchVerifiable, Verifiable},
+};
+
+use crate::state_transition::BlockSignatureStrategy;
+
+/// A signature together with the public keys and the signing root it must verify against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureSet {
+    pub signature: BLSSignature,
+    pub pubkeys: Vec<PubKey>,
+    pub message: B256,
+}
+
+impl SignatureSet {
+    pub fn single_pubkey(signature: BLSSignature, pubkey: PubKey, message: B256) -> Self {
+        Self {
+            signature,
+            pubkeys: vec![pubkey],
+            message,
+        }
+    }
+
+    pub fn multiple_pubkeys(signature: BLSSignature, pubkeys: Vec<PubKey>, message: B256) -> Self {
+        Self {
+            signature,
+            pubkeys,
+            message,
+        }
+    }
+
+    /// Verify the set, an empty set of public keys is only valid with the
+    /// ``G2_POINT_AT_INFINITY`` signature as in ``eth_fast_aggregate_verify``.
+    pub fn verify(&self) -> anyhow::Result<bool> {
+        match self.pubkeys.as_slice() {
+            [] => Ok(self.signature == BLSSignature::infinity()),
+            [pubkey] => self
+                .signature
+                .verify(pubkey, self.message.as_ref())
+                .map_err(|err| anyhow!("Failed to verify signature: {err:?}")),
+            pubkeys => self
+                .signature
+                .fast_aggregate_verify(pubkeys.iter().collect::<Vec<_>>(), self.message.as_ref())
+                .map_err(|err| anyhow!("Failed to verify fast aggregate: {err:?}")),
+        }
+    }
+}
+
+/// Verify ``signature_sets`` with a single randomized multi-pairing and return the index of the
+/// first invalid set, if any.
+///
+/// The batch only tells whether every set is valid, so when it fails the sets are verified one by
+/// one to find the offender.
+pub fn find_invalid_signature_set(
+    signature_sets: &[SignatureSet],
+) -> anyhow::Result<Option<usize>> {
+    // Sets without public keys are only valid with the infinity signature, which can't be part of
+    // a pairing check
+    let mut batch = Vec::with_capacity(signature_sets.len());
+    for (index, signature_set) in signature_sets.iter().enumerate() {
+        if signature_set.pubkeys.is_empty() {
+            if !signature_set.verify()? {
+                return Ok(Some(index));
+            }
+        } else {
+            batch.push(ream_bls::SignatureSet {
+                signature: &signature_set.signature,
+                pubkeys: &signature_set.pubkeys,
+                message: signature_set.message.as_slice(),
+            });
+        }
+    }
+
+    if let Ok(true) = BLSSignature::verify_signature_sets(&batch) {
+        return Ok(None);
+    }
+
+    for (index, signature_set) in signature_sets.iter().enumerate() {
+        if !signature_set.verify().unwrap_or(false) {
+            return Ok(Some(index));
+        }
+    }
+    bail!("Batch signature verification failed although every signature set is valid")
+}
+
+/// Applies a [`BlockSignatureStrategy`] to the signatures met while processing a block.
+///
+/// With [`BlockSignatureStrategy::VerifyBulk`] it collects every signature set of the block, which
+/// are then verified as a single batch by [`Self::finish`].
+#[derive(Debug, Default)]
+pub struct SignatureVerifier {
+    strategy: BlockSignatureStrategy,
+    pending: Vec<SignatureSet>,
+}
+
+impl SignatureVerifier {
+    pub fn new(strategy: BlockSignatureStrategy) -> Self {
+        Self {
+            strategy,
+            pending: vec![],
+        }
+    }
+
+    pub fn strategy(&self) -> BlockSignatureStrategy {
+        self.strategy
+    }
+
+    /// Verify ``signature_set`` now, or defer it depending on the strategy. Deferred and skipped
+    /// sets report ``true``, the outcome of deferred sets is known after [`Self::finish`].
+    pub fn verify(&mut self, signature_set: SignatureSet) -> anyhow::Result<bool> {
+        match self.strategy {
+            BlockSignatureStrategy::VerifyIndividual => signature_set.verify(),
+            BlockSignatureStrategy::VerifyBulk => {
+                self.pending.push(signature_set);
+                Ok(true)
+            }
+            BlockSignatureStrategy::NoVerification => Ok(true),
+        }
+    }
+
+    /// Verify all deferred signature sets as one batch.
+    pub fn finish(self) -> anyhow::Result<()> {
+        if let Some(index) = find_invalid_signature_set(&self.pending)? {
+            bail!(
+                "Invalid signature for {:?} over signing root {}",
+                self.pending[index].pubkeys,
+                self.pending[index].message
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+
+    fn invalid_signature_set() -> SignatureSet {
+        SignatureSet::single_pubkey(BLSSignature::default(), PubKey::default(), B256::ZERO)
+    }
+
+    #[test]
+    fn no_verification_accepts_every_set() {
+        let mut signature_verifier = SignatureVerifier::new(BlockSignatureStrategy::NoVerification);
+
+        assert!(signature_verifier.verify(invalid_signature_set()).unwrap());
+        signature_verifier.finish().unwrap();
+    }
+
+    #[test]
+    fn bulk_verification_defers_sets() {
+        let mut signature_verifier = SignatureVerifier::new(BlockSignatureStrategy::VerifyBulk);
+
+        assert!(signature_verifier.verify(invalid_signature_set()).unwrap());
+        assert_eq!(signature_verifier.pending.len(), 1);
+        assert!(signature_verifier.finish().is_err());
+    }
+
+    #[test]
+    fn empty_pubkeys_require_infinity_signature() {
+        let infinity = SignatureSet::multiple_pubkeys(BLSSignature::infinity(), vec![], B256::ZERO);
+        let not_infinity =
+            SignatureSet::multiple_pubkeys(BLSSignature::default(), vec![], B256::ZERO);
+
+        assert!(infinity.verify().unwrap());
+        assert!(!not_infinity.verify().unwrap());
+        assert_eq!(
+            find_invalid_signature_set(&[infinity, not_infinity]).unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn empty_batch_is_valid() {
+        assert_eq!(find_invalid_signature_set(&[]).unwrap(), None);
+    }
+}
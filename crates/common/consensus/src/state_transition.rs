@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+/// How ``state_transition`` checks the BLS signatures of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockSignatureStrategy {
+    /// Verify every signature as soon as it is encountered.
+    #[default]
+    VerifyIndividual,
+    /// Collect every signature of the block and verify them as a single batch once the block is
+    /// processed.
+    VerifyBulk,
+    /// Skip signature verification, for blocks which are already known to be valid.
+    NoVerification,
+}
+
+/// Time spent in each phase of ``state_transition``.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StateTransitionTimings {
+    pub process_slots: Duration,
+    pub process_block: Duration,
+    pub verify_signatures: Duration,
+    pub state_root: Duration,
+}
+
+impl StateTransitionTimings {
+    pub fn total(&self) -> Duration {
+        self.process_slots + self.process_block + self.verify_signatures + self.state_root
+    }
+}
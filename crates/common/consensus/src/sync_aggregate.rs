@@ -1,11 +1,120 @@
-use ream_bls::BLSSignature;
+use std::collections::HashSet;
+
+use anyhow::{anyhow, ensure};
+use ream_bls::{BLSSignature, traits::SignatureAggregatable};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{BitVector, typenum};
+use ssz_types::BitVector;
 use tree_hash_derive::TreeHash;
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+use crate::{
+    constants::{SYNC_COMMITTEE_SIZE, SYNC_COMMITTEE_SUBNET_COUNT},
+    preset::SyncCommitteeSize,
+    sync_committee_contribution::SyncCommitteeContribution,
+};
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct SyncAggregate {
-    pub sync_committee_bits: BitVector<typenum::U512>,
+    pub sync_committee_bits: BitVector<SyncCommitteeSize>,
     pub sync_committee_signature: BLSSignature,
 }
+
+impl SyncAggregate {
+    /// Merge the best contribution of each subcommittee into the ``SyncAggregate`` of a block.
+    ///
+    /// The contributions must be for distinct subcommittees, overlapping ones would count the
+    /// signatures of their common participants twice.
+    pub fn from_contributions(contributions: &[SyncCommitteeContribution]) -> anyhow::Result<Self> {
+        let mut sync_committee_bits = BitVector::new();
+        let mut signatures = vec![];
+        let mut subcommittee_indices = HashSet::new();
+        let sync_subcommittee_size = (SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT) as usize;
+        for contribution in contributions {
+            let subcommittee_index = contribution.subcommittee_index;
+            ensure!(
+                subcommittee_indices.insert(subcommittee_index),
+                "Multiple contributions for subcommittee {subcommittee_index}"
+            );
+            for (index, participated) in contribution.aggregation_bits.iter().enumerate() {
+                if participated {
+                    let participant_index =
+                        sync_subcommittee_size * subcommittee_index as usize + index;
+                    sync_committee_bits
+                        .set(participant_index, true)
+                        .map_err(|err| {
+                            anyhow!("Invalid participant index {participant_index}: {err:?}")
+                        })?;
+                }
+            }
+            signatures.push(&contribution.signature);
+        }
+
+        Ok(Self {
+            sync_committee_bits,
+            sync_committee_signature: BLSSignature::aggregate(&signatures)?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::traits::{Signable, Verifiable};
+
+    use super::*;
+    use crate::{genesis::interop_private_key, sync_committee_message::SyncCommitteeMessage};
+
+    #[test]
+    fn contributions_merge_into_a_verifiable_aggregate() {
+        let beacon_block_root = B256::repeat_byte(7);
+        let sync_subcommittee_size = (SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT) as usize;
+        let mut contributions = vec![
+            SyncCommitteeContribution::new(1, beacon_block_root, 0),
+            SyncCommitteeContribution::new(1, beacon_block_root, 3),
+        ];
+        let mut pubkeys = vec![];
+        for (validator_index, contribution_index, index_in_subcommittee) in
+            [(0, 0, 1), (1, 0, 2), (2, 1, 0)]
+        {
+            let private_key = interop_private_key(validator_index);
+            pubkeys.push(private_key.public_key().unwrap());
+            let message = SyncCommitteeMessage {
+                slot: 1,
+                beacon_block_root,
+                validator_index,
+                signature: private_key.sign(beacon_block_root.as_slice()).unwrap(),
+            };
+            contributions[contribution_index]
+                .add_message(index_in_subcommittee, &message)
+                .unwrap();
+        }
+
+        let sync_aggregate = SyncAggregate::from_contributions(&contributions).unwrap();
+
+        assert_eq!(sync_aggregate.sync_committee_bits.num_set_bits(), 3);
+        assert!(sync_aggregate.sync_committee_bits.get(1).unwrap());
+        assert!(
+            sync_aggregate
+                .sync_committee_bits
+                .get(3 * sync_subcommittee_size)
+                .unwrap()
+        );
+        assert!(
+            sync_aggregate
+                .sync_committee_signature
+                .fast_aggregate_verify(
+                    pubkeys.iter().collect::<Vec<_>>(),
+                    beacon_block_root.as_slice()
+                )
+                .unwrap()
+        );
+        assert!(
+            SyncAggregate::from_contributions(&[
+                contributions[0].clone(),
+                contributions[0].clone()
+            ])
+            .is_err()
+        );
+    }
+}
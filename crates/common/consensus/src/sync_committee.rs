@@ -1,11 +1,13 @@
 use ream_bls::PubKey;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{FixedVector, typenum::U512};
+use ssz_types::FixedVector;
 use tree_hash_derive::TreeHash;
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+use crate::preset::SyncCommitteeSize;
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct SyncCommittee {
-    pub pubkeys: FixedVector<PubKey, U512>,
+    pub pubkeys: FixedVector<PubKey, SyncCommitteeSize>,
     pub aggregate_pubkey: PubKey,
 }
@@ -0,0 +1,80 @@
+use alloy_primitives::B256;
+use anyhow::anyhow;
+use ethereum_hashing::hash;
+use ream_bls::{BLSSignature, traits::SignatureAggregatable};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::BitVector;
+use tree_hash_derive::TreeHash;
+
+use crate::{
+    constants::{
+        SYNC_COMMITTEE_SIZE, SYNC_COMMITTEE_SUBNET_COUNT, TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE,
+    },
+    preset::SyncSubcommitteeSize,
+    sync_committee_message::SyncCommitteeMessage,
+};
+
+/// The aggregate of the sync committee messages of one subcommittee.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SyncCommitteeContribution {
+    pub slot: u64,
+    pub beacon_block_root: B256,
+    pub subcommittee_index: u64,
+    pub aggregation_bits: BitVector<SyncSubcommitteeSize>,
+    pub signature: BLSSignature,
+}
+
+impl SyncCommitteeContribution {
+    /// Create a contribution without participants.
+    pub fn new(slot: u64, beacon_block_root: B256, subcommittee_index: u64) -> Self {
+        Self {
+            slot,
+            beacon_block_root,
+            subcommittee_index,
+            aggregation_bits: BitVector::new(),
+            signature: BLSSignature::infinity(),
+        }
+    }
+
+    /// Add the ``message`` of the member at ``index_in_subcommittee`` to the contribution. A
+    /// member already included is ignored, so a message is never aggregated twice.
+    pub fn add_message(
+        &mut self,
+        index_in_subcommittee: usize,
+        message: &SyncCommitteeMessage,
+    ) -> anyhow::Result<()> {
+        if self
+            .aggregation_bits
+            .get(index_in_subcommittee)
+            .map_err(|err| anyhow!("Invalid subcommittee index {index_in_subcommittee}: {err:?}"))?
+        {
+            return Ok(());
+        }
+
+        self.aggregation_bits
+            .set(index_in_subcommittee, true)
+            .map_err(|err| {
+                anyhow!("Invalid subcommittee index {index_in_subcommittee}: {err:?}")
+            })?;
+        self.signature = BLSSignature::aggregate(&[&self.signature, &message.signature])?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SyncAggregatorSelectionData {
+    pub slot: u64,
+    pub subcommittee_index: u64,
+}
+
+/// Check if the selection proof ``signature`` makes its signer an aggregator of its sync
+/// subcommittee.
+pub fn is_sync_committee_aggregator(signature: &BLSSignature) -> bool {
+    let modulo = (SYNC_COMMITTEE_SIZE
+        / SYNC_COMMITTEE_SUBNET_COUNT
+        / TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE)
+        .max(1);
+    let signature_hash = hash(signature.to_bytes());
+    u64::from_le_bytes(signature_hash[..8].try_into().expect("hash is 32 bytes")) % modulo == 0
+}
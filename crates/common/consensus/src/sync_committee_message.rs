@@ -0,0 +1,14 @@
+use alloy_primitives::B256;
+use ream_bls::BLSSignature;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+/// The signature of a sync committee member over the head block root of ``slot``.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SyncCommitteeMessage {
+    pub slot: u64,
+    pub beacon_block_root: B256,
+    pub validator_index: u64,
+    pub signature: BLSSignature,
+}
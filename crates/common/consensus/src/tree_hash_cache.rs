@@ -0,0 +1,341 @@
+use std::sync::{Arc, Mutex};
+
+use alloy_primitives::B256;
+use ethereum_hashing::hash32_concat;
+use ssz_types::typenum::{U1099511627776, Unsigned};
+use tree_hash::{BYTES_PER_CHUNK, TreeHash, TreeHashType, merkle_root, mix_in_length};
+
+use crate::{
+    preset::{EpochsPerHistoricalVector, SlotsPerHistoricalRoot},
+    validator::Validator,
+};
+
+/// A Merkle tree over an SSZ list or vector which only rehashes the chunks whose values changed
+/// since the last call, and the branches above them.
+#[derive(Debug, Clone)]
+pub struct TreeHashCache<T> {
+    /// The values the tree was last computed for, only kept for composite values as comparing
+    /// them is much cheaper than computing their roots. Packed basic values are compared through
+    /// their chunks instead.
+    values: Vec<T>,
+    /// The number of values the tree was last computed for.
+    length: usize,
+    /// ``layers[0]`` holds the chunks and ``layers[depth]`` the root, missing nodes are zero
+    /// subtrees.
+    layers: Vec<Vec<B256>>,
+    zero_hashes: Vec<B256>,
+    depth: usize,
+}
+
+impl<T: TreeHash + PartialEq + Clone> TreeHashCache<T> {
+    /// Create an empty cache for a list or vector of at most ``limit`` values.
+    pub fn new(limit: usize) -> Self {
+        let chunk_limit = limit.div_ceil(Self::packing_factor());
+        let depth = chunk_limit.next_power_of_two().trailing_zeros() as usize;
+        let mut zero_hashes = vec![B256::ZERO];
+        for level in 0..depth {
+            zero_hashes.push(B256::from(hash32_concat(
+                zero_hashes[level].as_slice(),
+                zero_hashes[level].as_slice(),
+            )));
+        }
+
+        Self {
+            values: vec![],
+            length: 0,
+            layers: vec![vec![]; depth + 1],
+            zero_hashes,
+            depth,
+        }
+    }
+
+    fn packing_factor() -> usize {
+        match T::tree_hash_type() {
+            TreeHashType::Basic => T::tree_hash_packing_factor(),
+            _ => 1,
+        }
+    }
+
+    fn chunk_root(values: &[T]) -> B256 {
+        match T::tree_hash_type() {
+            TreeHashType::Basic => {
+                let mut chunk = [0; BYTES_PER_CHUNK];
+                let packed = values
+                    .iter()
+                    .flat_map(|value| value.tree_hash_packed_encoding())
+                    .collect::<Vec<_>>();
+                chunk[..packed.len()].copy_from_slice(&packed);
+                B256::from(chunk)
+            }
+            _ => values
+                .first()
+                .map(|value| value.tree_hash_root())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Return the Merkle root of ``values`` without the length mixed in, as for an SSZ vector.
+    pub fn recalculate_merkle_root(&mut self, values: &[T]) -> B256 {
+        if values.len() < self.length {
+            self.values.clear();
+            self.layers = vec![vec![]; self.depth + 1];
+        }
+        self.length = values.len();
+
+        let packing_factor = Self::packing_factor();
+        let chunk_count = values.len().div_ceil(packing_factor);
+        let cached_chunk_count = self.layers[0].len();
+        self.layers[0].resize(chunk_count, B256::ZERO);
+        let mut dirty_nodes = vec![];
+        for chunk in 0..chunk_count {
+            let start = chunk * packing_factor;
+            let end = (start + packing_factor).min(values.len());
+            match T::tree_hash_type() {
+                TreeHashType::Basic => {
+                    let chunk_root = Self::chunk_root(&values[start..end]);
+                    if chunk < cached_chunk_count && self.layers[0][chunk] == chunk_root {
+                        continue;
+                    }
+                    self.layers[0][chunk] = chunk_root;
+                }
+                _ => {
+                    if self.values.get(chunk) == Some(&values[chunk]) {
+                        continue;
+                    }
+                    match self.values.get_mut(chunk) {
+                        Some(cached) => *cached = values[chunk].clone(),
+                        None => self.values.push(values[chunk].clone()),
+                    }
+                    self.layers[0][chunk] = Self::chunk_root(&values[start..end]);
+                }
+            }
+            dirty_nodes.push(chunk);
+        }
+
+        for level in 0..self.depth {
+            let parent_count = self.layers[level].len().div_ceil(2);
+            self.layers[level + 1].resize(parent_count, B256::ZERO);
+            dirty_nodes = dirty_nodes.into_iter().map(|node| node / 2).collect();
+            dirty_nodes.dedup();
+            for &parent in &dirty_nodes {
+                let left = self.layers[level][2 * parent];
+                let right = self.layers[level]
+                    .get(2 * parent + 1)
+                    .copied()
+                    .unwrap_or(self.zero_hashes[level]);
+                self.layers[level + 1][parent] =
+                    B256::from(hash32_concat(left.as_slice(), right.as_slice()));
+            }
+        }
+
+        self.layers[self.depth]
+            .first()
+            .copied()
+            .unwrap_or(self.zero_hashes[self.depth])
+    }
+
+    /// Return the root of ``values`` with the length mixed in, as for an SSZ list.
+    pub fn recalculate_list_root(&mut self, values: &[T]) -> B256 {
+        let root = self.recalculate_merkle_root(values);
+        mix_in_length(&root, values.len())
+    }
+}
+
+/// Tree hash caches for the large fields of ``BeaconState``, the other fields are cheap to hash
+/// from scratch.
+#[derive(Debug, Clone)]
+pub struct BeaconStateTreeHashCache {
+    pub block_roots: TreeHashCache<B256>,
+    pub state_roots: TreeHashCache<B256>,
+    pub validators: TreeHashCache<Validator>,
+    pub balances: TreeHashCache<u64>,
+    pub randao_mixes: TreeHashCache<B256>,
+    pub previous_epoch_participation: TreeHashCache<u8>,
+    pub current_epoch_participation: TreeHashCache<u8>,
+    pub inactivity_scores: TreeHashCache<u64>,
+}
+
+impl Default for BeaconStateTreeHashCache {
+    fn default() -> Self {
+        Self {
+            block_roots: TreeHashCache::new(SlotsPerHistoricalRoot::to_usize()),
+            state_roots: TreeHashCache::new(SlotsPerHistoricalRoot::to_usize()),
+            validators: TreeHashCache::new(U1099511627776::to_usize()),
+            balances: TreeHashCache::new(U1099511627776::to_usize()),
+            randao_mixes: TreeHashCache::new(EpochsPerHistoricalVector::to_usize()),
+            previous_epoch_participation: TreeHashCache::new(U1099511627776::to_usize()),
+            current_epoch_participation: TreeHashCache::new(U1099511627776::to_usize()),
+            inactivity_scores: TreeHashCache::new(U1099511627776::to_usize()),
+        }
+    }
+}
+
+/// The tree hash cache of a ``BeaconState``, shared by its clones so that successive versions of
+/// the state, as in ``process_slots``, only rehash what changed.
+///
+/// The cache finds changed leaves by comparing against what it last hashed, so it stays correct
+/// when clones of a state diverge, it is only slower.
+#[derive(Debug, Clone, Default)]
+pub struct BeaconStateTreeHashCacheCell(Arc<Mutex<BeaconStateTreeHashCache>>);
+
+impl BeaconStateTreeHashCacheCell {
+    /// Run ``f`` with the cache. A cache poisoned by a panic while in use may be partially
+    /// updated, so it is reset before use.
+    pub fn with<R>(&self, f: impl FnOnce(&mut BeaconStateTreeHashCache) -> R) -> R {
+        let mut cache = self.0.lock().unwrap_or_else(|poisoned| {
+            let mut cache = poisoned.into_inner();
+            *cache = BeaconStateTreeHashCache::default();
+            self.0.clear_poison();
+            cache
+        });
+        f(&mut cache)
+    }
+}
+
+/// The cache holds no consensus data, so it doesn't affect the equality of states.
+impl PartialEq for BeaconStateTreeHashCacheCell {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// Return the root of a container from the roots of its fields.
+pub fn merkleize_field_roots(field_roots: &[B256]) -> B256 {
+    let bytes = field_roots
+        .iter()
+        .flat_map(|root| root.0)
+        .collect::<Vec<u8>>();
+    merkle_root(&bytes, field_roots.len())
+}
+
+#[cfg(test)]
+mod test {
+    use ream_bls::PubKey;
+    use ssz_types::{FixedVector, VariableList, typenum::U8192};
+
+    use super::*;
+    use crate::constants::FAR_FUTURE_EPOCH;
+
+    #[test]
+    fn list_root_matches_tree_hash() {
+        let mut cache = TreeHashCache::<u64>::new(U1099511627776::to_usize());
+        let mut balances = VariableList::<u64, U1099511627776>::from(vec![]);
+        assert_eq!(
+            cache.recalculate_list_root(&balances),
+            balances.tree_hash_root()
+        );
+
+        balances = VariableList::from((0..1000).collect::<Vec<u64>>());
+        assert_eq!(
+            cache.recalculate_list_root(&balances),
+            balances.tree_hash_root()
+        );
+
+        balances[17] = 32_000_000_000;
+        balances[999] = 1;
+        assert_eq!(
+            cache.recalculate_list_root(&balances),
+            balances.tree_hash_root()
+        );
+
+        balances = VariableList::from((0..1001).rev().collect::<Vec<u64>>());
+        assert_eq!(
+            cache.recalculate_list_root(&balances),
+            balances.tree_hash_root()
+        );
+
+        balances = VariableList::from((0..3).collect::<Vec<u64>>());
+        assert_eq!(
+            cache.recalculate_list_root(&balances),
+            balances.tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn vector_root_matches_tree_hash() {
+        let mut cache = TreeHashCache::<B256>::new(U8192::to_usize());
+        let mut block_roots = FixedVector::<B256, U8192>::from(vec![B256::ZERO; 8192]);
+        assert_eq!(
+            cache.recalculate_merkle_root(&block_roots),
+            block_roots.tree_hash_root()
+        );
+
+        block_roots[4095] = B256::repeat_byte(0xaa);
+        assert_eq!(
+            cache.recalculate_merkle_root(&block_roots),
+            block_roots.tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn packed_bytes_root_matches_tree_hash() {
+        let mut cache = TreeHashCache::<u8>::new(U1099511627776::to_usize());
+        let mut participation = VariableList::<u8, U1099511627776>::from(vec![0; 70]);
+        assert_eq!(
+            cache.recalculate_list_root(&participation),
+            participation.tree_hash_root()
+        );
+
+        participation[65] = 7;
+        participation.push(3).expect("list is not full");
+        assert_eq!(
+            cache.recalculate_list_root(&participation),
+            participation.tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn validators_root_matches_tree_hash() {
+        let mut cache = TreeHashCache::<Validator>::new(U1099511627776::to_usize());
+        let mut validators = VariableList::<Validator, U1099511627776>::from(
+            (0..5)
+                .map(|index| Validator {
+                    pubkey: PubKey::default(),
+                    withdrawal_credentials: B256::ZERO,
+                    effective_balance: index,
+                    slashed: false,
+                    activation_eligibility_epoch: 0,
+                    activation_epoch: 0,
+                    exit_epoch: FAR_FUTURE_EPOCH,
+                    withdrawable_epoch: FAR_FUTURE_EPOCH,
+                })
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            cache.recalculate_list_root(&validators),
+            validators.tree_hash_root()
+        );
+
+        validators[3].slashed = true;
+        assert_eq!(
+            cache.recalculate_list_root(&validators),
+            validators.tree_hash_root()
+        );
+
+        validators = VariableList::from(validators[..2].to_vec());
+        assert_eq!(
+            cache.recalculate_list_root(&validators),
+            validators.tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn poisoned_cell_is_reset() {
+        let cell = BeaconStateTreeHashCacheCell::default();
+        let balances = VariableList::<u64, U1099511627776>::from((0..100).collect::<Vec<u64>>());
+        let shared_cell = cell.clone();
+        let _ = std::thread::spawn(move || {
+            shared_cell.with(|cache| {
+                cache.balances.recalculate_list_root(&[1, 2, 3]);
+                panic!("poison the cache");
+            })
+        })
+        .join();
+
+        assert_eq!(
+            cell.with(|cache| cache.balances.recalculate_list_root(&balances)),
+            balances.tree_hash_root()
+        );
+        assert!(!cell.0.is_poisoned());
+    }
+}
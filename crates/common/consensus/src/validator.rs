@@ -1,10 +1,71 @@
+use std::fmt;
+
 use alloy_primitives::B256;
 use ream_bls::PubKey;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use tree_hash_derive::TreeHash;
 
-use crate::constants::{ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH, MAX_EFFECTIVE_BALANCE};
+use crate::constants::{
+    COMPOUNDING_WITHDRAWAL_PREFIX, ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH,
+    MAX_EFFECTIVE_BALANCE, MAX_EFFECTIVE_BALANCE_ELECTRA, MIN_ACTIVATION_BALANCE,
+};
+
+/// The status of a validator in the Beacon API, from its deposit being processed to its balance
+/// being withdrawn.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    /// The validator is waiting for its activation eligibility
+    PendingInitialized,
+    /// The validator is eligible and waiting for its activation
+    PendingQueued,
+    ActiveOngoing,
+    /// The validator is active and has initiated its exit
+    ActiveExiting,
+    /// The validator is active and has been slashed
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    /// The validator is withdrawable and has a balance left to withdraw
+    WithdrawalPossible,
+    WithdrawalDone,
+}
+
+impl ValidatorStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidatorStatus::PendingInitialized => "pending_initialized",
+            ValidatorStatus::PendingQueued => "pending_queued",
+            ValidatorStatus::ActiveOngoing => "active_ongoing",
+            ValidatorStatus::ActiveExiting => "active_exiting",
+            ValidatorStatus::ActiveSlashed => "active_slashed",
+            ValidatorStatus::ExitedUnslashed => "exited_unslashed",
+            ValidatorStatus::ExitedSlashed => "exited_slashed",
+            ValidatorStatus::WithdrawalPossible => "withdrawal_possible",
+            ValidatorStatus::WithdrawalDone => "withdrawal_done",
+        }
+    }
+
+    /// Return the general status, ``pending``, ``active``, ``exited`` or ``withdrawal``, the
+    /// status belongs to.
+    pub fn general_status(&self) -> &'static str {
+        match self {
+            ValidatorStatus::PendingInitialized | ValidatorStatus::PendingQueued => "pending",
+            ValidatorStatus::ActiveOngoing
+            | ValidatorStatus::ActiveExiting
+            | ValidatorStatus::ActiveSlashed => "active",
+            ValidatorStatus::ExitedUnslashed | ValidatorStatus::ExitedSlashed => "exited",
+            ValidatorStatus::WithdrawalPossible | ValidatorStatus::WithdrawalDone => "withdrawal",
+        }
+    }
+}
+
+impl fmt::Display for ValidatorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct Validator {
@@ -52,9 +113,136 @@ impl Validator {
         self.activation_epoch <= epoch && epoch < self.exit_epoch
     }
 
+    /// Return the status of ``validator`` at ``epoch``, ``balance`` being its balance.
+    pub fn status(&self, epoch: u64, balance: u64) -> ValidatorStatus {
+        if epoch < self.activation_epoch {
+            if self.activation_eligibility_epoch == FAR_FUTURE_EPOCH {
+                ValidatorStatus::PendingInitialized
+            } else {
+                ValidatorStatus::PendingQueued
+            }
+        } else if epoch < self.exit_epoch {
+            if self.slashed {
+                ValidatorStatus::ActiveSlashed
+            } else if self.exit_epoch == FAR_FUTURE_EPOCH {
+                ValidatorStatus::ActiveOngoing
+            } else {
+                ValidatorStatus::ActiveExiting
+            }
+        } else if epoch < self.withdrawable_epoch {
+            if self.slashed {
+                ValidatorStatus::ExitedSlashed
+            } else {
+                ValidatorStatus::ExitedUnslashed
+            }
+        } else if balance > 0 {
+            ValidatorStatus::WithdrawalPossible
+        } else {
+            ValidatorStatus::WithdrawalDone
+        }
+    }
+
     /// Check if ``validator`` is eligible to be placed into the activation queue.
     pub fn is_eligible_for_activation_queue(&self) -> bool {
         self.activation_eligibility_epoch == FAR_FUTURE_EPOCH
             && self.effective_balance == MAX_EFFECTIVE_BALANCE
     }
+
+    /// Check if ``validator`` has an 0x02 prefixed "compounding" withdrawal credential.
+    pub fn has_compounding_withdrawal_credential(&self) -> bool {
+        self.withdrawal_credentials[0..1] == COMPOUNDING_WITHDRAWAL_PREFIX
+    }
+
+    /// Check if ``validator`` has a 0x01 or 0x02 prefixed withdrawal credential.
+    pub fn has_execution_withdrawal_credential(&self) -> bool {
+        self.has_eth1_withdrawal_credential() || self.has_compounding_withdrawal_credential()
+    }
+
+    /// Get max effective balance for ``validator``.
+    pub fn get_max_effective_balance(&self) -> u64 {
+        if self.has_compounding_withdrawal_credential() {
+            MAX_EFFECTIVE_BALANCE_ELECTRA
+        } else {
+            MIN_ACTIVATION_BALANCE
+        }
+    }
+
+    /// Check if ``validator`` is fully withdrawable, accepting compounding withdrawal credentials.
+    pub fn is_fully_withdrawable_validator_electra(&self, balance: u64, epoch: u64) -> bool {
+        self.has_execution_withdrawal_credential()
+            && self.withdrawable_epoch <= epoch
+            && balance > 0
+    }
+
+    /// Check if ``validator`` is partially withdrawable, with respect to its max effective
+    /// balance.
+    pub fn is_partially_withdrawable_validator_electra(&self, balance: u64) -> bool {
+        let max_effective_balance = self.get_max_effective_balance();
+        self.has_execution_withdrawal_credential()
+            && self.effective_balance == max_effective_balance
+            && balance > max_effective_balance
+    }
+
+    /// Check if ``validator`` is eligible to be placed into the activation queue, which only
+    /// requires ``MIN_ACTIVATION_BALANCE`` since Electra.
+    pub fn is_eligible_for_activation_queue_electra(&self) -> bool {
+        self.activation_eligibility_epoch == FAR_FUTURE_EPOCH
+            && self.effective_balance >= MIN_ACTIVATION_BALANCE
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn status_follows_the_lifecycle_of_a_validator() {
+        let mut validator = Validator {
+            pubkey: PubKey::default(),
+            withdrawal_credentials: B256::ZERO,
+            effective_balance: MIN_ACTIVATION_BALANCE,
+            slashed: false,
+            activation_eligibility_epoch: FAR_FUTURE_EPOCH,
+            activation_epoch: FAR_FUTURE_EPOCH,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+        };
+        let balance = MIN_ACTIVATION_BALANCE;
+        assert_eq!(
+            validator.status(0, balance),
+            ValidatorStatus::PendingInitialized
+        );
+
+        validator.activation_eligibility_epoch = 1;
+        validator.activation_epoch = 6;
+        assert_eq!(validator.status(5, balance), ValidatorStatus::PendingQueued);
+        assert_eq!(validator.status(6, balance), ValidatorStatus::ActiveOngoing);
+
+        validator.exit_epoch = 100;
+        validator.withdrawable_epoch = 356;
+        assert_eq!(
+            validator.status(99, balance),
+            ValidatorStatus::ActiveExiting
+        );
+        assert_eq!(
+            validator.status(100, balance),
+            ValidatorStatus::ExitedUnslashed
+        );
+        assert_eq!(
+            validator.status(356, balance),
+            ValidatorStatus::WithdrawalPossible
+        );
+        assert_eq!(validator.status(356, 0), ValidatorStatus::WithdrawalDone);
+
+        validator.slashed = true;
+        assert_eq!(
+            validator.status(99, balance),
+            ValidatorStatus::ActiveSlashed
+        );
+        assert_eq!(
+            validator.status(100, balance),
+            ValidatorStatus::ExitedSlashed
+        );
+        assert_eq!(ValidatorStatus::ExitedSlashed.general_status(), "exited");
+    }
 }
@@ -0,0 +1,281 @@
+use alloy_primitives::B256;
+use ream_bls::BLSSignature;
+use serde::Serialize;
+use ssz::{Decode, DecodeError, Encode};
+use ssz_types::{
+    VariableList,
+    typenum::{U1, U2, U8, U16, U128},
+};
+use tree_hash::TreeHash;
+
+use super::ForkVersioned;
+use crate::{
+    altair,
+    attestation::Attestation,
+    attester_slashing::AttesterSlashing,
+    beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader},
+    bellatrix, capella, deneb, electra,
+    fork_name::ForkName,
+    fork_schedule::ForkSchedule,
+    phase0,
+    proposer_slashing::ProposerSlashing,
+    sync_aggregate::SyncAggregate,
+};
+
+/// The message of a ``SignedBeaconBlock`` is encoded after its 4 byte offset and the 96 byte
+/// signature, and starts with the slot.
+const SLOT_OFFSET: usize = 4 + 96;
+
+/// A signed beacon block from any supported fork.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum VersionedSignedBeaconBlock {
+    Phase0(phase0::beacon_block::SignedBeaconBlock),
+    Altair(altair::beacon_block::SignedBeaconBlock),
+    Bellatrix(bellatrix::beacon_block::SignedBeaconBlock),
+    Capella(capella::beacon_block::SignedBeaconBlock),
+    Deneb(deneb::beacon_block::SignedBeaconBlock),
+    Electra(electra::beacon_block::SignedBeaconBlock),
+}
+
+/// The attestations of a block, their type changed in Electra with EIP-7549.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum VersionedAttestations<'a> {
+    Base(&'a VariableList<Attestation, U128>),
+    Electra(&'a VariableList<electra::attestation::Attestation, U8>),
+}
+
+impl VersionedAttestations<'_> {
+    pub fn len(&self) -> usize {
+        match self {
+            VersionedAttestations::Base(attestations) => attestations.len(),
+            VersionedAttestations::Electra(attestations) => attestations.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The attester slashings of a block, their type changed in Electra with EIP-7549.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum VersionedAttesterSlashings<'a> {
+    Base(&'a VariableList<AttesterSlashing, U2>),
+    Electra(&'a VariableList<electra::attester_slashing::AttesterSlashing, U1>),
+}
+
+impl VersionedAttesterSlashings<'_> {
+    pub fn len(&self) -> usize {
+        match self {
+            VersionedAttesterSlashings::Base(attester_slashings) => attester_slashings.len(),
+            VersionedAttesterSlashings::Electra(attester_slashings) => attester_slashings.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+macro_rules! map_signed_beacon_block {
+    ($block:expr, $inner:ident => $body:expr) => {
+        match $block {
+            VersionedSignedBeaconBlock::Phase0($inner) => $body,
+            VersionedSignedBeaconBlock::Altair($inner) => $body,
+            VersionedSignedBeaconBlock::Bellatrix($inner) => $body,
+            VersionedSignedBeaconBlock::Capella($inner) => $body,
+            VersionedSignedBeaconBlock::Deneb($inner) => $body,
+            VersionedSignedBeaconBlock::Electra($inner) => $body,
+        }
+    };
+}
+
+impl VersionedSignedBeaconBlock {
+    /// Decode a signed beacon block, selecting its fork from the slot encoded in ``bytes`` and the
+    /// fork schedule of the network.
+    pub fn from_ssz_bytes_with_fork_schedule(
+        bytes: &[u8],
+        fork_schedule: &ForkSchedule,
+    ) -> Result<Self, DecodeError> {
+        let slot_bytes =
+            bytes
+                .get(SLOT_OFFSET..SLOT_OFFSET + 8)
+                .ok_or(DecodeError::InvalidByteLength {
+                    len: bytes.len(),
+                    expected: SLOT_OFFSET + 8,
+                })?;
+        let slot = u64::from_ssz_bytes(slot_bytes)?;
+        Self::from_ssz_bytes_for_fork(fork_schedule.fork_name_at_slot(slot), bytes)
+    }
+
+    pub fn slot(&self) -> u64 {
+        map_signed_beacon_block!(self, block => block.message.slot)
+    }
+
+    pub fn proposer_index(&self) -> u64 {
+        map_signed_beacon_block!(self, block => block.message.proposer_index)
+    }
+
+    pub fn parent_root(&self) -> B256 {
+        map_signed_beacon_block!(self, block => block.message.parent_root)
+    }
+
+    pub fn state_root(&self) -> B256 {
+        map_signed_beacon_block!(self, block => block.message.state_root)
+    }
+
+    pub fn body_root(&self) -> B256 {
+        map_signed_beacon_block!(self, block => block.message.body.tree_hash_root())
+    }
+
+    pub fn signature(&self) -> &BLSSignature {
+        map_signed_beacon_block!(self, block => &block.signature)
+    }
+
+    pub fn proposer_slashings(&self) -> &VariableList<ProposerSlashing, U16> {
+        map_signed_beacon_block!(self, block => &block.message.body.proposer_slashings)
+    }
+
+    pub fn attester_slashings(&self) -> VersionedAttesterSlashings<'_> {
+        match self {
+            VersionedSignedBeaconBlock::Electra(block) => {
+                VersionedAttesterSlashings::Electra(&block.message.body.attester_slashings)
+            }
+            VersionedSignedBeaconBlock::Phase0(block) => {
+                VersionedAttesterSlashings::Base(&block.message.body.attester_slashings)
+            }
+            VersionedSignedBeaconBlock::Altair(block) => {
+                VersionedAttesterSlashings::Base(&block.message.body.attester_slashings)
+            }
+            VersionedSignedBeaconBlock::Bellatrix(block) => {
+                VersionedAttesterSlashings::Base(&block.message.body.attester_slashings)
+            }
+            VersionedSignedBeaconBlock::Capella(block) => {
+                VersionedAttesterSlashings::Base(&block.message.body.attester_slashings)
+            }
+            VersionedSignedBeaconBlock::Deneb(block) => {
+                VersionedAttesterSlashings::Base(&block.message.body.attester_slashings)
+            }
+        }
+    }
+
+    pub fn attestations(&self) -> VersionedAttestations<'_> {
+        match self {
+            VersionedSignedBeaconBlock::Electra(block) => {
+                VersionedAttestations::Electra(&block.message.body.attestations)
+            }
+            VersionedSignedBeaconBlock::Phase0(block) => {
+                VersionedAttestations::Base(&block.message.body.attestations)
+            }
+            VersionedSignedBeaconBlock::Altair(block) => {
+                VersionedAttestations::Base(&block.message.body.attestations)
+            }
+            VersionedSignedBeaconBlock::Bellatrix(block) => {
+                VersionedAttestations::Base(&block.message.body.attestations)
+            }
+            VersionedSignedBeaconBlock::Capella(block) => {
+                VersionedAttestations::Base(&block.message.body.attestations)
+            }
+            VersionedSignedBeaconBlock::Deneb(block) => {
+                VersionedAttestations::Base(&block.message.body.attestations)
+            }
+        }
+    }
+
+    /// Return the sync aggregate of the block, blocks before Altair have none.
+    pub fn sync_aggregate(&self) -> Option<&SyncAggregate> {
+        match self {
+            VersionedSignedBeaconBlock::Phase0(_) => None,
+            VersionedSignedBeaconBlock::Altair(block) => Some(&block.message.body.sync_aggregate),
+            VersionedSignedBeaconBlock::Bellatrix(block) => {
+                Some(&block.message.body.sync_aggregate)
+            }
+            VersionedSignedBeaconBlock::Capella(block) => Some(&block.message.body.sync_aggregate),
+            VersionedSignedBeaconBlock::Deneb(block) => Some(&block.message.body.sync_aggregate),
+            VersionedSignedBeaconBlock::Electra(block) => Some(&block.message.body.sync_aggregate),
+        }
+    }
+
+    /// Return the root of the unsigned block, which identifies the block.
+    pub fn block_root(&self) -> B256 {
+        map_signed_beacon_block!(self, block => block.message.tree_hash_root())
+    }
+
+    pub fn signed_block_header(&self) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                slot: self.slot(),
+                proposer_index: self.proposer_index(),
+                parent_root: self.parent_root(),
+                state_root: self.state_root(),
+                body_root: self.body_root(),
+            },
+            signature: self.signature().clone(),
+        }
+    }
+}
+
+impl From<deneb::beacon_block::SignedBeaconBlock> for VersionedSignedBeaconBlock {
+    fn from(block: deneb::beacon_block::SignedBeaconBlock) -> Self {
+        VersionedSignedBeaconBlock::Deneb(block)
+    }
+}
+
+impl From<electra::beacon_block::SignedBeaconBlock> for VersionedSignedBeaconBlock {
+    fn from(block: electra::beacon_block::SignedBeaconBlock) -> Self {
+        VersionedSignedBeaconBlock::Electra(block)
+    }
+}
+
+impl Encode for VersionedSignedBeaconBlock {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        map_signed_beacon_block!(self, block => block.ssz_append(buf))
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        map_signed_beacon_block!(self, block => block.ssz_bytes_len())
+    }
+}
+
+impl ForkVersioned for VersionedSignedBeaconBlock {
+    fn fork_name(&self) -> ForkName {
+        match self {
+            VersionedSignedBeaconBlock::Phase0(_) => ForkName::Phase0,
+            VersionedSignedBeaconBlock::Altair(_) => ForkName::Altair,
+            VersionedSignedBeaconBlock::Bellatrix(_) => ForkName::Bellatrix,
+            VersionedSignedBeaconBlock::Capella(_) => ForkName::Capella,
+            VersionedSignedBeaconBlock::Deneb(_) => ForkName::Deneb,
+            VersionedSignedBeaconBlock::Electra(_) => ForkName::Electra,
+        }
+    }
+
+    fn from_ssz_bytes_for_fork(fork_name: ForkName, bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(match fork_name {
+            ForkName::Phase0 => VersionedSignedBeaconBlock::Phase0(
+                phase0::beacon_block::SignedBeaconBlock::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Altair => VersionedSignedBeaconBlock::Altair(
+                altair::beacon_block::SignedBeaconBlock::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Bellatrix => VersionedSignedBeaconBlock::Bellatrix(
+                bellatrix::beacon_block::SignedBeaconBlock::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Capella => VersionedSignedBeaconBlock::Capella(
+                capella::beacon_block::SignedBeaconBlock::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Deneb => VersionedSignedBeaconBlock::Deneb(
+                deneb::beacon_block::SignedBeaconBlock::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Electra => VersionedSignedBeaconBlock::Electra(
+                electra::beacon_block::SignedBeaconBlock::from_ssz_bytes(bytes)?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,237 @@
+use alloy_primitives::B256;
+use anyhow::ensure;
+use serde::Serialize;
+use ssz::{Decode, DecodeError, Encode};
+use ssz_types::{VariableList, typenum::U1099511627776};
+use tree_hash::TreeHash;
+
+use super::ForkVersioned;
+use crate::{
+    altair, bellatrix, capella,
+    checkpoint::Checkpoint,
+    constants::{EPOCHS_PER_HISTORICAL_VECTOR, SLOTS_PER_HISTORICAL_ROOT},
+    deneb, electra,
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    fork_name::ForkName,
+    fork_schedule::ForkSchedule,
+    misc::{compute_epoch_at_slot, compute_weak_subjectivity_period},
+    phase0,
+    validator::Validator,
+    withdrawal::Withdrawal,
+};
+
+/// The slot of a ``BeaconState`` is encoded after ``genesis_time`` and
+/// ``genesis_validators_root``.
+const SLOT_OFFSET: usize = 8 + 32;
+
+/// A beacon state from any supported fork.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum VersionedBeaconState {
+    Phase0(phase0::beacon_state::BeaconState),
+    Altair(altair::beacon_state::BeaconState),
+    Bellatrix(bellatrix::beacon_state::BeaconState),
+    Capella(capella::beacon_state::BeaconState),
+    Deneb(deneb::beacon_state::BeaconState),
+    Electra(electra::beacon_state::BeaconState),
+}
+
+macro_rules! map_beacon_state {
+    ($state:expr, $inner:ident => $body:expr) => {
+        match $state {
+            VersionedBeaconState::Phase0($inner) => $body,
+            VersionedBeaconState::Altair($inner) => $body,
+            VersionedBeaconState::Bellatrix($inner) => $body,
+            VersionedBeaconState::Capella($inner) => $body,
+            VersionedBeaconState::Deneb($inner) => $body,
+            VersionedBeaconState::Electra($inner) => $body,
+        }
+    };
+}
+
+impl VersionedBeaconState {
+    /// Decode a beacon state, selecting its fork from the slot encoded in ``bytes`` and the fork
+    /// schedule of the network.
+    pub fn from_ssz_bytes_with_fork_schedule(
+        bytes: &[u8],
+        fork_schedule: &ForkSchedule,
+    ) -> Result<Self, DecodeError> {
+        let slot_bytes =
+            bytes
+                .get(SLOT_OFFSET..SLOT_OFFSET + 8)
+                .ok_or(DecodeError::InvalidByteLength {
+                    len: bytes.len(),
+                    expected: SLOT_OFFSET + 8,
+                })?;
+        let slot = u64::from_ssz_bytes(slot_bytes)?;
+        Self::from_ssz_bytes_for_fork(fork_schedule.fork_name_at_slot(slot), bytes)
+    }
+
+    pub fn slot(&self) -> u64 {
+        map_beacon_state!(self, state => state.slot)
+    }
+
+    pub fn genesis_validators_root(&self) -> B256 {
+        map_beacon_state!(self, state => state.genesis_validators_root)
+    }
+
+    pub fn fork(&self) -> Fork {
+        map_beacon_state!(self, state => state.fork)
+    }
+
+    pub fn eth1_data(&self) -> Eth1Data {
+        map_beacon_state!(self, state => state.eth1_data.clone())
+    }
+
+    pub fn validators(&self) -> &VariableList<Validator, U1099511627776> {
+        map_beacon_state!(self, state => &state.validators)
+    }
+
+    pub fn balances(&self) -> &VariableList<u64, U1099511627776> {
+        map_beacon_state!(self, state => &state.balances)
+    }
+
+    pub fn previous_justified_checkpoint(&self) -> Checkpoint {
+        map_beacon_state!(self, state => state.previous_justified_checkpoint)
+    }
+
+    pub fn current_justified_checkpoint(&self) -> Checkpoint {
+        map_beacon_state!(self, state => state.current_justified_checkpoint)
+    }
+
+    pub fn finalized_checkpoint(&self) -> Checkpoint {
+        map_beacon_state!(self, state => state.finalized_checkpoint)
+    }
+
+    pub fn get_current_epoch(&self) -> u64 {
+        compute_epoch_at_slot(self.slot())
+    }
+
+    pub fn get_randao_mix(&self, epoch: u64) -> B256 {
+        map_beacon_state!(
+            self,
+            state => state.randao_mixes[(epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize]
+        )
+    }
+
+    /// Return the block root at a recent ``slot``.
+    pub fn get_block_root_at_slot(&self, slot: u64) -> anyhow::Result<B256> {
+        ensure!(
+            slot < self.slot() && self.slot() <= slot + SLOTS_PER_HISTORICAL_ROOT,
+            "slot given was outside of block_roots range"
+        );
+        Ok(map_beacon_state!(
+            self,
+            state => state.block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT) as usize]
+        ))
+    }
+
+    /// Return the withdrawals of the next payload, there are none before Capella.
+    pub fn get_expected_withdrawals(&self) -> Vec<Withdrawal> {
+        match self {
+            VersionedBeaconState::Phase0(_)
+            | VersionedBeaconState::Altair(_)
+            | VersionedBeaconState::Bellatrix(_) => vec![],
+            VersionedBeaconState::Capella(state) => state.get_expected_withdrawals(),
+            VersionedBeaconState::Deneb(state) => state.get_expected_withdrawals(),
+            VersionedBeaconState::Electra(state) => state.get_expected_withdrawals().0,
+        }
+    }
+
+    /// Return the weak subjectivity period of the state, the formula changed in Electra with
+    /// the balance based churn.
+    pub fn compute_weak_subjectivity_period(&self) -> u64 {
+        match self {
+            VersionedBeaconState::Electra(state) => state.compute_weak_subjectivity_period(),
+            _ => {
+                let current_epoch = self.get_current_epoch();
+                let active_balances = self
+                    .validators()
+                    .iter()
+                    .filter(|validator| validator.is_active_validator(current_epoch))
+                    .map(|validator| validator.effective_balance)
+                    .collect::<Vec<_>>();
+                compute_weak_subjectivity_period(
+                    active_balances.len() as u64,
+                    active_balances.iter().sum(),
+                )
+            }
+        }
+    }
+
+    /// Return ``true`` if ``current_epoch`` is within the weak subjectivity period of the state.
+    pub fn is_within_weak_subjectivity_period(&self, current_epoch: u64) -> bool {
+        current_epoch <= self.get_current_epoch() + self.compute_weak_subjectivity_period()
+    }
+
+    pub fn state_root(&self) -> B256 {
+        match self {
+            VersionedBeaconState::Deneb(state) => state.cached_tree_hash_root(),
+            VersionedBeaconState::Electra(state) => state.cached_tree_hash_root(),
+            _ => map_beacon_state!(self, state => state.tree_hash_root()),
+        }
+    }
+}
+
+impl From<deneb::beacon_state::BeaconState> for VersionedBeaconState {
+    fn from(state: deneb::beacon_state::BeaconState) -> Self {
+        VersionedBeaconState::Deneb(state)
+    }
+}
+
+impl From<electra::beacon_state::BeaconState> for VersionedBeaconState {
+    fn from(state: electra::beacon_state::BeaconState) -> Self {
+        VersionedBeaconState::Electra(state)
+    }
+}
+
+impl Encode for VersionedBeaconState {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        map_beacon_state!(self, state => state.ssz_append(buf))
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        map_beacon_state!(self, state => state.ssz_bytes_len())
+    }
+}
+
+impl ForkVersioned for VersionedBeaconState {
+    fn fork_name(&self) -> ForkName {
+        match self {
+            VersionedBeaconState::Phase0(_) => ForkName::Phase0,
+            VersionedBeaconState::Altair(_) => ForkName::Altair,
+            VersionedBeaconState::Bellatrix(_) => ForkName::Bellatrix,
+            VersionedBeaconState::Capella(_) => ForkName::Capella,
+            VersionedBeaconState::Deneb(_) => ForkName::Deneb,
+            VersionedBeaconState::Electra(_) => ForkName::Electra,
+        }
+    }
+
+    fn from_ssz_bytes_for_fork(fork_name: ForkName, bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(match fork_name {
+            ForkName::Phase0 => VersionedBeaconState::Phase0(
+                phase0::beacon_state::BeaconState::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Altair => VersionedBeaconState::Altair(
+                altair::beacon_state::BeaconState::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Bellatrix => VersionedBeaconState::Bellatrix(
+                bellatrix::beacon_state::BeaconState::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Capella => VersionedBeaconState::Capella(
+                capella::beacon_state::BeaconState::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Deneb => VersionedBeaconState::Deneb(
+                deneb::beacon_state::BeaconState::from_ssz_bytes(bytes)?,
+            ),
+            ForkName::Electra => VersionedBeaconState::Electra(
+                electra::beacon_state::BeaconState::from_ssz_bytes(bytes)?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,16 @@
+pub mod beacon_block;
+pub mod beacon_state;
+
+use ssz::{DecodeError, Encode};
+
+use crate::fork_name::ForkName;
+
+/// A value whose SSZ layout depends on the fork it belongs to.
+///
+/// The SSZ encoding of the value is the encoding of the inner fork specific type, the fork must
+/// be known from elsewhere (a tag, or the slot and fork schedule) to decode it again.
+pub trait ForkVersioned: Encode + Sized {
+    fn fork_name(&self) -> ForkName;
+
+    fn from_ssz_bytes_for_fork(fork_name: ForkName, bytes: &[u8]) -> Result<Self, DecodeError>;
+}
@@ -0,0 +1,518 @@
+use std::{collections::BTreeMap, ops::Range, sync::Arc};
+
+use alloy_primitives::{Address, B256, b256};
+use anyhow::{anyhow, ensure};
+use ream_bls::{BLSSignature, PubKey};
+use ream_consensus::{
+    chain_spec::chain_spec,
+    constants::{EPOCHS_PER_ETH1_VOTING_PERIOD, SLOTS_PER_EPOCH},
+    deposit::Deposit,
+    deposit_data::DepositData,
+    deposit_tree::DepositTree,
+    deposit_tree_snapshot::DepositTreeSnapshot,
+    electra::beacon_state::BeaconState,
+    eth_1_block::Eth1Block,
+    eth_1_data::Eth1Data,
+};
+use ssz_types::FixedVector;
+use tree_hash::TreeHash;
+
+use crate::{
+    ExecutionEngine,
+    rpc_types::{
+        eth_block::BlockNumberOrTag,
+        eth_get_logs::{Log, LogFilter},
+    },
+};
+
+/// ``keccak256("DepositEvent(bytes,bytes,bytes,bytes,bytes)")``
+pub const DEPOSIT_EVENT_TOPIC: B256 =
+    b256!("0x649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5");
+
+/// The length of the data of a ``DepositEvent``, the ABI encoding of its five ``bytes`` values.
+const DEPOSIT_EVENT_DATA_LENGTH: usize = 576;
+
+/// The number of blocks to get the deposit logs of in a single ``eth_getLogs`` request.
+const BLOCKS_PER_LOG_QUERY: u64 = 1000;
+
+/// Return the number of blocks behind the follow distance to keep, enough to hold the candidate
+/// blocks of a whole voting period assuming blocks are ``SECONDS_PER_ETH1_BLOCK`` apart.
+fn block_cache_length() -> u64 {
+    let voting_period_seconds =
+        EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH * chain_spec().seconds_per_slot;
+    chain_spec().eth1_follow_distance
+        + voting_period_seconds.div_ceil(chain_spec().seconds_per_eth1_block)
+}
+
+/// Return the index and ``DepositData`` of a ``DepositEvent`` log.
+pub fn parse_deposit_log(log: &Log) -> anyhow::Result<(u64, DepositData)> {
+    let data = &log.data;
+    ensure!(
+        data.len() == DEPOSIT_EVENT_DATA_LENGTH,
+        "Deposit log data must be {DEPOSIT_EVENT_DATA_LENGTH} bytes, got {}",
+        data.len()
+    );
+
+    // Each value is at a fixed offset after its length, as their lengths are fixed
+    let amount = u64::from_le_bytes(data[352..360].try_into()?);
+    let index = u64::from_le_bytes(data[544..552].try_into()?);
+    Ok((
+        index,
+        DepositData {
+            pubkey: PubKey {
+                inner: FixedVector::from(data[192..240].to_vec()),
+            },
+            withdrawal_credentials: B256::from_slice(&data[288..320]),
+            amount,
+            signature: BLSSignature {
+                inner: FixedVector::from(data[416..512].to_vec()),
+            },
+        },
+    ))
+}
+
+/// Follows the deposit contract of the execution chain, to vote for ``Eth1Data`` and to know the
+/// deposits which blocks have to include.
+///
+/// Only blocks at least ``ETH1_FOLLOW_DISTANCE`` behind the head are followed, so reorgs of the
+/// execution chain are not handled.
+pub struct Eth1Follower {
+    execution_engine: Arc<ExecutionEngine>,
+    deposit_contract_address: Address,
+    deposit_tree: DepositTree,
+    /// The deposits which are not finalized yet, by index
+    deposits: BTreeMap<u64, DepositData>,
+    /// The blocks which may be voted for, in ascending order
+    blocks: Vec<Eth1Block>,
+    /// The number of the next block to process the deposits of
+    next_block_number: u64,
+}
+
+impl Eth1Follower {
+    pub fn new(
+        execution_engine: Arc<ExecutionEngine>,
+        deposit_contract_address: Address,
+        deposit_contract_deploy_block: u64,
+    ) -> Self {
+        Self {
+            execution_engine,
+            deposit_contract_address,
+            deposit_tree: DepositTree::default(),
+            deposits: BTreeMap::new(),
+            blocks: vec![],
+            next_block_number: deposit_contract_deploy_block,
+        }
+    }
+
+    /// Resume following the deposit contract after the finalized block of ``snapshot``.
+    pub fn from_snapshot(
+        execution_engine: Arc<ExecutionEngine>,
+        deposit_contract_address: Address,
+        snapshot: &DepositTreeSnapshot,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            execution_engine,
+            deposit_contract_address,
+            deposit_tree: DepositTree::from_snapshot(snapshot)?,
+            deposits: BTreeMap::new(),
+            blocks: vec![],
+            next_block_number: snapshot.execution_block_height + 1,
+        })
+    }
+
+    pub fn deposit_tree(&self) -> &DepositTree {
+        &self.deposit_tree
+    }
+
+    pub fn deposits(&self) -> &BTreeMap<u64, DepositData> {
+        &self.deposits
+    }
+
+    pub fn eth1_chain(&self) -> &[Eth1Block] {
+        &self.blocks
+    }
+
+    /// Return the ``Eth1Data`` to vote for in a block on top of ``state``.
+    pub fn get_eth1_vote(&self, state: &BeaconState) -> Eth1Data {
+        state.get_eth1_vote(&self.blocks)
+    }
+
+    /// Return the deposits of ``indices`` with their proofs against ``eth1_data.deposit_root``,
+    /// the root of the first ``eth1_data.deposit_count`` deposits.
+    pub fn get_deposits(
+        &self,
+        eth1_data: &Eth1Data,
+        indices: Range<u64>,
+    ) -> anyhow::Result<Vec<Deposit>> {
+        ensure!(
+            indices.end <= eth1_data.deposit_count,
+            "Deposits {indices:?} are out of range of {} deposits",
+            eth1_data.deposit_count
+        );
+        ensure!(
+            eth1_data.deposit_count <= self.deposit_tree.deposit_count(),
+            "Deposit {} has not been followed yet",
+            eth1_data.deposit_count - 1
+        );
+
+        // Rebuild the tree as it was after ``eth1_data.deposit_count`` deposits
+        let mut deposit_tree = match self.deposit_tree.get_snapshot() {
+            Ok(snapshot) => DepositTree::from_snapshot(&snapshot)?,
+            Err(_) => DepositTree::default(),
+        };
+        ensure!(
+            indices.start >= deposit_tree.deposit_count(),
+            "Deposit {} is finalized, proofs of finalized deposits are pruned",
+            indices.start
+        );
+        for index in deposit_tree.deposit_count()..eth1_data.deposit_count {
+            deposit_tree.push_leaf(self.deposits[&index].tree_hash_root())?;
+        }
+        ensure!(
+            deposit_tree.get_root() == eth1_data.deposit_root,
+            "Deposit root {} does not match the followed deposits",
+            eth1_data.deposit_root
+        );
+
+        indices
+            .map(|index| {
+                Ok(Deposit {
+                    proof: deposit_tree.get_proof(index)?.1,
+                    data: self.deposits[&index].clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Process the deposits of the blocks which reached the follow distance since the last
+    /// update, and cache the blocks which may be voted for.
+    pub async fn update(&mut self) -> anyhow::Result<()> {
+        let latest_block = self
+            .execution_engine
+            .eth_get_block_by_number(BlockNumberOrTag::Latest)
+            .await?
+            .ok_or_else(|| anyhow!("Execution node has no latest block"))?;
+        let follow_block_number = latest_block
+            .number
+            .saturating_sub(chain_spec().eth1_follow_distance);
+        let first_cached_block_number = follow_block_number.saturating_sub(block_cache_length());
+
+        while self.next_block_number <= follow_block_number {
+            let to_block =
+                (self.next_block_number + BLOCKS_PER_LOG_QUERY - 1).min(follow_block_number);
+            let mut logs = self
+                .execution_engine
+                .eth_get_logs(LogFilter {
+                    from_block: self.next_block_number,
+                    to_block,
+                    address: self.deposit_contract_address,
+                    topics: vec![DEPOSIT_EVENT_TOPIC],
+                })
+                .await?;
+            logs.sort_by_key(|log| (log.block_number, log.log_index));
+            ensure!(
+                logs.iter()
+                    .all(|log| (self.next_block_number..=to_block).contains(&log.block_number)),
+                "Execution node returned logs outside of blocks {} to {to_block}",
+                self.next_block_number
+            );
+
+            let mut logs = logs.into_iter().peekable();
+            for block_number in self.next_block_number..=to_block {
+                let block = match block_number >= first_cached_block_number {
+                    true => Some(
+                        self.execution_engine
+                            .eth_get_block_by_number(BlockNumberOrTag::Number(block_number))
+                            .await?
+                            .ok_or_else(|| anyhow!("Execution node has no block {block_number}"))?,
+                    ),
+                    false => None,
+                };
+                let block_logs =
+                    std::iter::from_fn(|| logs.next_if(|log| log.block_number == block_number))
+                        .collect::<Vec<_>>();
+                self.process_deposit_logs(&block_logs)?;
+                if let Some(block) = block {
+                    self.blocks.push(Eth1Block {
+                        hash: block.hash,
+                        number: block.number,
+                        timestamp: block.timestamp,
+                        deposit_root: self.deposit_tree.get_root(),
+                        deposit_count: self.deposit_tree.deposit_count(),
+                    });
+                }
+                // Progress is kept per block so a failed request can be retried
+                self.next_block_number = block_number + 1;
+            }
+        }
+
+        let expired_blocks = self
+            .blocks
+            .partition_point(|block| block.number < first_cached_block_number);
+        self.blocks.drain(..expired_blocks);
+        Ok(())
+    }
+
+    /// Finalize the deposit tree at the block of ``eth1_data`` and return the snapshot to persist.
+    pub fn finalize(&mut self, eth1_data: &Eth1Data) -> anyhow::Result<DepositTreeSnapshot> {
+        let block = self
+            .blocks
+            .iter()
+            .find(|block| block.hash == eth1_data.block_hash)
+            .ok_or_else(|| anyhow!("Block {} is not cached", eth1_data.block_hash))?;
+        self.deposit_tree
+            .finalize(eth1_data.clone(), block.number)?;
+        self.deposits = self.deposits.split_off(&eth1_data.deposit_count);
+        self.deposit_tree.get_snapshot()
+    }
+
+    /// Process the deposit ``logs`` of a block as a unit, so a failure leaves none of them
+    /// behind and processing the block can be retried.
+    fn process_deposit_logs(&mut self, logs: &[Log]) -> anyhow::Result<()> {
+        if logs.is_empty() {
+            return Ok(());
+        }
+
+        let mut deposit_tree = self.deposit_tree.clone();
+        let mut deposits = vec![];
+        for log in logs {
+            let (index, deposit_data) = parse_deposit_log(log)?;
+            ensure!(
+                index == deposit_tree.deposit_count(),
+                "Expected deposit {}, got deposit {index}",
+                deposit_tree.deposit_count()
+            );
+            deposit_tree.push_leaf(deposit_data.tree_hash_root())?;
+            deposits.push((index, deposit_data));
+        }
+
+        self.deposit_tree = deposit_tree;
+        self.deposits.extend(deposits);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::io::Write;
+
+    use alloy_primitives::Bytes;
+    use ream_consensus::genesis::interop_genesis_state;
+    use serde_json::{Value, json};
+    use warp::Filter;
+
+    use super::*;
+
+    const LATEST_BLOCK_NUMBER: u64 = 6000;
+    const FIRST_BLOCK_TIMESTAMP: u64 = 1_600_000_000;
+
+    fn block_hash(number: u64) -> B256 {
+        B256::left_padding_from(&number.to_be_bytes())
+    }
+
+    fn block_timestamp(number: u64) -> u64 {
+        FIRST_BLOCK_TIMESTAMP + number * chain_spec().seconds_per_eth1_block
+    }
+
+    /// Return the deposits of the stub contract, one every 10 blocks from block 10.
+    fn stub_deposits() -> Vec<(u64, DepositData)> {
+        (0..6u8)
+            .map(|index| {
+                let deposit_data = DepositData {
+                    pubkey: PubKey {
+                        inner: FixedVector::from(vec![index + 1; 48]),
+                    },
+                    withdrawal_credentials: B256::repeat_byte(index + 1),
+                    amount: 32_000_000_000,
+                    signature: BLSSignature {
+                        inner: FixedVector::from(vec![index + 1; 96]),
+                    },
+                };
+                (10 * (index as u64 + 1), deposit_data)
+            })
+            .collect()
+    }
+
+    /// ABI encode the values of a ``DepositEvent``.
+    fn encode_deposit_event(deposit_data: &DepositData, index: u64) -> Bytes {
+        let values = [
+            deposit_data.pubkey.inner.to_vec(),
+            deposit_data.withdrawal_credentials.to_vec(),
+            deposit_data.amount.to_le_bytes().to_vec(),
+            deposit_data.signature.inner.to_vec(),
+            index.to_le_bytes().to_vec(),
+        ];
+        let mut head = vec![];
+        let mut tail = vec![];
+        for value in values {
+            head.extend(B256::left_padding_from(&((160 + tail.len()) as u64).to_be_bytes()).0);
+            tail.extend(B256::left_padding_from(&(value.len() as u64).to_be_bytes()).0);
+            tail.extend(
+                value
+                    .iter()
+                    .chain(std::iter::repeat(&0))
+                    .take(value.len().div_ceil(32) * 32),
+            );
+        }
+        head.extend(tail);
+        Bytes::from(head)
+    }
+
+    fn parse_hex(value: &Value) -> u64 {
+        u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+    }
+
+    fn handle_request(request: Value) -> Value {
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap() {
+            "eth_getBlockByNumber" => {
+                let number = match params[0].as_str().unwrap() {
+                    "latest" => LATEST_BLOCK_NUMBER,
+                    _ => parse_hex(&params[0]),
+                };
+                json!({
+                    "hash": block_hash(number),
+                    "number": format!("{number:#x}"),
+                    "timestamp": format!("{:#x}", block_timestamp(number)),
+                })
+            }
+            "eth_getLogs" => {
+                let from_block = parse_hex(&params[0]["fromBlock"]);
+                let to_block = parse_hex(&params[0]["toBlock"]);
+                let logs = stub_deposits()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, (number, _))| (from_block..=to_block).contains(number))
+                    .map(|(index, (number, deposit_data))| {
+                        json!({
+                            "address": chain_spec().deposit_contract_address,
+                            "topics": [DEPOSIT_EVENT_TOPIC],
+                            "data": encode_deposit_event(&deposit_data, index as u64),
+                            "blockHash": block_hash(number),
+                            "blockNumber": format!("{number:#x}"),
+                            "logIndex": "0x0",
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!(logs)
+            }
+            method => panic!("Unexpected method {method}"),
+        };
+        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+    }
+
+    async fn start_stub_execution_node() -> (ExecutionEngine, tempfile::NamedTempFile) {
+        let route = warp::post()
+            .and(warp::body::json())
+            .map(|request: Value| warp::reply::json(&handle_request(request)));
+        let (address, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+
+        let mut jwt_file = tempfile::NamedTempFile::new().unwrap();
+        write!(jwt_file, "0x{}", "11".repeat(32)).unwrap();
+        let execution_engine = ExecutionEngine::new(
+            jwt_file.path().to_str().unwrap(),
+            format!("http://{address}"),
+        )
+        .unwrap();
+        (execution_engine, jwt_file)
+    }
+
+    fn deposit_log(deposit_data: &DepositData, index: u64, log_index: u64) -> Log {
+        Log {
+            address: chain_spec().deposit_contract_address,
+            topics: vec![DEPOSIT_EVENT_TOPIC],
+            data: encode_deposit_event(deposit_data, index),
+            block_hash: block_hash(10),
+            block_number: 10,
+            log_index,
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_block_leaves_no_deposits_behind() {
+        let (execution_engine, _jwt_file) = start_stub_execution_node().await;
+        let mut follower = Eth1Follower::new(
+            Arc::new(execution_engine),
+            chain_spec().deposit_contract_address,
+            0,
+        );
+        let deposits = stub_deposits();
+        let mut invalid_log = deposit_log(&deposits[1].1, 1, 1);
+        invalid_log.data = Bytes::from(vec![0; 32]);
+
+        assert!(
+            follower
+                .process_deposit_logs(&[deposit_log(&deposits[0].1, 0, 0), invalid_log])
+                .is_err()
+        );
+        assert_eq!(follower.deposit_tree().deposit_count(), 0);
+        assert!(follower.deposits().is_empty());
+
+        // Retrying the block starts from its first deposit again
+        follower
+            .process_deposit_logs(&[
+                deposit_log(&deposits[0].1, 0, 0),
+                deposit_log(&deposits[1].1, 1, 1),
+            ])
+            .unwrap();
+        assert_eq!(follower.deposit_tree().deposit_count(), 2);
+        assert_eq!(follower.deposits().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn follows_deposits_and_votes_for_candidate_blocks() {
+        let (execution_engine, _jwt_file) = start_stub_execution_node().await;
+        let mut follower = Eth1Follower::new(
+            Arc::new(execution_engine),
+            chain_spec().deposit_contract_address,
+            0,
+        );
+        follower.update().await.unwrap();
+
+        let mut deposit_tree = DepositTree::default();
+        for (_, deposit_data) in stub_deposits() {
+            deposit_tree
+                .push_leaf(deposit_data.tree_hash_root())
+                .unwrap();
+        }
+        let follow_block_number = LATEST_BLOCK_NUMBER - chain_spec().eth1_follow_distance;
+        let last_block = follower.eth1_chain().last().unwrap();
+        assert_eq!(last_block.number, follow_block_number);
+        assert_eq!(last_block.deposit_root, deposit_tree.get_root());
+        assert_eq!(follower.deposits().len(), 6);
+        assert_eq!(
+            follower.eth1_chain().first().unwrap().number,
+            follow_block_number - block_cache_length()
+        );
+
+        // The voting period starts when candidates are the blocks 2048 to 4096 blocks old
+        let period_start_block = 5000;
+        let mut state = interop_genesis_state(4, block_timestamp(period_start_block)).unwrap();
+        let eth1_data_at = |number: u64| {
+            follower
+                .eth1_chain()
+                .iter()
+                .find(|block| block.number == number)
+                .unwrap()
+                .eth1_data()
+        };
+        let newest_candidate = period_start_block - chain_spec().eth1_follow_distance;
+        assert_eq!(
+            follower.get_eth1_vote(&state),
+            eth1_data_at(newest_candidate)
+        );
+
+        // The most voted candidate wins, votes for blocks which are not candidates are ignored
+        for number in [3000, 3000, 3000, 1000, 2000, 2000, 1000] {
+            state.eth1_data_votes.push(eth1_data_at(number)).unwrap();
+        }
+        assert_eq!(follower.get_eth1_vote(&state), eth1_data_at(1000));
+
+        let snapshot = follower.finalize(&eth1_data_at(1000)).unwrap();
+        assert_eq!(snapshot.execution_block_height, 1000);
+        assert_eq!(snapshot.deposit_root, deposit_tree.get_root());
+        assert!(follower.deposits().is_empty());
+    }
+}
@@ -1,7 +1,8 @@
+pub mod eth1_follower;
 pub mod rpc_types;
 pub mod utils;
 
-use alloy_primitives::{B64, B256, hex};
+use alloy_primitives::{B64, B256, Bytes, hex};
 use anyhow::anyhow;
 use async_trait::async_trait;
 use jsonwebtoken::{EncodingKey, Header, encode, get_current_timestamp};
@@ -14,10 +15,12 @@ use ream_consensus::{
 };
 use reqwest::{Client, Request};
 use rpc_types::{
+    eth_block::{BlockHeader, BlockNumberOrTag},
+    eth_get_logs::{Log, LogFilter},
     eth_syncing::EthSyncing,
     execution_payload::ExecutionPayloadV3,
     forkchoice_update::{ForkchoiceStateV1, ForkchoiceUpdateResult, PayloadAttributesV3},
-    get_payload::PayloadV3,
+    get_payload::{PayloadV3, PayloadV4},
     payload_status::{PayloadStatus, PayloadStatusV1},
 };
 use serde_json::json;
@@ -70,14 +73,27 @@ impl ExecutionEngine {
             execution_payload,
             versioned_hashes,
             parent_beacon_block_root,
+            execution_requests,
         } = new_payload_request;
-        let payload_status = self
-            .engine_new_payload_v3(
-                execution_payload.into(),
-                versioned_hashes,
-                parent_beacon_block_root,
-            )
-            .await?;
+        let payload_status = match execution_requests {
+            Some(execution_requests) => {
+                self.engine_new_payload_v4(
+                    execution_payload.into(),
+                    versioned_hashes,
+                    parent_beacon_block_root,
+                    execution_requests.get_execution_requests_list(),
+                )
+                .await?
+            }
+            None => {
+                self.engine_new_payload_v3(
+                    execution_payload.into(),
+                    versioned_hashes,
+                    parent_beacon_block_root,
+                )
+                .await?
+            }
+        };
         Ok(payload_status.status)
     }
 
@@ -108,12 +124,53 @@ impl ExecutionEngine {
             .to_result()
     }
 
+    pub async fn eth_get_block_by_number(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> anyhow::Result<Option<BlockHeader>> {
+        let request_body = JsonRpcRequest {
+            id: 1,
+            jsonrpc: "2.0".to_string(),
+            method: "eth_getBlockByNumber".to_string(),
+            params: vec![json!(block), json!(false)],
+        };
+
+        let http_post_request = self.build_request(request_body)?;
+
+        self.http_client
+            .execute(http_post_request)
+            .await?
+            .json::<JsonRpcResponse<Option<BlockHeader>>>()
+            .await?
+            .to_result()
+    }
+
+    pub async fn eth_get_logs(&self, filter: LogFilter) -> anyhow::Result<Vec<Log>> {
+        let request_body = JsonRpcRequest {
+            id: 1,
+            jsonrpc: "2.0".to_string(),
+            method: "eth_getLogs".to_string(),
+            params: vec![json!(filter)],
+        };
+
+        let http_post_request = self.build_request(request_body)?;
+
+        self.http_client
+            .execute(http_post_request)
+            .await?
+            .json::<JsonRpcResponse<Vec<Log>>>()
+            .await?
+            .to_result()
+    }
+
     pub async fn engine_exchange_capabilities(&self) -> anyhow::Result<Vec<String>> {
         let capabilities: Vec<String> = vec![
             "engine_forkchoiceUpdatedV3".to_string(),
             "engine_getBlobsV1".to_string(),
             "engine_getPayloadV3".to_string(),
+            "engine_getPayloadV4".to_string(),
             "engine_newPayloadV3".to_string(),
+            "engine_newPayloadV4".to_string(),
         ];
         let request_body = JsonRpcRequest {
             id: 1,
@@ -150,6 +207,24 @@ impl ExecutionEngine {
             .to_result()
     }
 
+    pub async fn engine_get_payload_v4(&self, payload_id: B64) -> anyhow::Result<PayloadV4> {
+        let request_body = JsonRpcRequest {
+            id: 1,
+            jsonrpc: "2.0".to_string(),
+            method: "engine_getPayloadV4".to_string(),
+            params: vec![json!(payload_id)],
+        };
+
+        let http_post_request = self.build_request(request_body)?;
+
+        self.http_client
+            .execute(http_post_request)
+            .await?
+            .json::<JsonRpcResponse<PayloadV4>>()
+            .await?
+            .to_result()
+    }
+
     pub async fn engine_new_payload_v3(
         &self,
         execution_payload: ExecutionPayloadV3,
@@ -177,6 +252,35 @@ impl ExecutionEngine {
             .to_result()
     }
 
+    pub async fn engine_new_payload_v4(
+        &self,
+        execution_payload: ExecutionPayloadV3,
+        expected_blob_versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        execution_requests: Vec<Bytes>,
+    ) -> anyhow::Result<PayloadStatusV1> {
+        let request_body = JsonRpcRequest {
+            id: 1,
+            jsonrpc: "2.0".to_string(),
+            method: "engine_newPayloadV4".to_string(),
+            params: vec![
+                json!(execution_payload),
+                json!(expected_blob_versioned_hashes),
+                json!(parent_beacon_block_root),
+                json!(execution_requests),
+            ],
+        };
+
+        let http_post_request = self.build_request(request_body)?;
+
+        self.http_client
+            .execute(http_post_request)
+            .await?
+            .json::<JsonRpcResponse<PayloadStatusV1>>()
+            .await?
+            .to_result()
+    }
+
     pub async fn engine_forkchoice_updated_v3(
         &self,
         forkchoice_state: ForkchoiceStateV1,
@@ -0,0 +1,32 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize, Serializer};
+
+/// The block parameter of ``eth_getBlockByNumber``.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockNumberOrTag {
+    Latest,
+    Number(u64),
+}
+
+impl Serialize for BlockNumberOrTag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            BlockNumberOrTag::Latest => serializer.serialize_str("latest"),
+            BlockNumberOrTag::Number(number) => serializer.serialize_str(&format!("{number:#x}")),
+        }
+    }
+}
+
+/// The fields of a block returned by ``eth_getBlockByNumber`` which the eth1 follower uses.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockHeader {
+    pub hash: B256,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub number: u64,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub timestamp: u64,
+}
@@ -0,0 +1,26 @@
+use alloy_primitives::{Address, B256, Bytes};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFilter {
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub from_block: u64,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub to_block: u64,
+    pub address: Address,
+    pub topics: Vec<B256>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+    pub block_hash: B256,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub block_number: u64,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub log_index: u64,
+}
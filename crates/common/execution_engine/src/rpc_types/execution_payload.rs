@@ -1,5 +1,8 @@
 use alloy_primitives::{Address, B256, U256};
-use ream_consensus::{deneb::execution_payload::ExecutionPayload, withdrawal::Withdrawal};
+use ream_consensus::{
+    deneb::execution_payload::ExecutionPayload, preset::MaxWithdrawalsPerPayload,
+    withdrawal::Withdrawal,
+};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
@@ -35,7 +38,7 @@ pub struct ExecutionPayloadV3 {
     pub block_hash: B256,
     #[serde(with = "list_of_hex_var_list")]
     pub transactions: VariableList<VariableList<u8, typenum::U1073741824>, typenum::U1048576>,
-    pub withdrawals: VariableList<Withdrawal, typenum::U16>,
+    pub withdrawals: VariableList<Withdrawal, MaxWithdrawalsPerPayload>,
     #[serde(with = "serde_utils::u64_hex_be")]
     pub blob_gas_used: u64,
     #[serde(with = "serde_utils::u64_hex_be")]
@@ -65,3 +68,27 @@ impl From<ExecutionPayload> for ExecutionPayloadV3 {
         }
     }
 }
+
+impl From<ExecutionPayloadV3> for ExecutionPayload {
+    fn from(value: ExecutionPayloadV3) -> Self {
+        ExecutionPayload {
+            parent_hash: value.parent_hash,
+            fee_recipient: value.fee_recipient,
+            state_root: value.state_root,
+            receipts_root: value.receipts_root,
+            logs_bloom: value.logs_bloom,
+            prev_randao: value.prev_randao,
+            block_number: value.block_number,
+            gas_limit: value.gas_limit,
+            gas_used: value.gas_used,
+            timestamp: value.timestamp,
+            extra_data: value.extra_data,
+            base_fee_per_gas: value.base_fee_per_gas,
+            block_hash: value.block_hash,
+            transactions: value.transactions,
+            withdrawals: value.withdrawals,
+            blob_gas_used: value.blob_gas_used,
+            excess_blob_gas: value.excess_blob_gas,
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use alloy_primitives::B256;
-use ream_consensus::kzg_commitment::KZGCommitment;
+use alloy_primitives::{B256, Bytes};
+use ream_consensus::{
+    execution_engine::rpc_types::get_blobs::Blob, kzg_commitment::KZGCommitment,
+    polynomial_commitments::kzg_proof::KZGProof, preset::MaxBlobCommitmentsPerBlock,
+};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{VariableList, serde_utils::list_of_hex_var_list, typenum};
+use ssz_types::VariableList;
 use tree_hash_derive::TreeHash;
 
 use super::execution_payload::ExecutionPayloadV3;
@@ -10,11 +13,9 @@ use super::execution_payload::ExecutionPayloadV3;
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 #[serde(rename_all = "camelCase")]
 pub struct BlobsBundleV1 {
-    pub blobs: VariableList<KZGCommitment, typenum::U1048576>,
-    #[serde(with = "list_of_hex_var_list")]
-    pub commitments: VariableList<VariableList<u8, typenum::U96>, typenum::U1024>,
-    #[serde(with = "list_of_hex_var_list")]
-    pub proofs: VariableList<VariableList<u8, typenum::U96>, typenum::U1024>,
+    pub commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
+    pub proofs: VariableList<KZGProof, MaxBlobCommitmentsPerBlock>,
+    pub blobs: VariableList<Blob, MaxBlobCommitmentsPerBlock>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -25,3 +26,13 @@ pub struct PayloadV3 {
     pub blobs_bundle: BlobsBundleV1,
     pub should_overide_builder: bool,
 }
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadV4 {
+    pub execution_payload: ExecutionPayloadV3,
+    pub block_value: B256,
+    pub blobs_bundle: BlobsBundleV1,
+    pub should_override_builder: bool,
+    pub execution_requests: Vec<Bytes>,
+}
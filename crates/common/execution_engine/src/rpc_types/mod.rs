@@ -1,3 +1,5 @@
+pub mod eth_block;
+pub mod eth_get_logs;
 pub mod eth_syncing;
 pub mod execution_payload;
 pub mod forkchoice_update;
@@ -1,4 +0,0 @@
-pub const PROPOSER_SCORE_BOOST: u64 = 40;
-pub const REORG_HEAD_WEIGHT_THRESHOLD: u64 = 20;
-pub const REORG_MAX_EPOCHS_SINCE_FINALIZATION: u64 = 2;
-pub const REORG_PARENT_WEIGHT_THRESHOLD: u64 = 160;
@@ -8,8 +8,10 @@ use anyhow::{anyhow, bail, ensure};
 use ream_consensus::{
     attestation::Attestation,
     attester_slashing::AttesterSlashing,
+    blob_sidecar::BlobSidecar,
+    chain_spec::chain_spec,
     checkpoint::Checkpoint,
-    constants::{GENESIS_EPOCH, INTERVALS_PER_SLOT, SECONDS_PER_SLOT},
+    constants::{DOMAIN_BEACON_PROPOSER, GENESIS_EPOCH, INTERVALS_PER_SLOT},
     deneb::{
         beacon_block::{BeaconBlock, SignedBeaconBlock},
         beacon_state::BeaconState,
@@ -17,10 +19,14 @@ use ream_consensus::{
     execution_engine::{blob_versioned_hashes::blob_versioned_hashes, engine_trait::ExecutionApi},
     fork_choice::latest_message::LatestMessage,
     kzg_commitment::KZGCommitment,
-    misc::{compute_epoch_at_slot, compute_start_slot_at_epoch},
+    misc::{
+        compute_epoch_at_slot, compute_signing_root, compute_start_slot_at_epoch,
+        compute_subnet_for_blob_sidecar,
+    },
     predicates::is_slashable_attestation_data,
+    signature_sets::SignatureSet,
 };
-use ream_polynomial_commitments::handlers::verify_blob_kzg_proof_batch;
+use ream_polynomial_commitments::handlers::{verify_blob_kzg_proof, verify_blob_kzg_proof_batch};
 use tree_hash::TreeHash;
 
 use crate::store::Store;
@@ -190,8 +196,9 @@ pub async fn on_block(
     store.block_states.insert(block_root, state.clone());
 
     // Add block timeliness to the store
-    let time_into_slot = (store.time - store.genesis_time) % SECONDS_PER_SLOT;
-    let is_before_attesting_interval = time_into_slot < SECONDS_PER_SLOT / INTERVALS_PER_SLOT;
+    let time_into_slot = (store.time - store.genesis_time) % chain_spec().seconds_per_slot;
+    let is_before_attesting_interval =
+        time_into_slot < chain_spec().seconds_per_slot / INTERVALS_PER_SLOT;
     let is_timely = store.get_current_slot() == block.slot && is_before_attesting_interval;
     store
         .block_timeliness
@@ -337,6 +344,112 @@ pub fn validate_on_attestation(
     Ok(())
 }
 
+/// Run the gossip validations of ``blob_sidecar`` received on the subnet ``subnet_id``, except
+/// for the first seen check of its ``(slot, proposer_index, index)`` which is left to the caller.
+pub fn validate_blob_sidecar(
+    store: &Store,
+    blob_sidecar: &BlobSidecar,
+    subnet_id: u64,
+) -> anyhow::Result<()> {
+    let block_header = &blob_sidecar.signed_block_header.message;
+
+    // The sidecar's index must be consistent with the maximum number of blobs per block
+    ensure!(
+        blob_sidecar.index
+            < chain_spec().max_blobs_per_block_at_epoch(compute_epoch_at_slot(block_header.slot)),
+        "Blob index {} is out of range",
+        blob_sidecar.index
+    );
+    // The sidecar must be gossiped on the subnet of its index
+    ensure!(
+        compute_subnet_for_blob_sidecar(blob_sidecar.index, block_header.slot) == subnet_id,
+        "Blob sidecar {} doesn't belong to subnet {subnet_id}",
+        blob_sidecar.index
+    );
+    // The sidecar must not be from a future slot, nor from a finalized slot
+    ensure!(
+        block_header.slot <= store.get_current_slot(),
+        "Blob sidecar is from a future slot"
+    );
+    ensure!(
+        block_header.slot > compute_start_slot_at_epoch(store.finalized_checkpoint.epoch),
+        "Blob sidecar is from a finalized slot"
+    );
+
+    // The parent of the sidecar's block must be known, and descend from the finalized checkpoint
+    let parent_block = store
+        .blocks
+        .get(&block_header.parent_root)
+        .ok_or_else(|| anyhow!("Parent block {} is unknown", block_header.parent_root))?;
+    ensure!(
+        block_header.slot > parent_block.slot,
+        "Blob sidecar is not from a higher slot than its parent block"
+    );
+    ensure!(
+        get_checkpoint_block(
+            store,
+            block_header.parent_root,
+            store.finalized_checkpoint.epoch
+        ) == store.finalized_checkpoint.root,
+        "Blob sidecar doesn't descend from the finalized checkpoint"
+    );
+
+    ensure!(
+        blob_sidecar.verify_blob_sidecar_inclusion_proof(),
+        "Invalid blob sidecar inclusion proof"
+    );
+
+    // The sidecar must be signed by the expected proposer of its block
+    let mut state = store
+        .block_states
+        .get(&block_header.parent_root)
+        .ok_or_else(|| {
+            anyhow!(
+                "State of parent block {} is unknown",
+                block_header.parent_root
+            )
+        })?
+        .clone();
+    if state.slot < block_header.slot {
+        state.process_slots(block_header.slot)?;
+    }
+    ensure!(
+        block_header.proposer_index == state.get_beacon_proposer_index()?,
+        "Blob sidecar is not from the expected proposer"
+    );
+    let proposer = state
+        .validators
+        .get(block_header.proposer_index as usize)
+        .ok_or_else(|| anyhow!("Invalid proposer index"))?;
+    let signing_root = compute_signing_root(
+        block_header.clone(),
+        state.get_domain(
+            DOMAIN_BEACON_PROPOSER,
+            Some(compute_epoch_at_slot(block_header.slot)),
+        ),
+    );
+    ensure!(
+        SignatureSet::single_pubkey(
+            blob_sidecar.signed_block_header.signature.clone(),
+            proposer.pubkey.clone(),
+            signing_root,
+        )
+        .verify()?,
+        "Invalid blob sidecar proposer signature"
+    );
+
+    ensure!(
+        verify_blob_kzg_proof(
+            &blob_sidecar.blob,
+            &blob_sidecar.kzg_commitment,
+            &blob_sidecar.kzg_proof
+        )?,
+        "Invalid blob KZG proof"
+    );
+
+    Ok(())
+}
+
 pub fn store_target_checkpoint_state(store: &mut Store, target: Checkpoint) -> anyhow::Result<()> {
     // Store target checkpoint state if not yet seen
     if let Entry::Vacant(entry) = store.checkpoint_states.entry(target) {
@@ -354,9 +467,10 @@ pub fn store_target_checkpoint_state(store: &mut Store, target: Checkpoint) -> a
 pub fn on_tick(store: &mut Store, time: u64) -> anyhow::Result<()> {
     // If the ``store.time`` falls behind, while loop catches up slot by slot
     // to ensure that every previous slot is processed with ``on_tick_per_slot``
-    let tick_slot = (time - store.genesis_time) / SECONDS_PER_SLOT;
+    let tick_slot = (time - store.genesis_time) / chain_spec().seconds_per_slot;
     while store.get_current_slot() < tick_slot {
-        let previous_time = store.genesis_time + (store.get_current_slot() + 1) * SECONDS_PER_SLOT;
+        let previous_time =
+            store.genesis_time + (store.get_current_slot() + 1) * chain_spec().seconds_per_slot;
         on_tick_per_slot(store, previous_time)?;
     }
 
@@ -450,7 +564,7 @@ pub fn get_forkchoice_store(
     };
     let proposer_boost_root = B256::ZERO;
     Ok(Store {
-        time: anchor_state.genesis_time + SECONDS_PER_SLOT * anchor_state.slot,
+        time: anchor_state.genesis_time + chain_spec().seconds_per_slot * anchor_state.slot,
         genesis_time: anchor_state.genesis_time,
         justified_checkpoint,
         finalized_checkpoint,
@@ -474,3 +588,219 @@ pub fn get_forkchoice_store(
             .collect(),
     })
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::fixed_bytes;
+    use ream_bls::traits::Signable;
+    use ream_consensus::{
+        beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader},
+        blob_sidecar::compute_kzg_commitment_inclusion_proof,
+        deneb::{beacon_block_body::BeaconBlockBody, execution_payload::ExecutionPayload},
+        electra,
+        eth_1_data::Eth1Data,
+        execution_engine::rpc_types::get_blobs::Blob,
+        genesis::{interop_genesis_state, interop_private_key},
+        polynomial_commitments::kzg_proof::KZGProof,
+    };
+    use ssz_types::VariableList;
+
+    use super::*;
+
+    /// The compressed point at infinity, the commitment and proof of the zero blob.
+    const POINT_AT_INFINITY: [u8; 48] = {
+        let mut point = [0; 48];
+        point[0] = 0xc0;
+        point
+    };
+
+    /// Return the Deneb ``BeaconState`` with the fields of ``state``, as held by the ``Store``.
+    fn deneb_state(state: electra::beacon_state::BeaconState) -> BeaconState {
+        BeaconState {
+            genesis_time: state.genesis_time,
+            genesis_validators_root: state.genesis_validators_root,
+            slot: state.slot,
+            fork: state.fork,
+            latest_block_header: state.latest_block_header,
+            block_roots: state.block_roots,
+            state_roots: state.state_roots,
+            historical_roots: state.historical_roots,
+            eth1_data: state.eth1_data,
+            eth1_data_votes: state.eth1_data_votes,
+            eth1_deposit_index: state.eth1_deposit_index,
+            validators: state.validators,
+            balances: state.balances,
+            randao_mixes: state.randao_mixes,
+            slashings: state.slashings,
+            previous_epoch_participation: state.previous_epoch_participation,
+            current_epoch_participation: state.current_epoch_participation,
+            justification_bits: state.justification_bits,
+            previous_justified_checkpoint: state.previous_justified_checkpoint,
+            current_justified_checkpoint: state.current_justified_checkpoint,
+            finalized_checkpoint: state.finalized_checkpoint,
+            inactivity_scores: state.inactivity_scores,
+            current_sync_committee: state.current_sync_committee,
+            next_sync_committee: state.next_sync_committee,
+            latest_execution_payload_header: state.latest_execution_payload_header,
+            next_withdrawal_index: state.next_withdrawal_index,
+            next_withdrawal_validator_index: state.next_withdrawal_validator_index,
+            historical_summaries: state.historical_summaries,
+            tree_hash_cache: Default::default(),
+        }
+    }
+
+    /// Return a ``Store`` at slot 1 anchored at the interop genesis, and the sidecar of a zero blob
+    /// of a block of slot 1 on top of the anchor.
+    fn store_and_blob_sidecar() -> (Store, BlobSidecar) {
+        let anchor_state = deneb_state(interop_genesis_state(64, 0).unwrap());
+        let mut anchor_header = anchor_state.latest_block_header.clone();
+        anchor_header.state_root = anchor_state.tree_hash_root();
+        let anchor_root = anchor_header.tree_hash_root();
+        let anchor_checkpoint = Checkpoint {
+            epoch: 0,
+            root: anchor_root,
+        };
+        let anchor_block = BeaconBlock {
+            slot: 0,
+            proposer_index: 0,
+            parent_root: B256::ZERO,
+            state_root: anchor_header.state_root,
+            body: BeaconBlockBody {
+                randao_reveal: Default::default(),
+                eth1_data: Eth1Data::default(),
+                graffiti: B256::ZERO,
+                proposer_slashings: VariableList::default(),
+                attester_slashings: VariableList::default(),
+                attestations: VariableList::default(),
+                deposits: VariableList::default(),
+                voluntary_exits: VariableList::default(),
+                sync_aggregate: Default::default(),
+                execution_payload: ExecutionPayload::default(),
+                bls_to_execution_changes: VariableList::default(),
+                blob_kzg_commitments: VariableList::default(),
+            },
+        };
+
+        let body = electra::beacon_block_body::BeaconBlockBody {
+            blob_kzg_commitments: VariableList::from(vec![KZGCommitment(POINT_AT_INFINITY)]),
+            ..Default::default()
+        };
+        let mut state = anchor_state.clone();
+        state.process_slots(1).unwrap();
+        let blob_sidecar = BlobSidecar {
+            index: 0,
+            blob: Blob::ZERO,
+            kzg_commitment: KZGCommitment(POINT_AT_INFINITY),
+            kzg_proof: KZGProof::from(POINT_AT_INFINITY),
+            signed_block_header: sign_block_header(
+                &state,
+                BeaconBlockHeader {
+                    slot: 1,
+                    proposer_index: state.get_beacon_proposer_index().unwrap(),
+                    parent_root: anchor_root,
+                    state_root: B256::ZERO,
+                    body_root: body.tree_hash_root(),
+                },
+            ),
+            kzg_commitment_inclusion_proof: compute_kzg_commitment_inclusion_proof(&body, 0)
+                .unwrap(),
+        };
+
+        let store = Store {
+            time: chain_spec().seconds_per_slot,
+            genesis_time: 0,
+            justified_checkpoint: anchor_checkpoint,
+            finalized_checkpoint: anchor_checkpoint,
+            unrealized_justified_checkpoint: anchor_checkpoint,
+            unrealized_finalized_checkpoint: anchor_checkpoint,
+            proposer_boost_root: B256::ZERO,
+            equivocating_indices: vec![],
+            blocks: HashMap::from_iter([(anchor_root, anchor_block)]),
+            block_states: HashMap::from_iter([(anchor_root, anchor_state)]),
+            block_timeliness: HashMap::default(),
+            checkpoint_states: HashMap::default(),
+            latest_messages: HashMap::default(),
+            unrealized_justifications: HashMap::from_iter([(anchor_root, anchor_checkpoint)]),
+        };
+        (store, blob_sidecar)
+    }
+
+    fn sign_block_header(
+        state: &BeaconState,
+        block_header: BeaconBlockHeader,
+    ) -> SignedBeaconBlockHeader {
+        let signing_root = compute_signing_root(
+            block_header.clone(),
+            state.get_domain(DOMAIN_BEACON_PROPOSER, Some(0)),
+        );
+        SignedBeaconBlockHeader {
+            signature: interop_private_key(block_header.proposer_index)
+                .sign(signing_root.as_slice())
+                .unwrap(),
+            message: block_header,
+        }
+    }
+
+    #[test]
+    fn accepts_a_valid_blob_sidecar() {
+        let (store, blob_sidecar) = store_and_blob_sidecar();
+
+        validate_blob_sidecar(&store, &blob_sidecar, 0).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_blob_sidecar_on_another_subnet() {
+        let (store, mut blob_sidecar) = store_and_blob_sidecar();
+
+        assert!(validate_blob_sidecar(&store, &blob_sidecar, 1).is_err());
+
+        // Deneb has 6 blob sidecar subnets and blobs per block
+        blob_sidecar.index = 7;
+        assert!(
+            validate_blob_sidecar(&store, &blob_sidecar, 1)
+                .unwrap_err()
+                .to_string()
+                .contains("out of range")
+        );
+        blob_sidecar.index = 5;
+        assert!(
+            validate_blob_sidecar(&store, &blob_sidecar, 5)
+                .unwrap_err()
+                .to_string()
+                .contains("inclusion proof")
+        );
+    }
+
+    #[test]
+    fn rejects_a_blob_sidecar_of_another_proposer() {
+        let (store, mut blob_sidecar) = store_and_blob_sidecar();
+        let state = &store.block_states[&blob_sidecar.signed_block_header.message.parent_root];
+        let mut block_header = blob_sidecar.signed_block_header.message.clone();
+        block_header.proposer_index = (block_header.proposer_index + 1) % 64;
+        blob_sidecar.signed_block_header = sign_block_header(state, block_header);
+
+        assert!(
+            validate_blob_sidecar(&store, &blob_sidecar, 0)
+                .unwrap_err()
+                .to_string()
+                .contains("expected proposer")
+        );
+    }
+
+    #[test]
+    fn rejects_a_blob_sidecar_with_a_bad_kzg_proof() {
+        let (store, mut blob_sidecar) = store_and_blob_sidecar();
+        // The generator of G1 doesn't prove the zero blob
+        blob_sidecar.kzg_proof = fixed_bytes!(
+            "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+        );
+
+        assert!(
+            validate_blob_sidecar(&store, &blob_sidecar, 0)
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid blob KZG proof")
+        );
+    }
+}
@@ -1,3 +1,2 @@
-pub mod constants;
 pub mod handlers;
 pub mod store;
@@ -1,10 +1,9 @@
 use alloy_primitives::{B256, map::HashMap};
 use anyhow::anyhow;
 use ream_consensus::{
+    chain_spec::chain_spec,
     checkpoint::Checkpoint,
-    constants::{
-        GENESIS_EPOCH, GENESIS_SLOT, INTERVALS_PER_SLOT, SECONDS_PER_SLOT, SLOTS_PER_EPOCH,
-    },
+    constants::{GENESIS_EPOCH, GENESIS_SLOT, INTERVALS_PER_SLOT, SLOTS_PER_EPOCH},
     deneb::{beacon_block::BeaconBlock, beacon_state::BeaconState},
     fork_choice::latest_message::LatestMessage,
     helpers::{calculate_committee_fraction, get_total_active_balance},
@@ -12,11 +11,6 @@ use ream_consensus::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::constants::{
-    PROPOSER_SCORE_BOOST, REORG_HEAD_WEIGHT_THRESHOLD, REORG_MAX_EPOCHS_SINCE_FINALIZATION,
-    REORG_PARENT_WEIGHT_THRESHOLD,
-};
-
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Store {
     pub time: u64,
@@ -50,7 +44,7 @@ impl Store {
     }
 
     pub fn get_slots_since_genesis(&self) -> u64 {
-        (self.time - self.genesis_time) / SECONDS_PER_SLOT
+        (self.time - self.genesis_time) / chain_spec().seconds_per_slot
     }
 
     pub fn get_ancestor(&self, root: B256, slot: u64) -> anyhow::Result<B256> {
@@ -119,6 +113,36 @@ impl Store {
         Ok(false)
     }
 
+    pub fn get_filtered_block_tree(&self) -> anyhow::Result<HashMap<B256, BeaconBlock>> {
+        let base = self.justified_checkpoint.root;
+        let mut blocks = HashMap::default();
+        self.filter_block_tree(base, &mut blocks)?;
+        Ok(blocks)
+    }
+
+    /// Return the root of the head block, following the heaviest child from the justified
+    /// checkpoint, ties broken by the higher root.
+    pub fn get_head(&self) -> anyhow::Result<B256> {
+        let blocks = self.get_filtered_block_tree()?;
+        let mut head = self.justified_checkpoint.root;
+        loop {
+            let mut best_child = None;
+            for (root, block) in blocks.iter() {
+                if block.parent_root != head {
+                    continue;
+                }
+                let key = (self.get_weight(*root)?, *root);
+                if best_child.is_none_or(|best| key > best) {
+                    best_child = Some(key);
+                }
+            }
+            match best_child {
+                Some((_, root)) => head = root,
+                None => return Ok(head),
+            }
+        }
+    }
+
     pub fn update_checkpoints(
         &mut self,
         justified_checkpoint: Checkpoint,
@@ -158,15 +182,15 @@ impl Store {
     }
 
     pub fn is_proposing_on_time(&self) -> bool {
-        let time_into_slot = (self.time - self.genesis_time) % SECONDS_PER_SLOT;
-        let proposer_reorg_cutoff = SECONDS_PER_SLOT / INTERVALS_PER_SLOT / 2;
+        let time_into_slot = (self.time - self.genesis_time) % chain_spec().seconds_per_slot;
+        let proposer_reorg_cutoff = chain_spec().seconds_per_slot / INTERVALS_PER_SLOT / 2;
         time_into_slot <= proposer_reorg_cutoff
     }
 
     pub fn is_finalization_ok(&self, slot: u64) -> bool {
         let epochs_since_finalization =
             compute_epoch_at_slot(slot) - self.finalized_checkpoint.epoch;
-        epochs_since_finalization <= REORG_MAX_EPOCHS_SINCE_FINALIZATION
+        epochs_since_finalization <= chain_spec().reorg_max_epochs_since_finalization
     }
 
     pub fn get_proposer_score(&self) -> anyhow::Result<u64> {
@@ -176,7 +200,7 @@ impl Store {
             .ok_or(anyhow!("Failed to find checkpoint in checkpoint states"))?;
         let committee_weight =
             get_total_active_balance(justified_checkpoint_state) / SLOTS_PER_EPOCH;
-        Ok((committee_weight * PROPOSER_SCORE_BOOST) / 100)
+        Ok((committee_weight * chain_spec().proposer_score_boost) / 100)
     }
 
     pub fn get_weight(&self, root: B256) -> anyhow::Result<u64> {
@@ -232,7 +256,7 @@ impl Store {
             .ok_or(anyhow!("Justified checkpoint must exist in the store"))?;
 
         let reorg_threshold =
-            calculate_committee_fraction(justified_state, REORG_HEAD_WEIGHT_THRESHOLD);
+            calculate_committee_fraction(justified_state, chain_spec().reorg_head_weight_threshold);
         let head_weight = self.get_weight(head_root)?;
 
         Ok(head_weight < reorg_threshold)
@@ -244,8 +268,10 @@ impl Store {
             .get(&self.justified_checkpoint)
             .ok_or(anyhow!("Justified checkpoint must exist in the store"))?;
 
-        let parent_threshold =
-            calculate_committee_fraction(justified_state, REORG_PARENT_WEIGHT_THRESHOLD);
+        let parent_threshold = calculate_committee_fraction(
+            justified_state,
+            chain_spec().reorg_parent_weight_threshold,
+        );
         let parent_weight = self.get_weight(parent_root)?;
 
         Ok(parent_weight > parent_threshold)
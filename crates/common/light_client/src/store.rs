@@ -0,0 +1,390 @@
+use std::cmp::max;
+
+use alloy_primitives::B256;
+use anyhow::ensure;
+use ream_bls::PubKey;
+use ream_consensus::{
+    constants::{
+        CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA, DOMAIN_SYNC_COMMITTEE,
+        EPOCHS_PER_SYNC_COMMITTEE_PERIOD, FINALIZED_ROOT_GINDEX_ELECTRA, GENESIS_SLOT,
+        MIN_SYNC_COMMITTEE_PARTICIPANTS, NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA, SLOTS_PER_EPOCH,
+    },
+    deneb::beacon_state::{eth_fast_aggregate_verify, is_valid_merkle_branch},
+    electra::{
+        light_client_bootstrap::LightClientBootstrap,
+        light_client_finality_update::LightClientFinalityUpdate,
+        light_client_header::LightClientHeader,
+        light_client_optimistic_update::LightClientOptimisticUpdate,
+        light_client_update::LightClientUpdate,
+    },
+    fork_schedule::ForkSchedule,
+    misc::{
+        compute_domain, compute_epoch_at_slot, compute_signing_root,
+        compute_sync_committee_period_at_slot,
+    },
+    sync_committee::SyncCommittee,
+};
+use tree_hash::TreeHash;
+
+/// The number of slots after which the best valid update is applied even without finality.
+pub const UPDATE_TIMEOUT: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+
+/// The state of a light client following the chain through the sync committees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightClientStore {
+    /// Header that is finalized
+    pub finalized_header: LightClientHeader,
+    /// Sync committees corresponding to the finalized header
+    pub current_sync_committee: SyncCommittee,
+    pub next_sync_committee: SyncCommittee,
+    /// Best available header to switch finalized head to if we see nothing else
+    pub best_valid_update: Option<LightClientUpdate>,
+    /// Most recent available reasonably-safe header
+    pub optimistic_header: LightClientHeader,
+    /// Max number of active participants in a sync committee (used to calculate safety threshold)
+    pub previous_max_active_participants: u64,
+    pub current_max_active_participants: u64,
+    /// The network the light client follows, used to verify the sync committee signatures
+    pub genesis_validators_root: B256,
+    pub fork_schedule: ForkSchedule,
+}
+
+impl LightClientStore {
+    /// Initialize the store from a ``bootstrap`` for ``trusted_block_root``,
+    /// ``initialize_light_client_store`` in the specs.
+    pub fn new(
+        trusted_block_root: B256,
+        bootstrap: &LightClientBootstrap,
+        genesis_validators_root: B256,
+        fork_schedule: ForkSchedule,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            bootstrap.header.is_valid(),
+            "Bootstrap header has an invalid execution branch"
+        );
+        ensure!(
+            bootstrap.header.beacon.tree_hash_root() == trusted_block_root,
+            "Bootstrap header does not match the trusted block root"
+        );
+        ensure!(
+            is_valid_gindex_branch(
+                bootstrap.current_sync_committee.tree_hash_root(),
+                &bootstrap.current_sync_committee_branch,
+                CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+                bootstrap.header.beacon.state_root,
+            ),
+            "Bootstrap has an invalid current sync committee branch"
+        );
+
+        Ok(Self {
+            finalized_header: bootstrap.header.clone(),
+            current_sync_committee: bootstrap.current_sync_committee.clone(),
+            next_sync_committee: SyncCommittee::default(),
+            best_valid_update: None,
+            optimistic_header: bootstrap.header.clone(),
+            previous_max_active_participants: 0,
+            current_max_active_participants: 0,
+            genesis_validators_root,
+            fork_schedule,
+        })
+    }
+
+    pub fn is_next_sync_committee_known(&self) -> bool {
+        self.next_sync_committee != SyncCommittee::default()
+    }
+
+    /// Return the number of participants an update needs to move the optimistic header.
+    pub fn get_safety_threshold(&self) -> u64 {
+        max(
+            self.previous_max_active_participants,
+            self.current_max_active_participants,
+        ) / 2
+    }
+
+    /// Check ``update`` is valid against the store at ``current_slot``,
+    /// ``validate_light_client_update`` in the specs.
+    pub fn validate_light_client_update(
+        &self,
+        update: &LightClientUpdate,
+        current_slot: u64,
+    ) -> anyhow::Result<()> {
+        // Verify sync committee has sufficient participants
+        let sync_aggregate = &update.sync_aggregate;
+        ensure!(
+            sync_aggregate.sync_committee_bits.num_set_bits() as u64
+                >= MIN_SYNC_COMMITTEE_PARTICIPANTS,
+            "Not enough sync committee participants"
+        );
+
+        // Verify update does not skip a sync committee period
+        ensure!(
+            update.attested_header.is_valid(),
+            "Attested header has an invalid execution branch"
+        );
+        let update_attested_slot = update.attested_header.beacon.slot;
+        let update_finalized_slot = update.finalized_header.beacon.slot;
+        ensure!(
+            current_slot >= update.signature_slot
+                && update.signature_slot > update_attested_slot
+                && update_attested_slot >= update_finalized_slot,
+            "Update slots are out of order"
+        );
+        let store_period = compute_sync_committee_period_at_slot(self.finalized_header.beacon.slot);
+        let update_signature_period = compute_sync_committee_period_at_slot(update.signature_slot);
+        if self.is_next_sync_committee_known() {
+            ensure!(
+                update_signature_period == store_period
+                    || update_signature_period == store_period + 1,
+                "Update is signed by an unknown sync committee"
+            );
+        } else {
+            ensure!(
+                update_signature_period == store_period,
+                "Update is signed by an unknown sync committee"
+            );
+        }
+
+        // Verify update is relevant
+        let update_attested_period = compute_sync_committee_period_at_slot(update_attested_slot);
+        let update_has_next_sync_committee = !self.is_next_sync_committee_known()
+            && update.is_sync_committee_update()
+            && update_attested_period == store_period;
+        ensure!(
+            update_attested_slot > self.finalized_header.beacon.slot
+                || update_has_next_sync_committee,
+            "Update is not relevant"
+        );
+
+        // Verify that the ``finality_branch``, if present, confirms ``finalized_header`` to match
+        // the finalized checkpoint root saved in the state of ``attested_header``. Note that the
+        // genesis finalized checkpoint root is represented as a zero hash.
+        if !update.is_finality_update() {
+            ensure!(
+                update.finalized_header == LightClientHeader::default(),
+                "Update without finality has a finalized header"
+            );
+        } else {
+            let finalized_root = if update_finalized_slot == GENESIS_SLOT {
+                ensure!(
+                    update.finalized_header == LightClientHeader::default(),
+                    "Genesis finalized header must be empty"
+                );
+                B256::ZERO
+            } else {
+                ensure!(
+                    update.finalized_header.is_valid(),
+                    "Finalized header has an invalid execution branch"
+                );
+                update.finalized_header.beacon.tree_hash_root()
+            };
+            ensure!(
+                is_valid_gindex_branch(
+                    finalized_root,
+                    &update.finality_branch,
+                    FINALIZED_ROOT_GINDEX_ELECTRA,
+                    update.attested_header.beacon.state_root,
+                ),
+                "Update has an invalid finality branch"
+            );
+        }
+
+        // Verify that the ``next_sync_committee``, if present, actually is the next sync committee
+        // saved in the state of the ``attested_header``
+        if !update.is_sync_committee_update() {
+            ensure!(
+                update.next_sync_committee == SyncCommittee::default(),
+                "Update without sync committee branch has a next sync committee"
+            );
+        } else {
+            if update_attested_period == store_period && self.is_next_sync_committee_known() {
+                ensure!(
+                    update.next_sync_committee == self.next_sync_committee,
+                    "Update next sync committee contradicts the store"
+                );
+            }
+            ensure!(
+                is_valid_gindex_branch(
+                    update.next_sync_committee.tree_hash_root(),
+                    &update.next_sync_committee_branch,
+                    NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+                    update.attested_header.beacon.state_root,
+                ),
+                "Update has an invalid next sync committee branch"
+            );
+        }
+
+        // Verify sync committee aggregate signature
+        let sync_committee = if update_signature_period == store_period {
+            &self.current_sync_committee
+        } else {
+            &self.next_sync_committee
+        };
+        let participant_pubkeys = sync_aggregate
+            .sync_committee_bits
+            .iter()
+            .zip(sync_committee.pubkeys.iter())
+            .filter_map(|(bit, pubkey)| bit.then_some(pubkey))
+            .collect::<Vec<&PubKey>>();
+        let fork_version_slot = max(update.signature_slot, 1) - 1;
+        let fork_version = self
+            .fork_schedule
+            .fork_version_at_epoch(compute_epoch_at_slot(fork_version_slot));
+        let domain = compute_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            Some(fork_version),
+            Some(self.genesis_validators_root),
+        );
+        let signing_root = compute_signing_root(update.attested_header.beacon.clone(), domain);
+        ensure!(
+            eth_fast_aggregate_verify(
+                &participant_pubkeys,
+                signing_root,
+                &sync_aggregate.sync_committee_signature,
+            )?,
+            "Invalid sync committee signature"
+        );
+
+        Ok(())
+    }
+
+    /// Move the finalized header and the sync committees forward to ``update``,
+    /// ``apply_light_client_update`` in the specs.
+    pub fn apply_light_client_update(&mut self, update: &LightClientUpdate) -> anyhow::Result<()> {
+        let store_period = compute_sync_committee_period_at_slot(self.finalized_header.beacon.slot);
+        let update_finalized_period =
+            compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot);
+        if !self.is_next_sync_committee_known() {
+            ensure!(
+                update_finalized_period == store_period,
+                "Update is finalized in another period than the store"
+            );
+            self.next_sync_committee = update.next_sync_committee.clone();
+        } else if update_finalized_period == store_period + 1 {
+            self.current_sync_committee = self.next_sync_committee.clone();
+            self.next_sync_committee = update.next_sync_committee.clone();
+            self.previous_max_active_participants = self.current_max_active_participants;
+            self.current_max_active_participants = 0;
+        }
+        if update.finalized_header.beacon.slot > self.finalized_header.beacon.slot {
+            self.finalized_header = update.finalized_header.clone();
+            if self.finalized_header.beacon.slot > self.optimistic_header.beacon.slot {
+                self.optimistic_header = self.finalized_header.clone();
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the best valid update once ``UPDATE_TIMEOUT`` slots passed without finality,
+    /// ``process_light_client_store_force_update`` in the specs.
+    pub fn process_light_client_store_force_update(
+        &mut self,
+        current_slot: u64,
+    ) -> anyhow::Result<()> {
+        if current_slot <= self.finalized_header.beacon.slot + UPDATE_TIMEOUT {
+            return Ok(());
+        }
+        if let Some(mut best_valid_update) = self.best_valid_update.take() {
+            // Forced best update when the update timeout has elapsed. Because the apply logic
+            // waits for ``finalized_header.beacon.slot`` to indicate sync committee finality, the
+            // ``attested_header`` may be treated as ``finalized_header`` in extended periods of
+            // non-finality to guarantee progression into later sync committee periods according
+            // to ``is_better_update``.
+            if best_valid_update.finalized_header.beacon.slot <= self.finalized_header.beacon.slot {
+                best_valid_update.finalized_header = best_valid_update.attested_header.clone();
+            }
+            self.apply_light_client_update(&best_valid_update)?;
+        }
+        Ok(())
+    }
+
+    /// Validate ``update`` and update the store with it, ``process_light_client_update`` in the
+    /// specs.
+    pub fn process_light_client_update(
+        &mut self,
+        update: &LightClientUpdate,
+        current_slot: u64,
+    ) -> anyhow::Result<()> {
+        self.validate_light_client_update(update, current_slot)?;
+
+        let sync_committee_bits = &update.sync_aggregate.sync_committee_bits;
+        let num_active_participants = sync_committee_bits.num_set_bits() as u64;
+
+        // Update the best update in case we have to force-update to it if the timeout elapses
+        if self
+            .best_valid_update
+            .as_ref()
+            .is_none_or(|best_valid_update| update.is_better_update(best_valid_update))
+        {
+            self.best_valid_update = Some(update.clone());
+        }
+
+        // Track the maximum number of active participants in the committee signatures
+        self.current_max_active_participants = max(
+            self.current_max_active_participants,
+            num_active_participants,
+        );
+
+        // Update the optimistic header
+        if num_active_participants > self.get_safety_threshold()
+            && update.attested_header.beacon.slot > self.optimistic_header.beacon.slot
+        {
+            self.optimistic_header = update.attested_header.clone();
+        }
+
+        // Update finalized header
+        let update_has_finalized_next_sync_committee = !self.is_next_sync_committee_known()
+            && update.is_sync_committee_update()
+            && update.is_finality_update()
+            && compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot)
+                == compute_sync_committee_period_at_slot(update.attested_header.beacon.slot);
+        if num_active_participants * 3 >= sync_committee_bits.len() as u64 * 2
+            && (update.finalized_header.beacon.slot > self.finalized_header.beacon.slot
+                || update_has_finalized_next_sync_committee)
+        {
+            // Normal update through 2/3 threshold
+            self.apply_light_client_update(update)?;
+            self.best_valid_update = None;
+        }
+
+        Ok(())
+    }
+
+    /// ``process_light_client_finality_update`` in the specs.
+    pub fn process_light_client_finality_update(
+        &mut self,
+        finality_update: &LightClientFinalityUpdate,
+        current_slot: u64,
+    ) -> anyhow::Result<()> {
+        let update = LightClientUpdate {
+            attested_header: finality_update.attested_header.clone(),
+            finalized_header: finality_update.finalized_header.clone(),
+            finality_branch: finality_update.finality_branch.clone(),
+            sync_aggregate: finality_update.sync_aggregate.clone(),
+            signature_slot: finality_update.signature_slot,
+            ..Default::default()
+        };
+        self.process_light_client_update(&update, current_slot)
+    }
+
+    /// ``process_light_client_optimistic_update`` in the specs.
+    pub fn process_light_client_optimistic_update(
+        &mut self,
+        optimistic_update: &LightClientOptimisticUpdate,
+        current_slot: u64,
+    ) -> anyhow::Result<()> {
+        let update = LightClientUpdate {
+            attested_header: optimistic_update.attested_header.clone(),
+            sync_aggregate: optimistic_update.sync_aggregate.clone(),
+            signature_slot: optimistic_update.signature_slot,
+            ..Default::default()
+        };
+        self.process_light_client_update(&update, current_slot)
+    }
+}
+
+/// Check ``branch`` proves ``leaf`` at the generalized index ``gindex`` of ``root``.
+fn is_valid_gindex_branch(leaf: B256, branch: &[B256], gindex: u64, root: B256) -> bool {
+    let depth = gindex.ilog2();
+    branch.len() == depth as usize
+        && is_valid_merkle_branch(leaf, branch, depth as u64, gindex % 2u64.pow(depth), root)
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use crate::networks::{DEV, HOLESKY, HOODI, MAINNET, NetworkSpec, SEPOLIA};
 
@@ -9,8 +9,12 @@ pub fn network_parser(network_string: &str) -> Result<Arc<NetworkSpec>, String>
         "sepolia" => Ok(SEPOLIA.clone()),
         "hoodi" => Ok(HOODI.clone()),
         "dev" => Ok(DEV.clone()),
+        _ if Path::new(network_string).is_dir() => NetworkSpec::from_dir(Path::new(network_string))
+            .map(Arc::new)
+            .map_err(|err| format!("Failed to load network directory {network_string}: {err}")),
         _ => Err(format!(
-            "Not a valid network: {network_string}, try mainnet, holesky, sepolia, hoodi, or dev"
+            "Not a valid network: {network_string}, try mainnet, holesky, sepolia, hoodi, dev, or \
+             a path to a testnet directory"
         )),
     }
 }
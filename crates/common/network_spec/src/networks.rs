@@ -1,7 +1,14 @@
-use std::sync::{Arc, LazyLock};
+use std::{
+    fs,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, LazyLock},
+};
 
-use alloy_primitives::{Address, address, b256, fixed_bytes};
-use ream_consensus::genesis::Genesis;
+use alloy_primitives::{B256, address, aliases::B32, b256, fixed_bytes};
+use anyhow::{anyhow, ensure};
+use discv5::Enr;
+use ream_consensus::{chain_spec::ChainSpec, fork_schedule::ForkSchedule, genesis::Genesis};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Network {
@@ -10,6 +17,8 @@ pub enum Network {
     Sepolia,
     Hoodi,
     Dev,
+    /// A network loaded from a testnet directory, carrying its deposit chain id.
+    Custom(u64),
 }
 
 impl Network {
@@ -20,6 +29,7 @@ impl Network {
             Network::Sepolia => 11155111,
             Network::Hoodi => 560048,
             Network::Dev => 1,
+            Network::Custom(chain_id) => *chain_id,
         }
     }
 }
@@ -28,7 +38,111 @@ impl Network {
 pub struct NetworkSpec {
     pub network: Network,
     pub genesis: Genesis,
-    pub deposit_contract_address: Address,
+    pub chain_spec: ChainSpec,
+    /// The block the deposit contract was deployed in, where the eth1 deposit log scan starts.
+    pub deposit_contract_block: u64,
+    /// Bootnodes shipped with a testnet directory, the built-in networks use the bootnodes
+    /// bundled with the p2p crate.
+    pub bootnodes: Vec<Enr>,
+}
+
+impl NetworkSpec {
+    pub fn fork_schedule(&self) -> ForkSchedule {
+        self.chain_spec.fork_schedule()
+    }
+
+    pub fn current_fork_version(&self, epoch: u64) -> B32 {
+        self.fork_schedule().fork_version_at_epoch(epoch)
+    }
+
+    /// Return the fork digest of the fork active at ``epoch`` on this network.
+    pub fn fork_digest(&self, epoch: u64) -> B32 {
+        self.fork_schedule()
+            .fork_digest_at_epoch(epoch, self.genesis.genesis_validator_root)
+    }
+
+    pub fn next_fork_version(&self, epoch: u64) -> B32 {
+        self.fork_schedule().next_fork_version_at_epoch(epoch)
+    }
+
+    pub fn next_fork_epoch(&self, epoch: u64) -> u64 {
+        self.fork_schedule().next_fork_epoch_at_epoch(epoch)
+    }
+
+    /// Load a network from a standard Ethereum testnet directory, as produced by kurtosis or the
+    /// ``eth-clients`` repositories. ``config.yaml`` and ``genesis.ssz`` are required,
+    /// ``bootstrap_nodes.txt``/``bootstrap_nodes.yaml`` and ``deposit_contract_block.txt`` are
+    /// optional.
+    pub fn from_dir(path: &Path) -> anyhow::Result<Self> {
+        let chain_spec = ChainSpec::from_file(&path.join("config.yaml"))?;
+
+        let genesis_path = path.join("genesis.ssz");
+        let genesis_state = fs::read(&genesis_path)
+            .map_err(|err| anyhow!("Failed to read {}: {err}", genesis_path.display()))?;
+        // Every fork's BeaconState starts with the fixed size ``genesis_time`` and
+        // ``genesis_validators_root`` fields, so they can be read without knowing the fork.
+        ensure!(
+            genesis_state.len() >= 40,
+            "Genesis state {} is too short",
+            genesis_path.display()
+        );
+        let genesis = Genesis {
+            genesis_time: u64::from_le_bytes(
+                genesis_state[..8]
+                    .try_into()
+                    .expect("slice is exactly 8 bytes"),
+            ),
+            genesis_validator_root: B256::from_slice(&genesis_state[8..40]),
+            genesis_fork_version: chain_spec.genesis_fork_version,
+        };
+
+        let deposit_contract_block_path = path.join("deposit_contract_block.txt");
+        let deposit_contract_block = match deposit_contract_block_path.exists() {
+            true => fs::read_to_string(&deposit_contract_block_path)?
+                .trim()
+                .parse()
+                .map_err(|err| {
+                    anyhow!(
+                        "Invalid deposit contract block in {}: {err}",
+                        deposit_contract_block_path.display()
+                    )
+                })?,
+            false => 0,
+        };
+
+        Ok(Self {
+            network: Network::Custom(chain_spec.deposit_chain_id),
+            genesis,
+            chain_spec,
+            deposit_contract_block,
+            bootnodes: read_bootnodes(path)?,
+        })
+    }
+}
+
+/// Read the bootnodes of a testnet directory, either one ENR per line in ``bootstrap_nodes.txt``
+/// or a YAML list in ``bootstrap_nodes.yaml``.
+fn read_bootnodes(path: &Path) -> anyhow::Result<Vec<Enr>> {
+    let txt_path = path.join("bootstrap_nodes.txt");
+    if txt_path.exists() {
+        return fs::read_to_string(&txt_path)?
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .map(|enr| {
+                Enr::from_str(enr)
+                    .map_err(|err| anyhow!("Invalid bootnode in {}: {err}", txt_path.display()))
+            })
+            .collect();
+    }
+
+    let yaml_path = path.join("bootstrap_nodes.yaml");
+    if yaml_path.exists() {
+        return serde_yaml::from_str(&fs::read_to_string(&yaml_path)?)
+            .map_err(|err| anyhow!("Invalid bootnodes in {}: {err}", yaml_path.display()));
+    }
+
+    Ok(vec![])
 }
 
 pub static MAINNET: LazyLock<Arc<NetworkSpec>> = LazyLock::new(|| {
@@ -41,7 +155,9 @@ pub static MAINNET: LazyLock<Arc<NetworkSpec>> = LazyLock::new(|| {
             ),
             genesis_fork_version: fixed_bytes!("0x00000000"),
         },
-        deposit_contract_address: address!("0x00000000219ab540356cBB839Cbe05303d7705Fa"),
+        chain_spec: ChainSpec::mainnet(),
+        deposit_contract_block: 11052984,
+        bootnodes: vec![],
     }
     .into()
 });
@@ -56,7 +172,29 @@ pub static HOLESKY: LazyLock<Arc<NetworkSpec>> = LazyLock::new(|| {
             ),
             genesis_fork_version: fixed_bytes!("0x01017000"),
         },
-        deposit_contract_address: address!("0x4242424242424242424242424242424242424242"),
+        chain_spec: ChainSpec {
+            config_name: "holesky".to_string(),
+            genesis_fork_version: fixed_bytes!("0x01017000"),
+            min_genesis_time: 1695902100,
+            genesis_delay: 300,
+            ejection_balance: 28_000_000_000,
+            altair_fork_version: fixed_bytes!("0x02017000"),
+            altair_fork_epoch: 0,
+            bellatrix_fork_version: fixed_bytes!("0x03017000"),
+            bellatrix_fork_epoch: 0,
+            capella_fork_version: fixed_bytes!("0x04017000"),
+            capella_fork_epoch: 256,
+            deneb_fork_version: fixed_bytes!("0x05017000"),
+            deneb_fork_epoch: 29696,
+            electra_fork_version: fixed_bytes!("0x06017000"),
+            electra_fork_epoch: 115968,
+            deposit_chain_id: 17000,
+            deposit_network_id: 17000,
+            deposit_contract_address: address!("0x4242424242424242424242424242424242424242"),
+            ..ChainSpec::mainnet()
+        },
+        deposit_contract_block: 0,
+        bootnodes: vec![],
     }
     .into()
 });
@@ -71,7 +209,29 @@ pub static SEPOLIA: LazyLock<Arc<NetworkSpec>> = LazyLock::new(|| {
             ),
             genesis_fork_version: fixed_bytes!("0x90000069"),
         },
-        deposit_contract_address: address!("0x7f02C3E3c98b133055B8B348B2Ac625669Ed295D"),
+        chain_spec: ChainSpec {
+            config_name: "sepolia".to_string(),
+            genesis_fork_version: fixed_bytes!("0x90000069"),
+            min_genesis_active_validator_count: 1300,
+            min_genesis_time: 1655647200,
+            genesis_delay: 86400,
+            altair_fork_version: fixed_bytes!("0x90000070"),
+            altair_fork_epoch: 50,
+            bellatrix_fork_version: fixed_bytes!("0x90000071"),
+            bellatrix_fork_epoch: 100,
+            capella_fork_version: fixed_bytes!("0x90000072"),
+            capella_fork_epoch: 56832,
+            deneb_fork_version: fixed_bytes!("0x90000073"),
+            deneb_fork_epoch: 132608,
+            electra_fork_version: fixed_bytes!("0x90000074"),
+            electra_fork_epoch: 222464,
+            deposit_chain_id: 11155111,
+            deposit_network_id: 11155111,
+            deposit_contract_address: address!("0x7f02C3E3c98b133055B8B348B2Ac625669Ed295D"),
+            ..ChainSpec::mainnet()
+        },
+        deposit_contract_block: 1273020,
+        bootnodes: vec![],
     }
     .into()
 });
@@ -86,11 +246,34 @@ pub static HOODI: LazyLock<Arc<NetworkSpec>> = LazyLock::new(|| {
             ),
             genesis_fork_version: fixed_bytes!("0x10000910"),
         },
-        deposit_contract_address: address!("0x00000000219ab540356cBB839Cbe05303d7705Fa"),
+        chain_spec: ChainSpec {
+            config_name: "hoodi".to_string(),
+            genesis_fork_version: fixed_bytes!("0x10000910"),
+            min_genesis_time: 1742212800,
+            genesis_delay: 600,
+            altair_fork_version: fixed_bytes!("0x20000910"),
+            altair_fork_epoch: 0,
+            bellatrix_fork_version: fixed_bytes!("0x30000910"),
+            bellatrix_fork_epoch: 0,
+            capella_fork_version: fixed_bytes!("0x40000910"),
+            capella_fork_epoch: 0,
+            deneb_fork_version: fixed_bytes!("0x50000910"),
+            deneb_fork_epoch: 0,
+            electra_fork_version: fixed_bytes!("0x60000910"),
+            electra_fork_epoch: 2048,
+            deposit_chain_id: 560048,
+            deposit_network_id: 560048,
+            deposit_contract_address: address!("0x00000000219ab540356cBB839Cbe05303d7705Fa"),
+            ..ChainSpec::mainnet()
+        },
+        deposit_contract_block: 0,
+        bootnodes: vec![],
     }
     .into()
 });
 
+/// A single node development network with every fork active from genesis, it uses the default
+/// config of the compiled preset so ``minimal`` builds can run it.
 pub static DEV: LazyLock<Arc<NetworkSpec>> = LazyLock::new(|| {
     NetworkSpec {
         network: Network::Dev,
@@ -101,7 +284,89 @@ pub static DEV: LazyLock<Arc<NetworkSpec>> = LazyLock::new(|| {
             ),
             genesis_fork_version: fixed_bytes!("0x00000000"),
         },
-        deposit_contract_address: address!("0x00000000219ab540356cBB839Cbe05303d7705Fa"),
+        chain_spec: ChainSpec {
+            config_name: "dev".to_string(),
+            genesis_fork_version: fixed_bytes!("0x00000000"),
+            altair_fork_version: fixed_bytes!("0x01000000"),
+            altair_fork_epoch: 0,
+            bellatrix_fork_version: fixed_bytes!("0x02000000"),
+            bellatrix_fork_epoch: 0,
+            capella_fork_version: fixed_bytes!("0x03000000"),
+            capella_fork_epoch: 0,
+            deneb_fork_version: fixed_bytes!("0x04000000"),
+            deneb_fork_epoch: 0,
+            electra_fork_version: fixed_bytes!("0x05000000"),
+            electra_fork_epoch: 0,
+            deposit_chain_id: 1,
+            deposit_network_id: 1,
+            deposit_contract_address: address!("0x00000000219ab540356cBB839Cbe05303d7705Fa"),
+            ..ChainSpec::default()
+        },
+        deposit_contract_block: 0,
+        bootnodes: vec![],
     }
     .into()
 });
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_consensus::preset::PRESET_BASE;
+
+    use super::*;
+
+    const BOOTNODE: &str = "enr:-Ku4QFo-9q73SspYI8cac_4kTX7yF800VXqJW4Lj3HkIkb5CMqFLxciNHePmMt4XdJzHvhrCC5ADI4D_GkAsxGJRLnQBh2F0dG5ldHOIAAAAAAAAAACEZXRoMpAhnTT-AQFwAP__________gmlkgnY0gmlwhLKAiOmJc2VjcDI1NmsxoQORcM6e19T1T9gi7jxEZjk_sjVLGFscUNqAY9obgZaxbIN1ZHCCIyk";
+
+    #[test]
+    fn loads_testnet_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("config.yaml"),
+            format!(
+                "PRESET_BASE: '{PRESET_BASE}'\nCONFIG_NAME: 'testnet'\nGENESIS_FORK_VERSION: \
+                 0x10000038\nELECTRA_FORK_EPOCH: 0\nDEPOSIT_CHAIN_ID: 3151908\n"
+            ),
+        )
+        .unwrap();
+        let mut genesis_state = 1742213400u64.to_le_bytes().to_vec();
+        genesis_state.extend_from_slice(&[0xab; 32]);
+        genesis_state.extend_from_slice(&[0; 64]);
+        fs::write(dir.path().join("genesis.ssz"), genesis_state).unwrap();
+        fs::write(
+            dir.path().join("bootstrap_nodes.txt"),
+            format!("# EF\n{BOOTNODE}\n\n"),
+        )
+        .unwrap();
+        fs::write(dir.path().join("deposit_contract_block.txt"), "42\n").unwrap();
+
+        let network_spec = NetworkSpec::from_dir(dir.path()).unwrap();
+
+        assert_eq!(network_spec.network, Network::Custom(3151908));
+        assert_eq!(network_spec.genesis.genesis_time, 1742213400);
+        assert_eq!(
+            network_spec.genesis.genesis_validator_root,
+            B256::repeat_byte(0xab)
+        );
+        assert_eq!(
+            network_spec.genesis.genesis_fork_version,
+            fixed_bytes!("0x10000038")
+        );
+        assert_eq!(network_spec.chain_spec.electra_fork_epoch, 0);
+        assert_eq!(network_spec.deposit_contract_block, 42);
+        assert_eq!(
+            network_spec.bootnodes,
+            vec![Enr::from_str(BOOTNODE).unwrap()]
+        );
+    }
+
+    #[test]
+    fn mainnet_fork_digests() {
+        assert_eq!(MAINNET.fork_digest(0), fixed_bytes!("0xb5303f2a"));
+        assert_eq!(MAINNET.fork_digest(269568), fixed_bytes!("0x6a95a1a9"));
+        assert_eq!(
+            MAINNET.next_fork_version(269568),
+            fixed_bytes!("0x05000000")
+        );
+        assert_eq!(MAINNET.next_fork_epoch(269568), 364032);
+    }
+}
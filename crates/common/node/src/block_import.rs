@@ -0,0 +1,273 @@
+use alloy_primitives::B256;
+use anyhow::{anyhow, bail};
+use ream_consensus::{
+    chain_spec::chain_spec,
+    electra::beacon_state::upgrade_to_electra,
+    execution_engine::engine_trait::ExecutionApi,
+    misc::compute_start_slot_at_epoch,
+    versioned::{
+        ForkVersioned, beacon_block::VersionedSignedBeaconBlock, beacon_state::VersionedBeaconState,
+    },
+};
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use ream_sync::light_client::store_light_client_data;
+
+/// Import ``signed_block`` on top of the post state of its parent in ``db``, and store the block,
+/// its post state and the light client data it makes available. The checkpoints of ``db`` advance
+/// with the checkpoints of the post state. Return the root of the block.
+pub async fn import_block(
+    db: &ReamDB,
+    signed_block: VersionedSignedBeaconBlock,
+    execution_engine: &impl ExecutionApi,
+) -> anyhow::Result<B256> {
+    let block_root = signed_block.block_root();
+    if db.beacon_block_provider().get(block_root)?.is_some() {
+        return Ok(block_root);
+    }
+
+    let parent_root = signed_block.parent_root();
+    let parent_state = db
+        .beacon_state_provider()
+        .get(parent_root)?
+        .ok_or_else(|| anyhow!("Parent state {parent_root} not found"))?;
+
+    let state = match (parent_state, &signed_block) {
+        (VersionedBeaconState::Deneb(mut state), VersionedSignedBeaconBlock::Deneb(block)) => {
+            state
+                .state_transition(block, true, execution_engine)
+                .await?;
+            VersionedBeaconState::Deneb(state)
+        }
+        (VersionedBeaconState::Deneb(mut state), VersionedSignedBeaconBlock::Electra(block)) => {
+            // The parent is the last block before the fork, upgrade its state at the fork epoch
+            state.process_slots(compute_start_slot_at_epoch(chain_spec().electra_fork_epoch))?;
+            let mut state = upgrade_to_electra(state)?;
+            state
+                .state_transition(block, true, execution_engine)
+                .await?;
+            VersionedBeaconState::Electra(state)
+        }
+        (VersionedBeaconState::Electra(mut state), VersionedSignedBeaconBlock::Electra(block)) => {
+            state
+                .state_transition(block, true, execution_engine)
+                .await?;
+            VersionedBeaconState::Electra(state)
+        }
+        (parent_state, signed_block) => bail!(
+            "Unable to import a {} block on a {} state",
+            signed_block.fork_name(),
+            parent_state.fork_name()
+        ),
+    };
+
+    db.beacon_block_provider()
+        .insert(block_root, signed_block.clone())?;
+    db.beacon_state_provider()
+        .insert(block_root, state.clone())?;
+    db.slot_index_provider()
+        .insert(signed_block.slot(), block_root)?;
+    store_light_client_data(db, &signed_block, &state)?;
+
+    let justified_checkpoint = state.current_justified_checkpoint();
+    if db
+        .justified_checkpoint_provider()
+        .get()?
+        .is_none_or(|checkpoint| checkpoint.epoch < justified_checkpoint.epoch)
+    {
+        db.justified_checkpoint_provider()
+            .insert(justified_checkpoint)?;
+    }
+    let finalized_checkpoint = state.finalized_checkpoint();
+    if db
+        .finalized_checkpoint_provider()
+        .get()?
+        .is_none_or(|checkpoint| checkpoint.epoch < finalized_checkpoint.epoch)
+    {
+        db.finalized_checkpoint_provider()
+            .insert(finalized_checkpoint)?;
+    }
+
+    Ok(block_root)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_bls::{BLSSignature, traits::Signable};
+    use ream_consensus::{
+        constants::{
+            DOMAIN_BEACON_PROPOSER, DOMAIN_RANDAO, DOMAIN_SYNC_COMMITTEE, GENESIS_SLOT,
+            SLOTS_PER_EPOCH,
+        },
+        deneb::execution_payload::ExecutionPayload,
+        electra::{
+            beacon_block::{BeaconBlock, SignedBeaconBlock},
+            beacon_block_body::BeaconBlockBody,
+        },
+        execution_engine::mock_engine::MockExecutionEngine,
+        genesis::{interop_genesis_state, interop_private_key},
+        misc::{compute_epoch_at_slot, compute_signing_root},
+        sync_aggregate::SyncAggregate,
+    };
+    use ssz_types::BitVector;
+    use tree_hash::TreeHash;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn importing_an_electra_block_stores_its_light_client_data() {
+        let genesis_state = interop_genesis_state(64, 0).unwrap();
+        let genesis_block = SignedBeaconBlock {
+            message: BeaconBlock {
+                slot: GENESIS_SLOT,
+                proposer_index: 0,
+                parent_root: B256::ZERO,
+                state_root: genesis_state.tree_hash_root(),
+                body: BeaconBlockBody::default(),
+            },
+            signature: BLSSignature::infinity(),
+        };
+        let genesis_root = genesis_block.message.tree_hash_root();
+
+        let db = ReamDB::new(None, true).unwrap();
+        db.beacon_block_provider()
+            .insert(
+                genesis_root,
+                VersionedSignedBeaconBlock::Electra(genesis_block),
+            )
+            .unwrap();
+        db.beacon_state_provider()
+            .insert(
+                genesis_root,
+                VersionedBeaconState::Electra(genesis_state.clone()),
+            )
+            .unwrap();
+        db.slot_index_provider()
+            .insert(GENESIS_SLOT, genesis_root)
+            .unwrap();
+
+        // The first block of an epoch is a checkpoint block, a bootstrap is stored for it
+        let slot = SLOTS_PER_EPOCH;
+        let mut slot_state = genesis_state.clone();
+        slot_state.process_slots(slot).unwrap();
+        let epoch = compute_epoch_at_slot(slot);
+        let proposer_index = slot_state.get_beacon_proposer_index().unwrap();
+        let proposer_key = interop_private_key(proposer_index);
+
+        // A single member of the sync committee signs the genesis block
+        let sync_committee_member = slot_state
+            .validators
+            .iter()
+            .position(|validator| validator.pubkey == slot_state.current_sync_committee.pubkeys[0])
+            .unwrap();
+        let mut sync_committee_bits = BitVector::new();
+        sync_committee_bits.set(0, true).unwrap();
+        let sync_committee_signature = interop_private_key(sync_committee_member as u64)
+            .sign(
+                compute_signing_root(
+                    genesis_root,
+                    slot_state
+                        .get_domain(DOMAIN_SYNC_COMMITTEE, Some(compute_epoch_at_slot(slot - 1))),
+                )
+                .as_slice(),
+            )
+            .unwrap();
+
+        let mut block = BeaconBlock {
+            slot,
+            proposer_index,
+            parent_root: genesis_root,
+            state_root: B256::ZERO,
+            body: BeaconBlockBody {
+                randao_reveal: proposer_key
+                    .sign(
+                        compute_signing_root(
+                            epoch,
+                            slot_state.get_domain(DOMAIN_RANDAO, Some(epoch)),
+                        )
+                        .as_slice(),
+                    )
+                    .unwrap(),
+                eth1_data: slot_state.eth1_data.clone(),
+                execution_payload: ExecutionPayload {
+                    prev_randao: slot_state.get_randao_mix(epoch),
+                    timestamp: slot_state.compute_timestamp_at_slot(slot),
+                    ..Default::default()
+                },
+                sync_aggregate: SyncAggregate {
+                    sync_committee_bits,
+                    sync_committee_signature,
+                },
+                ..Default::default()
+            },
+        };
+        let execution_engine = MockExecutionEngine {
+            execution_valid: true,
+        };
+        let mut post_state = slot_state.clone();
+        post_state
+            .state_transition(
+                &SignedBeaconBlock {
+                    message: block.clone(),
+                    signature: BLSSignature::infinity(),
+                },
+                false,
+                &execution_engine,
+            )
+            .await
+            .unwrap();
+        block.state_root = post_state.tree_hash_root();
+        let signature = proposer_key
+            .sign(
+                compute_signing_root(&block, slot_state.get_domain(DOMAIN_BEACON_PROPOSER, None))
+                    .as_slice(),
+            )
+            .unwrap();
+
+        let block_root = import_block(
+            &db,
+            VersionedSignedBeaconBlock::Electra(SignedBeaconBlock {
+                message: block,
+                signature,
+            }),
+            &execution_engine,
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            db.beacon_block_provider()
+                .get(block_root)
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            db.beacon_state_provider()
+                .get(block_root)
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            db.slot_index_provider().get(slot).unwrap(),
+            Some(block_root)
+        );
+        assert!(
+            db.light_client_bootstrap_provider()
+                .get(block_root)
+                .unwrap()
+                .is_some()
+        );
+        let update = db.light_client_update_provider().get(0).unwrap().unwrap();
+        assert_eq!(update.attested_header.beacon.slot, GENESIS_SLOT);
+        assert_eq!(update.signature_slot, slot);
+        assert!(
+            db.light_client_optimistic_update_provider()
+                .get()
+                .unwrap()
+                .is_some()
+        );
+    }
+}
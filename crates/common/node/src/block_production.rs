@@ -0,0 +1,551 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, B256};
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use ream_bls::BLSSignature;
+use ream_consensus::{
+    blob_sidecar::{BlobSidecar, get_blob_sidecars},
+    constants::{EPOCHS_PER_ETH1_VOTING_PERIOD, MAX_DEPOSITS, SLOTS_PER_EPOCH},
+    deposit::Deposit,
+    electra::{
+        beacon_block::{BeaconBlock, SignedBeaconBlock},
+        beacon_block_body::BeaconBlockBody,
+        beacon_state::BeaconState,
+        execution_requests::ExecutionRequests,
+    },
+    eth_1_data::Eth1Data,
+    execution_engine::{
+        engine_trait::ExecutionApi, new_payload_request::NewPayloadRequest,
+        rpc_types::get_blobs::BlobsAndProofV1,
+    },
+    signature_sets::SignatureVerifier,
+    state_transition::BlockSignatureStrategy,
+    versioned::{beacon_block::VersionedSignedBeaconBlock, beacon_state::VersionedBeaconState},
+};
+use ream_execution_engine::{
+    ExecutionEngine,
+    eth1_follower::Eth1Follower,
+    rpc_types::{
+        forkchoice_update::{ForkchoiceStateV1, PayloadAttributesV3},
+        get_payload::BlobsBundleV1,
+    },
+};
+use ream_fork_choice::store::Store;
+use ream_operation_pool::operation_pool::OperationPool;
+use ream_storage::{db::ReamDB, tables::Table};
+use ssz_types::VariableList;
+use tokio::sync::RwLock;
+use tree_hash::TreeHash;
+
+/// What the validator client of the proposer chooses about its block.
+#[derive(Debug, Clone)]
+pub struct BlockProductionParameters {
+    pub slot: u64,
+    pub randao_reveal: BLSSignature,
+    pub graffiti: B256,
+    pub suggested_fee_recipient: Address,
+}
+
+/// An unsigned block and the blobs of its ``blob_kzg_commitments``, ready to be signed by the
+/// validator client of the proposer.
+#[derive(Debug, Clone)]
+pub struct ProducedBlock {
+    pub block: BeaconBlock,
+    pub blobs_bundle: BlobsBundleV1,
+    /// The value of the payload to the fee recipient, in wei
+    pub block_value: B256,
+    /// The rewards of the proposer for the operations of the block, in gwei
+    pub consensus_block_value: u64,
+}
+
+/// The services a block is produced from, shared with the tasks which keep them up to date.
+#[derive(Clone)]
+pub struct BlockProducer {
+    pub store: Arc<RwLock<Store>>,
+    pub db: ReamDB,
+    pub execution_engine: Arc<ExecutionEngine>,
+    pub eth1_follower: Arc<RwLock<Eth1Follower>>,
+    pub operation_pool: Arc<RwLock<OperationPool>>,
+    /// The fee recipient of the payloads of the produced blocks
+    pub suggested_fee_recipient: Address,
+}
+
+impl BlockProducer {
+    /// Build the block of ``slot``, see ``produce_block``.
+    pub async fn produce_block(
+        &self,
+        slot: u64,
+        randao_reveal: BLSSignature,
+        graffiti: B256,
+    ) -> anyhow::Result<ProducedBlock> {
+        let store = self.store.read().await;
+        let eth1_follower = self.eth1_follower.read().await;
+        let operation_pool = self.operation_pool.read().await;
+        produce_block(
+            &store,
+            &self.db,
+            &self.execution_engine,
+            &eth1_follower,
+            &operation_pool,
+            BlockProductionParameters {
+                slot,
+                randao_reveal,
+                graffiti,
+                suggested_fee_recipient: self.suggested_fee_recipient,
+            },
+        )
+        .await
+    }
+}
+
+/// Accepts the payloads it is notified of, as a payload built by the execution engine doesn't
+/// need to be sent back to it for validation.
+struct BuiltPayloadEngine;
+
+#[async_trait]
+impl ExecutionApi for BuiltPayloadEngine {
+    async fn verify_and_notify_new_payload(
+        &self,
+        _new_payload_request: NewPayloadRequest,
+    ) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+
+    async fn engine_get_blobs_v1(
+        &self,
+        blob_version_hashes: Vec<B256>,
+    ) -> anyhow::Result<Vec<Option<BlobsAndProofV1>>> {
+        Ok(blob_version_hashes.into_iter().map(|_| None).collect())
+    }
+}
+
+/// Build the block of ``parameters.slot`` on top of the proposer head, with the operations of
+/// ``operation_pool`` and a payload built by ``execution_engine``.
+pub async fn produce_block(
+    store: &Store,
+    db: &ReamDB,
+    execution_engine: &ExecutionEngine,
+    eth1_follower: &Eth1Follower,
+    operation_pool: &OperationPool,
+    parameters: BlockProductionParameters,
+) -> anyhow::Result<ProducedBlock> {
+    let BlockProductionParameters {
+        slot,
+        randao_reveal,
+        graffiti,
+        suggested_fee_recipient,
+    } = parameters;
+
+    // Only a head whose proposer boost has worn off may be re-orged
+    let head_root = store.get_head()?;
+    let parent_root = match store.proposer_boost_root == head_root {
+        true => head_root,
+        false => store.get_proposer_head(head_root, slot)?,
+    };
+    let mut state = match db.beacon_state_provider().get(parent_root)? {
+        Some(VersionedBeaconState::Electra(state)) => state,
+        Some(_) => bail!("Blocks can only be produced on top of Electra states"),
+        None => bail!("State of parent block {parent_root} not found"),
+    };
+    state.process_slots(slot)?;
+    let proposer_index = state.get_beacon_proposer_index()?;
+
+    let eth1_data = eth1_follower.get_eth1_vote(&state);
+    let operations = operation_pool.get_block_operations(&state)?;
+    let deposits = get_deposits(&state, &eth1_data, eth1_follower)?;
+
+    let (withdrawals, _) = state.get_expected_withdrawals();
+    let payload_attributes = PayloadAttributesV3 {
+        timestamp: state.compute_timestamp_at_slot(slot),
+        prev_randao: state.get_randao_mix(state.get_current_epoch()),
+        suggested_fee_recipient,
+        withdrawals: VariableList::new(withdrawals)
+            .map_err(|err| anyhow!("Too many expected withdrawals: {err:?}"))?,
+        parent_beacon_block_root: parent_root,
+    };
+    let forkchoice_state = ForkchoiceStateV1 {
+        head_block_hash: state.latest_execution_payload_header.block_hash,
+        safe_block_hash: get_execution_block_hash(db, store.justified_checkpoint.root)?,
+        finalized_block_hash: get_execution_block_hash(db, store.finalized_checkpoint.root)?,
+    };
+    let payload_id = execution_engine
+        .engine_forkchoice_updated_v3(forkchoice_state, Some(payload_attributes))
+        .await?
+        .payload_id
+        .ok_or_else(|| anyhow!("Execution engine did not start building a payload"))?;
+    let payload = execution_engine.engine_get_payload_v4(payload_id).await?;
+
+    let mut block = BeaconBlock {
+        slot,
+        proposer_index,
+        parent_root,
+        state_root: B256::ZERO,
+        body: BeaconBlockBody {
+            randao_reveal,
+            eth1_data,
+            graffiti,
+            proposer_slashings: operations.proposer_slashings,
+            attester_slashings: operations.attester_slashings,
+            attestations: operations.attestations,
+            deposits: VariableList::new(deposits)
+                .map_err(|err| anyhow!("Too many deposits: {err:?}"))?,
+            voluntary_exits: operations.voluntary_exits,
+            sync_aggregate: operation_pool.get_sync_aggregate(slot - 1, parent_root)?,
+            execution_payload: payload.execution_payload.into(),
+            bls_to_execution_changes: operations.bls_to_execution_changes,
+            blob_kzg_commitments: payload.blobs_bundle.commitments.clone(),
+            execution_requests: ExecutionRequests::from_execution_requests_list(
+                &payload.execution_requests,
+            )?,
+        },
+    };
+
+    // The block is unsigned, so its signatures are left to the validator client
+    let pre_state = state.clone();
+    state
+        .process_block_with_verifier(
+            &block,
+            &BuiltPayloadEngine,
+            &mut SignatureVerifier::new(BlockSignatureStrategy::NoVerification),
+        )
+        .await?;
+    block.state_root = state.tree_hash_root();
+    let consensus_block_value = pre_state.compute_block_rewards(&block)?.total;
+
+    Ok(ProducedBlock {
+        block,
+        blobs_bundle: payload.blobs_bundle,
+        block_value: payload.block_value,
+        consensus_block_value,
+    })
+}
+
+/// Return the sidecars of the blobs of ``signed_block``, a signed ``ProducedBlock`` whose blobs
+/// are in ``blobs_bundle``.
+pub fn get_produced_blob_sidecars(
+    signed_block: &SignedBeaconBlock,
+    blobs_bundle: BlobsBundleV1,
+) -> anyhow::Result<Vec<BlobSidecar>> {
+    get_blob_sidecars(
+        signed_block,
+        blobs_bundle.blobs.to_vec(),
+        blobs_bundle.proofs.to_vec(),
+    )
+}
+
+/// Return the deposits a block on top of ``state`` voting for ``eth1_data`` must include.
+fn get_deposits(
+    state: &BeaconState,
+    eth1_data: &Eth1Data,
+    eth1_follower: &Eth1Follower,
+) -> anyhow::Result<Vec<Deposit>> {
+    // The vote of the block is applied before its deposits are processed
+    let votes = state
+        .eth1_data_votes
+        .iter()
+        .filter(|vote| *vote == eth1_data)
+        .count() as u64
+        + 1;
+    let eth1_data = match votes * 2 > EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH {
+        true => eth1_data,
+        false => &state.eth1_data,
+    };
+
+    let eth1_deposit_index_limit = eth1_data
+        .deposit_count
+        .min(state.deposit_requests_start_index);
+    if state.eth1_deposit_index >= eth1_deposit_index_limit {
+        return Ok(vec![]);
+    }
+    let end = eth1_deposit_index_limit.min(state.eth1_deposit_index + MAX_DEPOSITS);
+    eth1_follower.get_deposits(eth1_data, state.eth1_deposit_index..end)
+}
+
+/// Return the hash of the execution block of the beacon block ``block_root``, or zero if it has
+/// no payload.
+fn get_execution_block_hash(db: &ReamDB, block_root: B256) -> anyhow::Result<B256> {
+    Ok(match db.beacon_block_provider().get(block_root)? {
+        Some(VersionedSignedBeaconBlock::Bellatrix(block)) => {
+            block.message.body.execution_payload.block_hash
+        }
+        Some(VersionedSignedBeaconBlock::Capella(block)) => {
+            block.message.body.execution_payload.block_hash
+        }
+        Some(VersionedSignedBeaconBlock::Deneb(block)) => {
+            block.message.body.execution_payload.block_hash
+        }
+        Some(VersionedSignedBeaconBlock::Electra(block)) => {
+            block.message.body.execution_payload.block_hash
+        }
+        _ => B256::ZERO,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::{
+        collections::HashMap,
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use ream_bls::{PubKey, traits::Signable};
+    use ream_consensus::{
+        chain_spec::chain_spec,
+        checkpoint::Checkpoint,
+        constants::{DOMAIN_BEACON_PROPOSER, DOMAIN_RANDAO},
+        deneb::{self, execution_payload::ExecutionPayload},
+        electra::withdrawal_request::WithdrawalRequest,
+        execution_engine::rpc_types::get_blobs::Blob,
+        genesis::{interop_genesis_state, interop_private_key},
+        kzg_commitment::KZGCommitment,
+        misc::{compute_epoch_at_slot, compute_signing_root},
+        polynomial_commitments::kzg_proof::KZGProof,
+    };
+    use ream_execution_engine::rpc_types::execution_payload::ExecutionPayloadV3;
+    use serde_json::{Value, json};
+    use warp::Filter;
+
+    use super::*;
+
+    const VALIDATOR_COUNT: u64 = 64;
+
+    fn withdrawal_request() -> WithdrawalRequest {
+        WithdrawalRequest {
+            source_address: Address::repeat_byte(1),
+            validator_pubkey: PubKey::default(),
+            amount: 0,
+        }
+    }
+
+    /// Answer the requests of ``produce_block`` and ``state_transition``, building the payload
+    /// from the attributes of the last ``engine_forkchoiceUpdatedV3``.
+    fn handle_request(
+        request: Value,
+        payload_request: &Mutex<Option<(ForkchoiceStateV1, PayloadAttributesV3)>>,
+    ) -> Value {
+        let params = &request["params"];
+        let valid = json!({ "status": "VALID", "latestValidHash": null, "validationError": null });
+        let result = match request["method"].as_str().unwrap() {
+            "engine_forkchoiceUpdatedV3" => {
+                *payload_request.lock().unwrap() = Some((
+                    serde_json::from_value(params[0].clone()).unwrap(),
+                    serde_json::from_value(params[1].clone()).unwrap(),
+                ));
+                json!({ "payloadStatus": valid, "payloadId": "0x0000000000000001" })
+            }
+            "engine_getPayloadV4" => {
+                let (forkchoice_state, payload_attributes) =
+                    payload_request.lock().unwrap().take().unwrap();
+                let mut execution_payload = ExecutionPayload {
+                    parent_hash: forkchoice_state.head_block_hash,
+                    fee_recipient: payload_attributes.suggested_fee_recipient,
+                    prev_randao: payload_attributes.prev_randao,
+                    block_number: 1,
+                    gas_limit: 30_000_000,
+                    timestamp: payload_attributes.timestamp,
+                    withdrawals: payload_attributes.withdrawals,
+                    ..Default::default()
+                };
+                execution_payload.block_hash =
+                    execution_payload.header_hash(payload_attributes.parent_beacon_block_root);
+                let execution_requests = ExecutionRequests {
+                    withdrawals: VariableList::from(vec![withdrawal_request()]),
+                    ..Default::default()
+                };
+                json!({
+                    "executionPayload": ExecutionPayloadV3::from(execution_payload),
+                    "blockValue": B256::left_padding_from(&1_000u64.to_be_bytes()),
+                    "blobsBundle": { "commitments": [], "proofs": [], "blobs": [] },
+                    "shouldOverrideBuilder": false,
+                    "executionRequests": execution_requests.get_execution_requests_list(),
+                })
+            }
+            "engine_newPayloadV4" => valid,
+            method => panic!("Unexpected method {method}"),
+        };
+        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+    }
+
+    async fn start_stub_execution_engine() -> (ExecutionEngine, tempfile::NamedTempFile) {
+        let payload_request = Arc::new(Mutex::new(None));
+        let route = warp::post()
+            .and(warp::body::json())
+            .map(move |request: Value| {
+                warp::reply::json(&handle_request(request, &payload_request))
+            });
+        let (address, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+
+        let mut jwt_file = tempfile::NamedTempFile::new().unwrap();
+        write!(jwt_file, "0x{}", "11".repeat(32)).unwrap();
+        let execution_engine = ExecutionEngine::new(
+            jwt_file.path().to_str().unwrap(),
+            format!("http://{address}"),
+        )
+        .unwrap();
+        (execution_engine, jwt_file)
+    }
+
+    /// Return a store whose head is ``anchor_root``, the genesis block, at ``slot``.
+    fn anchor_store(anchor_root: B256, genesis_time: u64, slot: u64) -> Store {
+        let anchor_checkpoint = Checkpoint {
+            epoch: 0,
+            root: anchor_root,
+        };
+        let anchor_block = deneb::beacon_block::BeaconBlock {
+            slot: 0,
+            proposer_index: 0,
+            parent_root: B256::ZERO,
+            state_root: B256::ZERO,
+            body: deneb::beacon_block_body::BeaconBlockBody {
+                randao_reveal: BLSSignature::default(),
+                eth1_data: Eth1Data::default(),
+                graffiti: B256::ZERO,
+                proposer_slashings: VariableList::default(),
+                attester_slashings: VariableList::default(),
+                attestations: VariableList::default(),
+                deposits: VariableList::default(),
+                voluntary_exits: VariableList::default(),
+                sync_aggregate: Default::default(),
+                execution_payload: ExecutionPayload::default(),
+                bls_to_execution_changes: VariableList::default(),
+                blob_kzg_commitments: VariableList::default(),
+            },
+        };
+        Store {
+            time: genesis_time + slot * chain_spec().seconds_per_slot,
+            genesis_time,
+            justified_checkpoint: anchor_checkpoint,
+            finalized_checkpoint: anchor_checkpoint,
+            unrealized_justified_checkpoint: anchor_checkpoint,
+            unrealized_finalized_checkpoint: anchor_checkpoint,
+            // The head is not re-orged while its proposer boost is on
+            proposer_boost_root: anchor_root,
+            equivocating_indices: vec![],
+            blocks: HashMap::from([(anchor_root, anchor_block)]),
+            block_states: HashMap::new(),
+            block_timeliness: HashMap::new(),
+            checkpoint_states: HashMap::new(),
+            latest_messages: HashMap::new(),
+            unrealized_justifications: HashMap::from([(anchor_root, anchor_checkpoint)]),
+        }
+    }
+
+    #[tokio::test]
+    async fn produced_block_passes_the_state_transition() {
+        let state = interop_genesis_state(VALIDATOR_COUNT, 0).unwrap();
+        let mut anchor_header = state.latest_block_header.clone();
+        anchor_header.state_root = state.tree_hash_root();
+        let anchor_root = anchor_header.tree_hash_root();
+
+        let db = ReamDB::new(None, true).unwrap();
+        db.beacon_state_provider()
+            .insert(anchor_root, VersionedBeaconState::Electra(state.clone()))
+            .unwrap();
+        let (execution_engine, _jwt_file) = start_stub_execution_engine().await;
+        let execution_engine = Arc::new(execution_engine);
+        let eth1_follower = Eth1Follower::new(
+            execution_engine.clone(),
+            chain_spec().deposit_contract_address,
+            0,
+        );
+
+        // The store's epoch must be past the anchor for its voting source to be unrealized
+        let slot = SLOTS_PER_EPOCH + 1;
+        let epoch = compute_epoch_at_slot(slot);
+        let mut slot_state = state.clone();
+        slot_state.process_slots(slot).unwrap();
+        let proposer_key = interop_private_key(slot_state.get_beacon_proposer_index().unwrap());
+        let randao_reveal = proposer_key
+            .sign(
+                compute_signing_root(epoch, slot_state.get_domain(DOMAIN_RANDAO, Some(epoch)))
+                    .as_slice(),
+            )
+            .unwrap();
+
+        let produced_block = produce_block(
+            &anchor_store(anchor_root, state.genesis_time, slot),
+            &db,
+            &execution_engine,
+            &eth1_follower,
+            &OperationPool::default(),
+            BlockProductionParameters {
+                slot,
+                randao_reveal,
+                graffiti: B256::repeat_byte(2),
+                suggested_fee_recipient: Address::repeat_byte(3),
+            },
+        )
+        .await
+        .unwrap();
+        let block = produced_block.block;
+        assert_eq!(block.parent_root, anchor_root);
+        assert_eq!(
+            block.body.execution_requests.withdrawals.to_vec(),
+            vec![withdrawal_request()]
+        );
+
+        let signature = proposer_key
+            .sign(
+                compute_signing_root(&block, slot_state.get_domain(DOMAIN_BEACON_PROPOSER, None))
+                    .as_slice(),
+            )
+            .unwrap();
+        let signed_block = SignedBeaconBlock {
+            message: block,
+            signature,
+        };
+        let mut post_state = state;
+        post_state
+            .state_transition(&signed_block, true, execution_engine.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(post_state.slot, slot);
+    }
+
+    #[test]
+    fn produced_blob_sidecars_prove_their_commitments() {
+        let blobs_bundle = BlobsBundleV1 {
+            commitments: VariableList::from(vec![KZGCommitment([1; 48]), KZGCommitment([2; 48])]),
+            proofs: VariableList::from(vec![KZGProof::repeat_byte(3), KZGProof::repeat_byte(4)]),
+            blobs: VariableList::from(vec![Blob::repeat_byte(5), Blob::repeat_byte(6)]),
+        };
+        let signed_block = SignedBeaconBlock {
+            message: BeaconBlock {
+                slot: 1,
+                proposer_index: 0,
+                parent_root: B256::ZERO,
+                state_root: B256::ZERO,
+                body: BeaconBlockBody {
+                    blob_kzg_commitments: blobs_bundle.commitments.clone(),
+                    ..Default::default()
+                },
+            },
+            signature: BLSSignature::default(),
+        };
+
+        let blob_sidecars =
+            get_produced_blob_sidecars(&signed_block, blobs_bundle.clone()).unwrap();
+
+        assert_eq!(blob_sidecars.len(), 2);
+        for (index, blob_sidecar) in blob_sidecars.iter().enumerate() {
+            assert_eq!(blob_sidecar.index, index as u64);
+            assert_eq!(blob_sidecar.blob, blobs_bundle.blobs[index]);
+            assert_eq!(blob_sidecar.kzg_commitment, blobs_bundle.commitments[index]);
+            assert_eq!(blob_sidecar.kzg_proof, blobs_bundle.proofs[index]);
+            assert_eq!(
+                blob_sidecar.signed_block_header.message,
+                signed_block.message.block_header()
+            );
+            assert!(blob_sidecar.verify_blob_sidecar_inclusion_proof());
+        }
+
+        // The bundle must have a blob for each commitment of the block
+        let mut missing_blob_bundle = blobs_bundle;
+        missing_blob_bundle.blobs = VariableList::from(vec![Blob::repeat_byte(5)]);
+        assert!(get_produced_blob_sidecars(&signed_block, missing_blob_bundle).is_err());
+    }
+}
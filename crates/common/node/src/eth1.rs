@@ -0,0 +1,71 @@
+use std::{sync::Arc, time::Duration};
+
+use ream_consensus::chain_spec::chain_spec;
+use ream_execution_engine::{ExecutionEngine, eth1_follower::Eth1Follower};
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use tokio::sync::RwLock;
+use tracing::warn;
+
+/// Return the ``Eth1Follower`` of the deposit contract, resumed from the deposit tree snapshot in
+/// ``db`` if there is one, else following from ``deposit_contract_block``.
+pub fn init_eth1_follower(
+    db: &ReamDB,
+    execution_engine: Arc<ExecutionEngine>,
+    deposit_contract_block: u64,
+) -> anyhow::Result<Eth1Follower> {
+    let deposit_contract_address = chain_spec().deposit_contract_address;
+    match db.deposit_tree_snapshot_provider().get()? {
+        Some(snapshot) => {
+            Eth1Follower::from_snapshot(execution_engine, deposit_contract_address, &snapshot)
+        }
+        None => Ok(Eth1Follower::new(
+            execution_engine,
+            deposit_contract_address,
+            deposit_contract_block,
+        )),
+    }
+}
+
+/// Finalize the deposit tree of ``follower`` at the ``eth1_data`` of the finalized state in
+/// ``db``, and persist its snapshot.
+pub fn finalize_deposit_tree(db: &ReamDB, follower: &mut Eth1Follower) -> anyhow::Result<()> {
+    let Some(finalized_checkpoint) = db.finalized_checkpoint_provider().get()? else {
+        return Ok(());
+    };
+    let Some(finalized_state) = db.beacon_state_provider().get(finalized_checkpoint.root)? else {
+        return Ok(());
+    };
+    let eth1_data = finalized_state.eth1_data();
+
+    // Nothing to do until the finalized ``eth1_data`` changes
+    if let Ok(snapshot) = follower.deposit_tree().get_snapshot() {
+        if snapshot.execution_block_hash == eth1_data.block_hash {
+            return Ok(());
+        }
+    }
+
+    let snapshot = follower.finalize(&eth1_data)?;
+    db.deposit_tree_snapshot_provider().insert(snapshot)?;
+    Ok(())
+}
+
+/// Follow the deposit contract every ``SECONDS_PER_ETH1_BLOCK``, persisting the snapshot of the
+/// deposit tree as the finalized ``eth1_data`` advances.
+pub async fn run_eth1_follower(db: ReamDB, follower: Arc<RwLock<Eth1Follower>>) {
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(chain_spec().seconds_per_eth1_block));
+    loop {
+        interval.tick().await;
+        let mut follower = follower.write().await;
+        if let Err(err) = follower.update().await {
+            warn!("Failed to follow the deposit contract: {err}");
+            continue;
+        }
+        if let Err(err) = finalize_deposit_tree(&db, &mut follower) {
+            warn!("Failed to finalize the deposit tree: {err}");
+        }
+    }
+}
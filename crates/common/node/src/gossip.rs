@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use ream_consensus::{
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    contribution_and_proof::SignedContributionAndProof,
+    electra::{aggregate_and_proof::SignedAggregateAndProof, attester_slashing::AttesterSlashing},
+    proposer_slashing::ProposerSlashing,
+    versioned::beacon_block::VersionedSignedBeaconBlock,
+    voluntary_exit::SignedVoluntaryExit,
+};
+use ream_execution_engine::ExecutionEngine;
+use ream_network_spec::networks::NetworkSpec;
+use ream_operation_pool::operation_pool::OperationPool;
+use ream_p2p::topics::{GossipTopic, GossipTopicKind};
+use ream_storage::{db::ReamDB, tables::Field};
+use ssz::Decode;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::{
+    block_import::import_block,
+    operation_pool::{get_operation_pool_state, prune_operation_pool},
+};
+
+/// Handles the gossip messages received by the network.
+pub struct GossipHandler {
+    pub db: ReamDB,
+    pub network_spec: Arc<NetworkSpec>,
+    /// Blocks are only imported when their payloads can be verified by an execution engine
+    pub execution_engine: Option<Arc<ExecutionEngine>>,
+    /// The pool gossiped operations are inserted in, shared with the Beacon API
+    pub operation_pool: Arc<RwLock<OperationPool>>,
+}
+
+impl GossipHandler {
+    /// Handle the decompressed ``data`` of a message received on ``topic``.
+    pub async fn handle_gossip_message(
+        &self,
+        topic: GossipTopic,
+        data: &[u8],
+    ) -> anyhow::Result<()> {
+        match topic.kind {
+            GossipTopicKind::BeaconBlock => self.handle_beacon_block(data).await,
+            GossipTopicKind::BeaconAggregateAndProof => {
+                let aggregate_and_proof = decode::<SignedAggregateAndProof>(data)?;
+                let state = get_operation_pool_state(&self.db)?;
+                self.operation_pool
+                    .write()
+                    .await
+                    .insert_attestation(aggregate_and_proof.message.aggregate, &state)
+            }
+            GossipTopicKind::VoluntaryExit => {
+                let voluntary_exit = decode::<SignedVoluntaryExit>(data)?;
+                let state = get_operation_pool_state(&self.db)?;
+                self.operation_pool
+                    .write()
+                    .await
+                    .insert_voluntary_exit(voluntary_exit, &state)
+            }
+            GossipTopicKind::ProposerSlashing => {
+                let proposer_slashing = decode::<ProposerSlashing>(data)?;
+                let state = get_operation_pool_state(&self.db)?;
+                self.operation_pool
+                    .write()
+                    .await
+                    .insert_proposer_slashing(proposer_slashing, &state)
+            }
+            GossipTopicKind::AttesterSlashing => {
+                let attester_slashing = decode::<AttesterSlashing>(data)?;
+                let state = get_operation_pool_state(&self.db)?;
+                self.operation_pool
+                    .write()
+                    .await
+                    .insert_attester_slashing(attester_slashing, &state)
+            }
+            GossipTopicKind::BlsToExecutionChange => {
+                let bls_to_execution_change = decode::<SignedBLSToExecutionChange>(data)?;
+                let state = get_operation_pool_state(&self.db)?;
+                self.operation_pool
+                    .write()
+                    .await
+                    .insert_bls_to_execution_change(bls_to_execution_change, &state)
+            }
+            GossipTopicKind::SyncCommitteeContributionAndProof => {
+                let contribution_and_proof = decode::<SignedContributionAndProof>(data)?;
+                self.operation_pool
+                    .write()
+                    .await
+                    .insert_sync_committee_contribution(
+                        contribution_and_proof.message.contribution,
+                    );
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn handle_beacon_block(&self, data: &[u8]) -> anyhow::Result<()> {
+        let Some(execution_engine) = &self.execution_engine else {
+            return Ok(());
+        };
+        let signed_block = VersionedSignedBeaconBlock::from_ssz_bytes_with_fork_schedule(
+            data,
+            &self.network_spec.fork_schedule(),
+        )
+        .map_err(|err| anyhow!("Invalid beacon block: {err:?}"))?;
+        let slot = signed_block.slot();
+        let finalized_checkpoint = self.db.finalized_checkpoint_provider().get()?;
+        let block_root = import_block(&self.db, signed_block, execution_engine.as_ref()).await?;
+        info!("Imported block {block_root} at slot {slot}");
+
+        if self.db.finalized_checkpoint_provider().get()? != finalized_checkpoint {
+            prune_operation_pool(&self.db, &mut *self.operation_pool.write().await)?;
+        }
+        Ok(())
+    }
+}
+
+fn decode<T: Decode>(data: &[u8]) -> anyhow::Result<T> {
+    T::from_ssz_bytes(data).map_err(|err| anyhow!("Invalid gossip message: {err:?}"))
+}
@@ -1 +1,6 @@
+pub mod block_import;
+pub mod block_production;
+pub mod eth1;
+pub mod gossip;
+pub mod operation_pool;
 pub mod version;
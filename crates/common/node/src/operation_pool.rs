@@ -0,0 +1,147 @@
+use anyhow::{anyhow, bail};
+use ream_consensus::{
+    electra::beacon_state::BeaconState,
+    versioned::{ForkVersioned, beacon_state::VersionedBeaconState},
+};
+use ream_operation_pool::operation_pool::OperationPool;
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+
+/// Return the ``OperationPool`` persisted in ``db`` by the previous run, else an empty pool.
+pub fn load_operation_pool(db: &ReamDB) -> anyhow::Result<OperationPool> {
+    Ok(db
+        .operation_pool_provider()
+        .get()?
+        .map(OperationPool::from_persisted)
+        .unwrap_or_default())
+}
+
+/// Persist the operations of ``operation_pool`` in ``db``, to be loaded on the next start.
+pub fn persist_operation_pool(db: &ReamDB, operation_pool: &OperationPool) -> anyhow::Result<()> {
+    db.operation_pool_provider()
+        .insert(operation_pool.to_persisted())?;
+    Ok(())
+}
+
+/// Return the state operations are validated against before entering the pool, the post state of
+/// the latest imported block, else the finalized state.
+pub fn get_operation_pool_state(db: &ReamDB) -> anyhow::Result<BeaconState> {
+    let head_root = match db.slot_index_provider().get_highest_slot()? {
+        Some(slot) => db.slot_index_provider().get(slot)?,
+        None => None,
+    };
+    let root = match head_root {
+        Some(root) => root,
+        None => {
+            db.finalized_checkpoint_provider()
+                .get()?
+                .ok_or_else(|| anyhow!("No block imported to validate operations against"))?
+                .root
+        }
+    };
+    match db.beacon_state_provider().get(root)? {
+        Some(VersionedBeaconState::Electra(state)) => Ok(state),
+        Some(state) => bail!(
+            "Operations are only validated against Electra states, not {}",
+            state.fork_name()
+        ),
+        None => bail!("State {root} not found"),
+    }
+}
+
+/// Remove the operations of ``operation_pool`` which can't be included in a block anymore, once
+/// the finalized checkpoint of ``db`` is finalized.
+pub fn prune_operation_pool(db: &ReamDB, operation_pool: &mut OperationPool) -> anyhow::Result<()> {
+    let Some(finalized_checkpoint) = db.finalized_checkpoint_provider().get()? else {
+        return Ok(());
+    };
+    match db.beacon_state_provider().get(finalized_checkpoint.root)? {
+        Some(VersionedBeaconState::Electra(finalized_state)) => {
+            operation_pool.prune(&finalized_state)
+        }
+        // Pre-Electra operations are not pooled
+        Some(_) => {}
+        None => bail!("Finalized state {} not found", finalized_checkpoint.root),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::BLSSignature;
+    use ream_consensus::{
+        checkpoint::Checkpoint,
+        genesis::interop_genesis_state,
+        voluntary_exit::{SignedVoluntaryExit, VoluntaryExit},
+    };
+    use ream_operation_pool::persisted_operation_pool::PersistedOperationPool;
+
+    use super::*;
+
+    #[test]
+    fn operation_pool_survives_a_restart() {
+        let db = ReamDB::new(None, true).unwrap();
+        assert_eq!(
+            load_operation_pool(&db).unwrap().to_persisted(),
+            PersistedOperationPool::default()
+        );
+
+        let persisted = PersistedOperationPool {
+            voluntary_exits: vec![SignedVoluntaryExit {
+                message: VoluntaryExit {
+                    epoch: 1,
+                    validator_index: 2,
+                },
+                signature: BLSSignature::infinity(),
+            }],
+            ..Default::default()
+        };
+        persist_operation_pool(&db, &OperationPool::from_persisted(persisted.clone())).unwrap();
+
+        assert_eq!(load_operation_pool(&db).unwrap().to_persisted(), persisted);
+    }
+
+    #[test]
+    fn finalization_prunes_the_operation_pool() {
+        let db = ReamDB::new(None, true).unwrap();
+        let voluntary_exit = SignedVoluntaryExit {
+            message: VoluntaryExit {
+                epoch: 0,
+                validator_index: 1,
+            },
+            signature: BLSSignature::infinity(),
+        };
+        let mut operation_pool = OperationPool::from_persisted(PersistedOperationPool {
+            voluntary_exits: vec![voluntary_exit],
+            ..Default::default()
+        });
+
+        // Nothing is pruned before anything is finalized
+        prune_operation_pool(&db, &mut operation_pool).unwrap();
+        assert_eq!(operation_pool.to_persisted().voluntary_exits.len(), 1);
+
+        let mut finalized_state = interop_genesis_state(4, 0).unwrap();
+        finalized_state.validators[1].exit_epoch = 256;
+        let finalized_root = B256::repeat_byte(0x01);
+        db.beacon_state_provider()
+            .insert(
+                finalized_root,
+                VersionedBeaconState::Electra(finalized_state),
+            )
+            .unwrap();
+        db.finalized_checkpoint_provider()
+            .insert(Checkpoint {
+                epoch: 0,
+                root: finalized_root,
+            })
+            .unwrap();
+
+        prune_operation_pool(&db, &mut operation_pool).unwrap();
+        assert!(operation_pool.to_persisted().voluntary_exits.is_empty());
+        assert!(get_operation_pool_state(&db).is_ok());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod max_cover;
+pub mod operation_pool;
+pub mod persisted_operation_pool;
@@ -0,0 +1,80 @@
+use std::{cmp::Reverse, collections::HashMap, hash::Hash};
+
+/// An item which may be selected, and the weight of each element it covers.
+#[derive(Debug, Clone)]
+pub struct CoverCandidate<T, K> {
+    pub item: T,
+    pub covering: HashMap<K, u64>,
+}
+
+impl<T, K> CoverCandidate<T, K> {
+    pub fn new(item: T, covering: HashMap<K, u64>) -> Self {
+        Self { item, covering }
+    }
+
+    /// Return the total weight of the elements covered by the candidate.
+    pub fn score(&self) -> u64 {
+        self.covering.values().sum()
+    }
+}
+
+/// Select at most ``limit`` candidates covering elements of the greatest total weight, an element
+/// covered by several selected candidates counting once.
+///
+/// The candidate adding the most weight is selected at each step, which is within a factor of
+/// ``1 - 1 / e`` of the optimum.
+pub fn maximum_cover<T, K: Eq + Hash>(
+    mut candidates: Vec<CoverCandidate<T, K>>,
+    limit: usize,
+) -> Vec<CoverCandidate<T, K>> {
+    let mut selected = vec![];
+    while selected.len() < limit {
+        let Some((best_index, best_score)) = candidates
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| (index, candidate.score()))
+            // Ties go to the earliest candidate
+            .max_by_key(|(index, score)| (*score, Reverse(*index)))
+        else {
+            break;
+        };
+        if best_score == 0 {
+            break;
+        }
+
+        let best = candidates.remove(best_index);
+        for candidate in candidates.iter_mut() {
+            candidate
+                .covering
+                .retain(|element, _| !best.covering.contains_key(element));
+        }
+        selected.push(best);
+    }
+    selected
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn candidate(item: &'static str, elements: &[u64]) -> CoverCandidate<&'static str, u64> {
+        CoverCandidate::new(item, elements.iter().map(|&element| (element, 1)).collect())
+    }
+
+    #[test]
+    fn selects_candidates_adding_the_most_uncovered_weight() {
+        let candidates = vec![
+            candidate("large", &[1, 2, 3, 4]),
+            candidate("overlapping", &[1, 2, 3, 5]),
+            candidate("disjoint", &[6, 7]),
+            candidate("covered", &[2, 3]),
+        ];
+
+        let selected = maximum_cover(candidates, 2)
+            .into_iter()
+            .map(|candidate| candidate.item)
+            .collect::<Vec<_>>();
+
+        assert_eq!(selected, vec!["large", "disjoint"]);
+    }
+}
@@ -0,0 +1,670 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use alloy_primitives::B256;
+use anyhow::ensure;
+use ream_consensus::{
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    constants::{
+        BLS_WITHDRAWAL_PREFIX, EFFECTIVE_BALANCE_INCREMENT, FAR_FUTURE_EPOCH,
+        MIN_ATTESTATION_INCLUSION_DELAY, PARTICIPATION_FLAG_WEIGHTS,
+    },
+    electra::{
+        attestation::Attestation, attester_slashing::AttesterSlashing, beacon_state::BeaconState,
+    },
+    misc::compute_epoch_at_slot,
+    proposer_slashing::ProposerSlashing,
+    signature_sets::SignatureVerifier,
+    state_transition::BlockSignatureStrategy,
+    sync_aggregate::SyncAggregate,
+    sync_committee_contribution::SyncCommitteeContribution,
+    voluntary_exit::SignedVoluntaryExit,
+};
+use ssz_types::{
+    VariableList,
+    typenum::{U1, U8, U16, Unsigned},
+};
+use tree_hash::TreeHash;
+
+use crate::{
+    max_cover::{CoverCandidate, maximum_cover},
+    persisted_operation_pool::PersistedOperationPool,
+};
+
+/// The operations of the pool to include in a block.
+#[derive(Debug, Default, Clone)]
+pub struct BlockOperations {
+    pub proposer_slashings: VariableList<ProposerSlashing, U16>,
+    pub attester_slashings: VariableList<AttesterSlashing, U1>,
+    pub attestations: VariableList<Attestation, U8>,
+    pub voluntary_exits: VariableList<SignedVoluntaryExit, U16>,
+    pub bls_to_execution_changes: VariableList<SignedBLSToExecutionChange, U16>,
+}
+
+/// The operations received from the network and the API, waiting to be included in a block.
+///
+/// Operations are validated against the head state when inserted, and checked again against the
+/// state of the block including them.
+#[derive(Debug, Default)]
+pub struct OperationPool {
+    /// The aggregates of a single committee, by the root of their data and their committee index
+    attestations: BTreeMap<(B256, u64), Vec<Attestation>>,
+    attester_slashings: Vec<AttesterSlashing>,
+    /// The proposer slashings by proposer index
+    proposer_slashings: BTreeMap<u64, ProposerSlashing>,
+    /// The voluntary exits by validator index
+    voluntary_exits: BTreeMap<u64, SignedVoluntaryExit>,
+    /// The BLS to execution changes by validator index
+    bls_to_execution_changes: BTreeMap<u64, SignedBLSToExecutionChange>,
+    /// The contributions by the slot and root of the block they sign
+    sync_committee_contributions: HashMap<(u64, B256), Vec<SyncCommitteeContribution>>,
+}
+
+impl OperationPool {
+    /// Rebuild the pool from the operations persisted by ``to_persisted``.
+    pub fn from_persisted(persisted: PersistedOperationPool) -> Self {
+        let mut operation_pool = Self::default();
+        for attestation in persisted.attestations {
+            if let [committee_index] = attestation.get_committee_indices()[..] {
+                operation_pool
+                    .attestations
+                    .entry((attestation.data.tree_hash_root(), committee_index))
+                    .or_default()
+                    .push(attestation);
+            }
+        }
+        operation_pool.attester_slashings = persisted.attester_slashings;
+        for proposer_slashing in persisted.proposer_slashings {
+            operation_pool.proposer_slashings.insert(
+                proposer_slashing.signed_header_1.message.proposer_index,
+                proposer_slashing,
+            );
+        }
+        for voluntary_exit in persisted.voluntary_exits {
+            operation_pool
+                .voluntary_exits
+                .insert(voluntary_exit.message.validator_index, voluntary_exit);
+        }
+        for bls_to_execution_change in persisted.bls_to_execution_changes {
+            operation_pool.bls_to_execution_changes.insert(
+                bls_to_execution_change.message.validator_index,
+                bls_to_execution_change,
+            );
+        }
+        operation_pool
+    }
+
+    /// Return the operations to persist across restarts. Sync committee contributions are only
+    /// useful for a slot, so they are not persisted.
+    pub fn to_persisted(&self) -> PersistedOperationPool {
+        PersistedOperationPool {
+            attestations: self.attestations.values().flatten().cloned().collect(),
+            attester_slashings: self.attester_slashings.clone(),
+            proposer_slashings: self.proposer_slashings.values().cloned().collect(),
+            voluntary_exits: self.voluntary_exits.values().cloned().collect(),
+            bls_to_execution_changes: self.bls_to_execution_changes.values().cloned().collect(),
+        }
+    }
+
+    /// Insert the aggregate of a single committee, merging it with the aggregates it has no
+    /// participant in common with.
+    pub fn insert_attestation(
+        &mut self,
+        attestation: Attestation,
+        state: &BeaconState,
+    ) -> anyhow::Result<()> {
+        let committee_indices = attestation.get_committee_indices();
+        ensure!(
+            committee_indices.len() == 1,
+            "Attestations must be of a single committee"
+        );
+        let target_epoch = attestation.data.target.epoch;
+        ensure!(
+            target_epoch == state.get_previous_epoch() || target_epoch == state.get_current_epoch(),
+            "Target epoch must be the previous or current epoch"
+        );
+        ensure!(
+            target_epoch == compute_epoch_at_slot(attestation.data.slot),
+            "Target epoch must match the computed epoch at slot"
+        );
+        ensure!(
+            state.is_valid_indexed_attestation(&state.get_indexed_attestation(&attestation)?)?,
+            "Attestation signature must be valid"
+        );
+
+        let aggregates = self
+            .attestations
+            .entry((attestation.data.tree_hash_root(), committee_indices[0]))
+            .or_default();
+        if aggregates.iter().any(|aggregate| {
+            attestation
+                .aggregation_bits
+                .is_subset(&aggregate.aggregation_bits)
+        }) {
+            return Ok(());
+        }
+        let attestation = match aggregates.iter().position(|aggregate| {
+            aggregate
+                .aggregation_bits
+                .intersection(&attestation.aggregation_bits)
+                .is_zero()
+        }) {
+            Some(index) => aggregates.swap_remove(index).aggregate(&attestation)?,
+            None => attestation,
+        };
+        aggregates.retain(|aggregate| {
+            !aggregate
+                .aggregation_bits
+                .is_subset(&attestation.aggregation_bits)
+        });
+        aggregates.push(attestation);
+        Ok(())
+    }
+
+    pub fn insert_attester_slashing(
+        &mut self,
+        attester_slashing: AttesterSlashing,
+        state: &BeaconState,
+    ) -> anyhow::Result<()> {
+        state.verify_attester_slashing(&attester_slashing)?;
+        if !self.attester_slashings.contains(&attester_slashing) {
+            self.attester_slashings.push(attester_slashing);
+        }
+        Ok(())
+    }
+
+    pub fn insert_proposer_slashing(
+        &mut self,
+        proposer_slashing: ProposerSlashing,
+        state: &BeaconState,
+    ) -> anyhow::Result<()> {
+        state.verify_proposer_slashing(&proposer_slashing)?;
+        self.proposer_slashings
+            .entry(proposer_slashing.signed_header_1.message.proposer_index)
+            .or_insert(proposer_slashing);
+        Ok(())
+    }
+
+    pub fn insert_voluntary_exit(
+        &mut self,
+        voluntary_exit: SignedVoluntaryExit,
+        state: &BeaconState,
+    ) -> anyhow::Result<()> {
+        state.verify_voluntary_exit(&voluntary_exit)?;
+        self.voluntary_exits
+            .entry(voluntary_exit.message.validator_index)
+            .or_insert(voluntary_exit);
+        Ok(())
+    }
+
+    pub fn insert_bls_to_execution_change(
+        &mut self,
+        bls_to_execution_change: SignedBLSToExecutionChange,
+        state: &BeaconState,
+    ) -> anyhow::Result<()> {
+        state.verify_bls_to_execution_change(&bls_to_execution_change)?;
+        self.bls_to_execution_changes
+            .entry(bls_to_execution_change.message.validator_index)
+            .or_insert(bls_to_execution_change);
+        Ok(())
+    }
+
+    pub fn insert_sync_committee_contribution(&mut self, contribution: SyncCommitteeContribution) {
+        self.sync_committee_contributions
+            .entry((contribution.slot, contribution.beacon_block_root))
+            .or_default()
+            .push(contribution);
+    }
+
+    /// Return the operations to include in a block on top of ``state``, which must have been
+    /// advanced to the slot of the block.
+    pub fn get_block_operations(&self, state: &BeaconState) -> anyhow::Result<BlockOperations> {
+        // Signatures were verified when the operations were inserted
+        let mut signature_verifier = SignatureVerifier::new(BlockSignatureStrategy::NoVerification);
+
+        let proposer_slashings = self
+            .proposer_slashings
+            .values()
+            .filter(|proposer_slashing| {
+                state
+                    .verify_proposer_slashing_with_verifier(
+                        proposer_slashing,
+                        &mut signature_verifier,
+                    )
+                    .is_ok()
+            })
+            .take(U16::USIZE)
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut slashed_indices = proposer_slashings
+            .iter()
+            .map(|proposer_slashing| proposer_slashing.signed_header_1.message.proposer_index)
+            .collect::<HashSet<_>>();
+
+        // Select the attester slashings slashing the most validators which are not slashed yet
+        let mut attester_slashing_candidates = vec![];
+        for attester_slashing in self.attester_slashings.iter() {
+            let Ok(slashable_indices) = state
+                .verify_attester_slashing_with_verifier(attester_slashing, &mut signature_verifier)
+            else {
+                continue;
+            };
+            let covering = slashable_indices
+                .into_iter()
+                .filter(|index| !slashed_indices.contains(index))
+                .map(|index| (index, 1))
+                .collect();
+            attester_slashing_candidates.push(CoverCandidate::new(attester_slashing, covering));
+        }
+        let attester_slashings = maximum_cover(attester_slashing_candidates, U1::USIZE)
+            .into_iter()
+            .map(|candidate| {
+                slashed_indices.extend(candidate.covering.into_keys());
+                candidate.item.clone()
+            })
+            .collect::<Vec<_>>();
+
+        // Slashed validators are exited by their slashing
+        let voluntary_exits = self
+            .voluntary_exits
+            .iter()
+            .filter(|&(validator_index, voluntary_exit)| {
+                !slashed_indices.contains(validator_index)
+                    && state
+                        .verify_voluntary_exit_with_verifier(
+                            voluntary_exit,
+                            &mut signature_verifier,
+                        )
+                        .is_ok()
+            })
+            .map(|(_, voluntary_exit)| voluntary_exit.clone())
+            .take(U16::USIZE)
+            .collect::<Vec<_>>();
+
+        let bls_to_execution_changes = self
+            .bls_to_execution_changes
+            .values()
+            .filter(|bls_to_execution_change| {
+                state
+                    .verify_bls_to_execution_change_with_verifier(
+                        bls_to_execution_change,
+                        &mut signature_verifier,
+                    )
+                    .is_ok()
+            })
+            .take(U16::USIZE)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        Ok(BlockOperations {
+            proposer_slashings: VariableList::from(proposer_slashings),
+            attester_slashings: VariableList::from(attester_slashings),
+            attestations: VariableList::from(self.get_attestations(state, U8::USIZE)?),
+            voluntary_exits: VariableList::from(voluntary_exits),
+            bls_to_execution_changes: VariableList::from(bls_to_execution_changes),
+        })
+    }
+
+    /// Return at most ``max_attestations`` on chain aggregates maximizing the reward of the
+    /// proposer of a block on top of ``state``.
+    ///
+    /// The aggregates of a committee are not disjoint, so only the best aggregate of each
+    /// committee is combined into the on chain aggregate of their data. The on chain aggregates
+    /// are then selected by maximum cover of the participation flags they set, weighted by
+    /// ``PARTICIPATION_FLAG_WEIGHTS``.
+    fn get_attestations(
+        &self,
+        state: &BeaconState,
+        max_attestations: usize,
+    ) -> anyhow::Result<Vec<Attestation>> {
+        let mut aggregates_by_data: BTreeMap<B256, Vec<&[Attestation]>> = BTreeMap::new();
+        for ((data_root, _), aggregates) in self.attestations.iter() {
+            aggregates_by_data
+                .entry(*data_root)
+                .or_default()
+                .push(aggregates);
+        }
+
+        let base_reward_per_increment = state.get_base_reward_per_increment();
+        let mut candidates = vec![];
+        for committees in aggregates_by_data.into_values() {
+            let Some(data) = committees
+                .first()
+                .and_then(|aggregates| aggregates.first())
+                .map(|aggregate| &aggregate.data)
+            else {
+                continue;
+            };
+            let target_epoch = data.target.epoch;
+            if data.slot + MIN_ATTESTATION_INCLUSION_DELAY > state.slot
+                || !(target_epoch == state.get_previous_epoch()
+                    || target_epoch == state.get_current_epoch())
+            {
+                continue;
+            }
+            // Attestations with a source other than the justified checkpoint are invalid
+            let Ok(participation_flag_indices) =
+                state.get_attestation_participation_flag_indices(data, state.slot - data.slot)
+            else {
+                continue;
+            };
+            let epoch_participation = if target_epoch == state.get_current_epoch() {
+                &state.current_epoch_participation
+            } else {
+                &state.previous_epoch_participation
+            };
+
+            let mut network_aggregates = vec![];
+            let mut covering = HashMap::new();
+            for aggregates in committees {
+                let mut best: Option<(&Attestation, HashMap<(u64, u8), u64>)> = None;
+                for aggregate in aggregates {
+                    let mut rewards = HashMap::new();
+                    for index in state.get_attesting_indices(aggregate)? {
+                        let participation_flags = epoch_participation
+                            .get(index as usize)
+                            .copied()
+                            .unwrap_or_default();
+                        let base_reward = state.validators[index as usize].effective_balance
+                            / EFFECTIVE_BALANCE_INCREMENT
+                            * base_reward_per_increment;
+                        for &flag_index in participation_flag_indices.iter() {
+                            if !BeaconState::has_flag(participation_flags, flag_index) {
+                                rewards.insert(
+                                    (index, flag_index),
+                                    base_reward * PARTICIPATION_FLAG_WEIGHTS[flag_index as usize],
+                                );
+                            }
+                        }
+                    }
+                    let score = rewards.values().sum::<u64>();
+                    if score > 0
+                        && best.as_ref().is_none_or(|(_, best_rewards)| {
+                            score > best_rewards.values().sum::<u64>()
+                        })
+                    {
+                        best = Some((aggregate, rewards));
+                    }
+                }
+                if let Some((aggregate, rewards)) = best {
+                    network_aggregates.push(aggregate.clone());
+                    covering.extend(rewards);
+                }
+            }
+            if network_aggregates.is_empty() {
+                continue;
+            }
+            candidates.push(CoverCandidate::new(
+                Attestation::compute_on_chain_aggregate(&network_aggregates)?,
+                covering,
+            ));
+        }
+
+        Ok(maximum_cover(candidates, max_attestations)
+            .into_iter()
+            .map(|candidate| candidate.item)
+            .collect())
+    }
+
+    /// Return the ``SyncAggregate`` for a block at ``slot + 1`` with parent ``beacon_block_root``,
+    /// made of the contribution with the most participants of each subcommittee.
+    pub fn get_sync_aggregate(
+        &self,
+        slot: u64,
+        beacon_block_root: B256,
+    ) -> anyhow::Result<SyncAggregate> {
+        let mut best_contributions: HashMap<u64, &SyncCommitteeContribution> = HashMap::new();
+        for contribution in self
+            .sync_committee_contributions
+            .get(&(slot, beacon_block_root))
+            .into_iter()
+            .flatten()
+        {
+            let best = best_contributions
+                .entry(contribution.subcommittee_index)
+                .or_insert(contribution);
+            if contribution.aggregation_bits.num_set_bits() > best.aggregation_bits.num_set_bits() {
+                *best = contribution;
+            }
+        }
+        SyncAggregate::from_contributions(
+            &best_contributions
+                .into_values()
+                .cloned()
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Remove the operations which can't be included in a block anymore once ``finalized_state``
+    /// is finalized.
+    pub fn prune(&mut self, finalized_state: &BeaconState) {
+        let finalized_epoch = finalized_state.get_current_epoch();
+        self.attestations.retain(|_, aggregates| {
+            aggregates
+                .first()
+                .is_some_and(|aggregate| aggregate.data.target.epoch >= finalized_epoch)
+        });
+        self.attester_slashings.retain(|attester_slashing| {
+            let indices_1 = attester_slashing
+                .attestation_1
+                .attesting_indices
+                .iter()
+                .collect::<HashSet<_>>();
+            attester_slashing
+                .attestation_2
+                .attesting_indices
+                .iter()
+                .filter(|index| indices_1.contains(index))
+                .any(|&index| {
+                    finalized_state
+                        .validators
+                        .get(index as usize)
+                        .is_some_and(|validator| validator.is_slashable_validator(finalized_epoch))
+                })
+        });
+        self.proposer_slashings.retain(|&proposer_index, _| {
+            finalized_state
+                .validators
+                .get(proposer_index as usize)
+                .is_some_and(|validator| validator.is_slashable_validator(finalized_epoch))
+        });
+        self.voluntary_exits.retain(|&validator_index, _| {
+            finalized_state
+                .validators
+                .get(validator_index as usize)
+                .is_some_and(|validator| validator.exit_epoch == FAR_FUTURE_EPOCH)
+        });
+        self.bls_to_execution_changes.retain(|&validator_index, _| {
+            finalized_state
+                .validators
+                .get(validator_index as usize)
+                .is_some_and(|validator| {
+                    &validator.withdrawal_credentials[..1] == BLS_WITHDRAWAL_PREFIX
+                })
+        });
+        self.sync_committee_contributions
+            .retain(|(slot, _), _| *slot >= finalized_state.slot);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_bls::{BLSSignature, traits::Signable};
+    use ream_consensus::{
+        attestation_data::AttestationData,
+        chain_spec::chain_spec,
+        checkpoint::Checkpoint,
+        constants::{DOMAIN_BEACON_ATTESTER, DOMAIN_VOLUNTARY_EXIT, SLOTS_PER_EPOCH},
+        genesis::{interop_genesis_state, interop_private_key},
+        misc::{compute_domain, compute_signing_root},
+        voluntary_exit::VoluntaryExit,
+    };
+    use ssz_types::{BitList, BitVector};
+
+    use super::*;
+
+    fn signed_voluntary_exit(state: &BeaconState, validator_index: u64) -> SignedVoluntaryExit {
+        let message = VoluntaryExit {
+            epoch: 0,
+            validator_index,
+        };
+        let domain = compute_domain(
+            DOMAIN_VOLUNTARY_EXIT,
+            Some(chain_spec().capella_fork_version),
+            Some(state.genesis_validators_root),
+        );
+        let signing_root = compute_signing_root(&message, domain);
+        SignedVoluntaryExit {
+            message,
+            signature: interop_private_key(validator_index)
+                .sign(signing_root.as_slice())
+                .unwrap(),
+        }
+    }
+
+    /// Return the attestation of the member at ``position`` of the first committee of ``slot``.
+    fn committee_attestation(state: &BeaconState, slot: u64, position: usize) -> Attestation {
+        let committee = state.get_beacon_committee(slot, 0).unwrap();
+        let epoch = compute_epoch_at_slot(slot);
+        let data = AttestationData {
+            slot,
+            index: 0,
+            beacon_block_root: state.get_block_root_at_slot(slot).unwrap(),
+            source: state.current_justified_checkpoint,
+            target: Checkpoint {
+                epoch,
+                root: state.get_block_root(epoch).unwrap(),
+            },
+        };
+        let signing_root =
+            compute_signing_root(&data, state.get_domain(DOMAIN_BEACON_ATTESTER, Some(epoch)));
+        let mut aggregation_bits = BitList::with_capacity(committee.len()).unwrap();
+        aggregation_bits.set(position, true).unwrap();
+        let mut committee_bits = BitVector::new();
+        committee_bits.set(0, true).unwrap();
+        Attestation {
+            aggregation_bits,
+            data,
+            signature: interop_private_key(committee[position])
+                .sign(signing_root.as_slice())
+                .unwrap(),
+            committee_bits,
+        }
+    }
+
+    /// Return the interop state at slot 2, with every validator active.
+    fn attesting_state() -> BeaconState {
+        let mut state = interop_genesis_state(64, 0).unwrap();
+        state.process_slots(2).unwrap();
+        state
+    }
+
+    #[test]
+    fn aggregates_the_attestations_of_a_committee() {
+        let state = attesting_state();
+        let committee_size = state.get_beacon_committee(0, 0).unwrap().len();
+        let mut operation_pool = OperationPool::default();
+
+        // Attestations without participants in common are merged into a single aggregate
+        for position in 0..committee_size {
+            operation_pool
+                .insert_attestation(committee_attestation(&state, 0, position), &state)
+                .unwrap();
+        }
+        let aggregates = operation_pool.to_persisted().attestations;
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(
+            aggregates[0].aggregation_bits.num_set_bits(),
+            committee_size
+        );
+
+        // An attestation whose participants are already aggregated is dropped
+        operation_pool
+            .insert_attestation(committee_attestation(&state, 0, 0), &state)
+            .unwrap();
+        assert_eq!(operation_pool.to_persisted().attestations, aggregates);
+
+        let mut forged_attestation = committee_attestation(&state, 1, 0);
+        forged_attestation.signature = BLSSignature::infinity();
+        assert!(
+            operation_pool
+                .insert_attestation(forged_attestation, &state)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn selects_the_attestations_with_the_most_rewards() {
+        let state = attesting_state();
+        let committee_size = state.get_beacon_committee(0, 0).unwrap().len();
+        let mut operation_pool = OperationPool::default();
+        for position in 0..committee_size {
+            operation_pool
+                .insert_attestation(committee_attestation(&state, 0, position), &state)
+                .unwrap();
+        }
+        operation_pool
+            .insert_attestation(committee_attestation(&state, 1, 0), &state)
+            .unwrap();
+
+        // The whole committee of slot 0 earns more than a single timely head vote of slot 1
+        let attestations = operation_pool.get_attestations(&state, 1).unwrap();
+        assert_eq!(attestations.len(), 1);
+        assert_eq!(attestations[0].data.slot, 0);
+        assert_eq!(
+            attestations[0].aggregation_bits.num_set_bits(),
+            committee_size
+        );
+
+        let operations = operation_pool.get_block_operations(&state).unwrap();
+        assert_eq!(operations.attestations.len(), 2);
+        let mut block_state = state.clone();
+        for attestation in operations.attestations.iter() {
+            block_state.process_attestation(attestation).unwrap();
+        }
+
+        // Once included the attestations earn nothing more
+        assert!(
+            operation_pool
+                .get_attestations(&block_state, 1)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn validates_operations_against_the_head_state() {
+        let mut state = interop_genesis_state(4, 0).unwrap();
+        let mut operation_pool = OperationPool::default();
+
+        // Validators must have been active for ``SHARD_COMMITTEE_PERIOD`` epochs to exit
+        let voluntary_exit = signed_voluntary_exit(&state, 1);
+        assert!(
+            operation_pool
+                .insert_voluntary_exit(voluntary_exit.clone(), &state)
+                .is_err()
+        );
+
+        state.slot = chain_spec().shard_committee_period * SLOTS_PER_EPOCH;
+        let mut forged_exit = voluntary_exit.clone();
+        forged_exit.signature = BLSSignature::infinity();
+        assert!(
+            operation_pool
+                .insert_voluntary_exit(forged_exit, &state)
+                .is_err()
+        );
+        operation_pool
+            .insert_voluntary_exit(voluntary_exit.clone(), &state)
+            .unwrap();
+
+        let operations = operation_pool.get_block_operations(&state).unwrap();
+        assert_eq!(operations.voluntary_exits.to_vec(), vec![voluntary_exit]);
+
+        let restored = OperationPool::from_persisted(operation_pool.to_persisted());
+        assert_eq!(restored.to_persisted(), operation_pool.to_persisted());
+
+        state.validators[1].exit_epoch = 300;
+        operation_pool.prune(&state);
+        assert!(operation_pool.voluntary_exits.is_empty());
+    }
+}
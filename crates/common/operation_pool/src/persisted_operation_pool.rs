@@ -0,0 +1,18 @@
+use ream_consensus::{
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    electra::{attestation::Attestation, attester_slashing::AttesterSlashing},
+    proposer_slashing::ProposerSlashing,
+    voluntary_exit::SignedVoluntaryExit,
+};
+use ssz_derive::{Decode, Encode};
+
+/// The operations of an ``OperationPool`` as they are stored, to restore the pool after a
+/// restart.
+#[derive(Debug, Default, PartialEq, Clone, Encode, Decode)]
+pub struct PersistedOperationPool {
+    pub attestations: Vec<Attestation>,
+    pub attester_slashings: Vec<AttesterSlashing>,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBLSToExecutionChange>,
+}
@@ -1,7 +1,9 @@
+use std::slice;
+
 use kzg::eip_4844::verify_blob_kzg_proof_batch_raw;
 use ream_consensus::{
-    execution_engine::rpc_types::get_blobs::Blob, kzg_commitment::KZGCommitment,
-    polynomial_commitments::kzg_proof::KZGProof,
+    blob_sidecar::BlobSidecar, execution_engine::rpc_types::get_blobs::Blob,
+    kzg_commitment::KZGCommitment, polynomial_commitments::kzg_proof::KZGProof,
 };
 
 use super::{error::KzgError, trusted_setup};
@@ -27,10 +29,7 @@ pub fn verify_blob_kzg_proof_batch(
         .map(KZGCommitment::to_fixed_bytes)
         .collect::<Vec<_>>();
 
-    let raw_proofs = proofs_bytes
-        .iter()
-        .map(KZGProof::to_fixed_bytes)
-        .collect::<Vec<_>>();
+    let raw_proofs = proofs_bytes.iter().map(|proof| proof.0).collect::<Vec<_>>();
 
     let result = verify_blob_kzg_proof_batch_raw(
         &raw_blobs,
@@ -41,3 +40,118 @@ pub fn verify_blob_kzg_proof_batch(
 
     result.map_err(KzgError::KzgError).map_err(Into::into)
 }
+
+/// Given a blob and a KZG proof, verify that the blob data corresponds to the provided
+/// commitment.
+pub fn verify_blob_kzg_proof(
+    blob: &Blob,
+    commitment_bytes: &KZGCommitment,
+    proof_bytes: &KZGProof,
+) -> anyhow::Result<bool> {
+    verify_blob_kzg_proof_batch(
+        slice::from_ref(blob),
+        slice::from_ref(commitment_bytes),
+        slice::from_ref(proof_bytes),
+    )
+}
+
+/// Verify that the commitments of ``blob_sidecars`` are part of the block bodies of their
+/// headers, and that their blobs correspond to the commitments.
+pub fn verify_blob_sidecars(blob_sidecars: &[BlobSidecar]) -> anyhow::Result<bool> {
+    if !blob_sidecars
+        .iter()
+        .all(BlobSidecar::verify_blob_sidecar_inclusion_proof)
+    {
+        return Ok(false);
+    }
+
+    let blobs = blob_sidecars
+        .iter()
+        .map(|blob_sidecar| blob_sidecar.blob)
+        .collect::<Vec<_>>();
+    let commitments = blob_sidecars
+        .iter()
+        .map(|blob_sidecar| blob_sidecar.kzg_commitment)
+        .collect::<Vec<_>>();
+    let proofs = blob_sidecars
+        .iter()
+        .map(|blob_sidecar| blob_sidecar.kzg_proof)
+        .collect::<Vec<_>>();
+    verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::fixed_bytes;
+    use ream_consensus::{
+        blob_sidecar::get_blob_sidecars,
+        electra::{
+            beacon_block::{BeaconBlock, SignedBeaconBlock},
+            beacon_block_body::BeaconBlockBody,
+        },
+    };
+    use ssz_types::VariableList;
+
+    use super::*;
+
+    /// The compressed point at infinity, the commitment and proof of the zero blob.
+    const POINT_AT_INFINITY: [u8; 48] = {
+        let mut point = [0; 48];
+        point[0] = 0xc0;
+        point
+    };
+
+    /// Return the sidecars of a block with two zero blobs.
+    fn blob_sidecars() -> Vec<BlobSidecar> {
+        let signed_block = SignedBeaconBlock {
+            message: BeaconBlock {
+                slot: 1,
+                proposer_index: 0,
+                parent_root: Default::default(),
+                state_root: Default::default(),
+                body: BeaconBlockBody {
+                    blob_kzg_commitments: VariableList::from(vec![
+                        KZGCommitment(POINT_AT_INFINITY);
+                        2
+                    ]),
+                    ..Default::default()
+                },
+            },
+            signature: Default::default(),
+        };
+        get_blob_sidecars(
+            &signed_block,
+            vec![Blob::ZERO; 2],
+            vec![KZGProof::from(POINT_AT_INFINITY); 2],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn verifies_blob_sidecars() {
+        let blob_sidecars = blob_sidecars();
+
+        assert!(verify_blob_sidecars(&blob_sidecars).unwrap());
+        assert!(verify_blob_sidecars(&[]).unwrap());
+    }
+
+    #[test]
+    fn rejects_blob_sidecars_with_a_bad_kzg_proof() {
+        let mut blob_sidecars = blob_sidecars();
+        // The generator of G1 doesn't prove the zero blob
+        blob_sidecars[1].kzg_proof = fixed_bytes!(
+            "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+        );
+
+        assert!(!verify_blob_sidecars(&blob_sidecars).unwrap());
+    }
+
+    #[test]
+    fn rejects_blob_sidecars_outside_their_block() {
+        let mut blob_sidecars = blob_sidecars();
+        blob_sidecars[1].index = 2;
+
+        assert!(!verify_blob_sidecars(&blob_sidecars).unwrap());
+    }
+}
@@ -0,0 +1,436 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, bail, ensure};
+use async_trait::async_trait;
+use ream_consensus::{
+    chain_spec::chain_spec,
+    checkpoint::Checkpoint,
+    constants::{DOMAIN_BEACON_PROPOSER, GENESIS_SLOT},
+    misc::{
+        compute_domain, compute_epoch_at_slot, compute_signing_root, compute_start_slot_at_epoch,
+    },
+    signature_sets::{SignatureSet, find_invalid_signature_set},
+    versioned::{beacon_block::VersionedSignedBeaconBlock, beacon_state::VersionedBeaconState},
+};
+use ream_network_spec::networks::NetworkSpec;
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use tracing::info;
+
+/// The number of slots requested in a single ``BlocksByRange`` request during backfill.
+pub const BACKFILL_BATCH_SIZE: u64 = 64;
+
+/// A source of historical blocks, e.g. peers serving ``BlocksByRange``.
+#[async_trait]
+pub trait BlocksByRangeProvider {
+    /// Return the blocks in ``[start_slot, start_slot + count)`` in ascending slot order, empty
+    /// slots are skipped.
+    async fn blocks_by_range(
+        &self,
+        start_slot: u64,
+        count: u64,
+    ) -> anyhow::Result<Vec<VersionedSignedBeaconBlock>>;
+}
+
+/// Return the slot backfill has to reach so the node can serve ``BlocksByRange`` requests for the
+/// last ``MIN_EPOCHS_FOR_BLOCK_REQUESTS`` epochs.
+pub fn compute_backfill_target_slot(current_epoch: u64) -> u64 {
+    compute_start_slot_at_epoch(
+        current_epoch.saturating_sub(chain_spec().min_epochs_for_block_requests),
+    )
+}
+
+/// Downloads the blocks preceding the checkpoint sync anchor, newest first, until
+/// ``target_slot`` or genesis is reached.
+///
+/// Blocks are only checked against the parent root chain of the anchor block, their proposer
+/// signatures and the optional weak subjectivity checkpoint, no state is replayed. Progress is
+/// persisted after every batch so an interrupted backfill resumes where it stopped.
+pub struct BackfillSync<P: BlocksByRangeProvider> {
+    db: ReamDB,
+    provider: P,
+    network_spec: Arc<NetworkSpec>,
+    anchor_state: VersionedBeaconState,
+    anchor_block_root: B256,
+    target_slot: u64,
+    ws_checkpoint: Option<Checkpoint>,
+}
+
+impl<P: BlocksByRangeProvider> BackfillSync<P> {
+    pub fn new(
+        db: ReamDB,
+        provider: P,
+        network_spec: Arc<NetworkSpec>,
+        anchor_state: VersionedBeaconState,
+        anchor_block_root: B256,
+        target_slot: u64,
+        ws_checkpoint: Option<Checkpoint>,
+    ) -> Self {
+        Self {
+            db,
+            provider,
+            network_spec,
+            anchor_state,
+            anchor_block_root,
+            target_slot,
+            ws_checkpoint,
+        }
+    }
+
+    pub async fn run(&self) -> anyhow::Result<()> {
+        let oldest_block_root = self
+            .db
+            .backfill_progress_provider()
+            .get()?
+            .unwrap_or(self.anchor_block_root);
+        let oldest_block = self
+            .db
+            .beacon_block_provider()
+            .get(oldest_block_root)?
+            .ok_or_else(|| anyhow!("Oldest stored block {oldest_block_root} not found"))?;
+
+        let mut expected_root = oldest_block.parent_root();
+        let mut end_slot = oldest_block.slot();
+        let mut child_slot = end_slot;
+        info!(
+            "Starting backfill from slot {end_slot} to slot {}",
+            self.target_slot
+        );
+
+        while end_slot > self.target_slot && end_slot != GENESIS_SLOT {
+            let start_slot = end_slot
+                .saturating_sub(BACKFILL_BATCH_SIZE)
+                .max(self.target_slot);
+            let blocks = self
+                .provider
+                .blocks_by_range(start_slot, end_slot - start_slot)
+                .await?;
+            end_slot = start_slot;
+
+            // The whole range was empty slots, the parent is further back
+            if blocks.is_empty() {
+                continue;
+            }
+
+            // Walk the batch newest first, every block must be the parent of the one after it
+            let mut block_roots = Vec::with_capacity(blocks.len());
+            for block in blocks.iter().rev() {
+                let block_root = block.block_root();
+                ensure!(
+                    block_root == expected_root,
+                    "Block at slot {} with root {block_root} is not the expected parent {expected_root}",
+                    block.slot()
+                );
+                self.verify_weak_subjectivity_checkpoint(block.slot(), child_slot, block_root)?;
+                expected_root = block.parent_root();
+                child_slot = block.slot();
+                block_roots.push(block_root);
+            }
+
+            self.verify_proposer_signatures(&blocks)?;
+
+            let beacon_block_provider = self.db.beacon_block_provider();
+            for (block, block_root) in blocks.iter().rev().zip(&block_roots) {
+                beacon_block_provider.insert(*block_root, block.clone())?;
+            }
+
+            let oldest_block_root = *block_roots
+                .last()
+                .ok_or_else(|| anyhow!("Backfill batch is empty"))?;
+            self.db
+                .backfill_progress_provider()
+                .insert(oldest_block_root)?;
+            info!(
+                "Backfilled {} blocks down to slot {}",
+                blocks.len(),
+                blocks[0].slot()
+            );
+
+            if blocks[0].slot() == GENESIS_SLOT {
+                break;
+            }
+        }
+
+        info!("Backfill complete");
+        Ok(())
+    }
+
+    /// The weak subjectivity checkpoint block is the last block at or before the first slot of
+    /// the checkpoint epoch, i.e. the block whose child is past that slot.
+    fn verify_weak_subjectivity_checkpoint(
+        &self,
+        slot: u64,
+        child_slot: u64,
+        block_root: B256,
+    ) -> anyhow::Result<()> {
+        let Some(ws_checkpoint) = self.ws_checkpoint else {
+            return Ok(());
+        };
+        let checkpoint_slot = compute_start_slot_at_epoch(ws_checkpoint.epoch);
+        if slot <= checkpoint_slot && checkpoint_slot < child_slot {
+            ensure!(
+                block_root == ws_checkpoint.root,
+                "Block {block_root} at slot {slot} contradicts the weak subjectivity checkpoint {}",
+                ws_checkpoint.root
+            );
+        }
+        Ok(())
+    }
+
+    /// Collect the proposer signature of every block in the batch and verify them together. The
+    /// validator registry is append only, so the anchor state knows every historical proposer.
+    ///
+    /// The anchor state only knows the fork it is in and the one before, so the domain of each
+    /// block is computed from the fork scheduled at its epoch instead.
+    fn verify_proposer_signatures(
+        &self,
+        blocks: &[VersionedSignedBeaconBlock],
+    ) -> anyhow::Result<()> {
+        let fork_schedule = self.network_spec.fork_schedule();
+        let genesis_validators_root = self.anchor_state.genesis_validators_root();
+
+        // The genesis block is not signed
+        let signed_blocks = blocks
+            .iter()
+            .filter(|block| block.slot() != GENESIS_SLOT)
+            .collect::<Vec<_>>();
+        let mut signature_sets = Vec::with_capacity(signed_blocks.len());
+        for block in &signed_blocks {
+            let proposer = self
+                .anchor_state
+                .validators()
+                .get(block.proposer_index() as usize)
+                .ok_or_else(|| anyhow!("Proposer index {} out of range", block.proposer_index()))?;
+            let domain = compute_domain(
+                DOMAIN_BEACON_PROPOSER,
+                Some(fork_schedule.fork_version_at_epoch(compute_epoch_at_slot(block.slot()))),
+                Some(genesis_validators_root),
+            );
+            // The root of a block is the root of its header
+            signature_sets.push(SignatureSet::single_pubkey(
+                block.signature().clone(),
+                proposer.pubkey.clone(),
+                compute_signing_root(block.signed_block_header().message, domain),
+            ));
+        }
+
+        if let Some(index) = find_invalid_signature_set(&signature_sets)? {
+            bail!(
+                "Invalid proposer signature for block at slot {}",
+                signed_blocks[index].slot()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_bls::{BLSSignature, traits::Signable};
+    use ream_consensus::{
+        constants::SLOTS_PER_EPOCH,
+        electra::{
+            beacon_block::{BeaconBlock, SignedBeaconBlock},
+            beacon_block_body::BeaconBlockBody,
+        },
+        genesis::{interop_genesis_state, interop_private_key},
+    };
+    use ream_network_spec::networks::DEV;
+    use tree_hash::TreeHash;
+
+    use super::*;
+
+    const VALIDATOR_COUNT: u64 = 4;
+
+    struct InMemoryProvider {
+        blocks: Vec<VersionedSignedBeaconBlock>,
+    }
+
+    #[async_trait]
+    impl BlocksByRangeProvider for InMemoryProvider {
+        async fn blocks_by_range(
+            &self,
+            start_slot: u64,
+            count: u64,
+        ) -> anyhow::Result<Vec<VersionedSignedBeaconBlock>> {
+            Ok(self
+                .blocks
+                .iter()
+                .filter(|block| (start_slot..start_slot + count).contains(&block.slot()))
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// A network that forks to Electra at epoch 1, so the chain spans two fork versions.
+    fn network_spec() -> Arc<NetworkSpec> {
+        let mut network_spec = (**DEV).clone();
+        network_spec.chain_spec.electra_fork_epoch = 1;
+        Arc::new(network_spec)
+    }
+
+    fn signed_block(
+        network_spec: &NetworkSpec,
+        genesis_validators_root: B256,
+        message: BeaconBlock,
+    ) -> VersionedSignedBeaconBlock {
+        let domain = compute_domain(
+            DOMAIN_BEACON_PROPOSER,
+            Some(
+                network_spec
+                    .fork_schedule()
+                    .fork_version_at_epoch(compute_epoch_at_slot(message.slot)),
+            ),
+            Some(genesis_validators_root),
+        );
+        let signature = interop_private_key(message.proposer_index)
+            .sign(compute_signing_root(&message, domain).as_slice())
+            .unwrap();
+        VersionedSignedBeaconBlock::Electra(SignedBeaconBlock { message, signature })
+    }
+
+    /// Return a chain from genesis into the second epoch, skipping slot 3.
+    fn build_chain(
+        network_spec: &NetworkSpec,
+        genesis_validators_root: B256,
+    ) -> Vec<VersionedSignedBeaconBlock> {
+        let mut blocks = vec![VersionedSignedBeaconBlock::Electra(SignedBeaconBlock {
+            message: BeaconBlock {
+                slot: GENESIS_SLOT,
+                proposer_index: 0,
+                parent_root: B256::ZERO,
+                state_root: B256::ZERO,
+                body: BeaconBlockBody::default(),
+            },
+            signature: BLSSignature::default(),
+        })];
+        for slot in (1..SLOTS_PER_EPOCH + 4).filter(|slot| *slot != 3) {
+            let parent_root = blocks.last().unwrap().block_root();
+            blocks.push(signed_block(
+                network_spec,
+                genesis_validators_root,
+                BeaconBlock {
+                    slot,
+                    proposer_index: slot % VALIDATOR_COUNT,
+                    parent_root,
+                    state_root: B256::ZERO,
+                    body: BeaconBlockBody::default(),
+                },
+            ));
+        }
+        blocks
+    }
+
+    fn backfill_sync(
+        network_spec: Arc<NetworkSpec>,
+        anchor_state: VersionedBeaconState,
+        mut blocks: Vec<VersionedSignedBeaconBlock>,
+        ws_checkpoint: Option<Checkpoint>,
+    ) -> BackfillSync<InMemoryProvider> {
+        let db = ReamDB::new(None, true).unwrap();
+        let anchor_block = blocks.pop().unwrap();
+        let anchor_block_root = anchor_block.block_root();
+        db.beacon_block_provider()
+            .insert(anchor_block_root, anchor_block)
+            .unwrap();
+        BackfillSync::new(
+            db,
+            InMemoryProvider { blocks },
+            network_spec,
+            anchor_state,
+            anchor_block_root,
+            GENESIS_SLOT,
+            ws_checkpoint,
+        )
+    }
+
+    #[tokio::test]
+    async fn backfills_a_chain_down_to_genesis() {
+        let network_spec = network_spec();
+        let anchor_state = interop_genesis_state(VALIDATOR_COUNT, 0).unwrap();
+        let blocks = build_chain(&network_spec, anchor_state.genesis_validators_root);
+        let genesis_root = blocks[0].block_root();
+        let ws_checkpoint = Checkpoint {
+            epoch: 1,
+            root: blocks[SLOTS_PER_EPOCH as usize - 1].block_root(),
+        };
+
+        let backfill_sync = backfill_sync(
+            network_spec,
+            VersionedBeaconState::Electra(anchor_state),
+            blocks.clone(),
+            Some(ws_checkpoint),
+        );
+        backfill_sync.run().await.unwrap();
+
+        for block in &blocks {
+            assert_eq!(
+                backfill_sync
+                    .db
+                    .beacon_block_provider()
+                    .get(block.block_root())
+                    .unwrap()
+                    .as_ref(),
+                Some(block)
+            );
+        }
+        assert_eq!(
+            backfill_sync.db.backfill_progress_provider().get().unwrap(),
+            Some(genesis_root)
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_a_broken_parent_link() {
+        let network_spec = network_spec();
+        let anchor_state = interop_genesis_state(VALIDATOR_COUNT, 0).unwrap();
+        let mut blocks = build_chain(&network_spec, anchor_state.genesis_validators_root);
+
+        // Replace a block in the middle of the chain by a sibling its child doesn't point to
+        let index = blocks.len() / 2;
+        let VersionedSignedBeaconBlock::Electra(block) = &blocks[index] else {
+            unreachable!()
+        };
+        let mut sibling = block.message.clone();
+        sibling.state_root = B256::repeat_byte(1);
+        assert_ne!(sibling.tree_hash_root(), block.message.tree_hash_root());
+        blocks[index] = signed_block(&network_spec, anchor_state.genesis_validators_root, sibling);
+
+        let backfill_sync = backfill_sync(
+            network_spec,
+            VersionedBeaconState::Electra(anchor_state),
+            blocks.clone(),
+            None,
+        );
+        assert!(backfill_sync.run().await.is_err());
+        assert!(
+            backfill_sync
+                .db
+                .beacon_block_provider()
+                .get(blocks[1].block_root())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_a_signature_over_the_wrong_fork() {
+        let network_spec = network_spec();
+        let anchor_state = interop_genesis_state(VALIDATOR_COUNT, 0).unwrap();
+
+        // Sign the chain as if Electra was active from genesis
+        let blocks = build_chain(&DEV, anchor_state.genesis_validators_root);
+
+        let backfill_sync = backfill_sync(
+            network_spec,
+            VersionedBeaconState::Electra(anchor_state),
+            blocks,
+            None,
+        );
+        assert!(backfill_sync.run().await.is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use async_trait::async_trait;
+use ream_consensus::{
+    checkpoint::Checkpoint,
+    versioned::{beacon_block::VersionedSignedBeaconBlock, beacon_state::VersionedBeaconState},
+};
+use ream_network_spec::networks::NetworkSpec;
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use reqwest::{Client, StatusCode, header::ACCEPT};
+use tracing::info;
+
+use crate::{backfill::BlocksByRangeProvider, weak_subjectivity::verify_anchor_state};
+
+/// A client of the SSZ endpoints of a trusted beacon node, used to fetch the checkpoint sync
+/// anchor and, until req/resp is available, the blocks preceding it.
+pub struct BeaconApiClient {
+    http_client: Client,
+    url: String,
+    network_spec: Arc<NetworkSpec>,
+}
+
+impl BeaconApiClient {
+    pub fn new(url: String, network_spec: Arc<NetworkSpec>) -> Self {
+        Self {
+            http_client: Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+            network_spec,
+        }
+    }
+
+    /// Return the SSZ encoded response of ``path``, or ``None`` if the resource doesn't exist.
+    async fn get_ssz(&self, path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let response = self
+            .http_client
+            .get(format!("{}{path}", self.url))
+            .header(ACCEPT, "application/octet-stream")
+            .send()
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.bytes().await?.to_vec()))
+    }
+
+    pub async fn get_block(
+        &self,
+        block_id: &str,
+    ) -> anyhow::Result<Option<VersionedSignedBeaconBlock>> {
+        let Some(bytes) = self
+            .get_ssz(&format!("/eth/v2/beacon/blocks/{block_id}"))
+            .await?
+        else {
+            return Ok(None);
+        };
+        VersionedSignedBeaconBlock::from_ssz_bytes_with_fork_schedule(
+            &bytes,
+            &self.network_spec.fork_schedule(),
+        )
+        .map(Some)
+        .map_err(|err| anyhow!("Failed to decode block {block_id}: {err:?}"))
+    }
+
+    pub async fn get_state(&self, state_root: B256) -> anyhow::Result<VersionedBeaconState> {
+        let bytes = self
+            .get_ssz(&format!("/eth/v2/debug/beacon/states/{state_root}"))
+            .await?
+            .ok_or_else(|| anyhow!("State {state_root} not found"))?;
+        let state = VersionedBeaconState::from_ssz_bytes_with_fork_schedule(
+            &bytes,
+            &self.network_spec.fork_schedule(),
+        )
+        .map_err(|err| anyhow!("Failed to decode state {state_root}: {err:?}"))?;
+        ensure!(
+            state.state_root() == state_root,
+            "Received state with root {} instead of {state_root}",
+            state.state_root()
+        );
+        Ok(state)
+    }
+}
+
+#[async_trait]
+impl BlocksByRangeProvider for BeaconApiClient {
+    async fn blocks_by_range(
+        &self,
+        start_slot: u64,
+        count: u64,
+    ) -> anyhow::Result<Vec<VersionedSignedBeaconBlock>> {
+        let mut blocks = vec![];
+        for slot in start_slot..start_slot + count {
+            // Empty slots are not found
+            if let Some(block) = self.get_block(&slot.to_string()).await? {
+                blocks.push(block);
+            }
+        }
+        Ok(blocks)
+    }
+}
+
+/// Initialize ``db`` from the finalized block and state of a trusted beacon node and return the
+/// root of the anchor block and the anchor state. The anchor is rejected if it is outside of its
+/// weak subjectivity period at ``current_epoch`` or contradicts ``ws_checkpoint``.
+///
+/// If ``db`` was already initialized, the node resumes from its finalized checkpoint instead.
+pub async fn checkpoint_sync(
+    db: &ReamDB,
+    client: &BeaconApiClient,
+    ws_checkpoint: Option<Checkpoint>,
+    current_epoch: u64,
+) -> anyhow::Result<(B256, VersionedBeaconState)> {
+    if let Some(finalized_checkpoint) = db.finalized_checkpoint_provider().get()? {
+        let anchor_state = db
+            .beacon_state_provider()
+            .get(finalized_checkpoint.root)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Finalized state {} not found in the database",
+                    finalized_checkpoint.root
+                )
+            })?;
+        info!(
+            "Resuming from the finalized checkpoint at epoch {}",
+            finalized_checkpoint.epoch
+        );
+        return Ok((finalized_checkpoint.root, anchor_state));
+    }
+
+    let anchor_block = client
+        .get_block("finalized")
+        .await?
+        .ok_or_else(|| anyhow!("Finalized block not found"))?;
+    let anchor_state = client.get_state(anchor_block.state_root()).await?;
+    let anchor_block_root = anchor_block.block_root();
+    verify_anchor_state(
+        &anchor_state,
+        anchor_block_root,
+        ws_checkpoint,
+        current_epoch,
+    )?;
+    let anchor_checkpoint = Checkpoint {
+        epoch: anchor_state.get_current_epoch(),
+        root: anchor_block_root,
+    };
+
+    db.beacon_block_provider()
+        .insert(anchor_block_root, anchor_block)?;
+    db.beacon_state_provider()
+        .insert(anchor_block_root, anchor_state.clone())?;
+    db.justified_checkpoint_provider()
+        .insert(anchor_checkpoint)?;
+    db.finalized_checkpoint_provider()
+        .insert(anchor_checkpoint)?;
+    info!(
+        "Checkpoint synced to block {anchor_block_root} at slot {}",
+        anchor_state.slot()
+    );
+
+    Ok((anchor_block_root, anchor_state))
+}
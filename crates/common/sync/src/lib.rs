@@ -0,0 +1,4 @@
+pub mod backfill;
+pub mod checkpoint_sync;
+pub mod light_client;
+pub mod weak_subjectivity;
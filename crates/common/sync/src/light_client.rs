@@ -0,0 +1,126 @@
+use alloy_primitives::B256;
+use anyhow::anyhow;
+use ream_consensus::{
+    constants::{GENESIS_SLOT, MIN_SYNC_COMMITTEE_PARTICIPANTS},
+    electra::{
+        light_client_bootstrap::LightClientBootstrap,
+        light_client_finality_update::LightClientFinalityUpdate,
+        light_client_optimistic_update::LightClientOptimisticUpdate,
+        light_client_update::LightClientUpdate,
+    },
+    misc::{
+        compute_epoch_at_slot, compute_start_slot_at_epoch, compute_sync_committee_period_at_slot,
+    },
+    versioned::{beacon_block::VersionedSignedBeaconBlock, beacon_state::VersionedBeaconState},
+};
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use tree_hash::TreeHash;
+
+/// Produce and store the light client data made available by importing ``block`` with its post
+/// state ``state``.
+///
+/// The parent of the block is the attested block of the update signed by its sync aggregate, it
+/// must already be in ``db`` along with its post state. Blocks before Electra, or whose parent is
+/// from a fork before Electra, are skipped.
+pub fn store_light_client_data(
+    db: &ReamDB,
+    block: &VersionedSignedBeaconBlock,
+    state: &VersionedBeaconState,
+) -> anyhow::Result<()> {
+    let (VersionedSignedBeaconBlock::Electra(block), VersionedBeaconState::Electra(state)) =
+        (block, state)
+    else {
+        return Ok(());
+    };
+    let parent_root = block.message.parent_root;
+    let (
+        Some(VersionedSignedBeaconBlock::Electra(attested_block)),
+        Some(VersionedBeaconState::Electra(attested_state)),
+    ) = (
+        db.beacon_block_provider().get(parent_root)?,
+        db.beacon_state_provider().get(parent_root)?,
+    )
+    else {
+        return Ok(());
+    };
+
+    // Bootstraps are requested for checkpoint roots, the last block at or before the first slot
+    // of an epoch
+    let epoch_start_slot = compute_start_slot_at_epoch(compute_epoch_at_slot(block.message.slot));
+    if block.message.slot == epoch_start_slot {
+        db.light_client_bootstrap_provider().insert(
+            block.message.tree_hash_root(),
+            LightClientBootstrap::new(state, block)?,
+        )?;
+    } else if attested_block.message.slot < epoch_start_slot {
+        db.light_client_bootstrap_provider().insert(
+            parent_root,
+            LightClientBootstrap::new(&attested_state, &attested_block)?,
+        )?;
+    }
+
+    if (block
+        .message
+        .body
+        .sync_aggregate
+        .sync_committee_bits
+        .num_set_bits() as u64)
+        < MIN_SYNC_COMMITTEE_PARTICIPANTS
+    {
+        return Ok(());
+    }
+
+    // A zero finalized root is the finalized checkpoint of genesis, whose block is the genesis
+    // block
+    let finalized_root = match attested_state.finalized_checkpoint.root {
+        root if root == B256::ZERO => db
+            .slot_index_provider()
+            .get(GENESIS_SLOT)?
+            .ok_or_else(|| anyhow!("Genesis block not found"))?,
+        root => root,
+    };
+    let finalized_block = match db.beacon_block_provider().get(finalized_root)? {
+        Some(VersionedSignedBeaconBlock::Electra(finalized_block)) => Some(finalized_block),
+        _ => None,
+    };
+    let update = LightClientUpdate::new(
+        state,
+        block,
+        &attested_state,
+        &attested_block,
+        finalized_block.as_ref(),
+    )?;
+
+    // Keep the best update of each sync committee period
+    let update_provider = db.light_client_update_provider();
+    let period = compute_sync_committee_period_at_slot(update.attested_header.beacon.slot);
+    if update_provider
+        .get(period)?
+        .is_none_or(|best_update| update.is_better_update(&best_update))
+    {
+        update_provider.insert(period, update.clone())?;
+    }
+
+    // Keep the updates with the latest attested header
+    let attested_slot = update.attested_header.beacon.slot;
+    let finality_update_provider = db.light_client_finality_update_provider();
+    if update.is_finality_update()
+        && finality_update_provider
+            .get()?
+            .is_none_or(|latest| latest.attested_header.beacon.slot < attested_slot)
+    {
+        finality_update_provider.insert(LightClientFinalityUpdate::from(&update))?;
+    }
+    let optimistic_update_provider = db.light_client_optimistic_update_provider();
+    if optimistic_update_provider
+        .get()?
+        .is_none_or(|latest| latest.attested_header.beacon.slot < attested_slot)
+    {
+        optimistic_update_provider.insert(LightClientOptimisticUpdate::from(&update))?;
+    }
+
+    Ok(())
+}
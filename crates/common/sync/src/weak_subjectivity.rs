@@ -0,0 +1,169 @@
+use alloy_primitives::B256;
+use anyhow::{bail, ensure};
+use ream_consensus::{
+    checkpoint::Checkpoint, constants::SLOTS_PER_HISTORICAL_ROOT,
+    misc::compute_start_slot_at_epoch, versioned::beacon_state::VersionedBeaconState,
+};
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use tracing::warn;
+
+/// Return an error if the chain stored in ``db`` contradicts ``ws_checkpoint``.
+///
+/// The checkpoint block is the last block at or before the first slot of the checkpoint epoch.
+/// Blocks the database does not know about yet are checked while they are synced.
+pub fn verify_weak_subjectivity_checkpoint(
+    db: &ReamDB,
+    ws_checkpoint: Checkpoint,
+) -> anyhow::Result<()> {
+    let slot_index_provider = db.slot_index_provider();
+
+    if let Some(block) = db.beacon_block_provider().get(ws_checkpoint.root)? {
+        ensure!(
+            slot_index_provider.get(block.slot())? == Some(ws_checkpoint.root),
+            "Weak subjectivity checkpoint block {} is not canonical",
+            ws_checkpoint.root
+        );
+        return Ok(());
+    }
+
+    let checkpoint_slot = compute_start_slot_at_epoch(ws_checkpoint.epoch);
+    if let Some(block_root) = slot_index_provider.get(checkpoint_slot)? {
+        ensure!(
+            block_root == ws_checkpoint.root,
+            "Block {block_root} at slot {checkpoint_slot} contradicts the weak subjectivity checkpoint {}",
+            ws_checkpoint.root
+        );
+    }
+
+    Ok(())
+}
+
+/// Return an error if the checkpoint sync ``anchor_state``, the state of block
+/// ``anchor_block_root``, is outside of its weak subjectivity period at ``current_epoch`` or
+/// contradicts ``ws_checkpoint``.
+///
+/// An anchor newer than the checkpoint is checked against its ``block_roots`` while the checkpoint
+/// is in range of them, older checkpoints are checked by backfill.
+pub fn verify_anchor_state(
+    anchor_state: &VersionedBeaconState,
+    anchor_block_root: B256,
+    ws_checkpoint: Option<Checkpoint>,
+    current_epoch: u64,
+) -> anyhow::Result<()> {
+    ensure!(
+        anchor_state.is_within_weak_subjectivity_period(current_epoch),
+        "Anchor state at epoch {} is outside of its weak subjectivity period of {} epochs",
+        anchor_state.get_current_epoch(),
+        anchor_state.compute_weak_subjectivity_period()
+    );
+
+    let Some(ws_checkpoint) = ws_checkpoint else {
+        return Ok(());
+    };
+    if anchor_block_root == ws_checkpoint.root {
+        return Ok(());
+    }
+    let checkpoint_slot = compute_start_slot_at_epoch(ws_checkpoint.epoch);
+    if anchor_state.slot() <= checkpoint_slot {
+        bail!(
+            "Anchor block {anchor_block_root} at slot {} does not descend from the weak subjectivity checkpoint {}",
+            anchor_state.slot(),
+            ws_checkpoint.root
+        );
+    }
+    if anchor_state.slot() <= checkpoint_slot + SLOTS_PER_HISTORICAL_ROOT {
+        let block_root = anchor_state.get_block_root_at_slot(checkpoint_slot)?;
+        ensure!(
+            block_root == ws_checkpoint.root,
+            "Anchor state contradicts the weak subjectivity checkpoint {}, its block at slot {checkpoint_slot} is {block_root}",
+            ws_checkpoint.root
+        );
+    }
+
+    Ok(())
+}
+
+/// Log a warning if the finalized state stored in ``db`` is outside of its weak subjectivity
+/// period at ``current_epoch``.
+pub fn warn_if_outside_weak_subjectivity_period(
+    db: &ReamDB,
+    current_epoch: u64,
+) -> anyhow::Result<()> {
+    let Some(finalized_checkpoint) = db.finalized_checkpoint_provider().get()? else {
+        return Ok(());
+    };
+    let Some(finalized_state) = db.beacon_state_provider().get(finalized_checkpoint.root)? else {
+        return Ok(());
+    };
+
+    if !finalized_state.is_within_weak_subjectivity_period(current_epoch) {
+        warn!(
+            "The finalized state at epoch {} is outside of the weak subjectivity period of {} epochs, restart from a recent weak subjectivity checkpoint",
+            finalized_checkpoint.epoch,
+            finalized_state.compute_weak_subjectivity_period()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_consensus::{constants::SLOTS_PER_EPOCH, genesis::interop_genesis_state};
+
+    use super::*;
+
+    #[test]
+    fn verifies_the_anchor_state_against_the_checkpoint() {
+        let mut state = interop_genesis_state(4, 0).unwrap();
+        state.process_slots(2 * SLOTS_PER_EPOCH).unwrap();
+        let anchor_state = VersionedBeaconState::Electra(state);
+        let anchor_block_root = B256::repeat_byte(1);
+        let genesis_checkpoint = Checkpoint {
+            epoch: 0,
+            root: anchor_state.get_block_root_at_slot(0).unwrap(),
+        };
+
+        assert!(
+            verify_anchor_state(
+                &anchor_state,
+                anchor_block_root,
+                Some(genesis_checkpoint),
+                2
+            )
+            .is_ok()
+        );
+        assert!(
+            verify_anchor_state(
+                &anchor_state,
+                anchor_block_root,
+                Some(Checkpoint {
+                    epoch: 0,
+                    root: B256::repeat_byte(2),
+                }),
+                2
+            )
+            .is_err()
+        );
+
+        // A checkpoint past the anchor can only be the anchor itself
+        let future_checkpoint = Checkpoint {
+            epoch: 3,
+            root: B256::repeat_byte(2),
+        };
+        assert!(
+            verify_anchor_state(&anchor_state, anchor_block_root, Some(future_checkpoint), 2)
+                .is_err()
+        );
+
+        let ws_period = anchor_state.compute_weak_subjectivity_period();
+        assert!(verify_anchor_state(&anchor_state, anchor_block_root, None, 2 + ws_period).is_ok());
+        assert!(
+            verify_anchor_state(&anchor_state, anchor_block_root, None, 3 + ws_period).is_err()
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use alloy_primitives::B256;
+
+/// A BLS secret key, the scalar as 32 big-endian bytes.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PrivateKey {
+    pub inner: B256,
+}
+
+impl PrivateKey {
+    pub fn to_bytes(&self) -> &[u8] {
+        self.inner.as_slice()
+    }
+}
@@ -0,0 +1,10 @@
+use crate::{pubkey::PubKey, signature::BLSSignature};
+
+/// A signature with the public keys and the message it was signed over, the unit of batch
+/// verification.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignatureSet<'a> {
+    pub signature: &'a BLSSignature,
+    pub pubkeys: &'a [PubKey],
+    pub message: &'a [u8],
+}
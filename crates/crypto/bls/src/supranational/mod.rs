@@ -1,4 +1,5 @@
 pub mod aggregate_pubkey;
 pub mod errors;
+pub mod private_key;
 pub mod pubkey;
 pub mod signature;
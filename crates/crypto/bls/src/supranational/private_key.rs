@@ -0,0 +1,36 @@
+use blst::min_pk::SecretKey as BlstSecretKey;
+
+use crate::{
+    BLSSignature, PubKey,
+    constants::DST,
+    errors::BLSError,
+    private_key::PrivateKey,
+    traits::{Signable, SupranationalSignable},
+};
+
+impl PrivateKey {
+    pub fn to_blst_secret_key(&self) -> Result<BlstSecretKey, BLSError> {
+        BlstSecretKey::from_bytes(self.to_bytes()).map_err(|err| BLSError::BlstError(err.into()))
+    }
+}
+
+impl Signable for PrivateKey {
+    type Error = BLSError;
+
+    fn public_key(&self) -> Result<PubKey, BLSError> {
+        Ok(PubKey::from(self.to_blst_secret_key()?.sk_to_pk()))
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<BLSSignature, BLSError> {
+        Ok(BLSSignature {
+            inner: self
+                .to_blst_secret_key()?
+                .sign(message, DST, &[])
+                .to_bytes()
+                .to_vec()
+                .into(),
+        })
+    }
+}
+
+impl SupranationalSignable for PrivateKey {}
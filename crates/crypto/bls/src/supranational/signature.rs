@@ -1,13 +1,27 @@
-use blst::{BLST_ERROR, min_pk::Signature as BlstSignature};
+use blst::{
+    BLST_ERROR, blst_scalar, blst_scalar_from_uint64,
+    min_pk::{
+        AggregatePublicKey as BlstAggregatePublicKey, AggregateSignature as BlstAggregateSignature,
+        Signature as BlstSignature,
+    },
+};
+use rand::Rng;
 
 use crate::{
     constants::DST,
     errors::BLSError,
     pubkey::PubKey,
     signature::BLSSignature,
-    traits::{SupranationalVerifiable, Verifiable},
+    signature_set::SignatureSet,
+    traits::{
+        BatchVerifiable, SignatureAggregatable, SupranationalBatchVerifiable,
+        SupranationalSignatureAggregatable, SupranationalVerifiable, Verifiable,
+    },
 };
 
+/// The number of random bits used to weight each signature set in batch verification.
+const RAND_BITS: usize = 64;
+
 impl BLSSignature {
     pub fn to_blst_signature(&self) -> Result<BlstSignature, BLSError> {
         BlstSignature::from_bytes(&self.inner).map_err(|e| BLSError::BlstError(e.into()))
@@ -48,3 +62,155 @@ impl Verifiable for BLSSignature {
 }
 
 impl SupranationalVerifiable for BLSSignature {}
+
+impl BatchVerifiable for BLSSignature {
+    type Error = BLSError;
+
+    fn verify_signature_sets(signature_sets: &[SignatureSet]) -> Result<bool, BLSError> {
+        if signature_sets.is_empty() {
+            return Ok(true);
+        }
+
+        let mut signatures = Vec::with_capacity(signature_sets.len());
+        let mut public_keys = Vec::with_capacity(signature_sets.len());
+        let mut messages = Vec::with_capacity(signature_sets.len());
+        for signature_set in signature_sets {
+            if signature_set.pubkeys.is_empty() {
+                return Ok(false);
+            }
+            let set_public_keys = signature_set
+                .pubkeys
+                .iter()
+                .map(|key| key.to_blst_pubkey())
+                .collect::<Result<Vec<_>, _>>()?;
+            let aggregate_public_key = BlstAggregatePublicKey::aggregate(
+                &set_public_keys.iter().collect::<Vec<_>>(),
+                true,
+            )
+            .map_err(|err| BLSError::BlstError(err.into()))?;
+
+            signatures.push(signature_set.signature.to_blst_signature()?);
+            public_keys.push(aggregate_public_key.to_public_key());
+            messages.push(signature_set.message);
+        }
+
+        // Non zero random scalars so an invalid set can't be cancelled out by another one
+        let mut rng = rand::thread_rng();
+        let scalars = (0..signature_sets.len())
+            .map(|_| {
+                let mut scalar = blst_scalar::default();
+                let limbs = [rng.gen_range(1..=u64::MAX), 0, 0, 0];
+                // SAFETY: `limbs` holds the 4 limbs `blst_scalar_from_uint64` reads.
+                unsafe { blst_scalar_from_uint64(&mut scalar, limbs.as_ptr()) };
+                scalar
+            })
+            .collect::<Vec<_>>();
+
+        Ok(BlstSignature::verify_multiple_aggregate_signatures(
+            &messages,
+            DST,
+            &public_keys.iter().collect::<Vec<_>>(),
+            false,
+            &signatures.iter().collect::<Vec<_>>(),
+            true,
+            &scalars,
+            RAND_BITS,
+        ) == BLST_ERROR::BLST_SUCCESS)
+    }
+}
+
+impl SupranationalBatchVerifiable for BLSSignature {}
+
+impl SignatureAggregatable for BLSSignature {
+    type Error = BLSError;
+
+    fn aggregate(signatures: &[&BLSSignature]) -> Result<BLSSignature, BLSError> {
+        if signatures.is_empty() {
+            return Ok(BLSSignature::infinity());
+        }
+
+        let blst_signatures = signatures
+            .iter()
+            .map(|signature| signature.to_blst_signature())
+            .collect::<Result<Vec<_>, _>>()?;
+        let aggregate_signature =
+            BlstAggregateSignature::aggregate(&blst_signatures.iter().collect::<Vec<_>>(), false)
+                .map_err(|err| BLSError::BlstError(err.into()))?;
+
+        Ok(BLSSignature {
+            inner: aggregate_signature
+                .to_signature()
+                .to_bytes()
+                .to_vec()
+                .into(),
+        })
+    }
+}
+
+impl SupranationalSignatureAggregatable for BLSSignature {}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+
+    use super::*;
+    use crate::{private_key::PrivateKey, traits::Signable};
+
+    #[test]
+    fn verifies_a_batch_of_valid_signature_sets() {
+        let messages = [[1; 32], [2; 32], [3; 32]];
+        let private_keys = (1..=4)
+            .map(|index| PrivateKey {
+                inner: B256::with_last_byte(index),
+            })
+            .collect::<Vec<_>>();
+        let pubkeys = private_keys
+            .iter()
+            .map(|private_key| private_key.public_key().unwrap())
+            .collect::<Vec<_>>();
+        // The last set is signed by two validators over the same message
+        let signatures = [
+            private_keys[0].sign(&messages[0]).unwrap(),
+            private_keys[1].sign(&messages[1]).unwrap(),
+            BLSSignature::aggregate(&[
+                &private_keys[2].sign(&messages[2]).unwrap(),
+                &private_keys[3].sign(&messages[2]).unwrap(),
+            ])
+            .unwrap(),
+        ];
+        let signature_sets = [
+            SignatureSet {
+                signature: &signatures[0],
+                pubkeys: &pubkeys[0..1],
+                message: &messages[0],
+            },
+            SignatureSet {
+                signature: &signatures[1],
+                pubkeys: &pubkeys[1..2],
+                message: &messages[1],
+            },
+            SignatureSet {
+                signature: &signatures[2],
+                pubkeys: &pubkeys[2..4],
+                message: &messages[2],
+            },
+        ];
+
+        assert!(BLSSignature::verify_signature_sets(&signature_sets).unwrap());
+        assert!(BLSSignature::verify_signature_sets(&signature_sets[2..]).unwrap());
+
+        // Swapping the signatures of two sets invalidates the batch
+        let swapped_signature_sets = [
+            SignatureSet {
+                signature: &signatures[1],
+                ..signature_sets[0]
+            },
+            SignatureSet {
+                signature: &signatures[0],
+                ..signature_sets[1]
+            },
+        ];
+        assert!(!BLSSignature::verify_signature_sets(&swapped_signature_sets).unwrap());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{AggregatePubKey, PubKey, errors::BLSError};
+use crate::{AggregatePubKey, BLSSignature, PubKey, SignatureSet, errors::BLSError};
 
 /// Trait for aggregating BLS public keys.
 ///
@@ -24,6 +24,31 @@ pub trait ZkcryptoAggregatable: Aggregatable<Error = BLSError> {}
 /// Marker trait for supranational/blst BLS public key aggregation implementation
 pub trait SupranationalAggregatable: Aggregatable<Error = anyhow::Error> {}
 
+/// Trait for aggregating BLS signatures.
+///
+/// This trait provides functionality to combine multiple BLS signatures into a single signature,
+/// which verifies against the aggregate of the signers' public keys when they all signed the same
+/// message.
+pub trait SignatureAggregatable {
+    type Error;
+
+    /// Aggregates multiple BLS signatures into a single signature.
+    ///
+    /// # Arguments
+    /// * `signatures` - Slice of signature references to aggregate, aggregating no signatures
+    ///   returns the point at infinity
+    ///
+    /// # Returns
+    /// * `Result<BLSSignature, Self::Error>` - The aggregated signature or an error
+    fn aggregate(signatures: &[&BLSSignature]) -> Result<BLSSignature, Self::Error>;
+}
+
+/// Marker trait for zkcrypto/bls12_381 BLS signature aggregation implementation
+pub trait ZkcryptoSignatureAggregatable: SignatureAggregatable<Error = BLSError> {}
+
+/// Marker trait for supranational/blst BLS signature aggregation implementation
+pub trait SupranationalSignatureAggregatable: SignatureAggregatable<Error = BLSError> {}
+
 /// Trait for verifying BLS signatures.
 ///
 /// This trait provides functionality to verify both individual and aggregate BLS signatures
@@ -63,3 +88,54 @@ pub trait ZkcryptoVerifiable: Verifiable<Error = BLSError> {}
 
 /// Marker trait for supranational/blst BLS signature verification implementation
 pub trait SupranationalVerifiable: Verifiable<Error = BLSError> {}
+
+/// Trait for verifying many BLS signatures at once.
+///
+/// All signature sets are checked with a single randomized multi-pairing, which is much cheaper
+/// than verifying them one by one but only tells whether every set is valid.
+pub trait BatchVerifiable {
+    type Error;
+
+    /// Verifies every signature set against the aggregate of its public keys.
+    ///
+    /// # Arguments
+    /// * `signature_sets` - The sets to verify, a set without public keys is invalid
+    ///
+    /// # Returns
+    /// * `Result<bool, BLSError>` - Ok(true) if every set is valid, Ok(false) if at least one set
+    ///   is invalid, or Err if there are issues with signature or public key bytes
+    fn verify_signature_sets(signature_sets: &[SignatureSet]) -> Result<bool, Self::Error>;
+}
+
+/// Marker trait for zkcrypto/bls12_381 BLS batch verification implementation
+pub trait ZkcryptoBatchVerifiable: BatchVerifiable<Error = BLSError> {}
+
+/// Marker trait for supranational/blst BLS batch verification implementation
+pub trait SupranationalBatchVerifiable: BatchVerifiable<Error = BLSError> {}
+
+/// Trait for signing messages with a BLS private key.
+pub trait Signable {
+    type Error;
+
+    /// Returns the public key of the private key.
+    ///
+    /// # Returns
+    /// * `Result<PubKey, Self::Error>` - The public key or an error if the private key is invalid
+    fn public_key(&self) -> Result<PubKey, Self::Error>;
+
+    /// Signs a message with the private key.
+    ///
+    /// # Arguments
+    /// * `message` - The message to sign
+    ///
+    /// # Returns
+    /// * `Result<BLSSignature, Self::Error>` - The signature or an error if the private key is
+    ///   invalid
+    fn sign(&self, message: &[u8]) -> Result<BLSSignature, Self::Error>;
+}
+
+/// Marker trait for zkcrypto/bls12_381 BLS signing implementation
+pub trait ZkcryptoSignable: Signable<Error = BLSError> {}
+
+/// Marker trait for supranational/blst BLS signing implementation
+pub trait SupranationalSignable: Signable<Error = BLSError> {}
@@ -0,0 +1,52 @@
+use bls12_381::{
+    G1Projective, G2Affine, G2Projective, Scalar,
+    hash_to_curve::{ExpandMsgXmd, HashToCurve},
+};
+
+use crate::{
+    BLSSignature, PubKey,
+    constants::DST,
+    errors::BLSError,
+    private_key::PrivateKey,
+    traits::{Signable, ZkcryptoSignable},
+};
+
+impl TryFrom<&PrivateKey> for Scalar {
+    type Error = BLSError;
+
+    fn try_from(value: &PrivateKey) -> Result<Self, Self::Error> {
+        // bls12_381 scalars are little-endian
+        let mut bytes = value.inner.0;
+        bytes.reverse();
+        match Scalar::from_bytes(&bytes).into_option() {
+            Some(scalar) if scalar != Scalar::zero() => Ok(scalar),
+            _ => Err(BLSError::InvalidPrivateKey),
+        }
+    }
+}
+
+impl Signable for PrivateKey {
+    type Error = BLSError;
+
+    fn public_key(&self) -> Result<PubKey, BLSError> {
+        Ok(PubKey::from(
+            G1Projective::generator() * Scalar::try_from(self)?,
+        ))
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<BLSSignature, BLSError> {
+        let h = <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(
+            [message],
+            DST,
+        );
+
+        Ok(BLSSignature {
+            inner: G2Affine::from(h * Scalar::try_from(self)?)
+                .to_compressed()
+                .to_vec()
+                .into(),
+        })
+    }
+}
+
+impl ZkcryptoSignable for PrivateKey {}
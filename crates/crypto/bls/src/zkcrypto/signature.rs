@@ -1,14 +1,18 @@
 use bls12_381::{
-    G1Affine, G2Affine, G2Projective,
+    G1Affine, G2Affine, G2Prepared, G2Projective, Gt, Scalar,
     hash_to_curve::{ExpandMsgXmd, HashToCurve},
-    pairing,
+    multi_miller_loop, pairing,
 };
+use rand::Rng;
 
 use crate::{
-    AggregatePubKey, BLSSignature, PubKey,
+    AggregatePubKey, BLSSignature, PubKey, SignatureSet,
     constants::DST,
     errors::BLSError,
-    traits::{Aggregatable, Verifiable, ZkcryptoVerifiable},
+    traits::{
+        Aggregatable, BatchVerifiable, SignatureAggregatable, Verifiable, ZkcryptoBatchVerifiable,
+        ZkcryptoSignatureAggregatable, ZkcryptoVerifiable,
+    },
 };
 
 impl TryFrom<&BLSSignature> for G2Affine {
@@ -65,3 +69,135 @@ impl Verifiable for BLSSignature {
 }
 
 impl ZkcryptoVerifiable for BLSSignature {}
+
+impl BatchVerifiable for BLSSignature {
+    type Error = BLSError;
+
+    fn verify_signature_sets(signature_sets: &[SignatureSet]) -> Result<bool, BLSError> {
+        if signature_sets.is_empty() {
+            return Ok(true);
+        }
+
+        // Check prod(e(r_i * pk_i, H(m_i))) == e(g1, sum(r_i * sig_i)) with random non zero r_i
+        let mut rng = rand::thread_rng();
+        let mut aggregate_signature = G2Projective::identity();
+        let mut terms = Vec::with_capacity(signature_sets.len() + 1);
+        for signature_set in signature_sets {
+            if signature_set.pubkeys.is_empty() {
+                return Ok(false);
+            }
+            let scalar = Scalar::from(rng.gen_range(1..=u64::MAX));
+            let aggregate_pubkey =
+                AggregatePubKey::aggregate(&signature_set.pubkeys.iter().collect::<Vec<_>>())?;
+            let h = <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(
+                [signature_set.message],
+                DST,
+            );
+
+            terms.push((
+                G1Affine::from(G1Affine::try_from(&aggregate_pubkey.to_pubkey())? * scalar),
+                G2Prepared::from(G2Affine::from(h)),
+            ));
+            aggregate_signature += G2Affine::try_from(signature_set.signature)? * scalar;
+        }
+        terms.push((
+            -G1Affine::generator(),
+            G2Prepared::from(G2Affine::from(aggregate_signature)),
+        ));
+
+        Ok(
+            multi_miller_loop(&terms.iter().map(|(g1, g2)| (g1, g2)).collect::<Vec<_>>())
+                .final_exponentiation()
+                == Gt::identity(),
+        )
+    }
+}
+
+impl ZkcryptoBatchVerifiable for BLSSignature {}
+
+impl SignatureAggregatable for BLSSignature {
+    type Error = BLSError;
+
+    fn aggregate(signatures: &[&BLSSignature]) -> Result<BLSSignature, BLSError> {
+        let aggregate_signature = signatures
+            .iter()
+            .try_fold(G2Projective::identity(), |acc, signature| {
+                Ok::<_, BLSError>(acc + G2Affine::try_from(*signature)?)
+            })?;
+
+        Ok(BLSSignature {
+            inner: G2Affine::from(aggregate_signature)
+                .to_compressed()
+                .to_vec()
+                .into(),
+        })
+    }
+}
+
+impl ZkcryptoSignatureAggregatable for BLSSignature {}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+
+    use super::*;
+    use crate::{private_key::PrivateKey, traits::Signable};
+
+    #[test]
+    fn verifies_a_batch_of_valid_signature_sets() {
+        let messages = [[1; 32], [2; 32], [3; 32]];
+        let private_keys = (1..=4)
+            .map(|index| PrivateKey {
+                inner: B256::with_last_byte(index),
+            })
+            .collect::<Vec<_>>();
+        let pubkeys = private_keys
+            .iter()
+            .map(|private_key| private_key.public_key().unwrap())
+            .collect::<Vec<_>>();
+        // The last set is signed by two validators over the same message
+        let signatures = [
+            private_keys[0].sign(&messages[0]).unwrap(),
+            private_keys[1].sign(&messages[1]).unwrap(),
+            BLSSignature::aggregate(&[
+                &private_keys[2].sign(&messages[2]).unwrap(),
+                &private_keys[3].sign(&messages[2]).unwrap(),
+            ])
+            .unwrap(),
+        ];
+        let signature_sets = [
+            SignatureSet {
+                signature: &signatures[0],
+                pubkeys: &pubkeys[0..1],
+                message: &messages[0],
+            },
+            SignatureSet {
+                signature: &signatures[1],
+                pubkeys: &pubkeys[1..2],
+                message: &messages[1],
+            },
+            SignatureSet {
+                signature: &signatures[2],
+                pubkeys: &pubkeys[2..4],
+                message: &messages[2],
+            },
+        ];
+
+        assert!(BLSSignature::verify_signature_sets(&signature_sets).unwrap());
+        assert!(BLSSignature::verify_signature_sets(&signature_sets[2..]).unwrap());
+
+        // Swapping the signatures of two sets invalidates the batch
+        let swapped_signature_sets = [
+            SignatureSet {
+                signature: &signatures[1],
+                ..signature_sets[0]
+            },
+            SignatureSet {
+                signature: &signatures[0],
+                ..signature_sets[1]
+            },
+        ];
+        assert!(!BLSSignature::verify_signature_sets(&swapped_signature_sets).unwrap());
+    }
+}
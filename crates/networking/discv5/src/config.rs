@@ -2,7 +2,10 @@ use std::net::{IpAddr, Ipv4Addr};
 
 use discv5::{ConfigBuilder, Enr, ListenConfig};
 
-use crate::subnet::{Subnet, Subnets};
+use crate::{
+    eth2::ENRForkID,
+    subnet::{Subnet, Subnets},
+};
 
 pub struct NetworkConfig {
     pub discv5_config: discv5::Config,
@@ -11,6 +14,8 @@ pub struct NetworkConfig {
     pub socket_port: Option<u16>,
     pub disable_discovery: bool,
     pub subnets: Subnets,
+    /// The fork digest and next fork advertised in our ENR.
+    pub enr_fork_id: ENRForkID,
 }
 
 impl Default for NetworkConfig {
@@ -33,6 +38,7 @@ impl Default for NetworkConfig {
             socket_port: Some(socket_port),
             disable_discovery: false,
             subnets,
+            enr_fork_id: ENRForkID::default(),
         }
     }
 }
@@ -78,7 +78,7 @@ impl Discovery {
             enr_builder.udp4(socker_port);
         }
         let enr = enr_builder
-            .add_value(ENR_ETH2_KEY, &ENRForkID::pectra())
+            .add_value(ENR_ETH2_KEY, &config.enr_fork_id)
             .add_value(ATTESTATION_BITFIELD_ENR_KEY, &config.subnets)
             .build(&enr_local)
             .map_err(|err| anyhow!("Failed to build ENR: {err}"))?;
@@ -124,6 +124,15 @@ impl Discovery {
         &self.local_enr
     }
 
+    /// Advertise ``enr_fork_id`` in the local ENR, as done when crossing a fork boundary.
+    pub fn update_enr_fork_id(&mut self, enr_fork_id: &ENRForkID) -> anyhow::Result<()> {
+        self.discv5
+            .enr_insert(ENR_ETH2_KEY, enr_fork_id)
+            .map_err(|err| anyhow!("Failed to update ENR: {err:?}"))?;
+        self.local_enr = self.discv5.local_enr();
+        Ok(())
+    }
+
     pub fn discover_peers(&mut self, target_peers: usize, subnet_id: Option<u8>) {
         // If the discv5 service isn't running or we are in the process of a query, don't bother
         // queuing a new one.
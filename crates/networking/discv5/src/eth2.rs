@@ -1,14 +1,12 @@
-use alloy_primitives::{B256, Bytes, aliases::B32, bytes, fixed_bytes};
+use alloy_primitives::{Bytes, aliases::B32, bytes};
 use alloy_rlp::{Decodable, Encodable};
-use ream_consensus::{constants::FAR_FUTURE_EPOCH, fork_data::ForkData};
+use ream_network_spec::networks::NetworkSpec;
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 
 pub const ENR_ETH2_KEY: &str = "eth2";
-pub const GENESIS_VALIDATORS_ROOT: B256 =
-    fixed_bytes!("0x0000000000000000000000000000000000000000000000000000000000000000");
 
-#[derive(Default, Debug, Encode, Decode)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct ENRForkID {
     pub fork_digest: B32,
     pub next_fork_version: B32,
@@ -16,21 +14,12 @@ pub struct ENRForkID {
 }
 
 impl ENRForkID {
-    pub fn pectra() -> Self {
-        let current_fork_version = fixed_bytes!("0x05000000");
-        let next_fork_version = current_fork_version;
-        let next_fork_epoch = FAR_FUTURE_EPOCH;
-
-        let fork_digest = ForkData {
-            current_version: current_fork_version,
-            genesis_validators_root: GENESIS_VALIDATORS_ROOT,
-        }
-        .compute_fork_digest();
-
+    /// Return the ``ENRForkID`` a node on ``network_spec`` advertises at ``epoch``.
+    pub fn new(network_spec: &NetworkSpec, epoch: u64) -> Self {
         Self {
-            fork_digest,
-            next_fork_version,
-            next_fork_epoch,
+            fork_digest: network_spec.fork_digest(epoch),
+            next_fork_version: network_spec.next_fork_version(epoch),
+            next_fork_epoch: network_spec.next_fork_epoch(epoch),
         }
     }
 }
@@ -54,6 +43,10 @@ impl Decodable for ENRForkID {
 
 #[cfg(test)]
 mod tests {
+    use alloy_primitives::fixed_bytes;
+    use ream_consensus::constants::FAR_FUTURE_EPOCH;
+    use ream_network_spec::networks::MAINNET;
+
     use super::*;
 
     #[test]
@@ -74,4 +67,13 @@ mod tests {
         assert_eq!(fork_id.next_fork_epoch, deserialized.next_fork_epoch);
         Ok(())
     }
+
+    #[test]
+    fn test_mainnet_fork_id() {
+        let fork_id = ENRForkID::new(&MAINNET, 364032);
+
+        assert_eq!(fork_id.fork_digest, MAINNET.fork_digest(364032),);
+        assert_eq!(fork_id.next_fork_version, fixed_bytes!("0x05000000"));
+        assert_eq!(fork_id.next_fork_epoch, FAR_FUTURE_EPOCH);
+    }
 }
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use anyhow::anyhow;
 use discv5::Enr;
-use ream_network_spec::networks::Network;
+use ream_network_spec::networks::{Network, NetworkSpec};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub enum Bootnodes {
@@ -30,8 +30,8 @@ impl FromStr for Bootnodes {
 }
 
 impl Bootnodes {
-    pub fn to_enrs(self, network: Network) -> Vec<Enr> {
-        let bootnodes: Vec<Enr> = match network {
+    pub fn to_enrs(self, network_spec: &NetworkSpec) -> Vec<Enr> {
+        let bootnodes: Vec<Enr> = match network_spec.network {
             Network::Mainnet => {
                 serde_yaml::from_str(include_str!("../resources/bootnodes_mainnet.yaml"))
                     .expect("should deserialize bootnodes")
@@ -49,6 +49,7 @@ impl Bootnodes {
                     .expect("should deserialize bootnodes")
             }
             Network::Dev => vec![],
+            Network::Custom(_) => network_spec.bootnodes.clone(),
         };
 
         match self {
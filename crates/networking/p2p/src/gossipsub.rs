@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, ensure};
+use ethereum_hashing::hash;
+use libp2p::gossipsub::{
+    Behaviour, ConfigBuilder, Message, MessageAuthenticity, MessageId, ValidationMode,
+};
+
+pub const MESSAGE_DOMAIN_INVALID_SNAPPY: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+pub const MESSAGE_DOMAIN_VALID_SNAPPY: [u8; 4] = [0x01, 0x00, 0x00, 0x00];
+
+/// The maximum size of an uncompressed gossip message.
+pub const GOSSIP_MAX_SIZE: usize = 10 * 1024 * 1024;
+
+/// The target, low and high watermarks of the mesh degree.
+pub const GOSSIP_D: usize = 8;
+pub const GOSSIP_D_LOW: usize = 6;
+pub const GOSSIP_D_HIGH: usize = 12;
+pub const GOSSIP_D_LAZY: usize = 6;
+
+pub const GOSSIP_HEARTBEAT_INTERVAL: Duration = Duration::from_millis(700);
+pub const GOSSIP_FANOUT_TTL: Duration = Duration::from_secs(60);
+
+/// The number of heartbeats messages are cached for, and gossiped for.
+pub const GOSSIP_MCACHE_LEN: usize = 6;
+pub const GOSSIP_MCACHE_GOSSIP: usize = 3;
+
+/// The number of heartbeats message ids are remembered for, 550 for ``SECONDS_PER_SLOT`` 12.
+pub const GOSSIP_SEEN_TTL: u32 = 550;
+
+/// Decompress the snappy ``data`` of a gossip message, which must not exceed ``GOSSIP_MAX_SIZE``
+/// once decompressed.
+pub fn decompress_gossip_message(data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let len = snap::raw::decompress_len(data)?;
+    ensure!(
+        len <= GOSSIP_MAX_SIZE,
+        "Gossip message of {len} bytes exceeds GOSSIP_MAX_SIZE"
+    );
+    Ok(snap::raw::Decoder::new().decompress_vec(data)?)
+}
+
+/// Return the ``message-id`` of a gossip message, the first 20 bytes of
+/// ``SHA256(domain + uint_to_bytes(uint64(len(topic))) + topic + data)`` where ``data`` is
+/// decompressed if it is valid snappy.
+pub fn compute_message_id(message: &Message) -> MessageId {
+    let topic = message.topic.as_str().as_bytes();
+    let (domain, data) = match decompress_gossip_message(&message.data) {
+        Ok(data) => (MESSAGE_DOMAIN_VALID_SNAPPY, data),
+        Err(_) => (MESSAGE_DOMAIN_INVALID_SNAPPY, message.data.clone()),
+    };
+    let mut preimage = domain.to_vec();
+    preimage.extend_from_slice(&(topic.len() as u64).to_le_bytes());
+    preimage.extend_from_slice(topic);
+    preimage.extend_from_slice(&data);
+    MessageId::from(&hash(&preimage)[..20])
+}
+
+/// Build the gossipsub behaviour with the parameters of the eth2 networking spec, messages are
+/// anonymous and identified by ``compute_message_id``.
+pub fn build_gossipsub() -> anyhow::Result<Behaviour> {
+    let config = ConfigBuilder::default()
+        .max_transmit_size(GOSSIP_MAX_SIZE)
+        .mesh_n(GOSSIP_D)
+        .mesh_n_low(GOSSIP_D_LOW)
+        .mesh_n_high(GOSSIP_D_HIGH)
+        .gossip_lazy(GOSSIP_D_LAZY)
+        .heartbeat_interval(GOSSIP_HEARTBEAT_INTERVAL)
+        .fanout_ttl(GOSSIP_FANOUT_TTL)
+        .history_length(GOSSIP_MCACHE_LEN)
+        .history_gossip(GOSSIP_MCACHE_GOSSIP)
+        .duplicate_cache_time(GOSSIP_HEARTBEAT_INTERVAL * GOSSIP_SEEN_TTL)
+        .validation_mode(ValidationMode::Anonymous)
+        .message_id_fn(compute_message_id)
+        .build()
+        .map_err(|err| anyhow!("Failed to build gossipsub config: {err}"))?;
+    Behaviour::new(MessageAuthenticity::Anonymous, config)
+        .map_err(|err| anyhow!("Failed to create gossipsub: {err}"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use libp2p::gossipsub::TopicHash;
+
+    use super::*;
+
+    fn message(data: Vec<u8>) -> Message {
+        Message {
+            source: None,
+            data,
+            sequence_number: None,
+            topic: TopicHash::from_raw("/eth2/6a95a1a9/beacon_block/ssz_snappy"),
+        }
+    }
+
+    #[test]
+    fn test_message_id_uses_decompressed_data() {
+        let topic = "/eth2/6a95a1a9/beacon_block/ssz_snappy";
+        let data = vec![1, 2, 3];
+        let compressed = snap::raw::Encoder::new().compress_vec(&data).unwrap();
+
+        let mut preimage = MESSAGE_DOMAIN_VALID_SNAPPY.to_vec();
+        preimage.extend_from_slice(&(topic.len() as u64).to_le_bytes());
+        preimage.extend_from_slice(topic.as_bytes());
+        preimage.extend_from_slice(&data);
+        assert_eq!(
+            compute_message_id(&message(compressed)),
+            MessageId::from(&hash(&preimage)[..20])
+        );
+
+        let invalid = vec![0xff; 4];
+        let mut preimage = MESSAGE_DOMAIN_INVALID_SNAPPY.to_vec();
+        preimage.extend_from_slice(&(topic.len() as u64).to_le_bytes());
+        preimage.extend_from_slice(topic.as_bytes());
+        preimage.extend_from_slice(&invalid);
+        assert_eq!(
+            compute_message_id(&message(invalid)),
+            MessageId::from(&hash(&preimage)[..20])
+        );
+    }
+}
@@ -1,2 +1,4 @@
 pub mod bootnodes;
+pub mod gossipsub;
 pub mod network;
+pub mod topics;
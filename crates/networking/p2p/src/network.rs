@@ -3,15 +3,19 @@ use std::{
     fmt::Debug,
     num::{NonZeroU8, NonZeroUsize},
     pin::Pin,
-    time::{Duration, Instant},
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
+use alloy_primitives::aliases::B32;
 use anyhow::anyhow;
 use discv5::Enr;
 use libp2p::{
     Multiaddr, PeerId, Swarm, SwarmBuilder, Transport, connection_limits,
     core::{muxing::StreamMuxerBox, transport::Boxed},
     futures::StreamExt,
+    gossipsub::{self, IdentTopic},
     identify,
     multiaddr::Protocol,
     noise,
@@ -19,12 +23,25 @@ use libp2p::{
     yamux,
 };
 use libp2p_identity::{Keypair, PublicKey, secp256k1};
+use ream_consensus::misc::compute_epoch_at_slot;
 use ream_discv5::{
     config::NetworkConfig,
     discovery::{DiscoveredPeers, Discovery},
+    eth2::ENRForkID,
 };
 use ream_executor::ReamExecutor;
-use tracing::{error, info, warn};
+use ream_network_spec::networks::NetworkSpec;
+use tokio::time::{Interval, interval};
+use tracing::{debug, error, info, trace, warn};
+
+use crate::{
+    gossipsub::{build_gossipsub, decompress_gossip_message},
+    topics::{GLOBAL_TOPIC_KINDS, GossipTopic},
+};
+
+/// The number of epochs before a fork the node subscribes to its topics, and after a fork it stays
+/// subscribed to the topics of the previous fork.
+const FORK_TOPIC_EPOCHS: u64 = 2;
 
 #[derive(NetworkBehaviour)]
 pub(crate) struct ReamBehaviour {
@@ -33,6 +50,8 @@ pub(crate) struct ReamBehaviour {
     /// The discovery domain: discv5
     pub discovery: Discovery,
 
+    pub gossipsub: gossipsub::Behaviour,
+
     pub connection_registry: connection_limits::Behaviour,
 }
 
@@ -47,11 +66,23 @@ pub enum ReamNetworkEvent {
     MetaData(PeerId),
     DisconnectPeer(PeerId),
     DiscoverPeers(usize),
+    /// A gossip message, ``data`` is the decompressed SSZ encoding of the message.
+    GossipMessage {
+        topic: GossipTopic,
+        data: Vec<u8>,
+    },
 }
 
 pub struct Network {
     peer_id: PeerId,
     swarm: Swarm<ReamBehaviour>,
+    network_spec: Arc<NetworkSpec>,
+    /// The ``ENRForkID`` advertised in our ENR.
+    enr_fork_id: ENRForkID,
+    /// The fork digests of the topics we are subscribed to.
+    fork_digests: Vec<B32>,
+    /// Checks every slot whether a fork boundary was crossed.
+    fork_timer: Interval,
 }
 
 struct Executor(ReamExecutor);
@@ -63,7 +94,11 @@ impl libp2p::swarm::Executor for Executor {
 }
 
 impl Network {
-    pub async fn init(executor: ReamExecutor, config: &NetworkConfig) -> anyhow::Result<Self> {
+    pub async fn init(
+        executor: ReamExecutor,
+        config: &NetworkConfig,
+        network_spec: Arc<NetworkSpec>,
+    ) -> anyhow::Result<Self> {
         let local_key = secp256k1::Keypair::generate();
 
         let discovery = {
@@ -93,10 +128,13 @@ impl Network {
             identify::Behaviour::new(identify_config)
         };
 
+        let gossipsub = build_gossipsub()?;
+
         let behaviour = {
             ReamBehaviour {
                 discovery,
                 identify,
+                gossipsub,
                 connection_registry: connection_limits,
             }
         };
@@ -122,12 +160,20 @@ impl Network {
                 .build()
         };
 
+        let fork_timer = interval(Duration::from_secs(
+            network_spec.chain_spec.seconds_per_slot,
+        ));
         let mut network = Network {
             peer_id: PeerId::from_public_key(&PublicKey::from(local_key.public().clone())),
             swarm,
+            network_spec,
+            enr_fork_id: config.enr_fork_id.clone(),
+            fork_digests: vec![],
+            fork_timer,
         };
 
         network.start_network_worker(config).await?;
+        network.update_fork();
 
         Ok(network)
     }
@@ -178,8 +224,81 @@ impl Network {
                         return event;
                     }
                 }
+                _ = self.fork_timer.tick() => {
+                    self.update_fork();
+                }
+            }
+        }
+    }
+
+    fn current_epoch(&self) -> u64 {
+        let current_slot = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is before the unix epoch")
+            .as_secs()
+            .saturating_sub(self.network_spec.genesis.genesis_time)
+            / self.network_spec.chain_spec.seconds_per_slot;
+        compute_epoch_at_slot(current_slot)
+    }
+
+    /// Advertise the ``ENRForkID`` of the current epoch and subscribe to the topics of the forks
+    /// active from ``FORK_TOPIC_EPOCHS`` before to ``FORK_TOPIC_EPOCHS`` after it, so gossip
+    /// carries on across a fork boundary.
+    fn update_fork(&mut self) {
+        let epoch = self.current_epoch();
+
+        let enr_fork_id = ENRForkID::new(&self.network_spec, epoch);
+        if enr_fork_id != self.enr_fork_id {
+            match self
+                .swarm
+                .behaviour_mut()
+                .discovery
+                .update_enr_fork_id(&enr_fork_id)
+            {
+                Ok(()) => {
+                    info!("Updated the ENR fork id to {enr_fork_id:?} at epoch {epoch}");
+                    self.enr_fork_id = enr_fork_id;
+                }
+                Err(err) => error!("Failed to update the ENR fork id: {err}"),
             }
         }
+
+        let mut fork_digests = vec![];
+        for epoch in [
+            epoch.saturating_sub(FORK_TOPIC_EPOCHS),
+            epoch,
+            epoch.saturating_add(FORK_TOPIC_EPOCHS),
+        ] {
+            let fork_digest = self.network_spec.fork_digest(epoch);
+            if !fork_digests.contains(&fork_digest) {
+                fork_digests.push(fork_digest);
+            }
+        }
+
+        let gossipsub = &mut self.swarm.behaviour_mut().gossipsub;
+        for &fork_digest in &self.fork_digests {
+            if fork_digests.contains(&fork_digest) {
+                continue;
+            }
+            for kind in GLOBAL_TOPIC_KINDS {
+                let topic = GossipTopic { fork_digest, kind };
+                if let Err(err) = gossipsub.unsubscribe(&IdentTopic::new(topic.to_string())) {
+                    warn!("Failed to unsubscribe from {topic}: {err:?}");
+                }
+            }
+        }
+        for &fork_digest in &fork_digests {
+            if self.fork_digests.contains(&fork_digest) {
+                continue;
+            }
+            for kind in GLOBAL_TOPIC_KINDS {
+                let topic = GossipTopic { fork_digest, kind };
+                if let Err(err) = gossipsub.subscribe(&IdentTopic::new(topic.to_string())) {
+                    warn!("Failed to subscribe to {topic}: {err:?}");
+                }
+            }
+        }
+        self.fork_digests = fork_digests;
     }
 
     fn parse_swarm_event(
@@ -187,7 +306,7 @@ impl Network {
         event: SwarmEvent<ReamBehaviourEvent>,
     ) -> Option<ReamNetworkEvent> {
         // currently no-op for any network events
-        info!("Event: {:?}", event);
+        trace!("Event: {:?}", event);
         match event {
             SwarmEvent::Behaviour(behaviour_event) => match behaviour_event {
                 ReamBehaviourEvent::Identify(_) => None,
@@ -195,6 +314,27 @@ impl Network {
                     self.handle_discovered_peers(peers);
                     None
                 }
+                ReamBehaviourEvent::Gossipsub(gossipsub::Event::Message { message, .. }) => {
+                    let topic = match GossipTopic::from_str(message.topic.as_str()) {
+                        Ok(topic) => topic,
+                        Err(err) => {
+                            warn!("Received a gossip message on an unknown topic: {err}");
+                            return None;
+                        }
+                    };
+                    debug!(
+                        "Received a {} gossip message of {} bytes",
+                        topic.kind,
+                        message.data.len()
+                    );
+                    match decompress_gossip_message(&message.data) {
+                        Ok(data) => Some(ReamNetworkEvent::GossipMessage { topic, data }),
+                        Err(err) => {
+                            warn!("Received an invalid {} gossip message: {err}", topic.kind);
+                            None
+                        }
+                    }
+                }
                 ream_behavior_event => {
                     info!("Unhandled behaviour event: {ream_behavior_event:?}");
                     None
@@ -0,0 +1,187 @@
+use std::{fmt, str::FromStr};
+
+use alloy_primitives::{aliases::B32, hex};
+use anyhow::{anyhow, bail};
+use ream_network_spec::networks::NetworkSpec;
+
+pub const TOPIC_PREFIX: &str = "eth2";
+pub const ENCODING_POSTFIX: &str = "ssz_snappy";
+
+/// A gossipsub topic, ``/eth2/{fork_digest}/{kind}/ssz_snappy``.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GossipTopic {
+    pub fork_digest: B32,
+    pub kind: GossipTopicKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GossipTopicKind {
+    BeaconBlock,
+    BeaconAggregateAndProof,
+    VoluntaryExit,
+    ProposerSlashing,
+    AttesterSlashing,
+    BeaconAttestation(u64),
+    SyncCommitteeContributionAndProof,
+    SyncCommittee(u64),
+    BlsToExecutionChange,
+    BlobSidecar(u64),
+    LightClientFinalityUpdate,
+    LightClientOptimisticUpdate,
+}
+
+/// The topics every node subscribes to for the lifetime of a fork.
+pub const GLOBAL_TOPIC_KINDS: [GossipTopicKind; 7] = [
+    GossipTopicKind::BeaconBlock,
+    GossipTopicKind::BeaconAggregateAndProof,
+    GossipTopicKind::VoluntaryExit,
+    GossipTopicKind::ProposerSlashing,
+    GossipTopicKind::AttesterSlashing,
+    GossipTopicKind::SyncCommitteeContributionAndProof,
+    GossipTopicKind::BlsToExecutionChange,
+];
+
+impl GossipTopic {
+    /// Return the topic for ``kind`` under the fork active at ``epoch`` on ``network_spec``.
+    pub fn new(network_spec: &NetworkSpec, epoch: u64, kind: GossipTopicKind) -> Self {
+        Self {
+            fork_digest: network_spec.fork_digest(epoch),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for GossipTopicKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GossipTopicKind::BeaconBlock => write!(f, "beacon_block"),
+            GossipTopicKind::BeaconAggregateAndProof => write!(f, "beacon_aggregate_and_proof"),
+            GossipTopicKind::VoluntaryExit => write!(f, "voluntary_exit"),
+            GossipTopicKind::ProposerSlashing => write!(f, "proposer_slashing"),
+            GossipTopicKind::AttesterSlashing => write!(f, "attester_slashing"),
+            GossipTopicKind::BeaconAttestation(subnet_id) => {
+                write!(f, "beacon_attestation_{subnet_id}")
+            }
+            GossipTopicKind::SyncCommitteeContributionAndProof => {
+                write!(f, "sync_committee_contribution_and_proof")
+            }
+            GossipTopicKind::SyncCommittee(subnet_id) => write!(f, "sync_committee_{subnet_id}"),
+            GossipTopicKind::BlsToExecutionChange => write!(f, "bls_to_execution_change"),
+            GossipTopicKind::BlobSidecar(subnet_id) => write!(f, "blob_sidecar_{subnet_id}"),
+            GossipTopicKind::LightClientFinalityUpdate => {
+                write!(f, "light_client_finality_update")
+            }
+            GossipTopicKind::LightClientOptimisticUpdate => {
+                write!(f, "light_client_optimistic_update")
+            }
+        }
+    }
+}
+
+impl FromStr for GossipTopicKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let subnet_id = |prefix: &str| {
+            s.strip_prefix(prefix)
+                .map(|subnet_id| {
+                    subnet_id
+                        .parse()
+                        .map_err(|err| anyhow!("Invalid subnet id in topic {s}: {err}"))
+                })
+                .transpose()
+        };
+
+        Ok(match s {
+            "beacon_block" => GossipTopicKind::BeaconBlock,
+            "beacon_aggregate_and_proof" => GossipTopicKind::BeaconAggregateAndProof,
+            "voluntary_exit" => GossipTopicKind::VoluntaryExit,
+            "proposer_slashing" => GossipTopicKind::ProposerSlashing,
+            "attester_slashing" => GossipTopicKind::AttesterSlashing,
+            "sync_committee_contribution_and_proof" => {
+                GossipTopicKind::SyncCommitteeContributionAndProof
+            }
+            "bls_to_execution_change" => GossipTopicKind::BlsToExecutionChange,
+            "light_client_finality_update" => GossipTopicKind::LightClientFinalityUpdate,
+            "light_client_optimistic_update" => GossipTopicKind::LightClientOptimisticUpdate,
+            _ => {
+                if let Some(subnet_id) = subnet_id("beacon_attestation_")? {
+                    GossipTopicKind::BeaconAttestation(subnet_id)
+                } else if let Some(subnet_id) = subnet_id("sync_committee_")? {
+                    GossipTopicKind::SyncCommittee(subnet_id)
+                } else if let Some(subnet_id) = subnet_id("blob_sidecar_")? {
+                    GossipTopicKind::BlobSidecar(subnet_id)
+                } else {
+                    bail!("Unknown gossip topic: {s}")
+                }
+            }
+        })
+    }
+}
+
+impl fmt::Display for GossipTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "/{TOPIC_PREFIX}/{}/{}/{ENCODING_POSTFIX}",
+            hex::encode(self.fork_digest),
+            self.kind
+        )
+    }
+}
+
+impl FromStr for GossipTopic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('/').collect();
+        let ["", TOPIC_PREFIX, fork_digest, kind, ENCODING_POSTFIX] = parts[..] else {
+            bail!("Invalid gossip topic: {s}");
+        };
+
+        Ok(Self {
+            fork_digest: B32::from_str(fork_digest)
+                .map_err(|err| anyhow!("Invalid fork digest in topic {s}: {err}"))?,
+            kind: kind.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use alloy_primitives::fixed_bytes;
+    use ream_network_spec::networks::MAINNET;
+
+    use super::*;
+
+    #[test]
+    fn test_topic_uses_fork_digest() {
+        let topic = GossipTopic::new(&MAINNET, 269568, GossipTopicKind::BeaconBlock);
+
+        assert_eq!(topic.fork_digest, fixed_bytes!("0x6a95a1a9"));
+        assert_eq!(topic.to_string(), "/eth2/6a95a1a9/beacon_block/ssz_snappy");
+    }
+
+    #[test]
+    fn test_topic_round_trip() {
+        for kind in [
+            GossipTopicKind::BeaconAggregateAndProof,
+            GossipTopicKind::BeaconAttestation(63),
+            GossipTopicKind::SyncCommittee(3),
+            GossipTopicKind::BlobSidecar(8),
+            GossipTopicKind::LightClientOptimisticUpdate,
+        ] {
+            let topic = GossipTopic {
+                fork_digest: fixed_bytes!("0xb5303f2a"),
+                kind,
+            };
+            assert_eq!(topic.to_string().parse::<GossipTopic>().unwrap(), topic);
+        }
+        assert!(
+            "/eth2/b5303f2a/beacon_block/ssz"
+                .parse::<GossipTopic>()
+                .is_err()
+        );
+    }
+}
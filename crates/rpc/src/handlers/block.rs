@@ -1,10 +1,9 @@
 use alloy_primitives::B256;
-use ream_consensus::deneb::beacon_block::SignedBeaconBlock;
+use ream_consensus::versioned::beacon_block::VersionedSignedBeaconBlock;
 use ream_storage::{
     db::ReamDB,
     tables::{Field, Table},
 };
-use serde::{Deserialize, Serialize};
 use warp::{
     http::status::StatusCode,
     reject::Rejection,
@@ -17,22 +16,6 @@ use crate::types::{
     response::{BeaconResponse, BeaconVersionedResponse, RootResponse},
 };
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-pub struct BlockRewards {
-    #[serde(with = "serde_utils::quoted_u64")]
-    pub proposer_index: u64,
-    #[serde(with = "serde_utils::quoted_i64")]
-    pub total: i64,
-    #[serde(with = "serde_utils::quoted_u64")]
-    pub attestations: u64,
-    #[serde(with = "serde_utils::quoted_u64")]
-    pub sync_aggregate: u64,
-    #[serde(with = "serde_utils::quoted_u64")]
-    pub proposer_slashings: u64,
-    #[serde(with = "serde_utils::quoted_u64")]
-    pub attester_slashings: u64,
-}
-
 pub async fn get_block_root_from_id(block_id: ID, db: &ReamDB) -> Result<B256, ApiError> {
     let block_root = match block_id {
         ID::Finalized => {
@@ -75,7 +58,7 @@ pub async fn get_block_root_from_id(block_id: ID, db: &ReamDB) -> Result<B256, A
 pub async fn get_beacon_block_from_id(
     block_id: ID,
     db: &ReamDB,
-) -> Result<SignedBeaconBlock, ApiError> {
+) -> Result<VersionedSignedBeaconBlock, ApiError> {
     let block_root = get_block_root_from_id(block_id, db).await?;
 
     db.beacon_block_provider()
@@ -91,7 +74,7 @@ pub async fn get_block_attestations(block_id: ID, db: ReamDB) -> Result<impl Rep
     let beacon_block = get_beacon_block_from_id(block_id, &db).await?;
 
     Ok(with_status(
-        BeaconVersionedResponse::json(beacon_block.message.body.attestations),
+        BeaconVersionedResponse::json(beacon_block.attestations()),
         StatusCode::OK,
     ))
 }
@@ -105,26 +88,6 @@ pub async fn get_block_root(block_id: ID, db: ReamDB) -> Result<impl Reply, Reje
     ))
 }
 
-// Called by `/beacon/blocks/{block_id}/rewards` to get the block rewards response
-pub async fn get_block_rewards(block_id: ID, db: ReamDB) -> Result<impl Reply, Rejection> {
-    let beacon_block = get_beacon_block_from_id(block_id, &db).await?;
-    let response = BlockRewards {
-        proposer_index: beacon_block.message.proposer_index,
-        total: 0, // todo: implement the calculate block reward logic
-        attestations: beacon_block.message.body.attestations.len() as u64,
-        sync_aggregate: beacon_block
-            .message
-            .body
-            .sync_aggregate
-            .sync_committee_bits
-            .num_set_bits() as u64,
-        proposer_slashings: beacon_block.message.body.proposer_slashings.len() as u64,
-        attester_slashings: beacon_block.message.body.attester_slashings.len() as u64,
-    };
-
-    Ok(with_status(BeaconResponse::json(response), StatusCode::OK))
-}
-
 /// Called by `/blocks/<block_id>` to get the Beacon Block.
 pub async fn get_block_from_id(block_id: ID, db: ReamDB) -> Result<impl Reply, Rejection> {
     let beacon_block = get_beacon_block_from_id(block_id, &db).await?;
@@ -0,0 +1,95 @@
+use alloy_primitives::U256;
+use ream_consensus::{
+    constants::ETH_TO_GWEI, electra::beacon_block::BeaconBlock,
+    execution_engine::rpc_types::get_blobs::Blob, polynomial_commitments::kzg_proof::KZGProof,
+    preset::MaxBlobCommitmentsPerBlock,
+};
+use ream_node::block_production::BlockProducer;
+use serde::Serialize;
+use ssz_types::VariableList;
+use tracing::error;
+use warp::{
+    http::status::StatusCode,
+    reject::Rejection,
+    reply::{Reply, json, with_header, with_status},
+};
+
+use crate::types::{
+    errors::ApiError,
+    query::ProduceBlockQuery,
+    response::{
+        ELECTRA, ETH_CONSENSUS_BLOCK_VALUE_HEADER, ETH_CONSENSUS_VERSION_HEADER,
+        ETH_EXECUTION_PAYLOAD_BLINDED_HEADER, ETH_EXECUTION_PAYLOAD_VALUE_HEADER,
+    },
+};
+
+/// An unsigned block and the blobs of its ``blob_kzg_commitments``.
+#[derive(Debug, Serialize)]
+pub struct BlockContents {
+    pub block: BeaconBlock,
+    pub kzg_proofs: VariableList<KZGProof, MaxBlobCommitmentsPerBlock>,
+    pub blobs: VariableList<Blob, MaxBlobCommitmentsPerBlock>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProduceBlockResponse {
+    pub version: String,
+    pub execution_payload_blinded: bool,
+    /// The value of the payload to the fee recipient, in wei
+    pub execution_payload_value: String,
+    /// The rewards of the proposer for the operations of the block, in wei
+    pub consensus_block_value: String,
+    pub data: BlockContents,
+}
+
+/// Called by `/validator/blocks/{slot}` to produce an unsigned block of ``slot``, with the blobs
+/// of its commitments. Blocks are always built with the payload of the local execution engine.
+pub async fn produce_block_v3(
+    slot: u64,
+    query: ProduceBlockQuery,
+    block_producer: BlockProducer,
+) -> Result<impl Reply, Rejection> {
+    let produced_block = block_producer
+        .produce_block(
+            slot,
+            query.randao_reveal,
+            query.graffiti.unwrap_or_default(),
+        )
+        .await
+        .map_err(|err| {
+            error!("Failed to produce the block of slot {slot}: {err}");
+            ApiError::InternalError
+        })?;
+
+    let execution_payload_value = U256::from_be_bytes(produced_block.block_value.0).to_string();
+    let consensus_block_value =
+        (U256::from(produced_block.consensus_block_value) * U256::from(ETH_TO_GWEI)).to_string();
+    let response = ProduceBlockResponse {
+        version: String::from(ELECTRA),
+        execution_payload_blinded: false,
+        execution_payload_value: execution_payload_value.clone(),
+        consensus_block_value: consensus_block_value.clone(),
+        data: BlockContents {
+            block: produced_block.block,
+            kzg_proofs: produced_block.blobs_bundle.proofs,
+            blobs: produced_block.blobs_bundle.blobs,
+        },
+    };
+
+    Ok(with_status(
+        with_header(
+            with_header(
+                with_header(
+                    with_header(json(&response), ETH_CONSENSUS_VERSION_HEADER, ELECTRA),
+                    ETH_EXECUTION_PAYLOAD_BLINDED_HEADER,
+                    "false",
+                ),
+                ETH_EXECUTION_PAYLOAD_VALUE_HEADER,
+                execution_payload_value,
+            ),
+            ETH_CONSENSUS_BLOCK_VALUE_HEADER,
+            consensus_block_value,
+        ),
+        StatusCode::OK,
+    ))
+}
@@ -36,9 +36,9 @@ pub async fn get_finality_checkpoint(state_id: ID, db: ReamDB) -> Result<impl Re
     let state = get_state_from_id(state_id, &db).await?;
     Ok(with_status(
         BeaconResponse::json(CheckpointData::new(
-            state.previous_justified_checkpoint,
-            state.current_justified_checkpoint,
-            state.finalized_checkpoint,
+            state.previous_justified_checkpoint(),
+            state.current_justified_checkpoint(),
+            state.finalized_checkpoint(),
         )),
         StatusCode::OK,
     ))
@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
 use alloy_primitives::{Address, aliases::B32};
-use ream_consensus::constants::{
-    DOMAIN_AGGREGATE_AND_PROOF, INACTIVITY_PENALTY_QUOTIENT_BELLATRIX,
+use ream_consensus::{
+    constants::{DOMAIN_AGGREGATE_AND_PROOF, INACTIVITY_PENALTY_QUOTIENT_BELLATRIX},
+    fork::Fork,
 };
 use ream_network_spec::networks::NetworkSpec;
 use serde::{Deserialize, Serialize};
@@ -31,8 +32,8 @@ impl DepositContract {
 pub async fn get_deposit_contract(network_spec: Arc<NetworkSpec>) -> Result<impl Reply, Rejection> {
     Ok(with_status(
         Data::json(DepositContract::new(
-            network_spec.network.chain_id(),
-            network_spec.deposit_contract_address,
+            network_spec.chain_spec.deposit_chain_id,
+            network_spec.chain_spec.deposit_contract_address,
         )),
         StatusCode::OK,
     ))
@@ -52,8 +53,8 @@ pub struct SpecConfig {
 impl From<Arc<NetworkSpec>> for SpecConfig {
     fn from(network_spec: Arc<NetworkSpec>) -> Self {
         Self {
-            deposit_contract_address: network_spec.deposit_contract_address,
-            deposit_network_id: network_spec.network.chain_id(),
+            deposit_contract_address: network_spec.chain_spec.deposit_contract_address,
+            deposit_network_id: network_spec.chain_spec.deposit_network_id,
             domain_aggregate_and_proof: DOMAIN_AGGREGATE_AND_PROOF,
             inactivity_penalty_quotient: INACTIVITY_PENALTY_QUOTIENT_BELLATRIX,
         }
@@ -65,3 +66,32 @@ pub async fn get_spec(network_spec: Arc<NetworkSpec>) -> Result<impl Reply, Reje
     let spec_config = SpecConfig::from(network_spec);
     Ok(with_status(Data::json(spec_config), StatusCode::OK))
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct ForkResponse {
+    previous_version: B32,
+    current_version: B32,
+    #[serde(with = "serde_utils::quoted_u64")]
+    epoch: u64,
+}
+
+impl From<Fork> for ForkResponse {
+    fn from(fork: Fork) -> Self {
+        Self {
+            previous_version: fork.previous_version,
+            current_version: fork.current_version,
+            epoch: fork.epoch,
+        }
+    }
+}
+
+/// Called by `/config/fork_schedule` to get the scheduled forks of the network.
+pub async fn get_fork_schedule(network_spec: Arc<NetworkSpec>) -> Result<impl Reply, Rejection> {
+    let fork_schedule = network_spec
+        .fork_schedule()
+        .scheduled_forks()
+        .into_iter()
+        .map(ForkResponse::from)
+        .collect::<Vec<_>>();
+    Ok(with_status(Data::json(fork_schedule), StatusCode::OK))
+}
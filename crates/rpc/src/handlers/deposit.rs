@@ -0,0 +1,20 @@
+use ream_storage::{db::ReamDB, tables::Field};
+use warp::{
+    http::status::StatusCode,
+    reject::Rejection,
+    reply::{Reply, with_status},
+};
+
+use super::Data;
+use crate::types::errors::ApiError;
+
+/// Called by `/deposit_snapshot` to get the EIP-4881 snapshot of the finalized deposit tree.
+pub async fn get_deposit_snapshot(db: ReamDB) -> Result<impl Reply, Rejection> {
+    let snapshot = db
+        .deposit_tree_snapshot_provider()
+        .get()
+        .map_err(|_| ApiError::InternalError)?
+        .ok_or_else(|| ApiError::NotFound(String::from("Deposit snapshot not found")))?;
+
+    Ok(with_status(Data::json(snapshot), StatusCode::OK))
+}
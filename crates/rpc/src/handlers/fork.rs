@@ -12,7 +12,7 @@ use crate::types::{id::ID, response::BeaconResponse};
 pub async fn get_fork(state_id: ID, db: ReamDB) -> Result<impl Reply, Rejection> {
     let state = get_state_from_id(state_id, &db).await?;
     Ok(with_status(
-        BeaconResponse::json(state.fork),
+        BeaconResponse::json(state.fork()),
         StatusCode::OK,
     ))
 }
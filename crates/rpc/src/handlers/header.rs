@@ -1,8 +1,7 @@
 use alloy_primitives::B256;
-use ream_consensus::beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader};
+use ream_consensus::beacon_block_header::SignedBeaconBlockHeader;
 use ream_storage::{db::ReamDB, tables::Table};
 use serde::{Deserialize, Serialize};
-use tree_hash::TreeHash;
 use warp::{
     http::status::StatusCode,
     reject::Rejection,
@@ -61,7 +60,7 @@ pub async fn get_headers(
 
             // fetch block header at `slot+1`
             let (child_header, child_block_root) =
-                get_header_from_slot(parent_block.message.slot + 1, db)
+                get_header_from_slot(parent_block.slot() + 1, db)
                     .await
                     .map_err(|_| {
                         ApiError::NotFound(format!(
@@ -102,20 +101,8 @@ pub async fn get_header_from_slot(
 ) -> Result<(SignedBeaconBlockHeader, B256), ApiError> {
     let beacon_block = get_beacon_block_from_id(ID::Slot(slot), &db).await?;
 
-    let header_message = BeaconBlockHeader {
-        slot: beacon_block.message.slot,
-        proposer_index: beacon_block.message.proposer_index,
-        state_root: beacon_block.message.state_root,
-        parent_root: beacon_block.message.parent_root,
-        body_root: beacon_block.message.body.tree_hash_root(),
-    };
-    let root = header_message.tree_hash_root();
-
     Ok((
-        SignedBeaconBlockHeader {
-            message: header_message,
-            signature: beacon_block.signature,
-        },
-        root,
+        beacon_block.signed_block_header(),
+        beacon_block.block_root(),
     ))
 }
@@ -0,0 +1,90 @@
+use alloy_primitives::B256;
+use ream_consensus::constants::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use warp::{
+    http::status::StatusCode,
+    reject::Rejection,
+    reply::{Reply, json, with_status},
+};
+
+use crate::types::{
+    errors::ApiError, query::LightClientUpdatesQuery, response::DataVersionedResponse,
+};
+
+/// Called by `/light_client/bootstrap/{block_root}` to get the light client bootstrap of a
+/// checkpoint block.
+pub async fn get_light_client_bootstrap(
+    block_root: B256,
+    db: ReamDB,
+) -> Result<impl Reply, Rejection> {
+    let bootstrap = db
+        .light_client_bootstrap_provider()
+        .get(block_root)
+        .map_err(|_| ApiError::InternalError)?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Light client bootstrap not found for {block_root}"))
+        })?;
+
+    Ok(with_status(
+        DataVersionedResponse::json(bootstrap),
+        StatusCode::OK,
+    ))
+}
+
+/// Called by `/light_client/updates` to get the best light client update of each of ``count``
+/// sync committee periods from ``start_period``. Updates are returned for consecutive periods
+/// only, so the response stops at the first period without an update.
+pub async fn get_light_client_updates(
+    query: LightClientUpdatesQuery,
+    db: ReamDB,
+) -> Result<impl Reply, Rejection> {
+    let count = query.count.min(MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+    let update_provider = db.light_client_update_provider();
+    let mut updates = vec![];
+    for period in query.start_period..query.start_period.saturating_add(count) {
+        match update_provider
+            .get(period)
+            .map_err(|_| ApiError::InternalError)?
+        {
+            Some(update) => updates.push(DataVersionedResponse::new(update)),
+            None => break,
+        }
+    }
+
+    Ok(with_status(json(&updates), StatusCode::OK))
+}
+
+/// Called by `/light_client/finality_update` to get the latest light client finality update.
+pub async fn get_light_client_finality_update(db: ReamDB) -> Result<impl Reply, Rejection> {
+    let finality_update = db
+        .light_client_finality_update_provider()
+        .get()
+        .map_err(|_| ApiError::InternalError)?
+        .ok_or_else(|| {
+            ApiError::NotFound(String::from("Light client finality update not found"))
+        })?;
+
+    Ok(with_status(
+        DataVersionedResponse::json(finality_update),
+        StatusCode::OK,
+    ))
+}
+
+/// Called by `/light_client/optimistic_update` to get the latest light client optimistic update.
+pub async fn get_light_client_optimistic_update(db: ReamDB) -> Result<impl Reply, Rejection> {
+    let optimistic_update = db
+        .light_client_optimistic_update_provider()
+        .get()
+        .map_err(|_| ApiError::InternalError)?
+        .ok_or_else(|| {
+            ApiError::NotFound(String::from("Light client optimistic update not found"))
+        })?;
+
+    Ok(with_status(
+        DataVersionedResponse::json(optimistic_update),
+        StatusCode::OK,
+    ))
+}
@@ -1,10 +1,15 @@
 pub mod block;
+pub mod block_production;
 pub mod checkpoint;
 pub mod config;
+pub mod deposit;
 pub mod fork;
 pub mod genesis;
 pub mod header;
+pub mod light_client;
+pub mod pool;
 pub mod randao;
+pub mod rewards;
 pub mod state;
 pub mod validator;
 pub mod version;
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use ream_consensus::{
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    electra::{attester_slashing::AttesterSlashing, beacon_state::BeaconState},
+    proposer_slashing::ProposerSlashing,
+    voluntary_exit::SignedVoluntaryExit,
+};
+use ream_node::operation_pool::get_operation_pool_state;
+use ream_operation_pool::operation_pool::OperationPool;
+use ream_storage::db::ReamDB;
+use tokio::sync::RwLock;
+use warp::{
+    http::status::StatusCode,
+    reject::Rejection,
+    reply::{Reply, reply, with_status},
+};
+
+use crate::types::errors::ApiError;
+
+/// Called by `/beacon/pool/voluntary_exits` to submit a voluntary exit to the operation pool.
+pub async fn post_voluntary_exit(
+    voluntary_exit: SignedVoluntaryExit,
+    db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
+) -> Result<impl Reply, Rejection> {
+    let state = get_pool_state(&db)?;
+    operation_pool
+        .write()
+        .await
+        .insert_voluntary_exit(voluntary_exit, &state)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid voluntary exit: {err}")))?;
+
+    Ok(with_status(reply(), StatusCode::OK))
+}
+
+/// Called by `/beacon/pool/proposer_slashings` to submit a proposer slashing to the operation
+/// pool.
+pub async fn post_proposer_slashing(
+    proposer_slashing: ProposerSlashing,
+    db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
+) -> Result<impl Reply, Rejection> {
+    let state = get_pool_state(&db)?;
+    operation_pool
+        .write()
+        .await
+        .insert_proposer_slashing(proposer_slashing, &state)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid proposer slashing: {err}")))?;
+
+    Ok(with_status(reply(), StatusCode::OK))
+}
+
+/// Called by `/beacon/pool/attester_slashings` to submit an attester slashing to the operation
+/// pool.
+pub async fn post_attester_slashing(
+    attester_slashing: AttesterSlashing,
+    db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
+) -> Result<impl Reply, Rejection> {
+    let state = get_pool_state(&db)?;
+    operation_pool
+        .write()
+        .await
+        .insert_attester_slashing(attester_slashing, &state)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid attester slashing: {err}")))?;
+
+    Ok(with_status(reply(), StatusCode::OK))
+}
+
+/// Called by `/beacon/pool/bls_to_execution_changes` to submit BLS to execution changes to the
+/// operation pool. The valid changes are pooled even if others are rejected.
+pub async fn post_bls_to_execution_changes(
+    bls_to_execution_changes: Vec<SignedBLSToExecutionChange>,
+    db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
+) -> Result<impl Reply, Rejection> {
+    let state = get_pool_state(&db)?;
+    let mut operation_pool = operation_pool.write().await;
+    let failures = bls_to_execution_changes
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, bls_to_execution_change)| {
+            operation_pool
+                .insert_bls_to_execution_change(bls_to_execution_change, &state)
+                .err()
+                .map(|err| format!("{index}: {err}"))
+        })
+        .collect::<Vec<_>>();
+    if !failures.is_empty() {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid BLS to execution changes, {}",
+            failures.join(", ")
+        ))
+        .into());
+    }
+
+    Ok(with_status(reply(), StatusCode::OK))
+}
+
+fn get_pool_state(db: &ReamDB) -> Result<BeaconState, ApiError> {
+    get_operation_pool_state(db).map_err(|err| {
+        ApiError::NotFound(format!("No state to validate operations against: {err}"))
+    })
+}
@@ -0,0 +1,192 @@
+use std::str::FromStr;
+
+use alloy_primitives::B256;
+use ream_consensus::{
+    constants::GENESIS_SLOT,
+    electra::{beacon_block::BeaconBlock, beacon_state::BeaconState},
+    misc::{compute_epoch_at_slot, compute_start_slot_at_epoch},
+    versioned::{beacon_block::VersionedSignedBeaconBlock, beacon_state::VersionedBeaconState},
+};
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use warp::{
+    http::status::StatusCode,
+    reject::Rejection,
+    reply::{Reply, with_status},
+};
+
+use super::block::get_beacon_block_from_id;
+use crate::types::{
+    errors::ApiError,
+    id::{ID, ValidatorID},
+    response::BeaconResponse,
+};
+
+/// Called by `/beacon/rewards/blocks/{block_id}` to get the rewards of the proposer of a block.
+pub async fn get_block_rewards(block_id: ID, db: ReamDB) -> Result<impl Reply, Rejection> {
+    let (block, parent_state) = get_block_and_parent_state(block_id, &db).await?;
+    let block_rewards = parent_state
+        .compute_block_rewards(&block)
+        .map_err(|err| ApiError::BadRequest(format!("Failed to compute block rewards: {err}")))?;
+
+    Ok(with_status(
+        BeaconResponse::json(block_rewards),
+        StatusCode::OK,
+    ))
+}
+
+/// Called by `/beacon/rewards/attestations/{epoch}` to get the rewards of validators for their
+/// attestations of an epoch.
+pub async fn get_attestation_rewards(
+    epoch: u64,
+    validator_ids: Vec<String>,
+    db: ReamDB,
+) -> Result<impl Reply, Rejection> {
+    // The attestations of ``epoch`` are rewarded at the end of the next epoch, which must have
+    // started
+    let next_epoch = epoch
+        .checked_add(1)
+        .ok_or_else(|| ApiError::BadRequest(format!("Invalid epoch {epoch}")))?;
+    let head_slot = db
+        .slot_index_provider()
+        .get_highest_slot()
+        .map_err(|_| ApiError::InternalError)?
+        .ok_or_else(|| ApiError::NotFound(String::from("Failed to find the head block")))?;
+    if next_epoch > compute_epoch_at_slot(head_slot) {
+        return Err(ApiError::NotFound(format!(
+            "Attestation rewards of epoch {epoch} are not available before epoch {next_epoch}"
+        ))
+        .into());
+    }
+    let slot = compute_start_slot_at_epoch(next_epoch + 1) - 1;
+    let mut state = get_latest_state_at_slot(slot, &db).await?;
+    if state.slot < slot {
+        state
+            .process_slots(slot)
+            .map_err(|err| ApiError::BadRequest(format!("Failed to process slots: {err}")))?;
+    }
+
+    let validator_indices = get_validator_indices(&state, validator_ids)?;
+    let attestation_rewards = state
+        .compute_attestation_rewards(&validator_indices)
+        .map_err(|err| {
+            ApiError::BadRequest(format!("Failed to compute attestation rewards: {err}"))
+        })?;
+
+    Ok(with_status(
+        BeaconResponse::json(attestation_rewards),
+        StatusCode::OK,
+    ))
+}
+
+/// Called by `/beacon/rewards/sync_committee/{block_id}` to get the rewards of the sync committee
+/// members for a block.
+pub async fn get_sync_committee_rewards(
+    block_id: ID,
+    validator_ids: Vec<String>,
+    db: ReamDB,
+) -> Result<impl Reply, Rejection> {
+    let (block, parent_state) = get_block_and_parent_state(block_id, &db).await?;
+    let validator_indices = get_validator_indices(&parent_state, validator_ids)?;
+    let sync_committee_rewards = parent_state
+        .compute_sync_committee_rewards(&block)
+        .map_err(|err| {
+            ApiError::BadRequest(format!("Failed to compute sync committee rewards: {err}"))
+        })?
+        .into_iter()
+        .filter(|reward| {
+            validator_indices.is_empty() || validator_indices.contains(&reward.validator_index)
+        })
+        .collect::<Vec<_>>();
+
+    Ok(with_status(
+        BeaconResponse::json(sync_committee_rewards),
+        StatusCode::OK,
+    ))
+}
+
+async fn get_block_and_parent_state(
+    block_id: ID,
+    db: &ReamDB,
+) -> Result<(BeaconBlock, BeaconState), ApiError> {
+    let block = match get_beacon_block_from_id(block_id, db).await? {
+        VersionedSignedBeaconBlock::Electra(block) => block.message,
+        _ => {
+            return Err(ApiError::BadRequest(String::from(
+                "Rewards are only computed for Electra blocks",
+            )));
+        }
+    };
+    let parent_state = get_electra_state(block.parent_root, db)?;
+    Ok((block, parent_state))
+}
+
+/// Return the state of the latest block at or before ``slot``, searching no further back than the
+/// finalized block, the anchor block of a checkpoint synced node.
+async fn get_latest_state_at_slot(slot: u64, db: &ReamDB) -> Result<BeaconState, ApiError> {
+    let finalized_slot = match db
+        .finalized_checkpoint_provider()
+        .get()
+        .map_err(|_| ApiError::InternalError)?
+    {
+        Some(finalized_checkpoint) => db
+            .beacon_block_provider()
+            .get(finalized_checkpoint.root)
+            .map_err(|_| ApiError::InternalError)?
+            .map_or(GENESIS_SLOT, |block| block.slot()),
+        None => GENESIS_SLOT,
+    };
+    for slot in (finalized_slot..=slot).rev() {
+        if let Some(block_root) = db
+            .slot_index_provider()
+            .get(slot)
+            .map_err(|_| ApiError::InternalError)?
+        {
+            return get_electra_state(block_root, db);
+        }
+    }
+    Err(ApiError::NotFound(format!(
+        "Failed to find a block between slot {finalized_slot} and slot {slot}"
+    )))
+}
+
+fn get_electra_state(block_root: B256, db: &ReamDB) -> Result<BeaconState, ApiError> {
+    match db
+        .beacon_state_provider()
+        .get(block_root)
+        .map_err(|_| ApiError::InternalError)?
+    {
+        Some(VersionedBeaconState::Electra(state)) => Ok(state),
+        Some(_) => Err(ApiError::BadRequest(String::from(
+            "Rewards are only computed for Electra states",
+        ))),
+        None => Err(ApiError::NotFound(format!(
+            "Failed to find `beacon_state` from {block_root:?}"
+        ))),
+    }
+}
+
+/// Resolve ``validator_ids``, indices or public keys, to validator indices.
+fn get_validator_indices(
+    state: &BeaconState,
+    validator_ids: Vec<String>,
+) -> Result<Vec<u64>, ApiError> {
+    validator_ids
+        .iter()
+        .map(|validator_id| match ValidatorID::from_str(validator_id)? {
+            ValidatorID::Index(index) => Ok(index),
+            ValidatorID::Address(pubkey) => state
+                .validators
+                .iter()
+                .position(|validator| validator.pubkey == pubkey)
+                .map(|index| index as u64)
+                .ok_or_else(|| {
+                    ApiError::ValidatorNotFound(format!(
+                        "Validator not found for pubkey: {pubkey:?}"
+                    ))
+                }),
+        })
+        .collect()
+}
@@ -1,10 +1,14 @@
-use ream_consensus::{deneb::beacon_state::BeaconState, withdrawal::Withdrawal};
+use alloy_primitives::B256;
+use ream_consensus::{
+    merkle_proof::{MerkleProof, parse_path},
+    versioned::beacon_state::VersionedBeaconState,
+    withdrawal::Withdrawal,
+};
 use ream_storage::{
     db::ReamDB,
     tables::{Field, Table},
 };
 use serde::{Deserialize, Serialize};
-use tree_hash::TreeHash;
 use warp::{
     http::status::StatusCode,
     reject::Rejection,
@@ -14,13 +18,17 @@ use warp::{
 use crate::types::{
     errors::ApiError,
     id::ID,
+    query::ProofQuery,
     response::{
         BeaconResponse, BeaconVersionedResponse, ELECTRA, ETH_CONSENSUS_VERSION_HEADER,
         RootResponse,
     },
 };
 
-pub async fn get_state_from_id(state_id: ID, db: &ReamDB) -> Result<BeaconState, ApiError> {
+pub async fn get_state_from_id(
+    state_id: ID,
+    db: &ReamDB,
+) -> Result<VersionedBeaconState, ApiError> {
     let block_root = match state_id {
         ID::Finalized => {
             let finalized_checkpoint = db
@@ -74,13 +82,60 @@ pub async fn get_state(state_id: ID, db: ReamDB) -> Result<impl Reply, Rejection
 pub async fn get_state_root(state_id: ID, db: ReamDB) -> Result<impl Reply, Rejection> {
     let state = get_state_from_id(state_id, &db).await?;
 
-    let state_root = state.tree_hash_root();
+    let state_root = state.state_root();
 
     Ok(with_status(
         BeaconResponse::json(RootResponse::new(state_root)),
         StatusCode::OK,
     ))
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateProof {
+    #[serde(with = "serde_utils::quoted_u64")]
+    gindex: u64,
+    leaf: B256,
+    branch: Vec<B256>,
+}
+
+/// Called by `/states/{state_id}/proof` to get the Merkle proof of the node of the state at the
+/// given `path` or `gindex`
+pub async fn get_state_proof(
+    state_id: ID,
+    query: ProofQuery,
+    db: ReamDB,
+) -> Result<impl Reply, Rejection> {
+    let VersionedBeaconState::Electra(state) = get_state_from_id(state_id, &db).await? else {
+        return Err(ApiError::BadRequest(String::from(
+            "Proofs are only supported for electra states",
+        )))?;
+    };
+
+    let gindex = match (query.path, query.gindex) {
+        (Some(path), None) => state
+            .get_generalized_index(&parse_path(&path))
+            .map_err(|err| ApiError::BadRequest(format!("Invalid path {path}: {err}")))?,
+        (None, Some(gindex)) => gindex,
+        _ => {
+            return Err(ApiError::BadRequest(String::from(
+                "Expected exactly one of `path` or `gindex`",
+            )))?;
+        }
+    };
+    let proof = state
+        .compute_merkle_proof(gindex)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid gindex {gindex}: {err}")))?;
+
+    Ok(with_status(
+        BeaconResponse::json(StateProof {
+            gindex,
+            leaf: proof.leaf,
+            branch: proof.branch,
+        }),
+        StatusCode::OK,
+    ))
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct WithdrawalData {
     #[serde(with = "serde_utils::quoted_u64")]
@@ -102,8 +157,8 @@ pub async fn get_pending_partial_withdrawals(
     let partial_withdrawals: Vec<Withdrawal> = withdrawals
         .into_iter()
         .filter(|withdrawal: &Withdrawal| {
-            let validator = &state.validators[withdrawal.validator_index as usize];
-            let balance = state.balances[withdrawal.validator_index as usize];
+            let validator = &state.validators()[withdrawal.validator_index as usize];
+            let balance = state.balances()[withdrawal.validator_index as usize];
             validator.is_partially_withdrawable_validator(balance)
         })
         .collect();
@@ -1,4 +1,4 @@
-use ream_consensus::validator::Validator;
+use ream_consensus::validator::{Validator, ValidatorStatus};
 use ream_storage::db::ReamDB;
 use serde::{Deserialize, Serialize};
 use warp::{
@@ -22,12 +22,12 @@ pub struct ValidatorData {
     index: u64,
     #[serde(with = "serde_utils::quoted_u64")]
     balance: u64,
-    status: String,
+    status: ValidatorStatus,
     validator: Validator,
 }
 
 impl ValidatorData {
-    pub fn new(index: u64, balance: u64, status: String, validator: Validator) -> Self {
+    pub fn new(index: u64, balance: u64, status: ValidatorStatus, validator: Validator) -> Self {
         Self {
             index,
             balance,
@@ -46,7 +46,7 @@ pub async fn get_validator_from_state(
 
     let (index, validator) = {
         match &validator_id {
-            ValidatorID::Index(i) => match state.validators.get(*i as usize) {
+            ValidatorID::Index(i) => match state.validators().get(*i as usize) {
                 Some(validator) => (*i as usize, validator.to_owned()),
                 None => {
                     return Err(ApiError::ValidatorNotFound(format!(
@@ -56,7 +56,7 @@ pub async fn get_validator_from_state(
             },
             ValidatorID::Address(pubkey) => {
                 match state
-                    .validators
+                    .validators()
                     .iter()
                     .enumerate()
                     .find(|(_, v)| v.pubkey == *pubkey)
@@ -72,11 +72,14 @@ pub async fn get_validator_from_state(
         }
     };
 
-    let balance = state.balances.get(index).ok_or(ApiError::NotFound(format!(
-        "Validator not found for index: {index}"
-    )))?;
+    let balance = state
+        .balances()
+        .get(index)
+        .ok_or(ApiError::NotFound(format!(
+            "Validator not found for index: {index}"
+        )))?;
 
-    let status = validator_status(&validator, &db).await?;
+    let status = validator.status(state.get_current_epoch(), *balance);
 
     Ok(with_status(
         BeaconResponse::json(ValidatorData::new(
@@ -89,23 +92,6 @@ pub async fn get_validator_from_state(
     ))
 }
 
-pub async fn validator_status(validator: &Validator, db: &ReamDB) -> Result<String, ApiError> {
-    let highest_slot = db
-        .slot_index_provider()
-        .get_highest_slot()
-        .map_err(|_| ApiError::InternalError)?
-        .ok_or(ApiError::NotFound(
-            "Failed to find highest slot".to_string(),
-        ))?;
-    let state = get_state_from_id(ID::Slot(highest_slot), db).await?;
-
-    if validator.exit_epoch < state.get_current_epoch() {
-        Ok("offline".to_string())
-    } else {
-        Ok("active_ongoing".to_string())
-    }
-}
-
 pub async fn get_validators_from_state(
     state_id: ID,
     id_query: IdQuery,
@@ -121,7 +107,7 @@ pub async fn get_validators_from_state(
         for validator_id in validator_ids {
             let (index, _) = {
                 match validator_id {
-                    ValidatorID::Index(i) => match state.validators.get(*i as usize) {
+                    ValidatorID::Index(i) => match state.validators().get(*i as usize) {
                         Some(validator) => (*i as usize, validator.to_owned()),
                         None => {
                             return Err(ApiError::ValidatorNotFound(format!(
@@ -131,7 +117,7 @@ pub async fn get_validators_from_state(
                     },
                     ValidatorID::Address(pubkey) => {
                         match state
-                            .validators
+                            .validators()
                             .iter()
                             .enumerate()
                             .find(|(_, v)| v.pubkey == *pubkey)
@@ -149,22 +135,24 @@ pub async fn get_validators_from_state(
             validator_indices_to_process.push(index);
         }
     } else {
-        validator_indices_to_process = (0..state.validators.len()).collect();
+        validator_indices_to_process = (0..state.validators().len()).collect();
     }
 
     for index in validator_indices_to_process {
-        let validator = &state.validators[index];
+        let validator = &state.validators()[index];
 
-        let status = validator_status(validator, &db).await?;
+        let balance = state
+            .balances()
+            .get(index)
+            .ok_or(ApiError::NotFound(format!(
+                "Validator not found for index: {index}"
+            )))?;
 
+        let status = validator.status(state.get_current_epoch(), *balance);
         if status_query.has_status() && !status_query.contains_status(&status) {
             continue;
         }
 
-        let balance = state.balances.get(index).ok_or(ApiError::NotFound(format!(
-            "Validator not found for index: {index}"
-        )))?;
-
         validators_data.push(ValidatorData::new(
             index as u64,
             *balance,
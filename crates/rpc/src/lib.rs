@@ -2,8 +2,11 @@ use std::sync::Arc;
 
 use config::ServerConfig;
 use ream_network_spec::networks::NetworkSpec;
+use ream_node::block_production::BlockProducer;
+use ream_operation_pool::operation_pool::OperationPool;
 use ream_storage::db::ReamDB;
 use routes::get_routes;
+use tokio::sync::RwLock;
 use tracing::info;
 use utils::error::handle_rejection;
 use warp::{Filter, serve};
@@ -14,9 +17,17 @@ pub mod routes;
 pub mod types;
 pub mod utils;
 
-/// Start the Beacon API server.
-pub async fn start_server(network_spec: Arc<NetworkSpec>, server_config: ServerConfig, db: ReamDB) {
-    let routes = get_routes(network_spec, db).recover(handle_rejection);
+/// Start the Beacon API server, submitting operations to ``operation_pool`` and serving block
+/// production if a ``block_producer`` is given.
+pub async fn start_server(
+    network_spec: Arc<NetworkSpec>,
+    server_config: ServerConfig,
+    db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
+    block_producer: Option<BlockProducer>,
+) {
+    let routes =
+        get_routes(network_spec, db, operation_pool, block_producer).recover(handle_rejection);
 
     info!("Starting server on {:?}", server_config.http_socket_address);
     serve(routes).run(server_config.http_socket_address).await;
@@ -1,7 +1,15 @@
 use std::sync::Arc;
 
+use alloy_primitives::B256;
+use ream_consensus::{
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    electra::attester_slashing::AttesterSlashing, proposer_slashing::ProposerSlashing,
+    voluntary_exit::SignedVoluntaryExit,
+};
 use ream_network_spec::networks::NetworkSpec;
+use ream_operation_pool::operation_pool::OperationPool;
 use ream_storage::db::ReamDB;
+use tokio::sync::RwLock;
 use warp::{
     Filter, Rejection, body,
     filters::{path::end, query::query},
@@ -9,16 +17,26 @@ use warp::{
     reply::Reply,
 };
 
-use super::with_db;
+use super::{with_db, with_operation_pool};
 use crate::{
     handlers::{
-        block::{get_block_attestations, get_block_from_id, get_block_rewards, get_block_root},
+        block::{get_block_attestations, get_block_from_id, get_block_root},
         checkpoint::get_finality_checkpoint,
+        deposit::get_deposit_snapshot,
         fork::get_fork,
         genesis::get_genesis,
         header::get_headers,
+        light_client::{
+            get_light_client_bootstrap, get_light_client_finality_update,
+            get_light_client_optimistic_update, get_light_client_updates,
+        },
+        pool::{
+            post_attester_slashing, post_bls_to_execution_changes, post_proposer_slashing,
+            post_voluntary_exit,
+        },
         randao::get_randao_mix,
-        state::{get_pending_partial_withdrawals, get_state_root},
+        rewards::{get_attestation_rewards, get_block_rewards, get_sync_committee_rewards},
+        state::{get_pending_partial_withdrawals, get_state_proof, get_state_root},
         validator::{
             get_validator_from_state, get_validators_from_state, post_validators_from_state,
         },
@@ -26,7 +44,10 @@ use crate::{
     types::{
         errors::ApiError,
         id::{ID, ValidatorID},
-        query::{IdQuery, ParentRootQuery, RandaoQuery, SlotQuery, StatusQuery},
+        query::{
+            IdQuery, LightClientUpdatesQuery, ParentRootQuery, ProofQuery, RandaoQuery, SlotQuery,
+            StatusQuery,
+        },
         request::ValidatorsPostRequest,
     },
     utils::error::parsed_param,
@@ -38,9 +59,11 @@ const MAX_VALIDATOR_COUNT: usize = 100;
 pub fn get_beacon_routes(
     network_spec: Arc<NetworkSpec>,
     db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     let beacon_base = path("beacon");
     let db_filter = with_db(db);
+    let operation_pool_filter = with_operation_pool(operation_pool);
 
     let genesis = beacon_base
         .and(path("genesis"))
@@ -49,6 +72,14 @@ pub fn get_beacon_routes(
         .and_then(move || get_genesis(network_spec.genesis.clone()))
         .with(log("genesis"));
 
+    let deposit_snapshot = beacon_base
+        .and(path("deposit_snapshot"))
+        .and(end())
+        .and(get())
+        .and(db_filter.clone())
+        .and_then(get_deposit_snapshot)
+        .with(log("deposit_snapshot"));
+
     let fork = beacon_base
         .and(path("states"))
         .and(parsed_param::<ID>())
@@ -69,6 +100,19 @@ pub fn get_beacon_routes(
         .and_then(move |state_id: ID, db: ReamDB| get_state_root(state_id, db))
         .with(log("state_root"));
 
+    let state_proof = beacon_base
+        .and(path("states"))
+        .and(parsed_param::<ID>())
+        .and(path("proof"))
+        .and(query::<ProofQuery>())
+        .and(end())
+        .and(get())
+        .and(db_filter.clone())
+        .and_then(move |state_id: ID, query: ProofQuery, db: ReamDB| {
+            get_state_proof(state_id, query, db)
+        })
+        .with(log("state_proof"));
+
     let randao = beacon_base
         .and(path("states"))
         .and(parsed_param::<ID>())
@@ -164,14 +208,40 @@ pub fn get_beacon_routes(
         .and_then(move |block_id: ID, db: ReamDB| get_block_root(block_id, db))
         .with(log("block_root"));
     let block_rewards = beacon_base
+        .and(path("rewards"))
         .and(path("blocks"))
         .and(parsed_param::<ID>())
-        .and(path("rewards"))
         .and(end())
         .and(get())
         .and(db_filter.clone())
         .and_then(move |block_id: ID, db: ReamDB| get_block_rewards(block_id, db))
         .with(log("block_rewards"));
+    let attestation_rewards = beacon_base
+        .and(path("rewards"))
+        .and(path("attestations"))
+        .and(path::param::<u64>())
+        .and(end())
+        .and(post())
+        .and(body::json::<Vec<String>>())
+        .and(db_filter.clone())
+        .and_then(move |epoch: u64, validator_ids: Vec<String>, db: ReamDB| {
+            get_attestation_rewards(epoch, validator_ids, db)
+        })
+        .with(log("attestation_rewards"));
+    let sync_committee_rewards = beacon_base
+        .and(path("rewards"))
+        .and(path("sync_committee"))
+        .and(parsed_param::<ID>())
+        .and(end())
+        .and(post())
+        .and(body::json::<Vec<String>>())
+        .and(db_filter.clone())
+        .and_then(
+            move |block_id: ID, validator_ids: Vec<String>, db: ReamDB| {
+                get_sync_committee_rewards(block_id, validator_ids, db)
+            },
+        )
+        .with(log("sync_committee_rewards"));
     let pending_partial_withdrawals = beacon_base
         .and(path("states"))
         .and(parsed_param::<ID>())
@@ -181,8 +251,73 @@ pub fn get_beacon_routes(
         .and(db_filter.clone())
         .and_then(move |state_id: ID, db: ReamDB| get_pending_partial_withdrawals(state_id, db))
         .with(log("pending_partial_withdrawals"));
+    let light_client_bootstrap = beacon_base
+        .and(path("light_client"))
+        .and(path("bootstrap"))
+        .and(path::param::<B256>())
+        .and(end())
+        .and(get())
+        .and(db_filter.clone())
+        .and_then(get_light_client_bootstrap)
+        .with(log("light_client_bootstrap"));
+    let light_client_updates = beacon_base
+        .and(path("light_client"))
+        .and(path("updates"))
+        .and(end())
+        .and(get())
+        .and(query::<LightClientUpdatesQuery>())
+        .and(db_filter.clone())
+        .and_then(get_light_client_updates)
+        .with(log("light_client_updates"));
+    let light_client_finality_update = beacon_base
+        .and(path("light_client"))
+        .and(path("finality_update"))
+        .and(end())
+        .and(get())
+        .and(db_filter.clone())
+        .and_then(get_light_client_finality_update)
+        .with(log("light_client_finality_update"));
+    let light_client_optimistic_update = beacon_base
+        .and(path("light_client"))
+        .and(path("optimistic_update"))
+        .and(end())
+        .and(get())
+        .and(db_filter.clone())
+        .and_then(get_light_client_optimistic_update)
+        .with(log("light_client_optimistic_update"));
+    let voluntary_exits = beacon_base
+        .and(path("pool"))
+        .and(path("voluntary_exits"))
+        .and(end())
+        .and(post())
+        .and(body::json::<SignedVoluntaryExit>())
+        .and(db_filter.clone())
+        .and(operation_pool_filter.clone())
+        .and_then(post_voluntary_exit)
+        .with(log("voluntary_exits"));
+    let proposer_slashings = beacon_base
+        .and(path("pool"))
+        .and(path("proposer_slashings"))
+        .and(end())
+        .and(post())
+        .and(body::json::<ProposerSlashing>())
+        .and(db_filter.clone())
+        .and(operation_pool_filter.clone())
+        .and_then(post_proposer_slashing)
+        .with(log("proposer_slashings"));
+    let bls_to_execution_changes = beacon_base
+        .and(path("pool"))
+        .and(path("bls_to_execution_changes"))
+        .and(end())
+        .and(post())
+        .and(body::json::<Vec<SignedBLSToExecutionChange>>())
+        .and(db_filter.clone())
+        .and(operation_pool_filter.clone())
+        .and_then(post_bls_to_execution_changes)
+        .with(log("bls_to_execution_changes"));
 
     genesis
+        .or(deposit_snapshot)
         .or(validator)
         .or(validators)
         .or(post_validators)
@@ -190,16 +325,28 @@ pub fn get_beacon_routes(
         .or(fork)
         .or(checkpoint)
         .or(state_root)
+        .or(state_proof)
         .or(block_root)
         .or(block_rewards)
+        .or(attestation_rewards)
+        .or(sync_committee_rewards)
         .or(pending_partial_withdrawals)
         .or(headers)
+        .or(light_client_bootstrap)
+        .or(light_client_updates)
+        .or(light_client_finality_update)
+        .or(light_client_optimistic_update)
+        .or(voluntary_exits)
+        .or(proposer_slashings)
+        .or(bls_to_execution_changes)
 }
 
 pub fn get_beacon_routes_v2(
     db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     let db_filter = with_db(db);
+    let operation_pool_filter = with_operation_pool(operation_pool);
     let beacon_base = path("beacon");
 
     let block = beacon_base
@@ -221,5 +368,16 @@ pub fn get_beacon_routes_v2(
         .and_then(move |block_id: ID, db: ReamDB| get_block_attestations(block_id, db))
         .with(log("attestations"));
 
-    block.or(attestation)
+    let attester_slashings = beacon_base
+        .and(path("pool"))
+        .and(path("attester_slashings"))
+        .and(end())
+        .and(post())
+        .and(body::json::<AttesterSlashing>())
+        .and(db_filter.clone())
+        .and(operation_pool_filter.clone())
+        .and_then(post_attester_slashing)
+        .with(log("attester_slashings"));
+
+    block.or(attestation).or(attester_slashings)
 }
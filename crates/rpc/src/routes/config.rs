@@ -3,7 +3,7 @@ use std::sync::Arc;
 use ream_network_spec::networks::NetworkSpec;
 use warp::{Filter, Rejection, filters::path::end, get, log, path, reply::Reply};
 
-use crate::handlers::config::{get_deposit_contract, get_spec};
+use crate::handlers::config::{get_deposit_contract, get_fork_schedule, get_spec};
 
 /// Creates and returns all `/config` routes.
 /// Creates and returns all `/config` routes.
@@ -25,9 +25,17 @@ pub fn get_config_routes(
         .and(path("spec"))
         .and(end())
         .and(get())
-        .and(with_network_spec)
+        .and(with_network_spec.clone())
         .and_then(|spec: Arc<NetworkSpec>| get_spec(spec))
         .with(log("spec_config"));
 
-    deposit_contract.or(spec_config)
+    let fork_schedule = path("config")
+        .and(path("fork_schedule"))
+        .and(end())
+        .and(get())
+        .and(with_network_spec)
+        .and_then(|spec: Arc<NetworkSpec>| get_fork_schedule(spec))
+        .with(log("fork_schedule"));
+
+    deposit_contract.or(spec_config).or(fork_schedule)
 }
@@ -5,21 +5,27 @@ use config::get_config_routes;
 use debug::get_debug_routes_v2;
 use node::get_node_routes;
 use ream_network_spec::networks::NetworkSpec;
+use ream_node::block_production::BlockProducer;
+use ream_operation_pool::operation_pool::OperationPool;
 use ream_storage::db::ReamDB;
+use tokio::sync::RwLock;
+use validator::get_validator_routes_v3;
 use warp::{Filter, Rejection, path, reply::Reply};
 
 pub mod beacon;
 pub mod config;
 pub mod debug;
 pub mod node;
+pub mod validator;
 
 fn get_v1_routes(
     network_spec: Arc<NetworkSpec>,
     db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     let eth_base_v1 = path("eth").and(path("v1"));
 
-    let beacon_routes = get_beacon_routes(network_spec.clone(), db.clone());
+    let beacon_routes = get_beacon_routes(network_spec.clone(), db.clone(), operation_pool);
 
     let node_routes = get_node_routes();
 
@@ -28,25 +34,41 @@ fn get_v1_routes(
     eth_base_v1.and(beacon_routes.or(node_routes).or(config_routes))
 }
 
-fn get_v2_routes(db: ReamDB) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+fn get_v2_routes(
+    db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     let eth_base_v2 = path("eth").and(path("v2"));
 
     let debug_routes_v2 = get_debug_routes_v2(db.clone());
 
-    let beacon_routes_v2 = get_beacon_routes_v2(db.clone());
+    let beacon_routes_v2 = get_beacon_routes_v2(db.clone(), operation_pool);
 
     eth_base_v2.and(debug_routes_v2.or(beacon_routes_v2))
 }
 
+fn get_v3_routes(
+    block_producer: Option<BlockProducer>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    let eth_base_v3 = path("eth").and(path("v3"));
+
+    let validator_routes_v3 = get_validator_routes_v3(block_producer);
+
+    eth_base_v3.and(validator_routes_v3)
+}
+
 /// Creates and returns all possible routes.
 pub fn get_routes(
     network_spec: Arc<NetworkSpec>,
     db: ReamDB,
+    operation_pool: Arc<RwLock<OperationPool>>,
+    block_producer: Option<BlockProducer>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    let v1_routes = get_v1_routes(network_spec.clone(), db.clone());
-    let v2_routes = get_v2_routes(db.clone());
+    let v1_routes = get_v1_routes(network_spec.clone(), db.clone(), operation_pool.clone());
+    let v2_routes = get_v2_routes(db.clone(), operation_pool);
+    let v3_routes = get_v3_routes(block_producer);
 
-    v2_routes.or(v1_routes)
+    v3_routes.or(v2_routes).or(v1_routes)
 }
 
 /// Creates a filter for DB.
@@ -55,3 +77,11 @@ fn with_db(
 ) -> impl Filter<Extract = (ReamDB,), Error = std::convert::Infallible> + Clone {
     warp::any().map(move || db.clone())
 }
+
+/// Creates a filter for the operation pool.
+fn with_operation_pool(
+    operation_pool: Arc<RwLock<OperationPool>>,
+) -> impl Filter<Extract = (Arc<RwLock<OperationPool>>,), Error = std::convert::Infallible> + Clone
+{
+    warp::any().map(move || operation_pool.clone())
+}
@@ -0,0 +1,42 @@
+use ream_node::block_production::BlockProducer;
+use warp::{
+    Filter, Rejection,
+    filters::{
+        path::{end, param},
+        query::query,
+    },
+    get, log, path, reject,
+    reply::Reply,
+};
+
+use crate::{handlers::block_production::produce_block_v3, types::query::ProduceBlockQuery};
+
+/// Creates and returns all `/validator` routes. Block production is only routed if a
+/// ``block_producer`` is given.
+pub fn get_validator_routes_v3(
+    block_producer: Option<BlockProducer>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    with_block_producer(block_producer)
+        .and(path("validator"))
+        .and(path("blocks"))
+        .and(param::<u64>())
+        .and(end())
+        .and(get())
+        .and(query::<ProduceBlockQuery>())
+        .and_then(
+            move |block_producer: BlockProducer, slot: u64, query: ProduceBlockQuery| {
+                produce_block_v3(slot, query, block_producer)
+            },
+        )
+        .with(log("produce_block_v3"))
+}
+
+/// Creates a filter for the ``block_producer``, rejecting every request as not found without one.
+fn with_block_producer(
+    block_producer: Option<BlockProducer>,
+) -> impl Filter<Extract = (BlockProducer,), Error = Rejection> + Clone {
+    warp::any().and_then(move || {
+        let block_producer = block_producer.clone();
+        async move { block_producer.ok_or_else(reject::not_found) }
+    })
+}
@@ -1,4 +1,6 @@
 use alloy_primitives::B256;
+use ream_bls::BLSSignature;
+use ream_consensus::validator::ValidatorStatus;
 use serde::Deserialize;
 
 use super::id::ValidatorID;
@@ -23,6 +25,24 @@ pub struct ParentRootQuery {
     pub parent_root: Option<B256>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProduceBlockQuery {
+    pub randao_reveal: BLSSignature,
+    pub graffiti: Option<B256>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProofQuery {
+    pub path: Option<String>,
+    pub gindex: Option<u64>,
+}
+
 #[derive(Default, Debug, Deserialize)]
 pub struct IdQuery {
     pub id: Option<Vec<ValidatorID>>,
@@ -41,9 +61,12 @@ impl StatusQuery {
         }
     }
 
-    pub fn contains_status(&self, status: &String) -> bool {
+    /// Check if ``status``, or the general status it belongs to, was queried.
+    pub fn contains_status(&self, status: &ValidatorStatus) -> bool {
         match &self.status {
-            Some(statuses) => statuses.contains(status),
+            Some(statuses) => statuses
+                .iter()
+                .any(|queried| queried == status.as_str() || queried == status.general_status()),
             None => true, // If no statuses specified, accept all
         }
     }
@@ -4,7 +4,10 @@ use serde_json::json;
 use warp::reply::{Json, json};
 
 pub const ELECTRA: &str = "electra";
+pub const ETH_CONSENSUS_BLOCK_VALUE_HEADER: &str = "Eth-Consensus-Block-Value";
 pub const ETH_CONSENSUS_VERSION_HEADER: &str = "Eth-Consensus-Version";
+pub const ETH_EXECUTION_PAYLOAD_BLINDED_HEADER: &str = "Eth-Execution-Payload-Blinded";
+pub const ETH_EXECUTION_PAYLOAD_VALUE_HEADER: &str = "Eth-Execution-Payload-Value";
 const EXECUTION_OPTIMISTIC: bool = false;
 const FINALIZED: bool = false;
 
@@ -47,6 +50,32 @@ impl<T: Serialize> BeaconResponse<T> {
     }
 }
 
+/// A data struct carrying the fork of its data, as used by the light client endpoints
+///
+/// # Example
+/// {
+///   "version": "electra",
+///   "data": T
+/// }
+#[derive(Debug, Serialize)]
+pub struct DataVersionedResponse<T> {
+    pub version: String,
+    pub data: T,
+}
+
+impl<T: Serialize> DataVersionedResponse<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            version: String::from(ELECTRA),
+            data,
+        }
+    }
+
+    pub fn json(data: T) -> Json {
+        json(&Self::new(data))
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct BeaconVersionedResponse<T> {
     pub version: String,
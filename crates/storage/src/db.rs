@@ -7,16 +7,30 @@ use crate::{
     dir,
     errors::StoreError,
     tables::{
+        backfill_progress::{BACKFILL_PROGRESS_FIELD, BackfillProgressField},
         beacon_block::{BEACON_BLOCK_TABLE, BeaconBlockTable},
         beacon_state::{BEACON_STATE_TABLE, BeaconStateTable},
         block_timeliness::{BLOCK_TIMELINESS_TABLE, BlockTimelinessTable},
         checkpoint_states::{CHECKPOINT_STATES_TABLE, CheckpointStatesTable},
+        deposit_tree_snapshot::{DEPOSIT_TREE_SNAPSHOT_FIELD, DepositTreeSnapshotField},
         equivocating_indices::{EQUIVOCATING_INDICES_FIELD, EquivocatingIndicesField},
         finalized_checkpoint::{FINALIZED_CHECKPOINT_FIELD, FinalizedCheckpointField},
         genesis_time::{GENESIS_TIME_FIELD, GenesisTimeField},
         justified_checkpoint::{JUSTIFIED_CHECKPOINT_FIELD, JustifiedCheckpointField},
         latest_messages::{LATEST_MESSAGES_TABLE, LatestMessagesTable},
+        light_client_bootstrap::{LIGHT_CLIENT_BOOTSTRAP_TABLE, LightClientBootstrapTable},
+        light_client_finality_update::{
+            LIGHT_CLIENT_FINALITY_UPDATE_FIELD, LightClientFinalityUpdateField,
+        },
+        light_client_optimistic_update::{
+            LIGHT_CLIENT_OPTIMISTIC_UPDATE_FIELD, LightClientOptimisticUpdateField,
+        },
+        light_client_update::{LIGHT_CLIENT_UPDATE_TABLE, LightClientUpdateTable},
+        operation_pool::{OPERATION_POOL_FIELD, OperationPoolField},
         proposer_boost_root::{PROPOSER_BOOST_ROOT_FIELD, ProposerBoostRootField},
+        schema_version::{
+            SCHEMA_VERSION, SCHEMA_VERSION_FIELD, SCHEMA_VERSION_KEY, SchemaVersionField,
+        },
         slot_index::{SLOT_INDEX_TABLE, SlotIndexTable},
         state_root_index::{STATE_ROOT_INDEX_TABLE, StateRootIndexTable},
         time::{TIME_FIELD, TimeField},
@@ -60,15 +74,41 @@ impl ReamDB {
             .map_err(|err| StoreError::Database(err.into()))?;
 
         let write_txn = db.begin_write()?;
+
+        // Tables of another schema version can't be decoded, they are refused before any is
+        // opened
+        let has_tables = write_txn.list_tables()?.next().is_some();
+        let mut schema_version_table = write_txn.open_table(SCHEMA_VERSION_FIELD)?;
+        let schema_version = match schema_version_table.get(SCHEMA_VERSION_KEY)? {
+            Some(schema_version) => schema_version.value(),
+            None if has_tables => 0,
+            None => SCHEMA_VERSION,
+        };
+        if schema_version != SCHEMA_VERSION {
+            return Err(StoreError::SchemaVersion {
+                found: schema_version,
+                expected: SCHEMA_VERSION,
+            });
+        }
+        schema_version_table.insert(SCHEMA_VERSION_KEY, SCHEMA_VERSION)?;
+        drop(schema_version_table);
+
+        write_txn.open_table(BACKFILL_PROGRESS_FIELD)?;
         write_txn.open_table(BEACON_BLOCK_TABLE)?;
         write_txn.open_table(BEACON_STATE_TABLE)?;
         write_txn.open_table(BLOCK_TIMELINESS_TABLE)?;
         write_txn.open_table(CHECKPOINT_STATES_TABLE)?;
+        write_txn.open_table(DEPOSIT_TREE_SNAPSHOT_FIELD)?;
         write_txn.open_table(EQUIVOCATING_INDICES_FIELD)?;
         write_txn.open_table(FINALIZED_CHECKPOINT_FIELD)?;
         write_txn.open_table(GENESIS_TIME_FIELD)?;
         write_txn.open_table(JUSTIFIED_CHECKPOINT_FIELD)?;
         write_txn.open_table(LATEST_MESSAGES_TABLE)?;
+        write_txn.open_table(LIGHT_CLIENT_BOOTSTRAP_TABLE)?;
+        write_txn.open_table(LIGHT_CLIENT_FINALITY_UPDATE_FIELD)?;
+        write_txn.open_table(LIGHT_CLIENT_OPTIMISTIC_UPDATE_FIELD)?;
+        write_txn.open_table(LIGHT_CLIENT_UPDATE_TABLE)?;
+        write_txn.open_table(OPERATION_POOL_FIELD)?;
         write_txn.open_table(PROPOSER_BOOST_ROOT_FIELD)?;
         write_txn.open_table(SLOT_INDEX_TABLE)?;
         write_txn.open_table(STATE_ROOT_INDEX_TABLE)?;
@@ -123,6 +163,12 @@ impl ReamDB {
         }
     }
 
+    pub fn schema_version_provider(&self) -> SchemaVersionField {
+        SchemaVersionField {
+            db: self.db.clone(),
+        }
+    }
+
     pub fn unrealized_finalized_checkpoint_provider(&self) -> UnrealizedFinalizedCheckpointField {
         UnrealizedFinalizedCheckpointField {
             db: self.db.clone(),
@@ -165,6 +211,12 @@ impl ReamDB {
         }
     }
 
+    pub fn backfill_progress_provider(&self) -> BackfillProgressField {
+        BackfillProgressField {
+            db: self.db.clone(),
+        }
+    }
+
     pub fn slot_index_provider(&self) -> SlotIndexTable {
         SlotIndexTable {
             db: self.db.clone(),
@@ -176,4 +228,40 @@ impl ReamDB {
             db: self.db.clone(),
         }
     }
+
+    pub fn light_client_bootstrap_provider(&self) -> LightClientBootstrapTable {
+        LightClientBootstrapTable {
+            db: self.db.clone(),
+        }
+    }
+
+    pub fn light_client_update_provider(&self) -> LightClientUpdateTable {
+        LightClientUpdateTable {
+            db: self.db.clone(),
+        }
+    }
+
+    pub fn light_client_finality_update_provider(&self) -> LightClientFinalityUpdateField {
+        LightClientFinalityUpdateField {
+            db: self.db.clone(),
+        }
+    }
+
+    pub fn light_client_optimistic_update_provider(&self) -> LightClientOptimisticUpdateField {
+        LightClientOptimisticUpdateField {
+            db: self.db.clone(),
+        }
+    }
+
+    pub fn deposit_tree_snapshot_provider(&self) -> DepositTreeSnapshotField {
+        DepositTreeSnapshotField {
+            db: self.db.clone(),
+        }
+    }
+
+    pub fn operation_pool_provider(&self) -> OperationPoolField {
+        OperationPoolField {
+            db: self.db.clone(),
+        }
+    }
 }
@@ -19,4 +19,13 @@ pub enum StoreError {
 
     #[error("Io error in creating DB file {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Decode error {0}")]
+    DecodeError(String),
+
+    #[error(
+        "Database schema version {found} is not supported, expected version {expected}. Remove \
+         the data directory to resync"
+    )]
+    SchemaVersion { found: u64, expected: u64 },
 }
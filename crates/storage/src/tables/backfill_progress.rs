@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use redb::{Database, Durability, TableDefinition};
+
+use super::{Field, SSZEncoding};
+use crate::errors::StoreError;
+
+/// Table definition for the Backfill_Progress table
+///
+/// Value: Root of the oldest block stored contiguously from the checkpoint sync anchor
+pub const BACKFILL_PROGRESS_FIELD: TableDefinition<&str, SSZEncoding<B256>> =
+    TableDefinition::new("backfill_progress");
+
+pub const BACKFILL_PROGRESS_KEY: &str = "backfill_progress_key";
+
+pub struct BackfillProgressField {
+    pub db: Arc<Database>,
+}
+
+impl Field for BackfillProgressField {
+    type Value = B256;
+
+    fn get(&self) -> Result<Option<Self::Value>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(BACKFILL_PROGRESS_FIELD)?;
+        let result = table.get(BACKFILL_PROGRESS_KEY)?;
+        Ok(result.map(|res| res.value()))
+    }
+
+    fn insert(&self, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(BACKFILL_PROGRESS_FIELD)?;
+        table.insert(BACKFILL_PROGRESS_KEY, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
@@ -1,21 +1,23 @@
 use std::sync::Arc;
 
 use alloy_primitives::B256;
-use ream_consensus::deneb::beacon_block::SignedBeaconBlock;
+use ream_consensus::versioned::beacon_block::VersionedSignedBeaconBlock;
 use redb::{Database, Durability, TableDefinition};
-use tree_hash::TreeHash;
 
 use super::{
-    SSZEncoding, Table, slot_index::SlotIndexTable, state_root_index::StateRootIndexTable,
+    ForkVersionedSSZEncoding, SSZEncoding, Table, slot_index::SlotIndexTable,
+    state_root_index::StateRootIndexTable,
 };
 use crate::errors::StoreError;
 
 /// Table definition for the Beacon Block table
 ///
 /// Key: block_id
-/// Value: BeaconBlock
-pub const BEACON_BLOCK_TABLE: TableDefinition<SSZEncoding<B256>, SSZEncoding<SignedBeaconBlock>> =
-    TableDefinition::new("beacon_block");
+/// Value: VersionedSignedBeaconBlock
+pub const BEACON_BLOCK_TABLE: TableDefinition<
+    SSZEncoding<B256>,
+    ForkVersionedSSZEncoding<VersionedSignedBeaconBlock>,
+> = TableDefinition::new("beacon_block");
 
 pub struct BeaconBlockTable {
     pub db: Arc<Database>,
@@ -24,34 +26,36 @@ pub struct BeaconBlockTable {
 impl Table for BeaconBlockTable {
     type Key = B256;
 
-    type Value = SignedBeaconBlock;
+    type Value = VersionedSignedBeaconBlock;
 
     fn get(&self, key: Self::Key) -> Result<Option<Self::Value>, StoreError> {
         let read_txn = self.db.begin_read()?;
 
         let table = read_txn.open_table(BEACON_BLOCK_TABLE)?;
         let result = table.get(key)?;
-        Ok(result.map(|res| res.value()))
+        result
+            .map(|res| ForkVersionedSSZEncoding::decode(res.value()))
+            .transpose()
     }
 
     fn insert(&self, key: Self::Key, value: Self::Value) -> Result<(), StoreError> {
         // insert entry to slot_index table
-        let block_root = value.tree_hash_root();
+        let block_root = value.block_root();
         let slot_index_table = SlotIndexTable {
             db: self.db.clone(),
         };
-        slot_index_table.insert(value.message.slot, block_root)?;
+        slot_index_table.insert(value.slot(), block_root)?;
 
         // insert entry to state root index table
         let state_root_index_table = StateRootIndexTable {
             db: self.db.clone(),
         };
-        state_root_index_table.insert(value.message.state_root, block_root)?;
+        state_root_index_table.insert(value.state_root(), block_root)?;
 
         let mut write_txn = self.db.begin_write()?;
         write_txn.set_durability(Durability::Immediate);
         let mut table = write_txn.open_table(BEACON_BLOCK_TABLE)?;
-        table.insert(key, value)?;
+        table.insert(key, ForkVersionedSSZEncoding::encode(&value).as_slice())?;
         drop(table);
         write_txn.commit()?;
         Ok(())
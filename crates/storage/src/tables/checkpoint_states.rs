@@ -1,18 +1,18 @@
 use std::sync::Arc;
 
-use ream_consensus::{checkpoint::Checkpoint, deneb::beacon_state::BeaconState};
+use ream_consensus::{checkpoint::Checkpoint, versioned::beacon_state::VersionedBeaconState};
 use redb::{Database, Durability, TableDefinition};
 
-use super::{SSZEncoding, Table};
+use super::{ForkVersionedSSZEncoding, SSZEncoding, Table};
 use crate::errors::StoreError;
 
 /// Table definition for the Checkpoint States table
 ///
 /// Key: checkpoint_states
-/// Value: BeaconState
+/// Value: VersionedBeaconState
 pub const CHECKPOINT_STATES_TABLE: TableDefinition<
     SSZEncoding<Checkpoint>,
-    SSZEncoding<BeaconState>,
+    ForkVersionedSSZEncoding<VersionedBeaconState>,
 > = TableDefinition::new("checkpoint_states");
 
 pub struct CheckpointStatesTable {
@@ -22,21 +22,23 @@ pub struct CheckpointStatesTable {
 impl Table for CheckpointStatesTable {
     type Key = Checkpoint;
 
-    type Value = BeaconState;
+    type Value = VersionedBeaconState;
 
     fn get(&self, key: Self::Key) -> Result<Option<Self::Value>, StoreError> {
         let read_txn = self.db.begin_read()?;
 
         let table = read_txn.open_table(CHECKPOINT_STATES_TABLE)?;
         let result = table.get(key)?;
-        Ok(result.map(|res| res.value()))
+        result
+            .map(|res| ForkVersionedSSZEncoding::decode(res.value()))
+            .transpose()
     }
 
     fn insert(&self, key: Self::Key, value: Self::Value) -> Result<(), StoreError> {
         let mut write_txn = self.db.begin_write()?;
         write_txn.set_durability(Durability::Immediate);
         let mut table = write_txn.open_table(CHECKPOINT_STATES_TABLE)?;
-        table.insert(key, value)?;
+        table.insert(key, ForkVersionedSSZEncoding::encode(&value).as_slice())?;
         drop(table);
         write_txn.commit()?;
         Ok(())
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use ream_consensus::deposit_tree_snapshot::DepositTreeSnapshot;
+use redb::{Database, Durability, TableDefinition};
+
+use super::{Field, SSZEncoding};
+use crate::errors::StoreError;
+
+/// Table definition for the Deposit_Tree_Snapshot table
+///
+/// Value: DepositTreeSnapshot
+pub const DEPOSIT_TREE_SNAPSHOT_FIELD: TableDefinition<&str, SSZEncoding<DepositTreeSnapshot>> =
+    TableDefinition::new("deposit_tree_snapshot");
+
+pub const DEPOSIT_TREE_SNAPSHOT_FIELD_KEY: &str = "deposit_tree_snapshot_key";
+
+pub struct DepositTreeSnapshotField {
+    pub db: Arc<Database>,
+}
+
+impl Field for DepositTreeSnapshotField {
+    type Value = DepositTreeSnapshot;
+
+    fn get(&self) -> Result<Option<Self::Value>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(DEPOSIT_TREE_SNAPSHOT_FIELD)?;
+        let result = table.get(DEPOSIT_TREE_SNAPSHOT_FIELD_KEY)?;
+        Ok(result.map(|res| res.value()))
+    }
+
+    fn insert(&self, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(DEPOSIT_TREE_SNAPSHOT_FIELD)?;
+        table.insert(DEPOSIT_TREE_SNAPSHOT_FIELD_KEY, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use ream_consensus::electra::light_client_bootstrap::LightClientBootstrap;
+use redb::{Database, Durability, TableDefinition};
+
+use super::{SSZEncoding, Table};
+use crate::errors::StoreError;
+
+/// Table definition for the Light Client Bootstrap table
+///
+/// Key: block_root
+/// Value: LightClientBootstrap
+pub const LIGHT_CLIENT_BOOTSTRAP_TABLE: TableDefinition<
+    SSZEncoding<B256>,
+    SSZEncoding<LightClientBootstrap>,
+> = TableDefinition::new("light_client_bootstrap");
+
+pub struct LightClientBootstrapTable {
+    pub db: Arc<Database>,
+}
+
+impl Table for LightClientBootstrapTable {
+    type Key = B256;
+
+    type Value = LightClientBootstrap;
+
+    fn get(&self, key: Self::Key) -> Result<Option<Self::Value>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(LIGHT_CLIENT_BOOTSTRAP_TABLE)?;
+        let result = table.get(key)?;
+        Ok(result.map(|res| res.value()))
+    }
+
+    fn insert(&self, key: Self::Key, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(LIGHT_CLIENT_BOOTSTRAP_TABLE)?;
+        table.insert(key, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
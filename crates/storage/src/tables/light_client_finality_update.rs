@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use ream_consensus::electra::light_client_finality_update::LightClientFinalityUpdate;
+use redb::{Database, Durability, TableDefinition};
+
+use super::{Field, SSZEncoding};
+use crate::errors::StoreError;
+
+/// Table definition for the Light_Client_Finality_Update table
+///
+/// Value: LightClientFinalityUpdate
+pub const LIGHT_CLIENT_FINALITY_UPDATE_FIELD: TableDefinition<
+    &str,
+    SSZEncoding<LightClientFinalityUpdate>,
+> = TableDefinition::new("light_client_finality_update");
+
+pub const LIGHT_CLIENT_FINALITY_UPDATE_FIELD_KEY: &str = "light_client_finality_update_key";
+
+pub struct LightClientFinalityUpdateField {
+    pub db: Arc<Database>,
+}
+
+impl Field for LightClientFinalityUpdateField {
+    type Value = LightClientFinalityUpdate;
+
+    fn get(&self) -> Result<Option<Self::Value>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(LIGHT_CLIENT_FINALITY_UPDATE_FIELD)?;
+        let result = table.get(LIGHT_CLIENT_FINALITY_UPDATE_FIELD_KEY)?;
+        Ok(result.map(|res| res.value()))
+    }
+
+    fn insert(&self, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(LIGHT_CLIENT_FINALITY_UPDATE_FIELD)?;
+        table.insert(LIGHT_CLIENT_FINALITY_UPDATE_FIELD_KEY, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
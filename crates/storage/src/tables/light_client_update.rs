@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use ream_consensus::electra::light_client_update::LightClientUpdate;
+use redb::{Database, Durability, TableDefinition};
+
+use super::{SSZEncoding, Table};
+use crate::errors::StoreError;
+
+/// Table definition for the Light Client Update table, the best update of each period
+///
+/// Key: sync committee period
+/// Value: LightClientUpdate
+pub const LIGHT_CLIENT_UPDATE_TABLE: TableDefinition<u64, SSZEncoding<LightClientUpdate>> =
+    TableDefinition::new("light_client_update");
+
+pub struct LightClientUpdateTable {
+    pub db: Arc<Database>,
+}
+
+impl Table for LightClientUpdateTable {
+    type Key = u64;
+
+    type Value = LightClientUpdate;
+
+    fn get(&self, key: Self::Key) -> Result<Option<Self::Value>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(LIGHT_CLIENT_UPDATE_TABLE)?;
+        let result = table.get(key)?;
+        Ok(result.map(|res| res.value()))
+    }
+
+    fn insert(&self, key: Self::Key, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(LIGHT_CLIENT_UPDATE_TABLE)?;
+        table.insert(key, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
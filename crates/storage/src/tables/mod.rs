@@ -1,13 +1,21 @@
+pub mod backfill_progress;
 pub mod beacon_block;
 pub mod beacon_state;
 pub mod block_timeliness;
 pub mod checkpoint_states;
+pub mod deposit_tree_snapshot;
 pub mod equivocating_indices;
 pub mod finalized_checkpoint;
 pub mod genesis_time;
 pub mod justified_checkpoint;
 pub mod latest_messages;
+pub mod light_client_bootstrap;
+pub mod light_client_finality_update;
+pub mod light_client_optimistic_update;
+pub mod light_client_update;
+pub mod operation_pool;
 pub mod proposer_boost_root;
+pub mod schema_version;
 pub mod slot_index;
 pub mod state_root_index;
 pub mod time;
@@ -17,6 +25,7 @@ pub mod unrealized_justified_checkpoint;
 
 use std::{any::type_name, fmt::Debug};
 
+use ream_consensus::{fork_name::ForkName, versioned::ForkVersioned};
 use redb::{Key, TypeName, Value};
 use ssz::{Decode, Encode};
 
@@ -92,3 +101,74 @@ where
         TypeName::new(&format!("SSZEncoding<{}>", type_name::<T>()))
     }
 }
+
+/// Wrapper type to handle values whose SSZ layout changes between forks
+///
+/// The SSZ bytes are prefixed with a one byte fork tag, so values from different forks can be
+/// stored in the same table and decoded into the right variant. The table holds the raw bytes,
+/// which the table providers ``encode`` and ``decode`` so a corrupted value is an error rather
+/// than a panic.
+#[derive(Debug)]
+pub struct ForkVersionedSSZEncoding<T>(pub T);
+
+impl<T> ForkVersionedSSZEncoding<T>
+where
+    T: ForkVersioned,
+{
+    pub fn encode(value: &T) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + value.ssz_bytes_len());
+        bytes.push(value.fork_name() as u8);
+        value.ssz_append(&mut bytes);
+        bytes
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn decode(data: &[u8]) -> Result<T, StoreError> {
+        let (fork_tag, bytes) = data
+            .split_first()
+            .ok_or_else(|| StoreError::DecodeError("Missing fork tag".to_string()))?;
+        let fork_name = ForkName::try_from(*fork_tag)
+            .map_err(|err| StoreError::DecodeError(err.to_string()))?;
+        T::from_ssz_bytes_for_fork(fork_name, bytes).map_err(|err| {
+            StoreError::DecodeError(format!("Invalid {fork_name} SSZ bytes: {err:?}"))
+        })
+    }
+}
+
+impl<T> Value for ForkVersionedSSZEncoding<T>
+where
+    T: Debug + ForkVersioned,
+{
+    type SelfType<'a>
+        = &'a [u8]
+    where
+        Self: 'a;
+
+    type AsBytes<'a>
+        = &'a [u8]
+    where
+        Self: 'a;
+
+    fn fixed_width() -> Option<usize> {
+        None
+    }
+
+    fn from_bytes<'a>(data: &'a [u8]) -> Self::SelfType<'a>
+    where
+        Self: 'a,
+    {
+        data
+    }
+
+    fn as_bytes<'a, 'b: 'a>(value: &'a Self::SelfType<'b>) -> Self::AsBytes<'a>
+    where
+        Self: 'a,
+        Self: 'b,
+    {
+        value
+    }
+
+    fn type_name() -> TypeName {
+        TypeName::new(&format!("ForkVersionedSSZEncoding<{}>", type_name::<T>()))
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use ream_operation_pool::persisted_operation_pool::PersistedOperationPool;
+use redb::{Database, Durability, TableDefinition};
+
+use super::{Field, SSZEncoding};
+use crate::errors::StoreError;
+
+/// Table definition for the Operation_Pool table
+///
+/// Value: PersistedOperationPool
+pub const OPERATION_POOL_FIELD: TableDefinition<&str, SSZEncoding<PersistedOperationPool>> =
+    TableDefinition::new("operation_pool");
+
+pub const OPERATION_POOL_FIELD_KEY: &str = "operation_pool_key";
+
+pub struct OperationPoolField {
+    pub db: Arc<Database>,
+}
+
+impl Field for OperationPoolField {
+    type Value = PersistedOperationPool;
+
+    fn get(&self) -> Result<Option<Self::Value>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(OPERATION_POOL_FIELD)?;
+        let result = table.get(OPERATION_POOL_FIELD_KEY)?;
+        Ok(result.map(|res| res.value()))
+    }
+
+    fn insert(&self, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(OPERATION_POOL_FIELD)?;
+        table.insert(OPERATION_POOL_FIELD_KEY, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
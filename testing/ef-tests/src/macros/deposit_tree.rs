@@ -0,0 +1,84 @@
+#[macro_export]
+macro_rules! test_deposit_tree {
+    () => {
+        #[cfg(test)]
+        mod tests_deposit_tree {
+            use alloy_primitives::B256;
+            use ream_consensus::{
+                deposit_data::DepositData, deposit_tree::DepositTree,
+                deposit_tree_snapshot::DepositTreeSnapshot, eth_1_data::Eth1Data,
+            };
+            use rstest::rstest;
+            use tree_hash::TreeHash;
+
+            #[derive(Debug, serde::Deserialize)]
+            struct DepositTestCase {
+                deposit_data: DepositData,
+                deposit_data_root: B256,
+                eth1_data: Eth1Data,
+                block_height: u64,
+                snapshot: DepositTreeSnapshot,
+            }
+
+            fn read_test_cases() -> Vec<DepositTestCase> {
+                let content = std::fs::read_to_string("eip4881/test_cases.yaml")
+                    .expect("cannot find test asset (test_cases.yaml)");
+                serde_yaml::from_str(&content).expect("Failed to parse test_cases.yaml")
+            }
+
+            #[rstest]
+            fn test_deposit_tree_roots() {
+                let test_cases = read_test_cases();
+                let mut deposit_tree = DepositTree::default();
+                for test_case in &test_cases {
+                    assert_eq!(
+                        test_case.deposit_data.tree_hash_root(),
+                        test_case.deposit_data_root
+                    );
+                    deposit_tree.push_leaf(test_case.deposit_data_root).unwrap();
+                    assert_eq!(deposit_tree.get_root(), test_case.eth1_data.deposit_root);
+                    assert_eq!(
+                        test_case.snapshot.calculate_root(),
+                        test_case.snapshot.deposit_root
+                    );
+                }
+            }
+
+            #[rstest]
+            fn test_deposit_tree_snapshots() {
+                let test_cases = read_test_cases();
+                let mut deposit_tree = DepositTree::default();
+                for test_case in &test_cases {
+                    deposit_tree.push_leaf(test_case.deposit_data_root).unwrap();
+                }
+
+                for (index, test_case) in test_cases.iter().enumerate() {
+                    println!("Testing case: {index}");
+                    let mut finalized_tree = deposit_tree.clone();
+                    finalized_tree
+                        .finalize(test_case.eth1_data.clone(), test_case.block_height)
+                        .unwrap();
+                    assert_eq!(finalized_tree.get_snapshot().unwrap(), test_case.snapshot);
+                    assert_eq!(finalized_tree.get_root(), deposit_tree.get_root());
+
+                    // A tree restored from the snapshot catches up by replaying later deposits
+                    let mut restored_tree =
+                        DepositTree::from_snapshot(&test_case.snapshot).unwrap();
+                    assert_eq!(restored_tree.get_root(), test_case.eth1_data.deposit_root);
+                    for later_case in &test_cases[index + 1..] {
+                        restored_tree
+                            .push_leaf(later_case.deposit_data_root)
+                            .unwrap();
+                    }
+                    assert_eq!(restored_tree.get_root(), deposit_tree.get_root());
+                    for deposit_index in index as u64 + 1..test_cases.len() as u64 {
+                        assert_eq!(
+                            restored_tree.get_proof(deposit_index).unwrap(),
+                            deposit_tree.get_proof(deposit_index).unwrap()
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
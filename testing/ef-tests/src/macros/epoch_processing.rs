@@ -1,6 +1,9 @@
 #[macro_export]
 macro_rules! test_epoch_processing {
     ($operation_name:ident, $processing_fn:path) => {
+        $crate::test_epoch_processing!(deneb, $operation_name, $processing_fn);
+    };
+    ($fork:ident, $operation_name:ident, $processing_fn:path) => {
         paste::paste! {
             #[cfg(test)]
             #[allow(non_snake_case)]
@@ -11,8 +14,10 @@ macro_rules! test_epoch_processing {
                 #[rstest]
                 fn test_epoch_processing() {
                     let base_path = format!(
-                        "mainnet/tests/mainnet/deneb/epoch_processing/{}/pyspec_tests",
-                        stringify!($operation_name)
+                        "{preset}/tests/{preset}/{}/epoch_processing/{}/pyspec_tests",
+                        stringify!($fork),
+                        stringify!($operation_name),
+                        preset = ream_consensus::preset::PRESET_BASE
                     );
 
                     for entry in std::fs::read_dir(base_path).unwrap() {
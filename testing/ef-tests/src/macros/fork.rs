@@ -0,0 +1,55 @@
+#[macro_export]
+macro_rules! test_fork {
+    ($fork:ident, $pre_state:ty, $upgrade_fn:path) => {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod tests_fork {
+            use rstest::rstest;
+
+            use super::*;
+
+            #[rstest]
+            fn test_fork() {
+                let base_path = format!(
+                    "{preset}/tests/{preset}/{}/fork/fork/pyspec_tests",
+                    stringify!($fork),
+                    preset = ream_consensus::preset::PRESET_BASE
+                );
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let pre_state: $pre_state =
+                        utils::read_ssz_snappy(&case_dir.join("pre.ssz_snappy"))
+                            .expect("cannot find test asset (pre.ssz_snappy)");
+
+                    let expected_post =
+                        utils::read_ssz_snappy::<BeaconState>(&case_dir.join("post.ssz_snappy"));
+
+                    match ($upgrade_fn(pre_state), expected_post) {
+                        (Ok(state), Some(expected)) => {
+                            assert_eq!(state, expected, "Post state mismatch in case {case_name}");
+                        }
+                        (Ok(_), None) => {
+                            panic!("Test case {case_name} should have failed but succeeded");
+                        }
+                        (Err(err), Some(_)) => {
+                            panic!("Test case {case_name} should have succeeded but failed, err={err:?}");
+                        }
+                        (Err(_), None) => {
+                            // Expected: an invalid upgrade results in an error and no post state.
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
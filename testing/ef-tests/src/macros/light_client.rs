@@ -0,0 +1,275 @@
+#[macro_export]
+macro_rules! test_light_client {
+    ($fork:ident) => {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod tests_light_client {
+            use std::{path::PathBuf, str::FromStr};
+
+            use alloy_primitives::B256;
+            use ream_consensus::{
+                chain_spec::{ChainSpec, chain_spec},
+                constants::{
+                    CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA, EXECUTION_PAYLOAD_GINDEX,
+                    FINALIZED_ROOT_GINDEX_ELECTRA, NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+                },
+                deneb::beacon_state::is_valid_merkle_branch,
+                electra::{
+                    light_client_bootstrap::LightClientBootstrap,
+                    light_client_header::LightClientHeader, light_client_update::LightClientUpdate,
+                },
+                fork_data::ForkData,
+                fork_name::ForkName,
+            };
+            use ream_light_client::store::LightClientStore;
+            use rstest::rstest;
+            use serde_yaml::Value;
+            use tree_hash::TreeHash;
+
+            use super::*;
+
+            #[derive(Debug, serde::Deserialize)]
+            struct Proof {
+                leaf: String,
+                leaf_index: u64,
+                branch: Vec<String>,
+            }
+
+            fn case_dirs(runner: &str, handler: &str) -> Vec<PathBuf> {
+                let base_path = format!(
+                    "{preset}/tests/{preset}/{}/light_client/{runner}/{handler}",
+                    stringify!($fork),
+                    preset = ream_consensus::preset::PRESET_BASE
+                );
+                std::fs::read_dir(base_path)
+                    .unwrap()
+                    .map(|entry| entry.unwrap().path())
+                    .filter(|path| path.is_dir())
+                    .collect()
+            }
+
+            fn read_proof(case_dir: &PathBuf) -> (B256, u64, Vec<B256>) {
+                let content = std::fs::read_to_string(case_dir.join("proof.yaml"))
+                    .expect("cannot find test asset (proof.yaml)");
+                let proof: Proof = serde_yaml::from_str(&content).unwrap();
+                let branch = proof
+                    .branch
+                    .iter()
+                    .map(|node| B256::from_str(node).unwrap())
+                    .collect();
+                (
+                    B256::from_str(&proof.leaf).unwrap(),
+                    proof.leaf_index,
+                    branch,
+                )
+            }
+
+            #[rstest]
+            fn test_beacon_state_merkle_proof() {
+                for case_dir in case_dirs("single_merkle_proof", "BeaconState") {
+                    println!("Testing case: {}", case_dir.display());
+                    let state: BeaconState =
+                        utils::read_ssz_snappy(&case_dir.join("object.ssz_snappy"))
+                            .expect("cannot find test asset (object.ssz_snappy)");
+                    let (leaf, leaf_index, expected_branch) = read_proof(&case_dir);
+
+                    let branch = match leaf_index {
+                        CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA => {
+                            state.current_sync_committee_branch().unwrap().to_vec()
+                        }
+                        NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA => {
+                            state.next_sync_committee_branch().unwrap().to_vec()
+                        }
+                        FINALIZED_ROOT_GINDEX_ELECTRA => state.finality_branch().unwrap().to_vec(),
+                        _ => panic!("Unknown leaf index {leaf_index}"),
+                    };
+
+                    assert_eq!(branch, expected_branch);
+                    let depth = leaf_index.ilog2();
+                    assert!(is_valid_merkle_branch(
+                        leaf,
+                        &branch,
+                        depth as u64,
+                        leaf_index % 2u64.pow(depth),
+                        state.tree_hash_root()
+                    ));
+                }
+            }
+
+            #[rstest]
+            fn test_beacon_block_body_merkle_proof() {
+                for case_dir in case_dirs("single_merkle_proof", "BeaconBlockBody") {
+                    let (leaf, leaf_index, expected_branch) = read_proof(&case_dir);
+                    if leaf_index != EXECUTION_PAYLOAD_GINDEX {
+                        continue;
+                    }
+                    println!("Testing case: {}", case_dir.display());
+                    let body: BeaconBlockBody =
+                        utils::read_ssz_snappy(&case_dir.join("object.ssz_snappy"))
+                            .expect("cannot find test asset (object.ssz_snappy)");
+
+                    assert_eq!(leaf, body.execution_payload.tree_hash_root());
+                    assert_eq!(
+                        body.execution_payload_branch().unwrap().to_vec(),
+                        expected_branch
+                    );
+                }
+            }
+
+            #[rstest]
+            fn test_update_ranking() {
+                for case_dir in case_dirs("update_ranking", "pyspec_tests") {
+                    println!("Testing case: {}", case_dir.display());
+                    let meta: Value = serde_yaml::from_str(
+                        &std::fs::read_to_string(case_dir.join("meta.yaml"))
+                            .expect("cannot find test asset (meta.yaml)"),
+                    )
+                    .unwrap();
+                    let updates_count = meta.get("updates_count").unwrap().as_u64().unwrap();
+                    let updates = (0..updates_count)
+                        .map(|index| {
+                            utils::read_ssz_snappy::<LightClientUpdate>(
+                                &case_dir.join(format!("updates_{index}.ssz_snappy")),
+                            )
+                            .expect("cannot find test asset (updates_<index>.ssz_snappy)")
+                        })
+                        .collect::<Vec<_>>();
+
+                    // The updates are sorted from best to worst
+                    for pair in updates.windows(2) {
+                        assert!(!pair[1].is_better_update(&pair[0]));
+                    }
+                }
+            }
+
+            #[derive(Debug, serde::Deserialize)]
+            struct Meta {
+                genesis_validators_root: B256,
+                trusted_block_root: B256,
+                bootstrap_fork_digest: String,
+                store_fork_digest: String,
+            }
+
+            #[derive(Debug, serde::Deserialize)]
+            struct HeaderCheck {
+                slot: u64,
+                beacon_root: B256,
+                execution_root: B256,
+            }
+
+            #[derive(Debug, serde::Deserialize)]
+            struct Checks {
+                finalized_header: HeaderCheck,
+                optimistic_header: HeaderCheck,
+            }
+
+            #[derive(Debug, serde::Deserialize)]
+            #[serde(rename_all = "snake_case")]
+            enum Step {
+                ForceUpdate {
+                    current_slot: u64,
+                    checks: Checks,
+                },
+                ProcessUpdate {
+                    update_fork_digest: String,
+                    update: String,
+                    current_slot: u64,
+                    checks: Checks,
+                },
+                UpgradeStore {},
+            }
+
+            fn assert_header(header: &LightClientHeader, check: &HeaderCheck) {
+                assert_eq!(header.beacon.slot, check.slot);
+                assert_eq!(header.beacon.tree_hash_root(), check.beacon_root);
+                assert_eq!(header.execution.tree_hash_root(), check.execution_root);
+            }
+
+            #[rstest]
+            fn test_sync() {
+                for case_dir in case_dirs("sync", "pyspec_tests") {
+                    let meta: Meta = serde_yaml::from_str(
+                        &std::fs::read_to_string(case_dir.join("meta.yaml"))
+                            .expect("cannot find test asset (meta.yaml)"),
+                    )
+                    .unwrap();
+                    let steps: Vec<Step> = serde_yaml::from_str(
+                        &std::fs::read_to_string(case_dir.join("steps.yaml"))
+                            .expect("cannot find test asset (steps.yaml)"),
+                    )
+                    .unwrap();
+                    let chain_spec = match case_dir.join("config.yaml") {
+                        path if path.exists() => ChainSpec::from_file(&path).unwrap(),
+                        _ => chain_spec().clone(),
+                    };
+
+                    // Only the light client data of this fork is supported, skip the cases
+                    // crossing a fork boundary
+                    let fork_digest = ForkData {
+                        current_version: chain_spec.fork_version(ForkName::Electra),
+                        genesis_validators_root: meta.genesis_validators_root,
+                    }
+                    .compute_fork_digest()
+                    .to_string();
+                    if meta.bootstrap_fork_digest != fork_digest
+                        || meta.store_fork_digest != fork_digest
+                        || steps.iter().any(|step| match step {
+                            Step::ProcessUpdate {
+                                update_fork_digest, ..
+                            } => *update_fork_digest != fork_digest,
+                            Step::UpgradeStore {} => true,
+                            Step::ForceUpdate { .. } => false,
+                        })
+                    {
+                        println!("Skipping case: {}", case_dir.display());
+                        continue;
+                    }
+                    println!("Testing case: {}", case_dir.display());
+
+                    let bootstrap: LightClientBootstrap =
+                        utils::read_ssz_snappy(&case_dir.join("bootstrap.ssz_snappy"))
+                            .expect("cannot find test asset (bootstrap.ssz_snappy)");
+                    let mut store = LightClientStore::new(
+                        meta.trusted_block_root,
+                        &bootstrap,
+                        meta.genesis_validators_root,
+                        chain_spec.fork_schedule(),
+                    )
+                    .unwrap();
+
+                    for step in steps {
+                        let checks = match step {
+                            Step::ForceUpdate {
+                                current_slot,
+                                checks,
+                            } => {
+                                store
+                                    .process_light_client_store_force_update(current_slot)
+                                    .unwrap();
+                                checks
+                            }
+                            Step::ProcessUpdate {
+                                update,
+                                current_slot,
+                                checks,
+                                ..
+                            } => {
+                                let update: LightClientUpdate = utils::read_ssz_snappy(
+                                    &case_dir.join(format!("{update}.ssz_snappy")),
+                                )
+                                .expect("cannot find test asset (update)");
+                                store
+                                    .process_light_client_update(&update, current_slot)
+                                    .unwrap();
+                                checks
+                            }
+                            Step::UpgradeStore {} => unreachable!(),
+                        };
+                        assert_header(&store.finalized_header, &checks.finalized_header);
+                        assert_header(&store.optimistic_header, &checks.optimistic_header);
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,92 @@
+#[macro_export]
+macro_rules! test_merkle_proof {
+    ($fork:ident) => {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod tests_merkle_proof {
+            use std::str::FromStr;
+
+            use alloy_primitives::B256;
+            use ream_consensus::{
+                deneb::beacon_state::is_valid_merkle_branch, merkle_proof::MerkleProof,
+            };
+            use rstest::rstest;
+            use tree_hash::TreeHash;
+
+            use super::*;
+
+            #[derive(Debug, serde::Deserialize)]
+            struct Proof {
+                leaf: String,
+                leaf_index: u64,
+                branch: Vec<String>,
+            }
+
+            fn case_dirs(runner: &str, handler: &str) -> Vec<std::path::PathBuf> {
+                let base_path = format!(
+                    "{preset}/tests/{preset}/{}/{runner}/single_merkle_proof/{handler}",
+                    stringify!($fork),
+                    preset = ream_consensus::preset::PRESET_BASE
+                );
+                std::fs::read_dir(base_path)
+                    .unwrap()
+                    .map(|entry| entry.unwrap().path())
+                    .filter(|path| path.is_dir())
+                    .collect()
+            }
+
+            fn assert_single_merkle_proof<T: MerkleProof + TreeHash>(
+                object: &T,
+                case_dir: &std::path::Path,
+            ) {
+                let proof: Proof = serde_yaml::from_str(
+                    &std::fs::read_to_string(case_dir.join("proof.yaml"))
+                        .expect("cannot find test asset (proof.yaml)"),
+                )
+                .unwrap();
+                let expected_leaf = B256::from_str(&proof.leaf).unwrap();
+                let expected_branch = proof
+                    .branch
+                    .iter()
+                    .map(|node| B256::from_str(node).unwrap())
+                    .collect::<Vec<_>>();
+
+                let computed = object.compute_merkle_proof(proof.leaf_index).unwrap();
+                assert_eq!(computed.leaf, expected_leaf);
+                assert_eq!(computed.branch, expected_branch);
+
+                let depth = proof.leaf_index.ilog2();
+                assert!(is_valid_merkle_branch(
+                    computed.leaf,
+                    &computed.branch,
+                    depth as u64,
+                    proof.leaf_index % 2u64.pow(depth),
+                    object.tree_hash_root()
+                ));
+            }
+
+            #[rstest]
+            fn test_beacon_block_body_single_merkle_proof() {
+                for case_dir in case_dirs("merkle_proof", "BeaconBlockBody") {
+                    println!("Testing case: {}", case_dir.display());
+                    let body: BeaconBlockBody =
+                        utils::read_ssz_snappy(&case_dir.join("object.ssz_snappy"))
+                            .expect("cannot find test asset (object.ssz_snappy)");
+                    assert_single_merkle_proof(&body, &case_dir);
+                }
+            }
+
+            #[rstest]
+            fn test_beacon_state_single_merkle_proof() {
+                // The light client proofs of the sync committees and the finalized root
+                for case_dir in case_dirs("light_client", "BeaconState") {
+                    println!("Testing case: {}", case_dir.display());
+                    let state: BeaconState =
+                        utils::read_ssz_snappy(&case_dir.join("object.ssz_snappy"))
+                            .expect("cannot find test asset (object.ssz_snappy)");
+                    assert_single_merkle_proof(&state, &case_dir);
+                }
+            }
+        }
+    };
+}
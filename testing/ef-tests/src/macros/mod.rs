@@ -1,7 +1,13 @@
+pub mod deposit_tree;
 pub mod epoch_processing;
+pub mod fork;
+pub mod light_client;
+pub mod merkle_proof;
 pub mod operations;
 pub mod rewards;
 pub mod sanity_blocks;
 pub mod sanity_slots;
 pub mod shuffling;
 pub mod ssz_static;
+pub mod tree_hash_cache;
+pub mod versioned_decoding;
@@ -1,9 +1,11 @@
 #[macro_export]
 macro_rules! test_operation_impl {
-    ($operation_name:ident, $operation_object:ty, $input_name:literal, $compute_result:expr) => {{
+    ($fork:ident, $operation_name:ident, $operation_object:ty, $input_name:literal, $compute_result:expr) => {{
         let base_path = format!(
-            "mainnet/tests/mainnet/deneb/operations/{}/pyspec_tests",
-            stringify!($operation_name)
+            "{preset}/tests/{preset}/{}/operations/{}/pyspec_tests",
+            stringify!($fork),
+            stringify!($operation_name),
+            preset = ream_consensus::preset::PRESET_BASE
         );
         for entry in std::fs::read_dir(&base_path).unwrap() {
             let entry = entry.unwrap();
@@ -55,8 +57,15 @@ macro_rules! test_operation_impl {
 
 #[macro_export]
 macro_rules! test_operation {
-    // Variant with a processing function provided.
+    // Variants defaulting to the deneb test vectors.
     ($operation_name:ident, $operation_object:ty, $input_name:literal, $processing_fn:path) => {
+        $crate::test_operation!(deneb, $operation_name, $operation_object, $input_name, $processing_fn);
+    };
+    ($operation_name:ident, $operation_object:ty, $input_name:literal) => {
+        $crate::test_operation!(deneb, $operation_name, $operation_object, $input_name);
+    };
+    // Variant with a processing function provided.
+    ($fork:ident, $operation_name:ident, $operation_object:ty, $input_name:literal, $processing_fn:path) => {
         paste::paste! {
             #[cfg(test)]
             #[allow(non_snake_case)]
@@ -69,7 +78,7 @@ macro_rules! test_operation {
 
                 #[tokio::test]
                 async fn test_operation() {
-                    test_operation_impl!($operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState>>, input: $operation_object, _case_dir: PathBuf| async move {
+                    test_operation_impl!($fork, $operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState>>, input: $operation_object, _case_dir: PathBuf| async move {
                         state.lock().await.$processing_fn(&input)
                     });
                 }
@@ -77,7 +86,7 @@ macro_rules! test_operation {
         }
     };
     // Variant that uses process_execution_payload with a mock engine.
-    ($operation_name:ident, $operation_object:ty, $input_name:literal) => {
+    ($fork:ident, $operation_name:ident, $operation_object:ty, $input_name:literal) => {
         #[cfg(test)]
         mod tests_process_execution_payload {
             use super::*;
@@ -88,7 +97,7 @@ macro_rules! test_operation {
 
             #[tokio::test]
             async fn test_operation() {
-                test_operation_impl!($operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState>>, input: $operation_object, case_dir: PathBuf| async move {
+                test_operation_impl!($fork, $operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState>>, input: $operation_object, case_dir: PathBuf| async move {
                     let mock_engine = MockExecutionEngine::new(&case_dir.as_path().join("execution.yaml"))
                         .expect("remove result");
                     state.lock().await.process_execution_payload(&input, &mock_engine).await
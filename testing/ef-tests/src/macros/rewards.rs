@@ -23,8 +23,9 @@ macro_rules! test_rewards {
                 #[rstest]
                 fn test_rewards() {
                     let base_path = format!(
-                        "mainnet/tests/mainnet/deneb/rewards/{}/pyspec_tests",
-                        stringify!($operation_name)
+                        "{preset}/tests/{preset}/deneb/rewards/{}/pyspec_tests",
+                        stringify!($operation_name),
+                        preset = ream_consensus::preset::PRESET_BASE
                     );
 
                     for entry in std::fs::read_dir(base_path).unwrap() {
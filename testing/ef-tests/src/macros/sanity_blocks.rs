@@ -1,12 +1,18 @@
 #[macro_export]
 macro_rules! test_sanity_blocks {
     () => {
+        $crate::test_sanity_blocks!(deneb);
+    };
+    ($fork:ident) => {
         #[cfg(test)]
         #[allow(non_snake_case)]
         mod test_sanity_blocks {
             use std::{fs, path::Path};
 
-            use ream_consensus::execution_engine::mock_engine::MockExecutionEngine;
+            use ream_consensus::{
+                execution_engine::mock_engine::MockExecutionEngine,
+                state_transition::BlockSignatureStrategy,
+            };
             use serde_yaml;
 
             use super::*;
@@ -19,9 +25,11 @@ macro_rules! test_sanity_blocks {
 
             #[tokio::test]
             async fn test_sanity_blocks() {
-                let base_path = std::env::current_dir()
-                    .unwrap()
-                    .join("mainnet/tests/mainnet/deneb/sanity/blocks/pyspec_tests");
+                let base_path = std::env::current_dir().unwrap().join(format!(
+                    "{preset}/tests/{preset}/{}/sanity/blocks/pyspec_tests",
+                    stringify!($fork),
+                    preset = ream_consensus::preset::PRESET_BASE
+                ));
 
                 let mock_engine = MockExecutionEngine {
                     execution_valid: true,
@@ -61,9 +69,16 @@ macro_rules! test_sanity_blocks {
                         let signed_block: SignedBeaconBlock = utils::read_ssz_snappy(&block_path)
                             .expect(&format!("cannot find test asset (blocks_{i}.ssz_snappy)"));
 
+                        // Verify the signatures of each block as a single batch, as the node does
                         result = state
-                            .state_transition(&signed_block, validate_result, &mock_engine)
+                            .state_transition_with_strategy(
+                                &signed_block,
+                                BlockSignatureStrategy::VerifyBulk,
+                                validate_result,
+                                &mock_engine,
+                            )
                             .await
+                            .map(|_| ())
                             .map_err(|err| err.to_string());
                     }
 
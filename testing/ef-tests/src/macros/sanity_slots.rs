@@ -1,6 +1,9 @@
 #[macro_export]
 macro_rules! test_sanity_slots {
     () => {
+        $crate::test_sanity_slots!(deneb);
+    };
+    ($fork:ident) => {
         #[cfg(test)]
         #[allow(non_snake_case)]
         mod tests_sanity {
@@ -18,9 +21,11 @@ macro_rules! test_sanity_slots {
 
             #[tokio::test]
             async fn test_sanity_slots() {
-                let base_path = std::env::current_dir()
-                    .unwrap()
-                    .join("mainnet/tests/mainnet/deneb/sanity/slots/pyspec_tests");
+                let base_path = std::env::current_dir().unwrap().join(format!(
+                    "{preset}/tests/{preset}/{}/sanity/slots/pyspec_tests",
+                    stringify!($fork),
+                    preset = ream_consensus::preset::PRESET_BASE
+                ));
 
                 for entry in std::fs::read_dir(&base_path).unwrap() {
                     let entry = entry.unwrap();
@@ -19,7 +19,10 @@ macro_rules! test_shuffling {
 
             #[rstest]
             fn test_shuffling() {
-                let base_path = "mainnet/tests/mainnet/phase0/shuffling/core/shuffle";
+                let base_path = format!(
+                    "{preset}/tests/{preset}/phase0/shuffling/core/shuffle",
+                    preset = ream_consensus::preset::PRESET_BASE
+                );
 
                 for entry in std::fs::read_dir(base_path).unwrap() {
                     let entry = entry.unwrap();
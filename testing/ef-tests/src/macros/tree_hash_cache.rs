@@ -0,0 +1,57 @@
+#[macro_export]
+macro_rules! test_tree_hash_cache {
+    () => {
+        $crate::test_tree_hash_cache!(deneb);
+    };
+    ($fork:ident) => {
+        #[cfg(test)]
+        mod tests_tree_hash_cache {
+            use std::str::FromStr;
+
+            use alloy_primitives::B256;
+            use ream_consensus::tree_hash_cache::BeaconStateTreeHashCache;
+            use serde_yaml::Value;
+            use snap::raw::Decoder;
+            use ssz::Decode;
+
+            use super::*;
+
+            #[test]
+            fn test_tree_hash_cache() {
+                // The cache is reused across cases, so every case after the first one exercises
+                // the incremental path against an unrelated state
+                let mut cache = BeaconStateTreeHashCache::default();
+
+                for case in ["case_0", "case_1", "case_2", "case_3", "case_4"] {
+                    let path = format!(
+                        "{preset}/tests/{preset}/{}/ssz_static/BeaconState/ssz_random/{case}/",
+                        stringify!($fork),
+                        preset = ream_consensus::preset::PRESET_BASE
+                    );
+
+                    let hash_root = {
+                        let hash_root_content =
+                            std::fs::read_to_string(format!("{path}roots.yaml"))
+                                .expect("cannot find test asset");
+                        let value: Value = serde_yaml::from_str(&hash_root_content).unwrap();
+                        B256::from_str(value.get("root").unwrap().as_str().unwrap()).unwrap()
+                    };
+
+                    let state = {
+                        let ssz_snappy = std::fs::read(format!("{path}serialized.ssz_snappy"))
+                            .expect("cannot find test asset");
+                        let mut decoder = Decoder::new();
+                        let ssz = decoder.decompress_vec(&ssz_snappy).unwrap();
+                        BeaconState::from_ssz_bytes(&ssz).unwrap()
+                    };
+
+                    assert_eq!(
+                        state.tree_hash_root_with_cache(&mut cache),
+                        hash_root,
+                        "{case}"
+                    );
+                }
+            }
+        }
+    };
+}
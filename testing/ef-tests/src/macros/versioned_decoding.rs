@@ -0,0 +1,92 @@
+/// Decode the states and blocks of the ``sanity/blocks`` tests of ``fork`` through the fork
+/// dispatching ``VersionedBeaconState`` and ``VersionedSignedBeaconBlock``, for forks whose
+/// state transition isn't run.
+#[macro_export]
+macro_rules! test_versioned_decoding {
+    ($fork:ident) => {
+        paste::paste! {
+            #[cfg(test)]
+            #[allow(non_snake_case)]
+            mod [<test_versioned_decoding_ $fork>] {
+                use ream_consensus::{
+                    fork_name::ForkName,
+                    fork_schedule::{ForkSchedule, ScheduledFork},
+                    versioned::{
+                        ForkVersioned, beacon_block::VersionedSignedBeaconBlock,
+                        beacon_state::VersionedBeaconState,
+                    },
+                };
+                use snap::raw::Decoder;
+                use ssz::Encode;
+
+                const FORK_NAME: ForkName = ForkName::[<$fork:camel>];
+
+                fn read_snappy(path: &std::path::Path) -> Vec<u8> {
+                    Decoder::new()
+                        .decompress_vec(&std::fs::read(path).unwrap())
+                        .unwrap()
+                }
+
+                #[test]
+                fn test_versioned_decoding() {
+                    let base_path = format!(
+                        "{preset}/tests/{preset}/{}/sanity/blocks/pyspec_tests",
+                        stringify!($fork),
+                        preset = ream_consensus::preset::PRESET_BASE
+                    );
+                    // The fork is active from genesis, as in the tests
+                    let fork_schedule = ForkSchedule::new(vec![ScheduledFork {
+                        fork_name: FORK_NAME,
+                        version: Default::default(),
+                        epoch: 0,
+                    }]);
+
+                    for entry in std::fs::read_dir(base_path).unwrap() {
+                        let case_dir = entry.unwrap().path();
+                        if !case_dir.is_dir() {
+                            continue;
+                        }
+                        let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                        println!("Testing case: {case_name}");
+
+                        for state_file in ["pre.ssz_snappy", "post.ssz_snappy"] {
+                            let state_path = case_dir.join(state_file);
+                            if !state_path.exists() {
+                                continue;
+                            }
+                            let bytes = read_snappy(&state_path);
+                            let state = VersionedBeaconState::from_ssz_bytes_with_fork_schedule(
+                                &bytes,
+                                &fork_schedule,
+                            )
+                            .unwrap_or_else(|err| {
+                                panic!("Failed to decode {state_file} of {case_name}: {err:?}")
+                            });
+                            assert_eq!(state.fork_name(), FORK_NAME);
+                            assert_eq!(state.as_ssz_bytes(), bytes, "{state_file} of {case_name}");
+                        }
+
+                        for block_entry in std::fs::read_dir(&case_dir).unwrap() {
+                            let block_path = block_entry.unwrap().path();
+                            let file_name = block_path.file_name().unwrap().to_str().unwrap();
+                            if !file_name.starts_with("blocks_") {
+                                continue;
+                            }
+                            let bytes = read_snappy(&block_path);
+                            let block =
+                                VersionedSignedBeaconBlock::from_ssz_bytes_with_fork_schedule(
+                                    &bytes,
+                                    &fork_schedule,
+                                )
+                                .unwrap_or_else(|err| {
+                                        panic!("Failed to decode {file_name} of {case_name}: {err:?}")
+                                    });
+                            assert_eq!(block.fork_name(), FORK_NAME);
+                            assert_eq!(block.as_ssz_bytes(), bytes, "{file_name} of {case_name}");
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
@@ -1,8 +1,9 @@
 #![cfg(feature = "ef-tests")]
 
 use ef_tests::{
-    test_consensus_type, test_epoch_processing, test_operation, test_rewards, test_sanity_blocks,
-    test_sanity_slots, test_shuffling, utils,
+    test_consensus_type, test_deposit_tree, test_epoch_processing, test_operation, test_rewards,
+    test_sanity_blocks, test_sanity_slots, test_shuffling, test_tree_hash_cache,
+    test_versioned_decoding, utils,
 };
 use ream_consensus::{
     attestation::Attestation,
@@ -67,6 +68,115 @@ test_consensus_type!(Validator);
 test_consensus_type!(VoluntaryExit);
 test_consensus_type!(Withdrawal);
 
+// Testing consensus types of earlier forks
+test_consensus_type!(
+    phase0,
+    BeaconBlock,
+    ream_consensus::phase0::beacon_block::BeaconBlock
+);
+test_consensus_type!(
+    phase0,
+    BeaconBlockBody,
+    ream_consensus::phase0::beacon_block_body::BeaconBlockBody
+);
+test_consensus_type!(
+    phase0,
+    BeaconState,
+    ream_consensus::phase0::beacon_state::BeaconState
+);
+test_consensus_type!(
+    phase0,
+    SignedBeaconBlock,
+    ream_consensus::phase0::beacon_block::SignedBeaconBlock
+);
+test_consensus_type!(
+    altair,
+    BeaconBlock,
+    ream_consensus::altair::beacon_block::BeaconBlock
+);
+test_consensus_type!(
+    altair,
+    BeaconBlockBody,
+    ream_consensus::altair::beacon_block_body::BeaconBlockBody
+);
+test_consensus_type!(
+    altair,
+    BeaconState,
+    ream_consensus::altair::beacon_state::BeaconState
+);
+test_consensus_type!(
+    altair,
+    SignedBeaconBlock,
+    ream_consensus::altair::beacon_block::SignedBeaconBlock
+);
+test_consensus_type!(
+    bellatrix,
+    BeaconBlock,
+    ream_consensus::bellatrix::beacon_block::BeaconBlock
+);
+test_consensus_type!(
+    bellatrix,
+    BeaconBlockBody,
+    ream_consensus::bellatrix::beacon_block_body::BeaconBlockBody
+);
+test_consensus_type!(
+    bellatrix,
+    BeaconState,
+    ream_consensus::bellatrix::beacon_state::BeaconState
+);
+test_consensus_type!(
+    bellatrix,
+    ExecutionPayload,
+    ream_consensus::bellatrix::execution_payload::ExecutionPayload
+);
+test_consensus_type!(
+    bellatrix,
+    ExecutionPayloadHeader,
+    ream_consensus::bellatrix::execution_payload_header::ExecutionPayloadHeader
+);
+test_consensus_type!(
+    bellatrix,
+    SignedBeaconBlock,
+    ream_consensus::bellatrix::beacon_block::SignedBeaconBlock
+);
+test_consensus_type!(
+    capella,
+    BeaconBlock,
+    ream_consensus::capella::beacon_block::BeaconBlock
+);
+test_consensus_type!(
+    capella,
+    BeaconBlockBody,
+    ream_consensus::capella::beacon_block_body::BeaconBlockBody
+);
+test_consensus_type!(
+    capella,
+    BeaconState,
+    ream_consensus::capella::beacon_state::BeaconState
+);
+test_consensus_type!(
+    capella,
+    ExecutionPayload,
+    ream_consensus::capella::execution_payload::ExecutionPayload
+);
+test_consensus_type!(
+    capella,
+    ExecutionPayloadHeader,
+    ream_consensus::capella::execution_payload_header::ExecutionPayloadHeader
+);
+test_consensus_type!(
+    capella,
+    SignedBeaconBlock,
+    ream_consensus::capella::beacon_block::SignedBeaconBlock
+);
+
+// Testing the fork dispatching decoding of earlier forks, their state transition isn't run so
+// they have no operations, epoch_processing or sanity runners
+test_versioned_decoding!(phase0);
+test_versioned_decoding!(altair);
+test_versioned_decoding!(bellatrix);
+test_versioned_decoding!(capella);
+
 // Testing operations for block processing
 test_operation!(attestation, Attestation, "attestation", process_attestation);
 test_operation!(
@@ -106,6 +216,9 @@ test_operation!(
 // Testing shuffling
 test_shuffling!();
 
+// Testing the EIP-4881 deposit tree
+test_deposit_tree!();
+
 // Testing epoch_processing
 test_epoch_processing!(effective_balance_updates, process_effective_balance_updates);
 test_epoch_processing!(eth1_data_reset, process_eth1_data_reset);
@@ -138,3 +251,314 @@ test_sanity_blocks!();
 
 // Testing sanity_slots
 test_sanity_slots!();
+
+// Testing the tree hash cache
+test_tree_hash_cache!();
+
+// Testing electra
+mod electra {
+    use ef_tests::{
+        test_consensus_type, test_epoch_processing, test_fork, test_light_client,
+        test_merkle_proof, test_operation, test_sanity_blocks, test_sanity_slots,
+        test_tree_hash_cache, utils,
+    };
+    use ream_consensus::{
+        bls_to_execution_change::SignedBLSToExecutionChange,
+        deneb::{self, execution_payload::ExecutionPayload},
+        deposit::Deposit,
+        electra::{
+            attestation::Attestation,
+            attester_slashing::AttesterSlashing,
+            beacon_block::{BeaconBlock, SignedBeaconBlock},
+            beacon_block_body::BeaconBlockBody,
+            beacon_state::{BeaconState, upgrade_to_electra},
+            consolidation_request::ConsolidationRequest,
+            deposit_request::DepositRequest,
+            withdrawal_request::WithdrawalRequest,
+        },
+        proposer_slashing::ProposerSlashing,
+        voluntary_exit::SignedVoluntaryExit,
+    };
+
+    // Testing consensus types
+    test_consensus_type!(
+        electra,
+        AggregateAndProof,
+        ream_consensus::electra::aggregate_and_proof::AggregateAndProof
+    );
+    test_consensus_type!(
+        electra,
+        Attestation,
+        ream_consensus::electra::attestation::Attestation
+    );
+    test_consensus_type!(
+        electra,
+        AttesterSlashing,
+        ream_consensus::electra::attester_slashing::AttesterSlashing
+    );
+    test_consensus_type!(
+        electra,
+        BeaconBlock,
+        ream_consensus::electra::beacon_block::BeaconBlock
+    );
+    test_consensus_type!(
+        electra,
+        BeaconBlockBody,
+        ream_consensus::electra::beacon_block_body::BeaconBlockBody
+    );
+    test_consensus_type!(
+        electra,
+        BeaconState,
+        ream_consensus::electra::beacon_state::BeaconState
+    );
+    test_consensus_type!(
+        electra,
+        BlobIdentifier,
+        ream_consensus::blob_sidecar::BlobIdentifier
+    );
+    test_consensus_type!(
+        electra,
+        BlobSidecar,
+        ream_consensus::blob_sidecar::BlobSidecar
+    );
+    test_consensus_type!(
+        electra,
+        ConsolidationRequest,
+        ream_consensus::electra::consolidation_request::ConsolidationRequest
+    );
+    test_consensus_type!(
+        electra,
+        ContributionAndProof,
+        ream_consensus::contribution_and_proof::ContributionAndProof
+    );
+    test_consensus_type!(
+        electra,
+        DepositRequest,
+        ream_consensus::electra::deposit_request::DepositRequest
+    );
+    test_consensus_type!(
+        electra,
+        ExecutionRequests,
+        ream_consensus::electra::execution_requests::ExecutionRequests
+    );
+    test_consensus_type!(
+        electra,
+        IndexedAttestation,
+        ream_consensus::electra::indexed_attestation::IndexedAttestation
+    );
+    test_consensus_type!(
+        electra,
+        LightClientBootstrap,
+        ream_consensus::electra::light_client_bootstrap::LightClientBootstrap
+    );
+    test_consensus_type!(
+        electra,
+        LightClientFinalityUpdate,
+        ream_consensus::electra::light_client_finality_update::LightClientFinalityUpdate
+    );
+    test_consensus_type!(
+        electra,
+        LightClientHeader,
+        ream_consensus::electra::light_client_header::LightClientHeader
+    );
+    test_consensus_type!(
+        electra,
+        LightClientOptimisticUpdate,
+        ream_consensus::electra::light_client_optimistic_update::LightClientOptimisticUpdate
+    );
+    test_consensus_type!(
+        electra,
+        LightClientUpdate,
+        ream_consensus::electra::light_client_update::LightClientUpdate
+    );
+    test_consensus_type!(
+        electra,
+        PendingConsolidation,
+        ream_consensus::electra::pending_consolidation::PendingConsolidation
+    );
+    test_consensus_type!(
+        electra,
+        PendingDeposit,
+        ream_consensus::electra::pending_deposit::PendingDeposit
+    );
+    test_consensus_type!(
+        electra,
+        PendingPartialWithdrawal,
+        ream_consensus::electra::pending_partial_withdrawal::PendingPartialWithdrawal
+    );
+    test_consensus_type!(
+        electra,
+        SignedAggregateAndProof,
+        ream_consensus::electra::aggregate_and_proof::SignedAggregateAndProof
+    );
+    test_consensus_type!(
+        electra,
+        SignedBeaconBlock,
+        ream_consensus::electra::beacon_block::SignedBeaconBlock
+    );
+    test_consensus_type!(
+        electra,
+        SignedContributionAndProof,
+        ream_consensus::contribution_and_proof::SignedContributionAndProof
+    );
+    test_consensus_type!(
+        electra,
+        SingleAttestation,
+        ream_consensus::electra::single_attestation::SingleAttestation
+    );
+    test_consensus_type!(
+        electra,
+        SyncAggregatorSelectionData,
+        ream_consensus::sync_committee_contribution::SyncAggregatorSelectionData
+    );
+    test_consensus_type!(
+        electra,
+        SyncCommitteeContribution,
+        ream_consensus::sync_committee_contribution::SyncCommitteeContribution
+    );
+    test_consensus_type!(
+        electra,
+        SyncCommitteeMessage,
+        ream_consensus::sync_committee_message::SyncCommitteeMessage
+    );
+    test_consensus_type!(
+        electra,
+        WithdrawalRequest,
+        ream_consensus::electra::withdrawal_request::WithdrawalRequest
+    );
+
+    // Testing operations for block processing
+    test_operation!(
+        electra,
+        attestation,
+        Attestation,
+        "attestation",
+        process_attestation
+    );
+    test_operation!(
+        electra,
+        attester_slashing,
+        AttesterSlashing,
+        "attester_slashing",
+        process_attester_slashing
+    );
+    test_operation!(
+        electra,
+        block_header,
+        BeaconBlock,
+        "block",
+        process_block_header
+    );
+    test_operation!(
+        electra,
+        bls_to_execution_change,
+        SignedBLSToExecutionChange,
+        "address_change",
+        process_bls_to_execution_change
+    );
+    test_operation!(
+        electra,
+        consolidation_request,
+        ConsolidationRequest,
+        "consolidation_request",
+        process_consolidation_request
+    );
+    test_operation!(electra, deposit, Deposit, "deposit", process_deposit);
+    test_operation!(
+        electra,
+        deposit_request,
+        DepositRequest,
+        "deposit_request",
+        process_deposit_request
+    );
+    test_operation!(electra, execution_payload, BeaconBlockBody, "body");
+    test_operation!(
+        electra,
+        proposer_slashing,
+        ProposerSlashing,
+        "proposer_slashing",
+        process_proposer_slashing
+    );
+    test_operation!(
+        electra,
+        voluntary_exit,
+        SignedVoluntaryExit,
+        "voluntary_exit",
+        process_voluntary_exit
+    );
+    test_operation!(
+        electra,
+        withdrawal_request,
+        WithdrawalRequest,
+        "withdrawal_request",
+        process_withdrawal_request
+    );
+    test_operation!(
+        electra,
+        withdrawals,
+        ExecutionPayload,
+        "execution_payload",
+        process_withdrawals
+    );
+
+    // Testing epoch_processing
+    test_epoch_processing!(
+        electra,
+        effective_balance_updates,
+        process_effective_balance_updates
+    );
+    test_epoch_processing!(electra, eth1_data_reset, process_eth1_data_reset);
+    test_epoch_processing!(
+        electra,
+        historical_summaries_update,
+        process_historical_summaries_update
+    );
+    test_epoch_processing!(electra, inactivity_updates, process_inactivity_updates);
+    test_epoch_processing!(
+        electra,
+        justification_and_finalization,
+        process_justification_and_finalization
+    );
+    test_epoch_processing!(
+        electra,
+        participation_flag_updates,
+        process_participation_flag_updates
+    );
+    test_epoch_processing!(
+        electra,
+        pending_consolidations,
+        process_pending_consolidations
+    );
+    test_epoch_processing!(electra, pending_deposits, process_pending_deposits);
+    test_epoch_processing!(electra, randao_mixes_reset, process_randao_mixes_reset);
+    test_epoch_processing!(electra, registry_updates, process_registry_updates);
+    test_epoch_processing!(
+        electra,
+        rewards_and_penalties,
+        process_rewards_and_penalties
+    );
+    test_epoch_processing!(electra, slashings, process_slashings);
+    test_epoch_processing!(electra, slashings_reset, process_slashings_reset);
+
+    // Testing light client data
+    test_light_client!(electra);
+
+    // Testing merkle_proof
+    test_merkle_proof!(electra);
+
+    // Testing sanity_blocks
+    test_sanity_blocks!(electra);
+
+    // Testing sanity_slots
+    test_sanity_slots!(electra);
+
+    // Testing the tree hash cache
+    test_tree_hash_cache!(electra);
+
+    // Testing the upgrade from deneb
+    test_fork!(
+        electra,
+        deneb::beacon_state::BeaconState,
+        upgrade_to_electra
+    );
+}